@@ -283,6 +283,7 @@ pub fn update_loop(
                 key: RafxImmutableSamplerKey::Name("smp"),
                 samplers: &[sampler],
             }],
+            bindless_descriptors: &[],
         })?;
 
         //
@@ -4,6 +4,10 @@ pub use crate::daemon_args::AssetDaemonArgs;
 use crate::time::TimeState;
 use rafx::assets::distill_impl::AssetResource;
 
+#[cfg(feature = "egui")]
+use rafx::api::RafxMemoryCategory;
+#[cfg(feature = "egui")]
+use rafx::assets::AssetManager;
 #[cfg(feature = "egui")]
 use rafx_plugins::features::egui::EguiContextResource;
 
@@ -21,7 +25,12 @@ use rafx_plugins::pipelines::modern::ModernPipelineTonemapDebugData;
 #[cfg(not(feature = "basic-pipeline"))]
 use rafx_plugins::pipelines::modern::TonemapperTypeAdv as TonemapperType;
 #[cfg(not(feature = "basic-pipeline"))]
-use rafx_plugins::pipelines::modern::{JitterPattern, TemporalAAOptions};
+use rafx_plugins::pipelines::modern::TransparencyMethodAdv as TransparencyMethod;
+#[cfg(not(feature = "basic-pipeline"))]
+use rafx_plugins::pipelines::modern::{
+    ColorGradingOptions, DepthOfFieldOptions, DynamicResolutionOptions, JitterPattern,
+    MotionBlurOptions, ScreenSpaceReflectionOptions, TemporalAAOptions, VolumetricFogOptions,
+};
 
 #[derive(Clone)]
 pub struct RenderOptions {
@@ -29,6 +38,22 @@ pub struct RenderOptions {
     pub enable_hdr: bool,
     #[cfg(not(feature = "basic-pipeline"))]
     pub enable_ssao: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_ssr: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub ssr_options: ScreenSpaceReflectionOptions,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_volumetric_fog: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub volumetric_fog_options: VolumetricFogOptions,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_depth_of_field: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub depth_of_field_options: DepthOfFieldOptions,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_motion_blur: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub motion_blur_options: MotionBlurOptions,
     pub enable_bloom: bool,
     pub enable_textures: bool,
     pub enable_lighting: bool,
@@ -42,6 +67,10 @@ pub struct RenderOptions {
     pub show_lights_debug_draw: bool,
     pub blur_pass_count: usize,
     pub tonemapper_type: TonemapperType,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_color_grading: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub color_grading_options: ColorGradingOptions,
     pub enable_visibility_update: bool,
     pub use_clustered_lighting: bool,
     #[cfg(not(feature = "basic-pipeline"))]
@@ -54,6 +83,12 @@ pub struct RenderOptions {
     pub sharpening_amount: f32,
     #[cfg(not(feature = "basic-pipeline"))]
     pub enable_occlusion_culling: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub transparency_method: TransparencyMethod,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub enable_dynamic_resolution: bool,
+    #[cfg(not(feature = "basic-pipeline"))]
+    pub dynamic_resolution_options: DynamicResolutionOptions,
 }
 
 impl RenderOptions {
@@ -63,6 +98,22 @@ impl RenderOptions {
             enable_hdr: false,
             #[cfg(not(feature = "basic-pipeline"))]
             enable_ssao: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_ssr: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            ssr_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_volumetric_fog: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            volumetric_fog_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_depth_of_field: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            depth_of_field_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_motion_blur: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            motion_blur_options: Default::default(),
             enable_bloom: false,
             enable_textures: true,
             enable_lighting: true,
@@ -76,6 +127,10 @@ impl RenderOptions {
             show_lights_debug_draw: false,
             blur_pass_count: 0,
             tonemapper_type: TonemapperType::None,
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_color_grading: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            color_grading_options: Default::default(),
             enable_visibility_update: true,
             use_clustered_lighting: true,
             #[cfg(not(feature = "basic-pipeline"))]
@@ -88,6 +143,12 @@ impl RenderOptions {
             sharpening_amount: 0.0,
             #[cfg(not(feature = "basic-pipeline"))]
             enable_occlusion_culling: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            transparency_method: TransparencyMethod::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_dynamic_resolution: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            dynamic_resolution_options: Default::default(),
         }
     }
 
@@ -97,6 +158,22 @@ impl RenderOptions {
             enable_hdr: true,
             #[cfg(not(feature = "basic-pipeline"))]
             enable_ssao: true,
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_ssr: true,
+            #[cfg(not(feature = "basic-pipeline"))]
+            ssr_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_volumetric_fog: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            volumetric_fog_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_depth_of_field: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            depth_of_field_options: Default::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_motion_blur: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            motion_blur_options: Default::default(),
             enable_bloom: true,
             enable_textures: true,
             enable_lighting: true,
@@ -110,6 +187,10 @@ impl RenderOptions {
             show_lights_debug_draw: false,
             blur_pass_count: 5,
             tonemapper_type: TonemapperType::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_color_grading: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            color_grading_options: Default::default(),
             enable_visibility_update: true,
             use_clustered_lighting: true,
             #[cfg(not(feature = "basic-pipeline"))]
@@ -122,6 +203,12 @@ impl RenderOptions {
             sharpening_amount: 1.0,
             #[cfg(not(feature = "basic-pipeline"))]
             enable_occlusion_culling: true,
+            #[cfg(not(feature = "basic-pipeline"))]
+            transparency_method: TransparencyMethod::default(),
+            #[cfg(not(feature = "basic-pipeline"))]
+            enable_dynamic_resolution: false,
+            #[cfg(not(feature = "basic-pipeline"))]
+            dynamic_resolution_options: Default::default(),
         }
     }
 }
@@ -178,12 +265,210 @@ impl RenderOptions {
                         );
                     });
                 }
+
+                #[cfg(not(feature = "basic-pipeline"))]
+                {
+                    ui.checkbox(&mut self.enable_color_grading, "enable_color_grading");
+                    if self.enable_color_grading {
+                        let color_grading_options = &mut self.color_grading_options;
+                        ui.indent("", |ui| {
+                            for (i, channel) in ["r", "g", "b"].iter().enumerate() {
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut color_grading_options.lift[i],
+                                        -0.5..=0.5,
+                                    )
+                                    .text(format!("lift_{}", channel)),
+                                );
+                            }
+                            for (i, channel) in ["r", "g", "b"].iter().enumerate() {
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut color_grading_options.gamma[i],
+                                        0.2..=5.0,
+                                    )
+                                    .logarithmic(true)
+                                    .text(format!("gamma_{}", channel)),
+                                );
+                            }
+                            for (i, channel) in ["r", "g", "b"].iter().enumerate() {
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut color_grading_options.gain[i],
+                                        0.0..=2.0,
+                                    )
+                                    .text(format!("gain_{}", channel)),
+                                );
+                            }
+                            ui.add(
+                                egui::Slider::new(&mut color_grading_options.saturation, 0.0..=2.0)
+                                    .text("saturation"),
+                            );
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut color_grading_options.temperature,
+                                    -100.0..=100.0,
+                                )
+                                .text("temperature"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut color_grading_options.tint, -100.0..=100.0)
+                                    .text("tint"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut color_grading_options.lut_blend, 0.0..=1.0)
+                                    .text("lut_blend"),
+                            );
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut color_grading_options.lut_contribution,
+                                    0.0..=1.0,
+                                )
+                                .text("lut_contribution"),
+                            );
+                        });
+                    }
+                }
             });
         }
 
         #[cfg(not(feature = "basic-pipeline"))]
         if self.anti_alias_method != AntiAliasMethod::Msaa4x {
             ui.checkbox(&mut self.enable_ssao, "enable_ssao");
+            ui.checkbox(&mut self.enable_ssr, "enable_ssr");
+
+            if self.enable_ssr {
+                let ssr_options = &mut self.ssr_options;
+                ui.indent("", |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.max_steps, 1..=256).text("max_steps"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.max_distance, 0.0..=200.0)
+                            .text("max_distance"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.thickness, 0.0..=5.0).text("thickness"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.max_roughness, 0.0..=1.0)
+                            .text("max_roughness"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.roughness_fade_start, 0.0..=1.0)
+                            .text("roughness_fade_start"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.history_weight, 0.0..=0.99)
+                            .text("history_weight"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut ssr_options.intensity, 0.0..=2.0).text("intensity"),
+                    );
+                });
+            }
+
+            let transparency_method_names: Vec<_> = (0..(TransparencyMethod::MAX as i32))
+                .map(|t| TransparencyMethod::from(t).display_name())
+                .collect();
+
+            egui::ComboBox::from_label("transparency_method")
+                .selected_text(transparency_method_names[self.transparency_method as usize])
+                .show_ui(ui, |ui| {
+                    for (i, name) in transparency_method_names.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut self.transparency_method,
+                            TransparencyMethod::from(i as i32),
+                            name,
+                        );
+                    }
+                });
+        }
+
+        #[cfg(not(feature = "basic-pipeline"))]
+        {
+            ui.checkbox(&mut self.enable_volumetric_fog, "enable_volumetric_fog");
+
+            if self.enable_volumetric_fog {
+                let fog_options = &mut self.volumetric_fog_options;
+                ui.indent("", |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut fog_options.density, 0.0..=0.5)
+                            .logarithmic(true)
+                            .text("density"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut fog_options.anisotropy, -0.9..=0.9)
+                            .text("anisotropy"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut fog_options.height_falloff, 0.0..=2.0)
+                            .text("height_falloff"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut fog_options.height_offset, -50.0..=50.0)
+                            .text("height_offset"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut fog_options.max_distance, 10.0..=500.0)
+                            .text("max_distance"),
+                    );
+                });
+            }
+
+            ui.checkbox(&mut self.enable_depth_of_field, "enable_depth_of_field");
+
+            if self.enable_depth_of_field {
+                let dof_options = &mut self.depth_of_field_options;
+                ui.indent("", |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.focus_distance, 0.1..=200.0)
+                            .logarithmic(true)
+                            .text("focus_distance"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.focal_length_mm, 10.0..=300.0)
+                            .text("focal_length_mm"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.f_stop, 0.7..=22.0)
+                            .logarithmic(true)
+                            .text("f_stop"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.sensor_height_mm, 4.0..=60.0)
+                            .text("sensor_height_mm"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.max_coc_radius, 1.0..=64.0)
+                            .text("max_coc_radius"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dof_options.radius_step, 0.25..=2.0)
+                            .text("radius_step"),
+                    );
+                });
+            }
+
+            ui.checkbox(&mut self.enable_motion_blur, "enable_motion_blur");
+
+            if self.enable_motion_blur {
+                let motion_blur_options = &mut self.motion_blur_options;
+                ui.indent("", |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut motion_blur_options.shutter_angle, 0.0..=360.0)
+                            .text("shutter_angle"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut motion_blur_options.max_blur_radius, 1.0..=128.0)
+                            .text("max_blur_radius"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut motion_blur_options.sample_count, 1..=64)
+                            .text("sample_count"),
+                    );
+                });
+            }
         }
 
         ui.checkbox(&mut self.show_lights_debug_draw, "show_lights_debug_draw");
@@ -229,6 +514,35 @@ impl RenderOptions {
             &mut self.enable_occlusion_culling,
             "enable_occlusion_culling",
         );
+
+        #[cfg(not(feature = "basic-pipeline"))]
+        {
+            ui.checkbox(
+                &mut self.enable_dynamic_resolution,
+                "enable_dynamic_resolution",
+            );
+
+            if self.enable_dynamic_resolution {
+                let dynamic_resolution_options = &mut self.dynamic_resolution_options;
+                ui.indent("", |ui| {
+                    ui.add(
+                        egui::Slider::new(
+                            &mut dynamic_resolution_options.target_frame_time_ms,
+                            4.0..=50.0,
+                        )
+                        .text("target_frame_time_ms"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dynamic_resolution_options.min_scale, 0.25..=1.0)
+                            .text("min_scale"),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut dynamic_resolution_options.max_scale, 0.25..=1.0)
+                            .text("max_scale"),
+                    );
+                });
+            }
+        }
     }
 }
 
@@ -242,6 +556,7 @@ pub struct DebugUiState {
     pub show_tonemap_debug: bool,
     #[cfg(not(feature = "basic-pipeline"))]
     pub show_mesh_culling_debug: bool,
+    pub show_gpu_memory: bool,
 
     #[cfg(feature = "profile-with-puffin")]
     pub show_profiler: bool,
@@ -311,6 +626,7 @@ pub fn draw_ui(resources: &Resources) {
         .get::<ModernPipelineMeshCullingDebugData>()
         .unwrap();
     let asset_resource = resources.get::<AssetResource>().unwrap();
+    let asset_manager = resources.get::<AssetManager>().unwrap();
 
     egui::TopBottomPanel::top("top_panel").show(&ctx, |ui| {
         egui::menu::bar(ui, |ui| {
@@ -331,6 +647,8 @@ pub fn draw_ui(resources: &Resources) {
                     "Mesh Culling Debug",
                 );
 
+                ui.checkbox(&mut debug_ui_state.show_gpu_memory, "GPU Memory");
+
                 #[cfg(feature = "profile-with-puffin")]
                 if ui
                     .checkbox(&mut debug_ui_state.show_profiler, "Profiler")
@@ -443,6 +761,70 @@ pub fn draw_ui(resources: &Resources) {
             .enable_debug_data_collection = debug_ui_state.show_mesh_culling_debug;
     }
 
+    if debug_ui_state.show_gpu_memory {
+        egui::Window::new("GPU Memory")
+            .open(&mut debug_ui_state.show_gpu_memory)
+            .show(&ctx, |ui| {
+                let memory_stats = asset_manager.device_context().memory_stats();
+                let to_mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+                for &category in &RafxMemoryCategory::ALL {
+                    let stats = memory_stats.category(category);
+                    ui.label(format!(
+                        "{}: {:.1} MB in {} allocations (peak {:.1} MB)",
+                        category.display_name(),
+                        to_mb(stats.allocated_bytes),
+                        stats.allocation_count,
+                        to_mb(stats.peak_allocated_bytes)
+                    ));
+                }
+                ui.label(format!(
+                    "Total: {:.1} MB in {} allocations",
+                    to_mb(memory_stats.total_allocated_bytes()),
+                    memory_stats.total_allocation_count()
+                ));
+
+                ui.separator();
+                for (heap_index, heap) in memory_stats.heaps.iter().enumerate() {
+                    let usage = match (heap.usage, heap.budget) {
+                        (Some(usage), Some(budget)) => {
+                            format!("{:.1} / {:.1} MB", to_mb(usage), to_mb(budget))
+                        }
+                        _ => "budget unavailable".to_string(),
+                    };
+                    ui.label(format!(
+                        "Heap {}{}: {:.1} MB, {}",
+                        heap_index,
+                        if heap.device_local {
+                            " (device local)"
+                        } else {
+                            ""
+                        },
+                        to_mb(heap.size),
+                        usage
+                    ));
+                }
+
+                let texture_streaming_context = asset_manager.texture_streaming_context();
+                if texture_streaming_context.is_enabled() {
+                    let stats = texture_streaming_context.stats();
+                    ui.separator();
+                    ui.label(format!(
+                        "Texture streaming: {} / {} images fully resident, {} uploads in flight",
+                        stats.fully_resident_image_count,
+                        stats.streamed_image_count,
+                        stats.uploads_in_flight
+                    ));
+                    ui.label(format!(
+                        "Streamed textures: {:.1} MB resident of {:.1} MB (budget {:.1} MB)",
+                        to_mb(stats.resident_bytes),
+                        to_mb(stats.full_chain_bytes),
+                        to_mb(stats.budget_bytes)
+                    ));
+                }
+            });
+    }
+
     if debug_ui_state.show_render_options {
         egui::Window::new("Render Options")
             .open(&mut debug_ui_state.show_render_options)
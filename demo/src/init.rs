@@ -40,6 +40,8 @@ pub fn rendering_init(
     window: &dyn HasRawWindowHandle,
     window_width: u32,
     window_height: u32,
+    // Only used by vulkan
+    #[allow(unused_variables)] enable_bindless: bool,
 ) -> RafxResult<()> {
     resources.insert(VisibilityResource::new());
     resources.insert(ViewportsResource::default());
@@ -96,6 +98,8 @@ pub fn rendering_init(
 
         api_def.vk_options = Some(rafx::api::RafxApiDefVulkan {
             physical_device_features: Some(physical_device_features),
+            // Falls back to regular descriptors if the device does not support it
+            enable_bindless,
             ..Default::default()
         });
     }
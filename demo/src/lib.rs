@@ -55,7 +55,8 @@ use rafx_plugins::features::mesh_adv::{
 use rafx_plugins::pipelines::modern::ModernPipelineMeshCullingDebugData;
 #[cfg(not(feature = "basic-pipeline"))]
 use rafx_plugins::pipelines::modern::{
-    ModernPipelineRenderOptions as PipelineRenderOptions, ModernPipelineTonemapDebugData,
+    ModernPipelineDynamicResolutionData, ModernPipelineRenderOptions as PipelineRenderOptions,
+    ModernPipelineTonemapDebugData,
 };
 
 #[cfg(all(feature = "profile-with-tracy-memory", not(feature = "stats_alloc")))]
@@ -107,6 +108,10 @@ pub struct DemoArgs {
     #[structopt(name = "external-daemon", long)]
     pub external_daemon: bool,
 
+    /// Use bindless descriptors if the device supports them (vulkan)
+    #[structopt(name = "enable-bindless", long)]
+    pub enable_bindless: bool,
+
     #[structopt(flatten)]
     pub daemon_args: AssetDaemonArgs,
 }
@@ -157,6 +162,8 @@ impl DemoApp {
         resources.insert(ModernPipelineTonemapDebugData::default());
         #[cfg(not(feature = "basic-pipeline"))]
         resources.insert(ModernPipelineMeshCullingDebugData::default());
+        #[cfg(not(feature = "basic-pipeline"))]
+        resources.insert(ModernPipelineDynamicResolutionData::default());
         resources.insert(DebugUiState::default());
 
         let asset_source = args.asset_source().unwrap();
@@ -168,6 +175,7 @@ impl DemoApp {
             window,
             physical_size.width,
             physical_size.height,
+            args.enable_bindless,
         )?;
 
         let world = World::default();
@@ -337,11 +345,32 @@ impl DemoApp {
             #[cfg(not(feature = "basic-pipeline"))]
             {
                 pipeline_render_options.enable_ssao = render_options.enable_ssao;
+                pipeline_render_options.enable_ssr = render_options.enable_ssr;
+                pipeline_render_options.ssr_options = render_options.ssr_options.clone();
+                pipeline_render_options.enable_volumetric_fog =
+                    render_options.enable_volumetric_fog;
+                pipeline_render_options.volumetric_fog_options =
+                    render_options.volumetric_fog_options.clone();
+                pipeline_render_options.enable_depth_of_field =
+                    render_options.enable_depth_of_field;
+                pipeline_render_options.depth_of_field_options =
+                    render_options.depth_of_field_options.clone();
+                pipeline_render_options.enable_motion_blur = render_options.enable_motion_blur;
+                pipeline_render_options.motion_blur_options =
+                    render_options.motion_blur_options.clone();
+                pipeline_render_options.enable_color_grading = render_options.enable_color_grading;
+                pipeline_render_options.color_grading_options =
+                    render_options.color_grading_options.clone();
                 pipeline_render_options.taa_options = render_options.taa_options.clone();
                 pipeline_render_options.enable_sharpening = render_options.enable_sharpening;
                 pipeline_render_options.sharpening_amount = render_options.sharpening_amount;
                 pipeline_render_options.enable_occlusion_culling =
                     render_options.enable_occlusion_culling;
+                pipeline_render_options.transparency_method = render_options.transparency_method;
+                pipeline_render_options.enable_dynamic_resolution =
+                    render_options.enable_dynamic_resolution;
+                pipeline_render_options.dynamic_resolution_options =
+                    render_options.dynamic_resolution_options.clone();
             }
 
             let mut render_config_resource =
@@ -443,6 +472,8 @@ impl DemoApp {
             add_to_extract_resources!(ModernPipelineTonemapDebugData);
             #[cfg(not(feature = "basic-pipeline"))]
             add_to_extract_resources!(ModernPipelineMeshCullingDebugData);
+            #[cfg(not(feature = "basic-pipeline"))]
+            add_to_extract_resources!(ModernPipelineDynamicResolutionData);
 
             extract_resources.insert(&mut self.world);
 
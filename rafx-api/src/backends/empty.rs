@@ -19,6 +19,8 @@ impl RafxApiEmpty {
 pub struct RafxDeviceContextEmpty;
 impl RafxDeviceContextEmpty {
     pub fn device_info(&self) -> &RafxDeviceInfo { unimplemented!() }
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker { unimplemented!() }
+    pub fn memory_heap_stats(&self) -> Vec<RafxMemoryHeapStats> { unimplemented!() }
 
     pub fn create_queue(&self, queue_type: RafxQueueType) -> RafxResult<RafxQueueEmpty> { unimplemented!(); }
     pub fn create_fence(&self) -> RafxResult<RafxFenceEmpty> { unimplemented!(); }
@@ -46,6 +48,7 @@ impl RafxDeviceContextEmpty {
 #[derive(Debug)]
 pub struct RafxBufferEmpty;
 impl RafxBufferEmpty {
+    pub(crate) fn set_memory_allocation(&mut self, memory_allocation: RafxMemoryAllocation) { unimplemented!() }
     pub fn buffer_def(&self) -> &RafxBufferDef { unimplemented!() }
     pub fn map_buffer(&self) -> RafxResult<*mut u8> { unimplemented!() }
     pub fn unmap_buffer(&self) -> RafxResult<()> { unimplemented!() }
@@ -58,6 +61,7 @@ impl RafxBufferEmpty {
 #[derive(Clone, Debug)]
 pub struct RafxTextureEmpty;
 impl RafxTextureEmpty {
+    pub(crate) fn set_memory_allocation(&mut self, memory_allocation: RafxMemoryAllocation) { unimplemented!() }
     pub fn texture_def(&self) -> &RafxTextureDef { unimplemented!() }
 }
 
@@ -163,6 +167,7 @@ impl RafxCommandBufferEmpty {
     pub fn cmd_resource_barrier(&self, buffer_barriers: &[RafxBufferBarrier], texture_barriers: &[RafxTextureBarrier]) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_copy_buffer_to_buffer(&self, src_buffer: &RafxBufferEmpty, dst_buffer: &RafxBufferEmpty, params: &RafxCmdCopyBufferToBufferParams) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_copy_buffer_to_texture(&self, src_buffer: &RafxBufferEmpty, dst_texture: &RafxTextureEmpty, params: &RafxCmdCopyBufferToTextureParams) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_copy_texture_to_buffer(&self, src_texture: &RafxTextureEmpty, dst_buffer: &RafxBufferEmpty, params: &RafxCmdCopyTextureToBufferParams) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_copy_texture_to_texture(&self, src_texture: &RafxTextureEmpty, dst_texture: &RafxTextureEmpty, params: &RafxCmdCopyTextureToTextureParams) -> RafxResult<()> { unimplemented!() }
 }
 
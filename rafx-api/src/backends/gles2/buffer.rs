@@ -1,5 +1,5 @@
 use crate::gles2::{BufferId, RafxDeviceContextGles2, NONE_BUFFER};
-use crate::{RafxBufferDef, RafxMemoryUsage, RafxResourceType, RafxResult};
+use crate::{RafxBufferDef, RafxMemoryAllocation, RafxMemoryUsage, RafxResourceType, RafxResult};
 
 use crate::gles2::gles2_bindings;
 use crate::gles2::gles2_bindings::types::GLenum;
@@ -77,6 +77,7 @@ pub struct RafxBufferGles2 {
     buffer_contents: Gles2BufferContents,
     mapped_count: AtomicU32,
     target: GLenum, // may be gles20::NONE
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 impl Drop for RafxBufferGles2 {
@@ -91,6 +92,15 @@ impl Drop for RafxBufferGles2 {
 }
 
 impl RafxBufferGles2 {
+    // Called by RafxDeviceContext right after creating the buffer so that it is counted in the
+    // device context's memory stats until it is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        self.memory_allocation = Some(memory_allocation);
+    }
+
     pub fn buffer_def(&self) -> &RafxBufferDef {
         &self.buffer_def
     }
@@ -257,6 +267,7 @@ impl RafxBufferGles2 {
             buffer_contents,
             mapped_count: AtomicU32::new(0),
             target,
+            memory_allocation: None,
         })
     }
 }
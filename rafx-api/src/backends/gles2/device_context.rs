@@ -1,8 +1,9 @@
 use crate::{
     RafxApiDefGles2, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
-    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType,
-    RafxResourceType, RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef,
-    RafxShaderModuleDefGles2, RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxMemoryHeapStats,
+    RafxMemoryTracker, RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef,
+    RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefGles2, RafxShaderStageDef,
+    RafxSwapchainDef, RafxTextureDef,
 };
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
@@ -27,6 +28,7 @@ pub struct RafxDeviceContextGles2Inner {
     gl_context_manager: GlContextManager,
     gl_context: Arc<GlContext>,
     destroyed: AtomicBool,
+    memory_tracker: RafxMemoryTracker,
     pub(crate) validate_shaders: bool,
 
     pub(crate) fullscreen_quad: FullscreenQuad,
@@ -85,6 +87,9 @@ impl RafxDeviceContextGles2Inner {
             upload_buffer_texture_row_alignment: pack_alignment,
             supports_clamp_to_border_color: false, // requires GLES 3.2 or an extension
             max_vertex_attribute_count,
+            supports_geometry_shaders: false,
+            supports_tessellation_shaders: false,
+            supports_bindless: false,
         };
 
         let fullscreen_quad = FullscreenQuad::new(&gl_context)?;
@@ -104,6 +109,7 @@ impl RafxDeviceContextGles2Inner {
             gl_context,
             fullscreen_quad,
             destroyed: AtomicBool::new(false),
+            memory_tracker: RafxMemoryTracker::default(),
             validate_shaders: gl_api_def.validate_shaders,
             gl_finish_call_count: AtomicU64::new(0),
 
@@ -196,6 +202,15 @@ impl RafxDeviceContextGles2 {
         &self.inner.gl_context
     }
 
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker {
+        &self.inner.memory_tracker
+    }
+
+    // GL ES has no way to query memory heaps or budgets
+    pub fn memory_heap_stats(&self) -> Vec<RafxMemoryHeapStats> {
+        Vec::default()
+    }
+
     pub fn gl_context_manager(&self) -> &GlContextManager {
         &self.inner.gl_context_manager
     }
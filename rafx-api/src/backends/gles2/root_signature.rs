@@ -176,6 +176,12 @@ impl RafxRootSignatureGles2 {
         let (pipeline_type, merged_resources, _merged_resources_name_index_map) =
             crate::internal_shared::merge_resources(root_signature_def)?;
 
+        crate::internal_shared::validate_bindless_descriptors(
+            root_signature_def,
+            &merged_resources,
+            device_context.device_info(),
+        )?;
+
         let mut layouts = [
             DescriptorSetLayoutInfo::default(),
             DescriptorSetLayoutInfo::default(),
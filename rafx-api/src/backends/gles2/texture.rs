@@ -2,8 +2,8 @@ use crate::gles2::conversions::GL_CUBE_MAP_TARGETS;
 use crate::gles2::gles2_bindings::types::GLenum;
 use crate::gles2::{gles2_bindings, RafxDeviceContextGles2, TextureId, NONE_TEXTURE};
 use crate::{
-    GlTextureFormatInfo, RafxResourceType, RafxResult, RafxSampleCount, RafxTextureDef,
-    RafxTextureDimensions,
+    GlTextureFormatInfo, RafxMemoryAllocation, RafxResourceType, RafxResult, RafxSampleCount,
+    RafxTextureDef, RafxTextureDimensions,
 };
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::Ordering;
@@ -39,6 +39,7 @@ pub struct RafxTextureGles2Inner {
     gl_target: GLenum,
     texture_id: u32,
     format_info: GlTextureFormatInfo,
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 impl Drop for RafxTextureGles2Inner {
@@ -82,6 +83,17 @@ impl Hash for RafxTextureGles2 {
 }
 
 impl RafxTextureGles2 {
+    // Called by RafxDeviceContext right after creating the texture so that it is counted in the
+    // device context's memory stats until the last clone is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        Arc::get_mut(&mut self.inner)
+            .expect("The memory allocation must be set before the texture is cloned")
+            .memory_allocation = Some(memory_allocation);
+    }
+
     pub fn texture_def(&self) -> &RafxTextureDef {
         &self.inner.texture_def
     }
@@ -192,6 +204,7 @@ impl RafxTextureGles2 {
             gl_target,
             texture_id,
             format_info,
+            memory_allocation: None,
         };
 
         return Ok(RafxTextureGles2 {
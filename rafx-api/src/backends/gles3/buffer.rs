@@ -1,5 +1,5 @@
 use crate::gles3::{BufferId, RafxDeviceContextGles3, NONE_BUFFER};
-use crate::{RafxBufferDef, RafxMemoryUsage, RafxResourceType, RafxResult};
+use crate::{RafxBufferDef, RafxMemoryAllocation, RafxMemoryUsage, RafxResourceType, RafxResult};
 
 use crate::gles3::gles3_bindings;
 use crate::gles3::gles3_bindings::types::GLenum;
@@ -77,6 +77,7 @@ pub struct RafxBufferGles3 {
     buffer_contents: Gles3BufferContents,
     mapped_count: AtomicU32,
     target: GLenum, // may be gles30::NONE
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 impl Drop for RafxBufferGles3 {
@@ -91,6 +92,15 @@ impl Drop for RafxBufferGles3 {
 }
 
 impl RafxBufferGles3 {
+    // Called by RafxDeviceContext right after creating the buffer so that it is counted in the
+    // device context's memory stats until it is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        self.memory_allocation = Some(memory_allocation);
+    }
+
     pub fn buffer_def(&self) -> &RafxBufferDef {
         &self.buffer_def
     }
@@ -265,6 +275,7 @@ impl RafxBufferGles3 {
             buffer_contents,
             mapped_count: AtomicU32::new(0),
             target,
+            memory_allocation: None,
         })
     }
 }
@@ -1,8 +1,9 @@
 use crate::{
     RafxApiDefGles3, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
-    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType,
-    RafxResourceType, RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef,
-    RafxShaderModuleDefGles3, RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxMemoryHeapStats,
+    RafxMemoryTracker, RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef,
+    RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefGles3, RafxShaderStageDef,
+    RafxSwapchainDef, RafxTextureDef,
 };
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
@@ -27,6 +28,7 @@ pub struct RafxDeviceContextGles3Inner {
     gl_context_manager: GlContextManager,
     gl_context: Arc<GlContext>,
     destroyed: AtomicBool,
+    memory_tracker: RafxMemoryTracker,
     pub(crate) validate_shaders: bool,
 
     pub(crate) fullscreen_quad: FullscreenQuad,
@@ -89,6 +91,9 @@ impl RafxDeviceContextGles3Inner {
             upload_buffer_texture_row_alignment: pack_alignment,
             supports_clamp_to_border_color: false, // requires GLES 3.2 or an extension
             max_vertex_attribute_count,
+            supports_geometry_shaders: false,
+            supports_tessellation_shaders: false,
+            supports_bindless: false,
         };
 
         // Enable sRGB framebuffers on desktop GL. This is enabled by default on ES 3.0
@@ -116,6 +121,7 @@ impl RafxDeviceContextGles3Inner {
             gl_context,
             fullscreen_quad,
             destroyed: AtomicBool::new(false),
+            memory_tracker: RafxMemoryTracker::default(),
             validate_shaders: gl_api_def.validate_shaders,
             gl_finish_call_count: AtomicU64::new(0),
 
@@ -208,6 +214,15 @@ impl RafxDeviceContextGles3 {
         &self.inner.gl_context
     }
 
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker {
+        &self.inner.memory_tracker
+    }
+
+    // GL ES has no way to query memory heaps or budgets
+    pub fn memory_heap_stats(&self) -> Vec<RafxMemoryHeapStats> {
+        Vec::default()
+    }
+
     pub fn gl_context_manager(&self) -> &GlContextManager {
         &self.inner.gl_context_manager
     }
@@ -37,7 +37,7 @@ impl RafxPrimitiveTopology {
             RafxPrimitiveTopology::LineStrip => Some(gles3_bindings::LINE_STRIP),
             RafxPrimitiveTopology::TriangleList => Some(gles3_bindings::TRIANGLES),
             RafxPrimitiveTopology::TriangleStrip => Some(gles3_bindings::TRIANGLE_STRIP),
-            RafxPrimitiveTopology::PatchList => None,
+            RafxPrimitiveTopology::PatchList { .. } => None,
         }
     }
 }
@@ -268,3 +268,38 @@ pub fn array_layer_to_cube_map_target(array_layer: u16) -> GLenum {
 
     GL_CUBE_MAP_TARGETS[array_layer as usize]
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{RafxBlendStateRenderTarget, RafxBlendStateTargets};
+
+    #[test]
+    fn test_blend_state_write_masks() {
+        // GL ES has a single blend state, so its write mask applies to every target
+        let blend_state = RafxBlendState {
+            render_target_blend_states: vec![RafxBlendStateRenderTarget {
+                masks: RafxColorFlags::RED | RafxColorFlags::ALPHA,
+                ..RafxBlendStateRenderTarget::default_alpha_enabled()
+            }],
+            render_target_mask: RafxBlendStateTargets::BLEND_STATE_TARGET_ALL,
+            independent_blend: false,
+        };
+
+        let gl_blend_state = blend_state.gles3_blend_state().unwrap();
+        assert!(gl_blend_state.enabled);
+        assert_eq!(gl_blend_state.src_factor, gles3_bindings::SRC_ALPHA);
+        assert_eq!(
+            gl_blend_state.dst_factor,
+            gles3_bindings::ONE_MINUS_SRC_ALPHA
+        );
+        assert_eq!(
+            gl_blend_state.color_flags,
+            RafxColorFlags::RED | RafxColorFlags::ALPHA
+        );
+
+        let gl_blend_state = RafxBlendState::default().gles3_blend_state().unwrap();
+        assert!(!gl_blend_state.enabled);
+        assert_eq!(gl_blend_state.color_flags, RafxColorFlags::ALL);
+    }
+}
@@ -1,11 +1,12 @@
 use crate::metal::RafxDeviceContextMetal;
-use crate::{RafxBufferDef, RafxMemoryUsage, RafxResourceType, RafxResult};
+use crate::{RafxBufferDef, RafxMemoryAllocation, RafxMemoryUsage, RafxResourceType, RafxResult};
 
 #[derive(Debug)]
 pub struct RafxBufferMetal {
     _device_context: RafxDeviceContextMetal,
     buffer_def: RafxBufferDef,
     buffer: metal_rs::Buffer,
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 // for metal_rs::Buffer
@@ -13,6 +14,15 @@ unsafe impl Send for RafxBufferMetal {}
 unsafe impl Sync for RafxBufferMetal {}
 
 impl RafxBufferMetal {
+    // Called by RafxDeviceContext right after creating the buffer so that it is counted in the
+    // device context's memory stats until it is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        self.memory_allocation = Some(memory_allocation);
+    }
+
     pub fn buffer_def(&self) -> &RafxBufferDef {
         &self.buffer_def
     }
@@ -100,6 +110,7 @@ impl RafxBufferMetal {
             _device_context: device_context.clone(),
             buffer_def: buffer_def.clone(),
             buffer,
+            memory_allocation: None,
         })
     }
 }
@@ -1,9 +1,10 @@
 use crate::{
     RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef, RafxDeviceContext,
-    RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType, RafxResourceType,
-    RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefMetal,
-    RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxMemoryHeapStats, RafxMemoryTracker,
+    RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef, RafxSampleCount,
+    RafxSamplerDef, RafxShaderModuleDefMetal, RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
 };
+use metal_rs::MTLArgumentBuffersTier;
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 
@@ -24,6 +25,7 @@ pub struct RafxDeviceContextMetalInner {
 
     device: metal_rs::Device,
     destroyed: AtomicBool,
+    memory_tracker: RafxMemoryTracker,
 
     #[cfg(debug_assertions)]
     #[cfg(feature = "track-device-contexts")]
@@ -48,18 +50,6 @@ impl Drop for RafxDeviceContextMetalInner {
 
 impl RafxDeviceContextMetalInner {
     pub fn new() -> RafxResult<Self> {
-        let device_info = RafxDeviceInfo {
-            supports_multithreaded_usage: true,
-            // pretty sure this is consistent across macOS device (maybe not M1, not sure)
-            min_uniform_buffer_offset_alignment: 256,
-            // based on one of the loosest vulkan limits (intel iGPU), can't find official value
-            min_storage_buffer_offset_alignment: 64,
-            upload_buffer_texture_alignment: 16,
-            upload_buffer_texture_row_alignment: 1,
-            supports_clamp_to_border_color: true, //TODO: Check for iOS support
-            max_vertex_attribute_count: 31,
-        };
-
         #[cfg(debug_assertions)]
         #[cfg(feature = "track-device-contexts")]
         let all_contexts = {
@@ -81,11 +71,32 @@ impl RafxDeviceContextMetalInner {
             metal_features.gpu_family_common
         );
 
+        let device_info = RafxDeviceInfo {
+            supports_multithreaded_usage: true,
+            // pretty sure this is consistent across macOS device (maybe not M1, not sure)
+            min_uniform_buffer_offset_alignment: 256,
+            // based on one of the loosest vulkan limits (intel iGPU), can't find official value
+            min_storage_buffer_offset_alignment: 64,
+            upload_buffer_texture_alignment: 16,
+            upload_buffer_texture_row_alignment: 1,
+            supports_clamp_to_border_color: true, //TODO: Check for iOS support
+            max_vertex_attribute_count: 31,
+            // Metal has no geometry shaders, and its tessellation model (compute-generated factors
+            // feeding a post-tessellation vertex function) does not map to tesc/tese stages
+            supports_geometry_shaders: false,
+            supports_tessellation_shaders: false,
+            // Argument buffers are always used for descriptor sets. Tier 2 is required to bind large
+            // arrays of textures and write them while the argument buffer is in use.
+            supports_bindless: metal_features.argument_buffers_tier
+                == MTLArgumentBuffersTier::Tier2,
+        };
+
         Ok(RafxDeviceContextMetalInner {
             device_info,
             device,
             metal_features,
             destroyed: AtomicBool::new(false),
+            memory_tracker: RafxMemoryTracker::default(),
 
             #[cfg(debug_assertions)]
             #[cfg(feature = "track-device-contexts")]
@@ -183,6 +194,23 @@ impl RafxDeviceContextMetal {
         &self.inner.metal_features
     }
 
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker {
+        &self.inner.memory_tracker
+    }
+
+    // Metal does not expose heaps, so report the whole device as one. The recommended working set
+    // size is the closest equivalent to a budget.
+    pub fn memory_heap_stats(&self) -> Vec<RafxMemoryHeapStats> {
+        let device = self.device();
+        let recommended_max_working_set_size = device.recommended_max_working_set_size();
+        vec![RafxMemoryHeapStats {
+            size: recommended_max_working_set_size,
+            device_local: true,
+            budget: Some(recommended_max_working_set_size),
+            usage: Some(device.current_allocated_size() as u64),
+        }]
+    }
+
     pub fn new(inner: Arc<RafxDeviceContextMetalInner>) -> RafxResult<Self> {
         Ok(RafxDeviceContextMetal {
             inner,
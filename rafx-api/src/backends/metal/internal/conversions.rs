@@ -1,16 +1,17 @@
 use crate::{
-    RafxBlendFactor, RafxBlendOp, RafxColorClearValue, RafxCompareOp, RafxCullMode, RafxFillMode,
-    RafxFilterType, RafxFrontFace, RafxIndexType, RafxLoadOp, RafxMemoryUsage, RafxMipMapMode,
-    RafxPrimitiveTopology, RafxSampleCount, RafxStencilOp, RafxStoreOp, RafxSwapchainColorSpace,
-    RafxVertexAttributeRate,
+    RafxBlendFactor, RafxBlendOp, RafxColorClearValue, RafxColorFlags, RafxCompareOp, RafxCullMode,
+    RafxFillMode, RafxFilterType, RafxFrontFace, RafxIndexType, RafxLoadOp, RafxMemoryUsage,
+    RafxMipMapMode, RafxPrimitiveTopology, RafxSampleCount, RafxStencilOp, RafxStoreOp,
+    RafxSwapchainColorSpace, RafxVertexAttributeRate,
 };
 use cocoa_foundation::foundation::NSUInteger;
 use core_foundation::string::CFStringRef;
 use metal_rs::{
-    MTLBlendFactor, MTLBlendOperation, MTLCPUCacheMode, MTLClearColor, MTLCompareFunction,
-    MTLCullMode, MTLIndexType, MTLLoadAction, MTLPrimitiveTopologyClass, MTLPrimitiveType,
-    MTLResourceOptions, MTLSamplerMinMagFilter, MTLSamplerMipFilter, MTLStencilOperation,
-    MTLStorageMode, MTLStoreAction, MTLTriangleFillMode, MTLVertexStepFunction, MTLWinding,
+    MTLBlendFactor, MTLBlendOperation, MTLCPUCacheMode, MTLClearColor, MTLColorWriteMask,
+    MTLCompareFunction, MTLCullMode, MTLIndexType, MTLLoadAction, MTLPrimitiveTopologyClass,
+    MTLPrimitiveType, MTLResourceOptions, MTLSamplerMinMagFilter, MTLSamplerMipFilter,
+    MTLStencilOperation, MTLStorageMode, MTLStoreAction, MTLTriangleFillMode,
+    MTLVertexStepFunction, MTLWinding,
 };
 
 impl Into<CFStringRef> for RafxSwapchainColorSpace {
@@ -76,7 +77,7 @@ impl Into<MTLPrimitiveTopologyClass> for RafxPrimitiveTopology {
             RafxPrimitiveTopology::LineStrip => MTLPrimitiveTopologyClass::Line,
             RafxPrimitiveTopology::TriangleList => MTLPrimitiveTopologyClass::Triangle,
             RafxPrimitiveTopology::TriangleStrip => MTLPrimitiveTopologyClass::Triangle,
-            RafxPrimitiveTopology::PatchList => MTLPrimitiveTopologyClass::Triangle,
+            RafxPrimitiveTopology::PatchList { .. } => MTLPrimitiveTopologyClass::Triangle,
         }
     }
 }
@@ -89,7 +90,7 @@ impl Into<MTLPrimitiveType> for RafxPrimitiveTopology {
             RafxPrimitiveTopology::LineStrip => MTLPrimitiveType::LineStrip,
             RafxPrimitiveTopology::TriangleList => MTLPrimitiveType::Triangle,
             RafxPrimitiveTopology::TriangleStrip => MTLPrimitiveType::TriangleStrip,
-            RafxPrimitiveTopology::PatchList => MTLPrimitiveType::Triangle,
+            RafxPrimitiveTopology::PatchList { .. } => MTLPrimitiveType::Triangle,
         }
     }
 }
@@ -193,6 +194,25 @@ impl Into<MTLBlendFactor> for RafxBlendFactor {
     }
 }
 
+impl Into<MTLColorWriteMask> for RafxColorFlags {
+    fn into(self) -> MTLColorWriteMask {
+        let mut mask = MTLColorWriteMask::empty();
+        if self.intersects(RafxColorFlags::RED) {
+            mask |= MTLColorWriteMask::Red
+        }
+        if self.intersects(RafxColorFlags::GREEN) {
+            mask |= MTLColorWriteMask::Green
+        }
+        if self.intersects(RafxColorFlags::BLUE) {
+            mask |= MTLColorWriteMask::Blue
+        }
+        if self.intersects(RafxColorFlags::ALPHA) {
+            mask |= MTLColorWriteMask::Alpha
+        }
+        mask
+    }
+}
+
 impl Into<MTLLoadAction> for RafxLoadOp {
     fn into(self) -> MTLLoadAction {
         match self {
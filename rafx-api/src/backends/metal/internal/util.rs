@@ -4,8 +4,8 @@ use crate::{
 };
 use cocoa_foundation::foundation::NSUInteger;
 use metal_rs::{
-    MTLArgumentAccess, MTLCompareFunction, MTLDataType, MTLResourceUsage, MTLSamplerAddressMode,
-    MTLStoreAction, RenderPipelineColorAttachmentDescriptorArrayRef,
+    MTLArgumentAccess, MTLColorWriteMask, MTLCompareFunction, MTLDataType, MTLResourceUsage,
+    MTLSamplerAddressMode, MTLStoreAction, RenderPipelineColorAttachmentDescriptorArrayRef,
 };
 
 pub fn vertex_buffer_adjusted_buffer_index(binding: u32) -> NSUInteger {
@@ -104,6 +104,7 @@ pub(crate) fn blend_def_to_attachment(
 
     if !blend_state.render_target_blend_states.is_empty() {
         for attachment_index in 0..color_attachment_count {
+            let descriptor = attachments.object_at(attachment_index as _).unwrap();
             if blend_state
                 .render_target_mask
                 .intersects(RafxBlendStateTargets::from_bits(1 << attachment_index).unwrap())
@@ -115,8 +116,8 @@ pub(crate) fn blend_def_to_attachment(
                     0
                 };
 
-                let descriptor = attachments.object_at(attachment_index as _).unwrap();
                 let def = &blend_state.render_target_blend_states[def_index];
+                descriptor.set_write_mask(def.masks.into());
                 descriptor.set_blending_enabled(def.blend_enabled());
                 descriptor.set_rgb_blend_operation(def.blend_op.into());
                 descriptor.set_alpha_blend_operation(def.blend_op_alpha.into());
@@ -124,7 +125,11 @@ pub(crate) fn blend_def_to_attachment(
                 descriptor.set_source_alpha_blend_factor(def.src_factor_alpha.into());
                 descriptor.set_destination_rgb_blend_factor(def.dst_factor.into());
                 descriptor.set_destination_alpha_blend_factor(def.dst_factor_alpha.into());
-            };
+            } else {
+                // Targets not in render_target_mask are not written (on vulkan they get a default
+                // attachment state, which has an empty write mask)
+                descriptor.set_write_mask(MTLColorWriteMask::empty());
+            }
         }
     }
 }
@@ -191,3 +196,64 @@ pub fn color_render_target_binding_mtl_store_op(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{RafxBlendStateRenderTarget, RafxColorFlags};
+
+    fn write_masks(
+        blend_state: &RafxBlendState,
+        color_attachment_count: usize,
+    ) -> Vec<MTLColorWriteMask> {
+        let descriptor = metal_rs::RenderPipelineDescriptor::new();
+        let attachments = descriptor.color_attachments();
+        blend_def_to_attachment(blend_state, attachments, color_attachment_count);
+        (0..color_attachment_count)
+            .map(|i| attachments.object_at(i as _).unwrap().write_mask())
+            .collect()
+    }
+
+    #[test]
+    fn test_blend_state_write_masks() {
+        let red_alpha = RafxBlendStateRenderTarget {
+            masks: RafxColorFlags::RED | RafxColorFlags::ALPHA,
+            ..RafxBlendStateRenderTarget::default_alpha_disabled()
+        };
+
+        // One blend state for every target in the mask
+        let blend_state = RafxBlendState {
+            render_target_blend_states: vec![red_alpha.clone()],
+            render_target_mask: RafxBlendStateTargets::BLEND_STATE_TARGET_0
+                | RafxBlendStateTargets::BLEND_STATE_TARGET_2,
+            independent_blend: false,
+        };
+
+        assert_eq!(
+            write_masks(&blend_state, 3),
+            vec![
+                MTLColorWriteMask::Red | MTLColorWriteMask::Alpha,
+                MTLColorWriteMask::empty(),
+                MTLColorWriteMask::Red | MTLColorWriteMask::Alpha,
+            ]
+        );
+
+        // A blend state per target
+        let blend_state = RafxBlendState {
+            render_target_blend_states: vec![
+                RafxBlendStateRenderTarget::default_alpha_disabled(),
+                red_alpha,
+            ],
+            render_target_mask: RafxBlendStateTargets::BLEND_STATE_TARGET_1,
+            independent_blend: true,
+        };
+
+        assert_eq!(
+            write_masks(&blend_state, 2),
+            vec![
+                MTLColorWriteMask::empty(),
+                MTLColorWriteMask::Red | MTLColorWriteMask::Alpha,
+            ]
+        );
+    }
+}
@@ -84,6 +84,17 @@ impl RafxPipelineMetal {
         device_context: &RafxDeviceContextMetal,
         pipeline_def: &RafxGraphicsPipelineDef,
     ) -> RafxResult<Self> {
+        let unsupported_stages = RafxShaderStageFlags::GEOMETRY
+            | RafxShaderStageFlags::TESSELLATION_CONTROL
+            | RafxShaderStageFlags::TESSELLATION_EVALUATION;
+        let stage_flags = pipeline_def.shader.metal_shader().unwrap().stage_flags();
+        if stage_flags.intersects(unsupported_stages) {
+            return Err(format!(
+                "The metal backend does not support geometry or tessellation shader stages (stages: {:?})",
+                stage_flags
+            ))?;
+        }
+
         let pipeline = metal_rs::RenderPipelineDescriptor::new();
 
         let mut vertex_function = None;
@@ -173,6 +173,12 @@ impl RafxRootSignatureMetal {
         let (pipeline_type, mut merged_resources, _merged_resources_name_index_map) =
             crate::internal_shared::merge_resources(root_signature_def)?;
 
+        crate::internal_shared::validate_bindless_descriptors(
+            root_signature_def,
+            &merged_resources,
+            device_context.device_info(),
+        )?;
+
         merged_resources.sort_by(|lhs, rhs| lhs.binding.cmp(&rhs.binding));
 
         let mut layouts = [
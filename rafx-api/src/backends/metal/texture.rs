@@ -1,7 +1,7 @@
 use crate::metal::RafxDeviceContextMetal;
 use crate::{
-    RafxMemoryUsage, RafxResourceType, RafxResult, RafxSampleCount, RafxTextureDef,
-    RafxTextureDimensions,
+    RafxMemoryAllocation, RafxMemoryUsage, RafxResourceType, RafxResult, RafxSampleCount,
+    RafxTextureDef, RafxTextureDimensions,
 };
 use metal_rs::{MTLTextureType, MTLTextureUsage};
 use std::hash::{Hash, Hasher};
@@ -36,6 +36,7 @@ pub struct RafxTextureMetalInner {
     image: RafxRawImageMetal,
     mip_level_uav_views: Vec<metal_rs::Texture>,
     texture_id: u32,
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 /// Holds the vk::Image and allocation as well as a few vk::ImageViews depending on the
@@ -70,6 +71,17 @@ impl Hash for RafxTextureMetal {
 }
 
 impl RafxTextureMetal {
+    // Called by RafxDeviceContext right after creating the texture so that it is counted in the
+    // device context's memory stats until the last clone is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        Arc::get_mut(&mut self.inner)
+            .expect("The memory allocation must be set before the texture is cloned")
+            .memory_allocation = Some(memory_allocation);
+    }
+
     pub fn texture_def(&self) -> &RafxTextureDef {
         &self.inner.texture_def
     }
@@ -223,6 +235,7 @@ impl RafxTextureMetal {
             image,
             mip_level_uav_views,
             texture_id,
+            memory_allocation: None,
         };
 
         Ok(RafxTextureMetal {
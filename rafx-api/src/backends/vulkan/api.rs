@@ -45,6 +45,11 @@ pub struct RafxApiDefVulkan {
 
     /// Override the default enabled features with a custom set of features
     pub physical_device_features: Option<vk::PhysicalDeviceFeatures>,
+
+    /// Enable descriptor indexing (VK_EXT_descriptor_indexing) if the device supports it. This
+    /// allows root signatures to have bindless descriptors. Check
+    /// `RafxDeviceInfo::supports_bindless` to see if it was enabled.
+    pub enable_bindless: bool,
     // The OS-specific layers/extensions are already included. Debug layers/extension are included
     // if enable_validation is true
     //TODO: Additional instance layer names
@@ -59,6 +64,7 @@ impl Default for RafxApiDefVulkan {
             link_method: Default::default(),
             validation_mode: Default::default(),
             physical_device_features: None,
+            enable_bindless: false,
         }
     }
 }
@@ -126,6 +132,7 @@ impl RafxApiVulkan {
         let inner = Arc::new(RafxDeviceContextVulkanInner::new(
             &instance,
             &vk_api_def.physical_device_features,
+            vk_api_def.enable_bindless,
         )?);
         let device_context = RafxDeviceContextVulkan::new(inner)?;
 
@@ -140,6 +147,11 @@ impl RafxApiVulkan {
             // Clear any internal caches that may hold references to the device
             let inner = device_context.inner.clone();
             inner.descriptor_heap.clear_pools(device_context.device());
+            if let Some(update_after_bind_descriptor_heap) =
+                &inner.update_after_bind_descriptor_heap
+            {
+                update_after_bind_descriptor_heap.clear_pools(device_context.device());
+            }
             inner.resource_cache.clear_caches();
 
             #[cfg(debug_assertions)]
@@ -17,9 +17,20 @@ pub struct RafxBufferVulkan {
     buffer_def: RafxBufferDef,
     uniform_texel_view: Option<vk::BufferView>,
     storage_texel_view: Option<vk::BufferView>,
+
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 impl RafxBufferVulkan {
+    // Called by RafxDeviceContext right after creating the buffer so that it is counted in the
+    // device context's memory stats until it is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        self.memory_allocation = Some(memory_allocation);
+    }
+
     pub fn vk_buffer(&self) -> vk::Buffer {
         self.buffer_raw.as_ref().unwrap().buffer
     }
@@ -113,8 +124,10 @@ impl RafxBufferVulkan {
             buffer_def.format != RafxFormat::UNDEFINED,
         );
 
+        // GpuToCpu buffers are typically readback targets for texture/buffer copies
         if buffer_def.memory_usage == RafxMemoryUsage::GpuOnly
             || buffer_def.memory_usage == RafxMemoryUsage::CpuToGpu
+            || buffer_def.memory_usage == RafxMemoryUsage::GpuToCpu
         {
             usage_flags |= vk::BufferUsageFlags::TRANSFER_DST;
         }
@@ -214,6 +227,7 @@ impl RafxBufferVulkan {
             buffer_def: buffer_def.clone(),
             uniform_texel_view,
             storage_texel_view,
+            memory_allocation: None,
         })
     }
 }
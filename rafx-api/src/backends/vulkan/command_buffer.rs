@@ -872,6 +872,47 @@ impl RafxCommandBufferVulkan {
         Ok(())
     }
 
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTextureVulkan,
+        dst_buffer: &RafxBufferVulkan,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        let texture_def = src_texture.texture_def();
+
+        let width = 1.max(texture_def.extents.width >> params.mip_level);
+        let height = 1.max(texture_def.extents.height >> params.mip_level);
+        let depth = 1.max(texture_def.extents.depth >> params.mip_level);
+
+        unsafe {
+            self.device_context.device().cmd_copy_image_to_buffer(
+                self.vk_command_buffer,
+                src_texture.vk_image(),
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst_buffer.vk_buffer(),
+                &[vk::BufferImageCopy {
+                    image_extent: vk::Extent3D {
+                        width,
+                        height,
+                        depth,
+                    },
+                    image_offset: vk::Offset3D { x: 0, y: 0, z: 0 },
+                    image_subresource: vk::ImageSubresourceLayers {
+                        aspect_mask: src_texture.vk_aspect_mask(),
+                        mip_level: params.mip_level as u32,
+                        base_array_layer: params.array_layer as u32,
+                        layer_count: 1,
+                    },
+                    buffer_offset: params.buffer_offset,
+                    buffer_image_height: 0,
+                    buffer_row_length: 0,
+                }],
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_copy_texture_to_texture(
         &self,
         src_texture: &RafxTextureVulkan,
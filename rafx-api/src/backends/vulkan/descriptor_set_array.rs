@@ -1,4 +1,4 @@
-use crate::vulkan::{DescriptorSetLayoutInfo, RafxDescriptorHeapVulkan, RafxDeviceContextVulkan};
+use crate::vulkan::{DescriptorSetLayoutInfo, RafxDeviceContextVulkan};
 use crate::*;
 use ash::version::DeviceV1_0;
 use ash::vk;
@@ -85,7 +85,6 @@ impl RafxDescriptorSetArrayVulkan {
 
     pub(crate) fn new(
         device_context: &RafxDeviceContextVulkan,
-        heap: &RafxDescriptorHeapVulkan,
         descriptor_set_array_def: &RafxDescriptorSetArrayDef,
     ) -> RafxResult<Self> {
         let root_signature = descriptor_set_array_def
@@ -122,6 +121,15 @@ impl RafxDescriptorSetArrayVulkan {
             // }
         }
 
+        // Layouts with bindless descriptors must be allocated from pools that allow update-after-bind
+        let heap = if root_signature.inner.layouts[layout_index].update_after_bind {
+            device_context.update_after_bind_descriptor_heap().ok_or(
+                "Descriptor set layout has bindless descriptors but bindless is not enabled",
+            )?
+        } else {
+            device_context.descriptor_heap()
+        };
+
         let descriptor_sets =
             heap.allocate_descriptor_sets(device_context.device(), &descriptors_set_layouts)?;
 
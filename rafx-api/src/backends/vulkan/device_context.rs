@@ -1,6 +1,6 @@
 use super::internal::*;
 use crate::*;
-use ash::version::{DeviceV1_0, InstanceV1_0};
+use ash::version::{DeviceV1_0, InstanceV1_0, InstanceV1_1};
 use ash::vk;
 use raw_window_handle::HasRawWindowHandle;
 use std::mem::ManuallyDrop;
@@ -60,6 +60,8 @@ pub struct PhysicalDeviceInfo {
     pub queue_family_indices: VkQueueFamilyIndices,
     pub properties: vk::PhysicalDeviceProperties,
     pub features: vk::PhysicalDeviceFeatures,
+    // True if the descriptor indexing features needed for bindless descriptors are available
+    pub supports_bindless: bool,
     pub extension_properties: Vec<ash::vk::ExtensionProperties>,
     pub all_queue_families: Vec<ash::vk::QueueFamilyProperties>,
 }
@@ -74,6 +76,8 @@ pub struct VkQueueFamilyIndices {
 pub struct RafxDeviceContextVulkanInner {
     pub(crate) resource_cache: RafxDeviceVulkanResourceCache,
     pub(crate) descriptor_heap: RafxDescriptorHeapVulkan,
+    // Only exists if bindless descriptors are enabled
+    pub(crate) update_after_bind_descriptor_heap: Option<RafxDescriptorHeapVulkan>,
     pub(crate) device_info: RafxDeviceInfo,
     pub(crate) queue_allocator: VkQueueAllocatorSet,
 
@@ -88,6 +92,8 @@ pub struct RafxDeviceContextVulkanInner {
     instance: ash::Instance,
     physical_device: vk::PhysicalDevice,
     physical_device_info: PhysicalDeviceInfo,
+    supports_memory_budget: bool,
+    memory_tracker: RafxMemoryTracker,
 
     #[cfg(debug_assertions)]
     #[cfg(feature = "track-device-contexts")]
@@ -120,6 +126,7 @@ impl RafxDeviceContextVulkanInner {
     pub fn new(
         instance: &VkInstance,
         physical_device_features: &Option<vk::PhysicalDeviceFeatures>,
+        enable_bindless: bool,
     ) -> RafxResult<Self> {
         let physical_device_type_priority = vec![
             PhysicalDeviceType::DiscreteGpu,
@@ -139,6 +146,16 @@ impl RafxDeviceContextVulkanInner {
             VkQueueAllocationStrategy::ShareFirstQueueInFamily,
         );
 
+        let supports_memory_budget =
+            has_device_extension(&physical_device_info, vk::ExtMemoryBudgetFn::name());
+
+        let supports_bindless = if enable_bindless && !physical_device_info.supports_bindless {
+            log::warn!("Bindless descriptors were requested but the device does not support the required descriptor indexing features");
+            false
+        } else {
+            enable_bindless
+        };
+
         // Create a logical device
         let logical_device = create_logical_device(
             &instance.instance,
@@ -146,6 +163,7 @@ impl RafxDeviceContextVulkanInner {
             &physical_device_info,
             &queue_requirements,
             physical_device_features,
+            supports_bindless,
         )?;
 
         let queue_allocator = VkQueueAllocatorSet::new(
@@ -166,6 +184,11 @@ impl RafxDeviceContextVulkanInner {
 
         let limits = &physical_device_info.properties.limits;
 
+        // Features that were explicitly requested, otherwise the defaults from create_logical_device
+        let enabled_features = physical_device_features
+            .as_ref()
+            .unwrap_or(&physical_device_info.features);
+
         let device_info = RafxDeviceInfo {
             supports_multithreaded_usage: true,
             min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment as u32,
@@ -175,10 +198,20 @@ impl RafxDeviceContextVulkanInner {
                 as u32,
             supports_clamp_to_border_color: true,
             max_vertex_attribute_count: limits.max_vertex_input_attributes,
+            supports_geometry_shaders: enabled_features.geometry_shader != 0,
+            supports_tessellation_shaders: enabled_features.tessellation_shader != 0,
+            supports_bindless,
         };
 
         let resource_cache = RafxDeviceVulkanResourceCache::default();
         let descriptor_heap = RafxDescriptorHeapVulkan::new(&logical_device)?;
+        let update_after_bind_descriptor_heap = if supports_bindless {
+            Some(RafxDescriptorHeapVulkan::new_update_after_bind(
+                &logical_device,
+            )?)
+        } else {
+            None
+        };
 
         #[cfg(debug_assertions)]
         #[cfg(feature = "track-device-contexts")]
@@ -192,6 +225,7 @@ impl RafxDeviceContextVulkanInner {
         Ok(RafxDeviceContextVulkanInner {
             resource_cache,
             descriptor_heap,
+            update_after_bind_descriptor_heap,
             device_info,
             queue_allocator,
             dedicated_present_queue_lock: Mutex::default(),
@@ -199,6 +233,8 @@ impl RafxDeviceContextVulkanInner {
             instance: instance.instance.clone(),
             physical_device,
             physical_device_info,
+            supports_memory_budget,
+            memory_tracker: RafxMemoryTracker::default(),
             device: logical_device,
             allocator: ManuallyDrop::new(Mutex::new(allocator)),
             destroyed: AtomicBool::new(false),
@@ -294,6 +330,10 @@ impl RafxDeviceContextVulkan {
         &self.inner.descriptor_heap
     }
 
+    pub(crate) fn update_after_bind_descriptor_heap(&self) -> Option<&RafxDescriptorHeapVulkan> {
+        self.inner.update_after_bind_descriptor_heap.as_ref()
+    }
+
     pub fn device_info(&self) -> &RafxDeviceInfo {
         &self.inner.device_info
     }
@@ -326,6 +366,50 @@ impl RafxDeviceContextVulkan {
         &self.inner.allocator
     }
 
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker {
+        &self.inner.memory_tracker
+    }
+
+    // gpu-allocator does not expose heap budgets, so they are queried directly. Budget and usage
+    // are only filled in if VK_EXT_memory_budget is available.
+    pub fn memory_heap_stats(&self) -> Vec<RafxMemoryHeapStats> {
+        let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties = vk::PhysicalDeviceMemoryProperties2::builder();
+        if self.inner.supports_memory_budget {
+            memory_properties = memory_properties.push_next(&mut budget_properties);
+        }
+
+        let mut memory_properties = memory_properties.build();
+        unsafe {
+            self.instance().get_physical_device_memory_properties2(
+                self.physical_device(),
+                &mut memory_properties,
+            );
+        }
+
+        let supports_memory_budget = self.inner.supports_memory_budget;
+        let memory_properties = memory_properties.memory_properties;
+        (0..memory_properties.memory_heap_count as usize)
+            .map(|heap_index| {
+                let heap = &memory_properties.memory_heaps[heap_index];
+                RafxMemoryHeapStats {
+                    size: heap.size,
+                    device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                    budget: if supports_memory_budget {
+                        Some(budget_properties.heap_budget[heap_index])
+                    } else {
+                        None
+                    },
+                    usage: if supports_memory_budget {
+                        Some(budget_properties.heap_usage[heap_index])
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect()
+    }
+
     pub fn queue_allocator(&self) -> &VkQueueAllocatorSet {
         &self.inner.queue_allocator
     }
@@ -422,7 +506,7 @@ impl RafxDeviceContextVulkan {
         &self,
         descriptor_set_array_def: &RafxDescriptorSetArrayDef,
     ) -> RafxResult<RafxDescriptorSetArrayVulkan> {
-        RafxDescriptorSetArrayVulkan::new(self, self.descriptor_heap(), descriptor_set_array_def)
+        RafxDescriptorSetArrayVulkan::new(self, descriptor_set_array_def)
     }
 
     pub fn create_graphics_pipeline(
@@ -622,6 +706,7 @@ fn query_physical_device_info(
         unsafe { instance.enumerate_device_extension_properties(device)? };
     let features: vk::PhysicalDeviceFeatures =
         unsafe { instance.get_physical_device_features(device) };
+    let supports_bindless = query_supports_bindless(instance, device, &extensions);
     let all_queue_families: Vec<ash::vk::QueueFamilyProperties> =
         unsafe { instance.get_physical_device_queue_family_properties(device) };
 
@@ -659,6 +744,7 @@ fn query_physical_device_info(
             properties,
             extension_properties: extensions,
             features,
+            supports_bindless,
             all_queue_families,
         };
 
@@ -797,12 +883,72 @@ fn find_queue_families(
     }
 }
 
+fn has_device_extension(
+    physical_device_info: &PhysicalDeviceInfo,
+    name: &CStr,
+) -> bool {
+    physical_device_info
+        .extension_properties
+        .iter()
+        .any(|extension| unsafe { CStr::from_ptr(extension.extension_name.as_ptr()) } == name)
+}
+
+// The descriptor indexing features required for bindless descriptors. Arrays of textures and
+// storage buffers can be indexed with non-uniform values, partially bound, and written after the
+// descriptor set is bound as long as in-flight work does not use the written elements.
+fn bindless_descriptor_indexing_features() -> vk::PhysicalDeviceDescriptorIndexingFeatures {
+    vk::PhysicalDeviceDescriptorIndexingFeatures::builder()
+        .shader_sampled_image_array_non_uniform_indexing(true)
+        .shader_storage_buffer_array_non_uniform_indexing(true)
+        .descriptor_binding_sampled_image_update_after_bind(true)
+        .descriptor_binding_storage_image_update_after_bind(true)
+        .descriptor_binding_storage_buffer_update_after_bind(true)
+        .descriptor_binding_update_unused_while_pending(true)
+        .descriptor_binding_partially_bound(true)
+        .runtime_descriptor_array(true)
+        .build()
+}
+
+fn query_supports_bindless(
+    instance: &ash::Instance,
+    device: ash::vk::PhysicalDevice,
+    extensions: &[ash::vk::ExtensionProperties],
+) -> bool {
+    let has_descriptor_indexing_extension = extensions.iter().any(|extension| {
+        let extension_name = unsafe { CStr::from_ptr(extension.extension_name.as_ptr()) };
+        extension_name == vk::ExtDescriptorIndexingFn::name()
+    });
+
+    if !has_descriptor_indexing_extension {
+        return false;
+    }
+
+    let mut supported = vk::PhysicalDeviceDescriptorIndexingFeatures::default();
+    {
+        let mut features2 = vk::PhysicalDeviceFeatures2::builder().push_next(&mut supported);
+        unsafe {
+            instance.get_physical_device_features2(device, &mut features2);
+        }
+    }
+
+    // Must match the features enabled by bindless_descriptor_indexing_features()
+    supported.shader_sampled_image_array_non_uniform_indexing != 0
+        && supported.shader_storage_buffer_array_non_uniform_indexing != 0
+        && supported.descriptor_binding_sampled_image_update_after_bind != 0
+        && supported.descriptor_binding_storage_image_update_after_bind != 0
+        && supported.descriptor_binding_storage_buffer_update_after_bind != 0
+        && supported.descriptor_binding_update_unused_while_pending != 0
+        && supported.descriptor_binding_partially_bound != 0
+        && supported.runtime_descriptor_array != 0
+}
+
 fn create_logical_device(
     instance: &ash::Instance,
     physical_device: ash::vk::PhysicalDevice,
     physical_device_info: &PhysicalDeviceInfo,
     queue_requirements: &VkQueueRequirements,
     physical_device_features: &Option<vk::PhysicalDeviceFeatures>,
+    enable_bindless: bool,
 ) -> RafxResult<ash::Device> {
     //TODO: Ideally we would set up validation layers for the logical device too.
 
@@ -813,14 +959,21 @@ fn create_logical_device(
     let mut device_extension_names = vec![khr::Swapchain::name().as_ptr()];
 
     // Add VK_KHR_portability_subset if the extension exists (this is mandated by spec)
-    let portability_subset_extension_name = khr_portability_subset_extension_name();
-    for extension in &physical_device_info.extension_properties {
-        let extension_name = unsafe { CStr::from_ptr(extension.extension_name.as_ptr()) };
+    if has_device_extension(
+        physical_device_info,
+        khr_portability_subset_extension_name(),
+    ) {
+        device_extension_names.push(khr_portability_subset_extension_name().as_ptr());
+    }
 
-        if extension_name == portability_subset_extension_name {
-            device_extension_names.push(khr_portability_subset_extension_name().as_ptr());
-            break;
-        }
+    // Used to report heap budgets, optional
+    if has_device_extension(physical_device_info, vk::ExtMemoryBudgetFn::name()) {
+        device_extension_names.push(vk::ExtMemoryBudgetFn::name().as_ptr());
+    }
+
+    // Used for bindless descriptors, optional. The caller already checked that it's supported.
+    if enable_bindless {
+        device_extension_names.push(vk::ExtDescriptorIndexingFn::name().as_ptr());
     }
 
     // If no features were specified, enable a few that are very widely supported features.
@@ -832,6 +985,9 @@ fn create_logical_device(
             .fill_mode_non_solid(true)
             // We can trivially fake this if the feature isn't available, so we can have it on by default
             .multi_draw_indirect(physical_device_info.features.multi_draw_indirect != 0)
+            // Enabled if available so that pipelines with these stages can be created
+            .geometry_shader(physical_device_info.features.geometry_shader != 0)
+            .tessellation_shader(physical_device_info.features.tessellation_shader != 0)
             .build()
     });
 
@@ -850,11 +1006,17 @@ fn create_logical_device(
         })
         .collect();
 
-    let device_create_info = vk::DeviceCreateInfo::builder()
+    let mut descriptor_indexing_features = bindless_descriptor_indexing_features();
+
+    let mut device_create_info = vk::DeviceCreateInfo::builder()
         .queue_create_infos(&queue_infos)
         .enabled_extension_names(&device_extension_names)
         .enabled_features(&physical_device_features);
 
+    if enable_bindless {
+        device_create_info = device_create_info.push_next(&mut descriptor_indexing_features);
+    }
+
     let device: ash::Device =
         unsafe { instance.create_device(physical_device, &device_create_info, None)? };
 
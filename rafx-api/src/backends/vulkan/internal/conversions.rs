@@ -135,7 +135,7 @@ impl Into<vk::PrimitiveTopology> for RafxPrimitiveTopology {
             RafxPrimitiveTopology::LineStrip => vk::PrimitiveTopology::LINE_STRIP,
             RafxPrimitiveTopology::TriangleList => vk::PrimitiveTopology::TRIANGLE_LIST,
             RafxPrimitiveTopology::TriangleStrip => vk::PrimitiveTopology::TRIANGLE_STRIP,
-            RafxPrimitiveTopology::PatchList => vk::PrimitiveTopology::PATCH_LIST,
+            RafxPrimitiveTopology::PatchList { .. } => vk::PrimitiveTopology::PATCH_LIST,
         }
     }
 }
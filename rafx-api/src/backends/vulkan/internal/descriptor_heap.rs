@@ -40,6 +40,27 @@ impl Default for RafxDescriptorHeapPoolConfig {
 }
 
 impl RafxDescriptorHeapPoolConfig {
+    // Pools for descriptor sets with bindless descriptors. Layouts that can be updated after bind
+    // may not contain dynamic buffers. Bindless descriptors are large arrays, so most of the budget
+    // goes to sampled images and storage buffers.
+    fn update_after_bind() -> Self {
+        RafxDescriptorHeapPoolConfig {
+            pool_flags: vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND,
+            descriptor_sets: 256,
+            samplers: 256,
+            combined_image_samplers: 0,
+            sampled_images: 16384,
+            storage_images: 1024,
+            uniform_texel_buffers: 256,
+            storage_texel_buffers: 256,
+            uniform_buffers: 1024,
+            storage_buffers: 16384,
+            dynamic_uniform_buffers: 0,
+            dynamic_storage_buffers: 0,
+            input_attachments: 0,
+        }
+    }
+
     fn create_pool(
         &self,
         device: &ash::Device,
@@ -124,7 +145,19 @@ pub(crate) struct RafxDescriptorHeapVulkan {
 
 impl RafxDescriptorHeapVulkan {
     pub(crate) fn new(device: &ash::Device) -> RafxResult<Self> {
-        let heap_pool_config = RafxDescriptorHeapPoolConfig::default();
+        Self::with_config(device, RafxDescriptorHeapPoolConfig::default())
+    }
+
+    // Allocates descriptor sets whose layouts were created with UPDATE_AFTER_BIND_POOL. Requires
+    // descriptor indexing to be enabled on the device.
+    pub(crate) fn new_update_after_bind(device: &ash::Device) -> RafxResult<Self> {
+        Self::with_config(device, RafxDescriptorHeapPoolConfig::update_after_bind())
+    }
+
+    fn with_config(
+        device: &ash::Device,
+        heap_pool_config: RafxDescriptorHeapPoolConfig,
+    ) -> RafxResult<Self> {
         let pool = heap_pool_config.create_pool(device)?;
 
         let inner = RafxDescriptorHeapVulkanInner {
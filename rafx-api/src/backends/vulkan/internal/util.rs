@@ -438,3 +438,64 @@ pub fn blend_state_to_create_info(
         blend_state: blend_state_create_info,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::RafxColorFlags;
+
+    #[test]
+    fn test_blend_state_write_masks() {
+        let red_alpha = RafxBlendStateRenderTarget {
+            masks: RafxColorFlags::RED | RafxColorFlags::ALPHA,
+            ..RafxBlendStateRenderTarget::default_alpha_disabled()
+        };
+
+        // One blend state for every target in the mask
+        let blend_state = RafxBlendState {
+            render_target_blend_states: vec![red_alpha.clone()],
+            render_target_mask: RafxBlendStateTargets::BLEND_STATE_TARGET_0
+                | RafxBlendStateTargets::BLEND_STATE_TARGET_2,
+            independent_blend: false,
+        };
+
+        let create_info = blend_state_to_create_info(&blend_state, 3);
+        let write_masks: Vec<_> = create_info
+            ._attachments
+            .iter()
+            .map(|x| x.color_write_mask)
+            .collect();
+        assert_eq!(
+            write_masks,
+            vec![
+                vk::ColorComponentFlags::R | vk::ColorComponentFlags::A,
+                vk::ColorComponentFlags::empty(),
+                vk::ColorComponentFlags::R | vk::ColorComponentFlags::A,
+            ]
+        );
+
+        // A blend state per target
+        let blend_state = RafxBlendState {
+            render_target_blend_states: vec![
+                RafxBlendStateRenderTarget::default_alpha_disabled(),
+                red_alpha,
+            ],
+            render_target_mask: RafxBlendStateTargets::BLEND_STATE_TARGET_1,
+            independent_blend: true,
+        };
+
+        let create_info = blend_state_to_create_info(&blend_state, 2);
+        let write_masks: Vec<_> = create_info
+            ._attachments
+            .iter()
+            .map(|x| x.color_write_mask)
+            .collect();
+        assert_eq!(
+            write_masks,
+            vec![
+                vk::ColorComponentFlags::empty(),
+                vk::ColorComponentFlags::R | vk::ColorComponentFlags::A,
+            ]
+        );
+    }
+}
@@ -48,6 +48,33 @@ impl RafxPipelineVulkan {
     ) -> RafxResult<Self> {
         //log::trace!("Create pipeline\n{:#?}", pipeline_def);
 
+        let stage_flags = pipeline_def.shader.vk_shader().unwrap().stage_flags();
+        let device_info = device_context.device_info();
+        if stage_flags.intersects(RafxShaderStageFlags::GEOMETRY)
+            && !device_info.supports_geometry_shaders
+        {
+            return Err("The shader has a geometry stage but geometry shaders are not supported or not enabled on this device")?;
+        }
+
+        let has_tessellation_stages = stage_flags.intersects(
+            RafxShaderStageFlags::TESSELLATION_CONTROL
+                | RafxShaderStageFlags::TESSELLATION_EVALUATION,
+        );
+        if has_tessellation_stages && !device_info.supports_tessellation_shaders {
+            return Err("The shader has tessellation stages but tessellation shaders are not supported or not enabled on this device")?;
+        }
+
+        let patch_control_points = match pipeline_def.primitive_topology {
+            RafxPrimitiveTopology::PatchList { control_points } => Some(control_points),
+            _ => None,
+        };
+        if has_tessellation_stages != patch_control_points.is_some() {
+            return Err(format!(
+                "Pipelines must use RafxPrimitiveTopology::PatchList if and only if they have tessellation stages (primitive topology: {:?}, stages: {:?})",
+                pipeline_def.primitive_topology, stage_flags
+            ))?;
+        }
+
         //TODO: Cache
         let vk_root_signature = pipeline_def.root_signature.vk_root_signature().unwrap();
 
@@ -151,7 +178,8 @@ impl RafxPipelineVulkan {
             .topology(pipeline_def.primitive_topology.into())
             .primitive_restart_enable(false);
 
-        // Tesselation not supported
+        let tessellation_state = vk::PipelineTessellationStateCreateInfo::builder()
+            .patch_control_points(patch_control_points.unwrap_or(0));
 
         // Set up for dynamic viewport/scissor
         let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
@@ -181,7 +209,7 @@ impl RafxPipelineVulkan {
         let dynamic_states_create_info =
             vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(&dynamic_states);
 
-        let pipeline_create_info = vk::GraphicsPipelineCreateInfo::builder()
+        let mut pipeline_create_info = vk::GraphicsPipelineCreateInfo::builder()
             .stages(&stages)
             .vertex_input_state(&vertex_input_state)
             .input_assembly_state(&input_assembly_state)
@@ -195,8 +223,13 @@ impl RafxPipelineVulkan {
             .render_pass(renderpass.vk_renderpass())
             .subpass(0)
             .base_pipeline_handle(vk::Pipeline::null())
-            .base_pipeline_index(-1)
-            .build();
+            .base_pipeline_index(-1);
+
+        if has_tessellation_stages {
+            pipeline_create_info = pipeline_create_info.tessellation_state(&tessellation_state);
+        }
+
+        let pipeline_create_info = pipeline_create_info.build();
 
         let pipeline = unsafe {
             match device_context.device().create_graphics_pipelines(
@@ -48,6 +48,9 @@ pub(crate) struct DescriptorSetLayoutInfo {
     pub(crate) update_data_count_per_set: u32,
     // This indexes into the descriptors list
     pub(crate) dynamic_descriptor_indexes: Vec<RafxDescriptorIndex>,
+    // True if the layout contains bindless descriptors. Descriptor sets with this layout must be
+    // allocated from a pool that supports update-after-bind
+    pub(crate) update_after_bind: bool,
 }
 
 #[derive(Debug)]
@@ -193,6 +196,12 @@ impl RafxRootSignatureVulkan {
         let (pipeline_type, merged_resources, _merged_resources_name_index_map) =
             crate::internal_shared::merge_resources(root_signature_def)?;
 
+        crate::internal_shared::validate_bindless_descriptors(
+            root_signature_def,
+            &merged_resources,
+            device_context.device_info(),
+        )?;
+
         let mut layouts = [
             DescriptorSetLayoutInfo::default(),
             DescriptorSetLayoutInfo::default(),
@@ -201,6 +210,7 @@ impl RafxRootSignatureVulkan {
         ];
 
         let mut vk_set_bindings = [vec![], vec![], vec![], vec![]];
+        let mut vk_set_binding_flags = [vec![], vec![], vec![], vec![]];
         let mut push_constant_descriptors = [None; ALL_SHADER_STAGE_FLAGS.len()];
 
         let mut name_to_descriptor_index = FnvHashMap::default();
@@ -210,7 +220,7 @@ impl RafxRootSignatureVulkan {
         // We don't create descriptors for immutable samplers
         //
         for resource in &merged_resources {
-            let vk_stage_flags = resource.used_in_shader_stages.into();
+            let mut vk_stage_flags = resource.used_in_shader_stages.into();
 
             resource.validate()?;
 
@@ -218,6 +228,23 @@ impl RafxRootSignatureVulkan {
                 let vk_descriptor_type =
                     super::util::resource_type_to_descriptor_type(resource.resource_type).unwrap();
 
+                let is_bindless = crate::internal_shared::is_bindless_descriptor(
+                    root_signature_def.bindless_descriptors,
+                    resource.set_index,
+                    resource.binding,
+                );
+
+                let vk_binding_flags = if is_bindless {
+                    // Bindless descriptors are usually shared by many pipelines. Making them visible
+                    // to all stages keeps layouts compatible regardless of which stages use them.
+                    vk_stage_flags = vk::ShaderStageFlags::ALL;
+                    vk::DescriptorBindingFlags::PARTIALLY_BOUND
+                        | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+                        | vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                } else {
+                    vk::DescriptorBindingFlags::empty()
+                };
+
                 // It's not a push constant, so create a vk binding for it
                 let mut binding = vk::DescriptorSetLayoutBinding::builder()
                     .binding(resource.binding)
@@ -270,6 +297,10 @@ impl RafxRootSignatureVulkan {
                 let vk_bindings: &mut Vec<vk::DescriptorSetLayoutBinding> =
                     &mut vk_set_bindings[resource.set_index as usize];
 
+                if is_bindless {
+                    layout.update_after_bind = true;
+                }
+
                 if immutable_sampler.is_some()
                     && !resource
                         .resource_type
@@ -335,6 +366,7 @@ impl RafxRootSignatureVulkan {
 
                 // Add the binding to the list
                 vk_bindings.push(binding.build());
+                vk_set_binding_flags[resource.set_index as usize].push(vk_binding_flags);
             } else {
                 let vk_push_constant_range = vk::PushConstantRange::builder()
                     .offset(0)
@@ -409,11 +441,25 @@ impl RafxRootSignatureVulkan {
             // Create this layout
             //
             {
+                let mut binding_flags_create_info =
+                    vk::DescriptorSetLayoutBindingFlagsCreateInfo::builder()
+                        .binding_flags(&vk_set_binding_flags[layout_index]);
+
+                let mut create_info =
+                    vk::DescriptorSetLayoutCreateInfo::builder().bindings(&vk_bindings);
+
+                // Only chain the binding flags if there are bindless descriptors, descriptor
+                // indexing may not be enabled on the device
+                if layouts[layout_index].update_after_bind {
+                    create_info = create_info
+                        .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+                        .push_next(&mut binding_flags_create_info);
+                }
+
                 let descriptor_set_layout = unsafe {
-                    device_context.device().create_descriptor_set_layout(
-                        &*vk::DescriptorSetLayoutCreateInfo::builder().bindings(&vk_bindings),
-                        None,
-                    )?
+                    device_context
+                        .device()
+                        .create_descriptor_set_layout(&*create_info, None)?
                 };
 
                 descriptor_set_layouts[descriptor_set_layout_count] = descriptor_set_layout;
@@ -71,6 +71,8 @@ pub struct RafxTextureVulkanInner {
     texture_id: u32,
     render_target_view: Option<vk::ImageView>,
     render_target_view_slices: Vec<vk::ImageView>,
+
+    memory_allocation: Option<RafxMemoryAllocation>,
 }
 
 impl Drop for RafxTextureVulkanInner {
@@ -131,6 +133,17 @@ impl Hash for RafxTextureVulkan {
 }
 
 impl RafxTextureVulkan {
+    // Called by RafxDeviceContext right after creating the texture so that it is counted in the
+    // device context's memory stats until the last clone is dropped
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        Arc::get_mut(&mut self.inner)
+            .expect("The memory allocation must be set before the texture is cloned")
+            .memory_allocation = Some(memory_allocation);
+    }
+
     pub fn texture_def(&self) -> &RafxTextureDef {
         &self.inner.texture_def
     }
@@ -538,6 +551,7 @@ impl RafxTextureVulkan {
             render_target_view,
             render_target_view_slices,
             is_in_initial_undefined_layout: AtomicBool::new(true),
+            memory_allocation: None,
         };
 
         Ok(RafxTextureVulkan {
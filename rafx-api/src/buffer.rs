@@ -16,7 +16,7 @@ use crate::gles3::RafxBufferGles3;
 use crate::metal::RafxBufferMetal;
 #[cfg(feature = "rafx-vulkan")]
 use crate::vulkan::RafxBufferVulkan;
-use crate::{RafxBufferDef, RafxResult};
+use crate::{RafxBufferDef, RafxMemoryAllocation, RafxResult};
 
 /// Memory that can be accessed by the rendering API. It may reside in CPU or GPU memory.
 ///
@@ -44,6 +44,33 @@ pub enum RafxBuffer {
 }
 
 impl RafxBuffer {
+    // Called by RafxDeviceContext right after creating the buffer
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxBuffer::Vk(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-metal")]
+            RafxBuffer::Metal(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-gles2")]
+            RafxBuffer::Gles2(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-gles3")]
+            RafxBuffer::Gles3(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxBuffer::Empty(inner) => inner.set_memory_allocation(memory_allocation),
+        }
+    }
+
     /// Copy all the data in the given slice into the buffer. This function will fail if the buffer
     /// is not writable by the CPU. This function will assert/panic if the buffer is too small to
     /// hold the data.
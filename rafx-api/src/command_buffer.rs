@@ -18,10 +18,11 @@ use crate::metal::RafxCommandBufferMetal;
 use crate::vulkan::RafxCommandBufferVulkan;
 use crate::{
     RafxBuffer, RafxBufferBarrier, RafxCmdCopyBufferToBufferParams,
-    RafxCmdCopyBufferToTextureParams, RafxCmdCopyTextureToTextureParams,
-    RafxColorRenderTargetBinding, RafxDepthStencilRenderTargetBinding, RafxDescriptorIndex,
-    RafxDescriptorSetArray, RafxDescriptorSetHandle, RafxIndexBufferBinding, RafxPipeline,
-    RafxResult, RafxRootSignature, RafxTexture, RafxTextureBarrier, RafxVertexBufferBinding,
+    RafxCmdCopyBufferToTextureParams, RafxCmdCopyTextureToBufferParams,
+    RafxCmdCopyTextureToTextureParams, RafxColorRenderTargetBinding,
+    RafxDepthStencilRenderTargetBinding, RafxDescriptorIndex, RafxDescriptorSetArray,
+    RafxDescriptorSetHandle, RafxIndexBufferBinding, RafxPipeline, RafxResult, RafxRootSignature,
+    RafxTexture, RafxTextureBarrier, RafxVertexBufferBinding,
 };
 
 /// A list of commands recorded by the CPU and submitted to the GPU.
@@ -1028,6 +1029,58 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Copy the contents of a texture into a buffer. This occurs on the GPU and can be used to read
+    /// back the contents of a texture by copying it into a buffer that is accessible to the CPU.
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTexture,
+        dst_buffer: &RafxBuffer,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.vk_texture().unwrap(),
+                dst_buffer.vk_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.metal_texture().unwrap(),
+                dst_buffer.metal_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(_inner) => {
+                let _ = src_texture;
+                let _ = dst_buffer;
+                let _ = params;
+                unimplemented!()
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(_inner) => {
+                let _ = src_texture;
+                let _ = dst_buffer;
+                let _ = params;
+                unimplemented!()
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.empty_texture().unwrap(),
+                dst_buffer.empty_buffer().unwrap(),
+                params,
+            ),
+        }
+    }
+
     /// Copy the contents of a buffer into a texture. This occurs on the GPU and allows modifying
     /// resources that are not accessible to the CPU.
     pub fn cmd_copy_texture_to_texture(
@@ -106,6 +106,61 @@ impl RafxDeviceContext {
         }
     }
 
+    /// The tracker that counts memory allocated by textures and buffers created from this device
+    /// context
+    pub fn memory_tracker(&self) -> &RafxMemoryTracker {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxDeviceContext::Vk(inner) => inner.memory_tracker(),
+            #[cfg(feature = "rafx-metal")]
+            RafxDeviceContext::Metal(inner) => inner.memory_tracker(),
+            #[cfg(feature = "rafx-gles2")]
+            RafxDeviceContext::Gles2(inner) => inner.memory_tracker(),
+            #[cfg(feature = "rafx-gles3")]
+            RafxDeviceContext::Gles3(inner) => inner.memory_tracker(),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxDeviceContext::Empty(inner) => inner.memory_tracker(),
+        }
+    }
+
+    /// Get a snapshot of memory allocated per category, and the size/budget of each memory heap
+    /// if the backend can report it
+    pub fn memory_stats(&self) -> RafxMemoryStats {
+        let heaps = match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxDeviceContext::Vk(inner) => inner.memory_heap_stats(),
+            #[cfg(feature = "rafx-metal")]
+            RafxDeviceContext::Metal(inner) => inner.memory_heap_stats(),
+            #[cfg(feature = "rafx-gles2")]
+            RafxDeviceContext::Gles2(inner) => inner.memory_heap_stats(),
+            #[cfg(feature = "rafx-gles3")]
+            RafxDeviceContext::Gles3(inner) => inner.memory_heap_stats(),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxDeviceContext::Empty(inner) => inner.memory_heap_stats(),
+        };
+
+        RafxMemoryStats {
+            categories: self.memory_tracker().category_stats(),
+            heaps,
+        }
+    }
+
     pub fn find_supported_format(
         &self,
         candidates: &[RafxFormat],
@@ -358,12 +413,21 @@ impl RafxDeviceContext {
         })
     }
 
-    /// Create a texture
+    /// Create a texture, counted as `RafxMemoryCategory::Texture` in the memory stats
     pub fn create_texture(
         &self,
         texture_def: &RafxTextureDef,
     ) -> RafxResult<RafxTexture> {
-        Ok(match self {
+        self.create_texture_in_category(texture_def, RafxMemoryCategory::Texture)
+    }
+
+    /// Create a texture, counted as the given category in the memory stats
+    pub fn create_texture_in_category(
+        &self,
+        texture_def: &RafxTextureDef,
+        memory_category: RafxMemoryCategory,
+    ) -> RafxResult<RafxTexture> {
+        let mut texture = match self {
             #[cfg(feature = "rafx-vulkan")]
             RafxDeviceContext::Vk(inner) => RafxTexture::Vk(inner.create_texture(texture_def)?),
             #[cfg(feature = "rafx-metal")]
@@ -390,15 +454,30 @@ impl RafxDeviceContext {
             RafxDeviceContext::Empty(inner) => {
                 RafxTexture::Empty(inner.create_texture(texture_def)?)
             }
-        })
+        };
+
+        texture.set_memory_allocation(
+            self.memory_tracker()
+                .track_allocation(memory_category, texture_def.estimated_size_in_bytes()),
+        );
+        Ok(texture)
     }
 
-    /// Create a buffer
+    /// Create a buffer, counted as `RafxMemoryCategory::Buffer` in the memory stats
     pub fn create_buffer(
         &self,
         buffer_def: &RafxBufferDef,
     ) -> RafxResult<RafxBuffer> {
-        Ok(match self {
+        self.create_buffer_in_category(buffer_def, RafxMemoryCategory::Buffer)
+    }
+
+    /// Create a buffer, counted as the given category in the memory stats
+    pub fn create_buffer_in_category(
+        &self,
+        buffer_def: &RafxBufferDef,
+        memory_category: RafxMemoryCategory,
+    ) -> RafxResult<RafxBuffer> {
+        let mut buffer = match self {
             #[cfg(feature = "rafx-vulkan")]
             RafxDeviceContext::Vk(inner) => RafxBuffer::Vk(inner.create_buffer(buffer_def)?),
             #[cfg(feature = "rafx-metal")]
@@ -417,7 +496,13 @@ impl RafxDeviceContext {
                 ))
             ))]
             RafxDeviceContext::Empty(inner) => RafxBuffer::Empty(inner.create_buffer(buffer_def)?),
-        })
+        };
+
+        buffer.set_memory_allocation(
+            self.memory_tracker()
+                .track_allocation(memory_category, buffer_def.size),
+        );
+        Ok(buffer)
     }
 
     pub fn create_shader_module(
@@ -426,20 +511,36 @@ impl RafxDeviceContext {
     ) -> RafxResult<RafxShaderModule> {
         Ok(match self {
             #[cfg(feature = "rafx-vulkan")]
-            RafxDeviceContext::Vk(inner) => {
-                RafxShaderModule::Vk(inner.create_shader_module(shader_module_def.vk.unwrap())?)
-            }
+            RafxDeviceContext::Vk(inner) => RafxShaderModule::Vk(
+                inner.create_shader_module(
+                    shader_module_def
+                        .vk
+                        .ok_or("The shader module has no vulkan data")?,
+                )?,
+            ),
             #[cfg(feature = "rafx-metal")]
             RafxDeviceContext::Metal(inner) => RafxShaderModule::Metal(
-                inner.create_shader_module(shader_module_def.metal.unwrap())?,
+                inner.create_shader_module(
+                    shader_module_def
+                        .metal
+                        .ok_or("The shader module has no metal data")?,
+                )?,
             ),
             #[cfg(feature = "rafx-gles2")]
             RafxDeviceContext::Gles2(inner) => RafxShaderModule::Gles2(
-                inner.create_shader_module(shader_module_def.gles2.unwrap())?,
+                inner.create_shader_module(
+                    shader_module_def
+                        .gles2
+                        .ok_or("The shader module has no GL ES 2.0 data")?,
+                )?,
             ),
             #[cfg(feature = "rafx-gles3")]
             RafxDeviceContext::Gles3(inner) => RafxShaderModule::Gles3(
-                inner.create_shader_module(shader_module_def.gles3.unwrap())?,
+                inner.create_shader_module(
+                    shader_module_def
+                        .gles3
+                        .ok_or("The shader module has no GL ES 3.0 data")?,
+                )?,
             ),
             #[cfg(any(
                 feature = "rafx-empty",
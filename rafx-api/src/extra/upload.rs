@@ -1,7 +1,7 @@
 use crate::{
     RafxBuffer, RafxBufferDef, RafxCommandBuffer, RafxCommandBufferDef, RafxCommandPool,
-    RafxCommandPoolDef, RafxDeviceContext, RafxError, RafxFence, RafxFenceStatus, RafxMemoryUsage,
-    RafxQueue, RafxQueueType, RafxResourceType, RafxResult,
+    RafxCommandPoolDef, RafxDeviceContext, RafxError, RafxFence, RafxFenceStatus,
+    RafxMemoryCategory, RafxMemoryUsage, RafxQueue, RafxQueueType, RafxResourceType, RafxResult,
 };
 use crossbeam_channel::{Receiver, Sender};
 use std::ops::{Deref, DerefMut};
@@ -60,13 +60,16 @@ impl RafxUploadBufferPool {
         let mut unused_buffers = Vec::with_capacity(buffer_count as usize);
 
         for _ in 0..buffer_count {
-            let buffer = device_context.create_buffer(&RafxBufferDef {
-                size: buffer_size,
-                memory_usage: RafxMemoryUsage::CpuToGpu,
-                queue_type: RafxQueueType::Transfer,
-                resource_type: RafxResourceType::BUFFER,
-                ..Default::default()
-            })?;
+            let buffer = device_context.create_buffer_in_category(
+                &RafxBufferDef {
+                    size: buffer_size,
+                    memory_usage: RafxMemoryUsage::CpuToGpu,
+                    queue_type: RafxQueueType::Transfer,
+                    resource_type: RafxResourceType::BUFFER,
+                    ..Default::default()
+                },
+                RafxMemoryCategory::UploadStaging,
+            )?;
             unused_buffers.push(buffer);
         }
 
@@ -254,13 +257,16 @@ impl RafxUpload {
         let buffer = if let Some(buffer_pool) = buffer_pool {
             UploadBuffer::Pooled(buffer_pool.take(buffer_size)?)
         } else {
-            UploadBuffer::NonPooled(device_context.create_buffer(&RafxBufferDef {
-                size: buffer_size,
-                memory_usage: RafxMemoryUsage::CpuToGpu,
-                queue_type: RafxQueueType::Transfer,
-                resource_type: RafxResourceType::BUFFER,
-                ..Default::default()
-            })?)
+            UploadBuffer::NonPooled(device_context.create_buffer_in_category(
+                &RafxBufferDef {
+                    size: buffer_size,
+                    memory_usage: RafxMemoryUsage::CpuToGpu,
+                    queue_type: RafxQueueType::Transfer,
+                    resource_type: RafxResourceType::BUFFER,
+                    ..Default::default()
+                },
+                RafxMemoryCategory::UploadStaging,
+            )?)
         };
 
         let (buffer_begin, buffer_end, buffer_write_pointer) = unsafe {
@@ -5,10 +5,13 @@
     feature = "rafx-gles3"
 ))]
 use crate::{
-    RafxImmutableSamplerKey, RafxImmutableSamplers, RafxPipelineType, RafxResult,
-    RafxRootSignatureDef, RafxShaderResource, RafxShaderStageFlags,
+    RafxDeviceInfo, RafxImmutableSamplerKey, RafxImmutableSamplers, RafxPipelineType,
+    RafxResourceType, RafxResult, RafxRootSignatureDef, RafxShaderResource, RafxShaderStageFlags,
 };
 
+#[cfg(feature = "rafx-vulkan")]
+use crate::RafxBindlessDescriptorKey;
+
 #[cfg(any(
     feature = "rafx-metal",
     feature = "rafx-vulkan",
@@ -58,6 +61,76 @@ pub(crate) fn find_immutable_sampler_index(
     None
 }
 
+#[cfg(feature = "rafx-vulkan")]
+pub(crate) fn is_bindless_descriptor(
+    bindless_descriptors: &[RafxBindlessDescriptorKey],
+    set_index: u32,
+    binding: u32,
+) -> bool {
+    bindless_descriptors
+        .iter()
+        .any(|x| x.set_index == set_index && x.binding == binding)
+}
+
+#[cfg(any(
+    feature = "rafx-metal",
+    feature = "rafx-vulkan",
+    feature = "rafx-gles2",
+    feature = "rafx-gles3"
+))]
+pub(crate) fn validate_bindless_descriptors(
+    root_signature_def: &RafxRootSignatureDef,
+    merged_resources: &[RafxShaderResource],
+    device_info: &RafxDeviceInfo,
+) -> RafxResult<()> {
+    if root_signature_def.bindless_descriptors.is_empty() {
+        return Ok(());
+    }
+
+    if !device_info.supports_bindless {
+        let message = format!(
+            "Root signature has bindless descriptors {:?} but the device does not support bindless descriptors",
+            root_signature_def.bindless_descriptors
+        );
+        log::error!("{}", message);
+        Err(message)?;
+    }
+
+    for bindless_descriptor in root_signature_def.bindless_descriptors {
+        let resource = merged_resources.iter().find(|x| {
+            x.resource_type != RafxResourceType::ROOT_CONSTANT
+                && x.set_index == bindless_descriptor.set_index
+                && x.binding == bindless_descriptor.binding
+        });
+
+        let resource = if let Some(resource) = resource {
+            resource
+        } else {
+            let message = format!(
+                "Bindless descriptor (set={:?} binding={:?}) does not exist in any shader in the root signature",
+                bindless_descriptor.set_index, bindless_descriptor.binding
+            );
+            log::error!("{}", message);
+            return Err(message)?;
+        };
+
+        let allowed_types = RafxResourceType::TEXTURE
+            | RafxResourceType::TEXTURE_READ_WRITE
+            | RafxResourceType::BUFFER
+            | RafxResourceType::BUFFER_READ_WRITE;
+        if !allowed_types.contains(resource.resource_type) {
+            let message = format!(
+                "Bindless descriptor (set={:?} binding={:?} name={:?}) has resource_type {:?}. Only textures and storage buffers may be bindless",
+                resource.set_index, resource.binding, resource.name, resource.resource_type
+            );
+            log::error!("{}", message);
+            Err(message)?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(any(
     feature = "rafx-metal",
     feature = "rafx-vulkan",
@@ -285,6 +285,7 @@ mod command_pool;
 mod descriptor_set_array;
 mod device_context;
 mod fence;
+mod memory_stats;
 mod pipeline;
 mod queue;
 mod root_signature;
@@ -319,6 +320,7 @@ pub use device_context::*;
 pub use error::*;
 pub use extra::swapchain_helper::*;
 pub use fence::*;
+pub use memory_stats::*;
 pub use pipeline::*;
 pub use queue::*;
 pub use root_signature::*;
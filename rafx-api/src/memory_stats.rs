@@ -0,0 +1,241 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Broad categories of GPU memory use. Textures and buffers created with
+/// `RafxDeviceContext::create_texture`/`create_buffer` are counted as `Texture` and `Buffer`.
+/// Use `create_texture_in_category`/`create_buffer_in_category` to count them as something else.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RafxMemoryCategory {
+    Texture,
+    Buffer,
+    /// Transient images and buffers allocated by the render graph
+    RenderGraph,
+    /// CPU-visible buffers used to copy data to the GPU
+    UploadStaging,
+}
+
+impl RafxMemoryCategory {
+    pub const COUNT: usize = 4;
+
+    pub const ALL: [RafxMemoryCategory; Self::COUNT] = [
+        RafxMemoryCategory::Texture,
+        RafxMemoryCategory::Buffer,
+        RafxMemoryCategory::RenderGraph,
+        RafxMemoryCategory::UploadStaging,
+    ];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            RafxMemoryCategory::Texture => "Textures",
+            RafxMemoryCategory::Buffer => "Buffers",
+            RafxMemoryCategory::RenderGraph => "Render Graph",
+            RafxMemoryCategory::UploadStaging => "Upload Staging",
+        }
+    }
+}
+
+/// Memory allocated in a single `RafxMemoryCategory`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RafxMemoryCategoryStats {
+    pub allocation_count: u64,
+    pub allocated_bytes: u64,
+    /// Highest value allocated_bytes has reached since the device context was created
+    pub peak_allocated_bytes: u64,
+}
+
+/// Size and budget of a memory heap as reported by the backend. The budget is how much of the heap
+/// the process can use without degrading performance, and usage includes allocations made by
+/// other means than rafx (i.e. the swapchain). These are only available if the backend supports
+/// querying them (VK_EXT_memory_budget on vulkan).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RafxMemoryHeapStats {
+    pub size: u64,
+    pub device_local: bool,
+    pub budget: Option<u64>,
+    pub usage: Option<u64>,
+}
+
+/// A snapshot of GPU memory use, returned by `RafxDeviceContext::memory_stats`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RafxMemoryStats {
+    pub categories: [RafxMemoryCategoryStats; RafxMemoryCategory::COUNT],
+    pub heaps: Vec<RafxMemoryHeapStats>,
+}
+
+impl RafxMemoryStats {
+    pub fn category(
+        &self,
+        category: RafxMemoryCategory,
+    ) -> &RafxMemoryCategoryStats {
+        &self.categories[category as usize]
+    }
+
+    pub fn total_allocation_count(&self) -> u64 {
+        self.categories.iter().map(|x| x.allocation_count).sum()
+    }
+
+    pub fn total_allocated_bytes(&self) -> u64 {
+        self.categories.iter().map(|x| x.allocated_bytes).sum()
+    }
+}
+
+#[derive(Default)]
+struct RafxMemoryCategoryCounters {
+    allocation_count: AtomicU64,
+    allocated_bytes: AtomicU64,
+    peak_allocated_bytes: AtomicU64,
+}
+
+#[derive(Default)]
+struct RafxMemoryTrackerInner {
+    categories: [RafxMemoryCategoryCounters; RafxMemoryCategory::COUNT],
+}
+
+/// Counts bytes allocated per `RafxMemoryCategory`. Every device context owns one, and textures
+/// and buffers hold a `RafxMemoryAllocation` that removes them from the counts when dropped.
+#[derive(Clone, Default)]
+pub struct RafxMemoryTracker {
+    inner: Arc<RafxMemoryTrackerInner>,
+}
+
+impl std::fmt::Debug for RafxMemoryTracker {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("RafxMemoryTracker")
+            .field("categories", &self.category_stats())
+            .finish()
+    }
+}
+
+impl RafxMemoryTracker {
+    pub fn track_allocation(
+        &self,
+        category: RafxMemoryCategory,
+        size_in_bytes: u64,
+    ) -> RafxMemoryAllocation {
+        let counters = &self.inner.categories[category as usize];
+        counters.allocation_count.fetch_add(1, Ordering::Relaxed);
+        let allocated_bytes = counters
+            .allocated_bytes
+            .fetch_add(size_in_bytes, Ordering::Relaxed)
+            + size_in_bytes;
+        counters
+            .peak_allocated_bytes
+            .fetch_max(allocated_bytes, Ordering::Relaxed);
+
+        RafxMemoryAllocation {
+            tracker: self.clone(),
+            category,
+            size_in_bytes,
+        }
+    }
+
+    pub fn category_stats(&self) -> [RafxMemoryCategoryStats; RafxMemoryCategory::COUNT] {
+        let mut stats = [RafxMemoryCategoryStats::default(); RafxMemoryCategory::COUNT];
+        for (stats, counters) in stats.iter_mut().zip(&self.inner.categories) {
+            *stats = RafxMemoryCategoryStats {
+                allocation_count: counters.allocation_count.load(Ordering::Relaxed),
+                allocated_bytes: counters.allocated_bytes.load(Ordering::Relaxed),
+                peak_allocated_bytes: counters.peak_allocated_bytes.load(Ordering::Relaxed),
+            };
+        }
+
+        stats
+    }
+
+    fn free_allocation(
+        &self,
+        category: RafxMemoryCategory,
+        size_in_bytes: u64,
+    ) {
+        let counters = &self.inner.categories[category as usize];
+        counters.allocation_count.fetch_sub(1, Ordering::Relaxed);
+        counters
+            .allocated_bytes
+            .fetch_sub(size_in_bytes, Ordering::Relaxed);
+    }
+}
+
+/// Keeps an allocation counted by a `RafxMemoryTracker` until dropped
+pub struct RafxMemoryAllocation {
+    tracker: RafxMemoryTracker,
+    category: RafxMemoryCategory,
+    size_in_bytes: u64,
+}
+
+impl RafxMemoryAllocation {
+    pub fn category(&self) -> RafxMemoryCategory {
+        self.category
+    }
+
+    pub fn size_in_bytes(&self) -> u64 {
+        self.size_in_bytes
+    }
+}
+
+impl std::fmt::Debug for RafxMemoryAllocation {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("RafxMemoryAllocation")
+            .field("category", &self.category)
+            .field("size_in_bytes", &self.size_in_bytes)
+            .finish()
+    }
+}
+
+impl Drop for RafxMemoryAllocation {
+    fn drop(&mut self) {
+        self.tracker
+            .free_allocation(self.category, self.size_in_bytes);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_memory_tracker_counts_allocations() {
+        let tracker = RafxMemoryTracker::default();
+        let texture = tracker.track_allocation(RafxMemoryCategory::Texture, 1024);
+        let staging_a = tracker.track_allocation(RafxMemoryCategory::UploadStaging, 256);
+        let staging_b = tracker.track_allocation(RafxMemoryCategory::UploadStaging, 512);
+
+        let stats = tracker.category_stats();
+        let texture_stats = stats[RafxMemoryCategory::Texture as usize];
+        assert_eq!(texture_stats.allocation_count, 1);
+        assert_eq!(texture_stats.allocated_bytes, 1024);
+        let staging_stats = stats[RafxMemoryCategory::UploadStaging as usize];
+        assert_eq!(staging_stats.allocation_count, 2);
+        assert_eq!(staging_stats.allocated_bytes, 768);
+        assert_eq!(
+            stats[RafxMemoryCategory::Buffer as usize].allocated_bytes,
+            0
+        );
+
+        drop(staging_a);
+        drop(texture);
+
+        let stats = tracker.category_stats();
+        let staging_stats = stats[RafxMemoryCategory::UploadStaging as usize];
+        assert_eq!(staging_stats.allocation_count, 1);
+        assert_eq!(staging_stats.allocated_bytes, 512);
+        assert_eq!(staging_stats.peak_allocated_bytes, 768);
+        assert_eq!(
+            stats[RafxMemoryCategory::Texture as usize].allocated_bytes,
+            0
+        );
+
+        drop(staging_b);
+        let memory_stats = RafxMemoryStats {
+            categories: tracker.category_stats(),
+            heaps: vec![],
+        };
+        assert_eq!(memory_stats.total_allocation_count(), 0);
+        assert_eq!(memory_stats.total_allocated_bytes(), 0);
+    }
+}
@@ -16,7 +16,7 @@ use crate::gles3::RafxTextureGles3;
 use crate::metal::RafxTextureMetal;
 #[cfg(feature = "rafx-vulkan")]
 use crate::vulkan::RafxTextureVulkan;
-use crate::RafxTextureDef;
+use crate::{RafxMemoryAllocation, RafxTextureDef};
 
 /// An image that can be used by the GPU.
 ///
@@ -44,6 +44,33 @@ pub enum RafxTexture {
 }
 
 impl RafxTexture {
+    // Called by RafxDeviceContext right after creating the texture
+    pub(crate) fn set_memory_allocation(
+        &mut self,
+        memory_allocation: RafxMemoryAllocation,
+    ) {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxTexture::Vk(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-metal")]
+            RafxTexture::Metal(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-gles2")]
+            RafxTexture::Gles2(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(feature = "rafx-gles3")]
+            RafxTexture::Gles3(inner) => inner.set_memory_allocation(memory_allocation),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxTexture::Empty(inner) => inner.set_memory_allocation(memory_allocation),
+        }
+    }
+
     /// Return the metadata used to create the texture
     pub fn texture_def(&self) -> &RafxTextureDef {
         match self {
@@ -115,7 +115,8 @@ impl RafxBufferDef {
 }
 
 /// Determines how many dimensions the texture will have.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
 pub enum RafxTextureDimensions {
     /// Assume 2D if depth = 1, otherwise 3d
     Auto,
@@ -220,6 +221,26 @@ impl RafxTextureDef {
             "Cannot use depth stencil as UAV"
         );
     }
+
+    /// Approximate amount of memory the texture will use, including all mips, layers and samples.
+    /// The backend may add padding or alignment on top of this.
+    pub fn estimated_size_in_bytes(&self) -> u64 {
+        let block_width = self.format.block_width_in_pixels().max(1);
+        let block_height = self.format.block_height_in_pixels().max(1);
+        let block_size = self.format.block_or_pixel_size_in_bytes() as u64;
+
+        let mut size = 0;
+        for mip in 0..self.mip_count {
+            let width = (self.extents.width >> mip).max(1);
+            let height = (self.extents.height >> mip).max(1);
+            let depth = (self.extents.depth >> mip).max(1);
+            let blocks_wide = ((width + block_width - 1) / block_width) as u64;
+            let blocks_high = ((height + block_height - 1) / block_height) as u64;
+            size += blocks_wide * blocks_high * depth as u64 * block_size;
+        }
+
+        size * self.array_length as u64 * self.sample_count.as_u32() as u64
+    }
 }
 
 /// Used to create a `RafxCommandPool`
@@ -336,10 +357,31 @@ impl<'a> RafxImmutableSamplers<'a> {
     }
 }
 
+/// Indicates which descriptor is bindless. Bindless descriptors do not need every element to be
+/// bound, and elements that are not used by in-flight work may be written after the descriptor set
+/// has been bound. Requires `RafxDeviceInfo::supports_bindless`.
+///
+/// Only arrays of textures and storage buffers may be bindless.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RafxBindlessDescriptorKey {
+    pub set_index: u32,
+    pub binding: u32,
+}
+
+impl RafxBindlessDescriptorKey {
+    pub fn new(
+        set_index: u32,
+        binding: u32,
+    ) -> Self {
+        RafxBindlessDescriptorKey { set_index, binding }
+    }
+}
+
 /// Used to create a `RafxRootSignature`
 pub struct RafxRootSignatureDef<'a> {
     pub shaders: &'a [RafxShader],
     pub immutable_samplers: &'a [RafxImmutableSamplers<'a>],
+    pub bindless_descriptors: &'a [RafxBindlessDescriptorKey],
 }
 
 impl<'a> RafxRootSignatureDef<'a> {
@@ -354,6 +396,7 @@ impl<'a> RafxRootSignatureDef<'a> {
         shader_hashes: &[ShaderHashT],
         immutable_sampler_keys: &[RafxImmutableSamplerKey],
         immutable_sampler_hashes: &[Vec<ImmutableSamplerHashT>],
+        bindless_descriptors: &[RafxBindlessDescriptorKey],
     ) {
         // Hash all the shader hashes and xor them together, this keeps them order-independent
         let mut combined_shaders_hash = 0;
@@ -373,9 +416,18 @@ impl<'a> RafxRootSignatureDef<'a> {
             combined_immutable_samplers_hash ^= h.finish();
         }
 
-        // Hash both combined hashes to produce the final hash
+        // Bindless descriptors change the descriptor set layouts, so they must be hashed too
+        let mut combined_bindless_descriptors_hash = 0;
+        for bindless_descriptor in bindless_descriptors {
+            let mut h = FnvHasher::default();
+            bindless_descriptor.hash(&mut h);
+            combined_bindless_descriptors_hash ^= h.finish();
+        }
+
+        // Hash all combined hashes to produce the final hash
         combined_shaders_hash.hash(hasher);
         combined_immutable_samplers_hash.hash(hasher);
+        combined_bindless_descriptors_hash.hash(hasher);
     }
 }
 
@@ -604,8 +656,8 @@ pub struct RafxBlendState {
     /// Individual blend states for blend targets
     pub render_target_blend_states: Vec<RafxBlendStateRenderTarget>,
 
-    /// Indicates which blend targets to affect. Blend targets with unset bits are left in default
-    /// state.
+    /// Indicates which blend targets to affect. Blend targets with unset bits are not written.
+    /// (GL ES backends only support a single blend state, which applies to every target.)
     pub render_target_mask: RafxBlendStateTargets,
 
     /// If false, `render_target_blend_states[0]` will apply to all render targets indicated by
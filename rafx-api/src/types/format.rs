@@ -1692,6 +1692,62 @@ impl RafxFormat {
         self == RafxFormat::UNDEFINED
     }
 
+    // Signed integer formats, these are read as int/ivecN in shaders (not normalized to floats)
+    pub fn is_sint(self) -> bool {
+        match self {
+            RafxFormat::R8_SINT
+            | RafxFormat::R8G8_SINT
+            | RafxFormat::R8G8B8_SINT
+            | RafxFormat::B8G8R8_SINT
+            | RafxFormat::R8G8B8A8_SINT
+            | RafxFormat::B8G8R8A8_SINT
+            | RafxFormat::A8B8G8R8_SINT_PACK32
+            | RafxFormat::A2R10G10B10_SINT_PACK32
+            | RafxFormat::A2B10G10R10_SINT_PACK32
+            | RafxFormat::R16_SINT
+            | RafxFormat::R16G16_SINT
+            | RafxFormat::R16G16B16_SINT
+            | RafxFormat::R16G16B16A16_SINT
+            | RafxFormat::R32_SINT
+            | RafxFormat::R32G32_SINT
+            | RafxFormat::R32G32B32_SINT
+            | RafxFormat::R32G32B32A32_SINT
+            | RafxFormat::R64_SINT
+            | RafxFormat::R64G64_SINT
+            | RafxFormat::R64G64B64_SINT
+            | RafxFormat::R64G64B64A64_SINT => true,
+            _ => false,
+        }
+    }
+
+    // Unsigned integer formats, these are read as uint/uvecN in shaders (not normalized to floats)
+    pub fn is_uint(self) -> bool {
+        match self {
+            RafxFormat::R8_UINT
+            | RafxFormat::R8G8_UINT
+            | RafxFormat::R8G8B8_UINT
+            | RafxFormat::B8G8R8_UINT
+            | RafxFormat::R8G8B8A8_UINT
+            | RafxFormat::B8G8R8A8_UINT
+            | RafxFormat::A8B8G8R8_UINT_PACK32
+            | RafxFormat::A2R10G10B10_UINT_PACK32
+            | RafxFormat::A2B10G10R10_UINT_PACK32
+            | RafxFormat::R16_UINT
+            | RafxFormat::R16G16_UINT
+            | RafxFormat::R16G16B16_UINT
+            | RafxFormat::R16G16B16A16_UINT
+            | RafxFormat::R32_UINT
+            | RafxFormat::R32G32_UINT
+            | RafxFormat::R32G32B32_UINT
+            | RafxFormat::R32G32B32A32_UINT
+            | RafxFormat::R64_UINT
+            | RafxFormat::R64G64_UINT
+            | RafxFormat::R64G64B64_UINT
+            | RafxFormat::R64G64B64A64_UINT => true,
+            _ => false,
+        }
+    }
+
     pub fn is_compressed(self) -> bool {
         match self {
             RafxFormat::BC1_RGB_UNORM_BLOCK
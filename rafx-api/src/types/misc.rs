@@ -47,6 +47,15 @@ pub struct RafxDeviceInfo {
     pub supports_clamp_to_border_color: bool,
 
     pub max_vertex_attribute_count: u32,
+
+    // Currently only available on vulkan
+    pub supports_geometry_shaders: bool,
+    pub supports_tessellation_shaders: bool,
+
+    // Bindless descriptors (RafxRootSignatureDef::bindless_descriptors) may be used. On vulkan this
+    // requires descriptor indexing to be enabled with RafxApiDefVulkan::enable_bindless. On metal
+    // this requires tier 2 argument buffers.
+    pub supports_bindless: bool,
     //max_vertex_input_binding_count: u32,
     // max_root_signature_dwords: u32,
     // wave_lane_count: u32,
@@ -170,6 +179,18 @@ impl Default for RafxSampleCount {
     }
 }
 
+impl RafxSampleCount {
+    pub fn as_u32(self) -> u32 {
+        match self {
+            RafxSampleCount::SampleCount1 => 1,
+            RafxSampleCount::SampleCount2 => 2,
+            RafxSampleCount::SampleCount4 => 4,
+            RafxSampleCount::SampleCount8 => 8,
+            RafxSampleCount::SampleCount16 => 16,
+        }
+    }
+}
+
 bitflags::bitflags! {
     /// Indicates how a resource will be used. In some cases, multiple flags are allowed.
     #[derive(Default)]
@@ -414,7 +435,11 @@ pub enum RafxPrimitiveTopology {
     LineStrip,
     TriangleList,
     TriangleStrip,
-    PatchList,
+    /// Required when the pipeline has tessellation stages. control_points is the number of
+    /// vertices that make up each patch (i.e. 3 for triangles, 4 for quads)
+    PatchList {
+        control_points: u32,
+    },
 }
 
 /// The size of index buffer elements
@@ -838,6 +863,14 @@ pub struct RafxCmdCopyBufferToTextureParams {
     pub mip_level: u8,
 }
 
+/// Parameters for copying a texture to a buffer. Rows are tightly packed in the buffer.
+#[derive(Default, Clone)]
+pub struct RafxCmdCopyTextureToBufferParams {
+    pub buffer_offset: u64,
+    pub array_layer: u16,
+    pub mip_level: u8,
+}
+
 #[derive(Default, Clone)]
 pub struct RafxCmdCopyTextureToTextureParams {
     pub src_offset: RafxExtents3D,
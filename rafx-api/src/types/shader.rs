@@ -47,6 +47,18 @@ pub enum RafxShaderPackageVulkan {
     SpvBytes(Vec<u8>),
 }
 
+/// WGSL shader package for WebGPU. There is no WebGPU backend in rafx, this is included so that
+/// tools and experimental backends can consume the same cooked shaders.
+///
+/// Bindings use the same numbering as vulkan: descriptor set N is `@group(N)`, and binding
+/// indices are unchanged. Entry points keep their original names.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
+pub enum RafxShaderPackageWgsl {
+    /// WGSL source code, translated from SPIR-V
+    Src(String),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
 #[doc(hidden)]
@@ -66,6 +78,7 @@ pub struct RafxShaderPackage {
     pub gles3: Option<RafxShaderPackageGles3>,
     pub metal: Option<RafxShaderPackageMetal>,
     pub vk: Option<RafxShaderPackageVulkan>,
+    pub wgsl: Option<RafxShaderPackageWgsl>,
 }
 
 impl RafxShaderPackage {
@@ -1,5 +1,6 @@
 use crate::assets::ImageAssetData;
 use crate::assets::{BufferAsset, ImageAsset, MaterialAsset};
+use crate::assets::{TextureStreamingConfig, TextureStreamingContext, TextureStreamingStats};
 use crate::{
     AssetLookup, AssetTypeHandler, BufferAssetData, GenericLoader, MaterialInstanceSlotAssignment,
 };
@@ -27,7 +28,9 @@ use rafx_framework::descriptor_sets::{
     DescriptorSetWriteElementImage,
 };
 use rafx_framework::render_features::RenderRegistry;
-use rafx_framework::upload::{UploadQueue, UploadQueueConfig, UploadQueueContext};
+use rafx_framework::upload::{
+    UploadQueue, UploadQueueConfig, UploadQueueContext, UploadQueueMetrics,
+};
 use rafx_framework::DescriptorSetAllocator;
 use rafx_framework::DynCommandPoolAllocator;
 use rafx_framework::DynResourceAllocatorSetProvider;
@@ -40,6 +43,8 @@ use std::sync::Arc;
 pub struct AssetManagerMetrics {
     pub resource_manager_metrics: ResourceManagerMetrics,
     pub material_instance_descriptor_sets_metrics: DescriptorSetAllocatorMetrics,
+    pub upload_queue_metrics: UploadQueueMetrics,
+    pub texture_streaming_stats: TextureStreamingStats,
     //TODO: Metrics per asset type
 }
 
@@ -55,6 +60,7 @@ pub struct AssetManager {
     material_instance_descriptor_sets: DescriptorSetAllocator,
     graphics_queue: RafxQueue,
     transfer_queue: RafxQueue,
+    texture_streaming_context: TextureStreamingContext,
 
     asset_types: FnvHashMap<TypeId, Box<dyn AssetTypeHandler>>,
     // Extremely rare that we modify asset_registration_order but we need to iterate it while
@@ -68,6 +74,7 @@ impl AssetManager {
         device_context: &RafxDeviceContext,
         render_registry: &RenderRegistry,
         upload_queue_config: UploadQueueConfig,
+        texture_streaming_config: TextureStreamingConfig,
         graphics_queue: &RafxQueue,
         transfer_queue: &RafxQueue,
     ) -> RafxResult<Self> {
@@ -86,6 +93,7 @@ impl AssetManager {
             material_instance_descriptor_sets: DescriptorSetAllocator::new(device_context),
             graphics_queue: graphics_queue.clone(),
             transfer_queue: transfer_queue.clone(),
+            texture_streaming_context: TextureStreamingContext::new(texture_streaming_config),
 
             asset_types: Default::default(),
             asset_registration_order: Default::default(),
@@ -293,6 +301,10 @@ impl AssetManager {
         self.upload_queue.upload_queue_context()
     }
 
+    pub fn texture_streaming_context(&self) -> &TextureStreamingContext {
+        &self.texture_streaming_context
+    }
+
     //
     // Loaders
     //
@@ -351,6 +363,8 @@ impl AssetManager {
             resource_manager_metrics,
             //loaded_asset_metrics,
             material_instance_descriptor_sets_metrics,
+            upload_queue_metrics: self.upload_queue.metrics(),
+            texture_streaming_stats: self.texture_streaming_context.stats(),
         }
     }
 
@@ -379,7 +393,12 @@ impl AssetManager {
                         dst_binding: location.binding_index,
                         array_index: slot_assignment.array_index,
                     })
-                    .unwrap();
+                    .ok_or_else(|| {
+                        format!(
+                            "Material instance assigns slot '{}' at array index {}, but the shader does not declare that many elements for it",
+                            slot_assignment.slot_name, slot_assignment.array_index
+                        )
+                    })?;
 
                 let what_to_bind = rafx_framework::descriptor_sets::what_to_bind(write);
 
@@ -447,6 +466,8 @@ impl AssetManager {
             )?;
         }
 
+        pass.validate_write_sets(&pass_descriptor_set_writes)?;
+
         Ok(pass_descriptor_set_writes)
     }
 }
@@ -3,7 +3,7 @@ use crate::assets::upload_asset_op::{UploadAssetOp, UploadAssetOpResult};
 use crate::{BufferAsset, BufferAssetData};
 use crossbeam_channel::{Receiver, Sender};
 use rafx_api::RafxBuffer;
-use rafx_framework::upload::UploadQueueContext;
+use rafx_framework::upload::{UploadPriority, UploadQueueContext};
 use rafx_framework::RafxResult;
 
 pub type BufferAssetUploadOpResult = UploadAssetOpResult<RafxBuffer, BufferAsset>;
@@ -41,6 +41,7 @@ impl BufferAssetUploadQueue {
             op,
             request.asset.resource_type,
             request.asset.data,
+            UploadPriority::Normal,
         )
     }
 }
@@ -8,8 +8,9 @@ use distill::loader::handle::Handle;
 use distill::loader::LoadHandle;
 use fnv::FnvHashMap;
 use rafx_api::{
-    RafxBlendState, RafxBlendStateRenderTarget, RafxCompareOp, RafxCullMode, RafxDepthState,
-    RafxError, RafxFillMode, RafxFrontFace, RafxRasterizerState, RafxResult, RafxSamplerDef,
+    RafxBlendState, RafxBlendStateRenderTarget, RafxBlendStateTargets, RafxCompareOp, RafxCullMode,
+    RafxDepthState, RafxError, RafxFillMode, RafxFrontFace, RafxRasterizerState, RafxResult,
+    RafxSamplerDef,
 };
 pub use rafx_framework::DescriptorSetLayoutResource;
 pub use rafx_framework::GraphicsPipelineResource;
@@ -86,6 +87,10 @@ pub struct FixedFunctionStateData {
     fill_mode: Option<RafxFillMode>,
     #[serde(default)]
     depth_bias: Option<i32>,
+    // Limits which color targets are written, i.e. Some((bits: 1)) leaves every target but the
+    // first untouched when the pass has more color targets than the shader writes
+    #[serde(default)]
+    render_target_mask: Option<RafxBlendStateTargets>,
 }
 
 impl FixedFunctionStateData {
@@ -145,6 +150,10 @@ impl FixedFunctionStateData {
             }
         }
 
+        if let Some(render_target_mask) = self.render_target_mask {
+            blend_state.render_target_mask = render_target_mask;
+        }
+
         rasterizer_state.depth_bias = self.depth_bias.unwrap_or(0);
 
         if let Some(cull_mode) = self.cull_mode {
@@ -172,6 +181,14 @@ pub struct GraphicsPipelineShaderStage {
     pub stage: MaterialShaderStage,
     pub shader_module: Handle<ShaderAsset>,
     pub entry_name: String,
+    // Selects a permutation of a shader that declares variants. If None, the default (first)
+    // variant is used.
+    #[serde(default)]
+    pub variant: Option<String>,
+    // Used instead of `variant` if the device supports bindless descriptors, for shaders that can
+    // only index bindless slots that way (i.e. with nonuniformEXT)
+    #[serde(default)]
+    pub bindless_variant: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -180,6 +197,10 @@ pub struct MaterialPassData {
     pub phase: Option<String>,
     pub fixed_function_state: FixedFunctionStateData,
     pub shaders: Vec<GraphicsPipelineShaderStage>,
+    // Slot names whose bindings are created as bindless descriptors. Ignored (bound normally) if
+    // the device does not support bindless.
+    #[serde(default)]
+    pub bindless_slots: Vec<String>,
 }
 
 impl MaterialPassData {
@@ -204,6 +225,27 @@ impl MaterialPassData {
             );
 
             let shader_asset = asset_manager.latest_asset(&stage.shader_module).unwrap();
+            let variant = if asset_manager
+                .device_context()
+                .device_info()
+                .supports_bindless
+            {
+                stage.bindless_variant.as_ref().or(stage.variant.as_ref())
+            } else {
+                stage.variant.as_ref()
+            };
+            let shader_asset = if let Some(variant_name) = variant {
+                shader_asset.variant(variant_name).ok_or_else(|| {
+                    let error_message = format!(
+                        "Load Material Failed - Pass refers to shader variant named {}, but the shader does not declare a variant with that name",
+                        variant_name
+                    );
+                    log::error!("{}", error_message);
+                    error_message
+                })?
+            } else {
+                shader_asset
+            };
             shader_modules.push(shader_asset.shader_module.clone());
 
             let reflection_data = shader_asset.reflection_data.get(&stage.entry_name);
@@ -238,11 +280,13 @@ impl MaterialPassData {
         // We now have everything needed to create the framework-level material pass
         //
         let resource_context = asset_manager.resource_manager().resource_context();
-        let material_pass = MaterialPass::new(
+        let material_pass = MaterialPass::new_with_bindless_slots(
             &resource_context,
             fixed_function_state,
             shader_modules,
             &entry_points,
+            &self.bindless_slots,
+            self.name.as_deref(),
         )
         .map_err(|x| {
             RafxError::StringError(format!(
@@ -72,7 +72,7 @@ impl Importer for MaterialImporter {
     where
         Self: Sized,
     {
-        2
+        4
     }
 
     fn version(&self) -> u32 {
@@ -1,4 +1,5 @@
 use super::asset_upload_queue::{ImageAssetUploadOpResult, ImageAssetUploadQueue};
+use super::texture_streaming::TextureStreamer;
 use crate::distill_impl::{AssetResource, ResourceAssetLoader};
 use crate::{
     AssetLookup, AssetManager, AssetTypeHandler, DynAssetLookup, ImageAsset, ImageAssetData,
@@ -11,6 +12,7 @@ pub struct ImageAssetTypeHandler {
     asset_lookup: AssetLookup<ImageAsset>,
     load_queues: LoadQueues<ImageAssetData, ImageAsset>,
     image_upload_queue: ImageAssetUploadQueue,
+    texture_streamer: TextureStreamer,
 }
 
 impl ImageAssetTypeHandler {
@@ -25,11 +27,14 @@ impl ImageAssetTypeHandler {
         ));
 
         let image_upload_queue = ImageAssetUploadQueue::new(asset_manager.upload_queue_context())?;
+        let texture_streamer =
+            TextureStreamer::new(asset_manager.texture_streaming_context().clone());
 
         Ok(Box::new(Self {
             asset_lookup: AssetLookup::new(asset_resource.loader()),
             load_queues,
             image_upload_queue,
+            texture_streamer,
         }))
     }
 }
@@ -42,7 +47,8 @@ impl AssetTypeHandler for ImageAssetTypeHandler {
         for request in self.load_queues.take_load_requests() {
             //TODO: Route the request directly to the upload queue
             log::trace!("Uploading image {:?}", request.load_handle);
-            self.image_upload_queue.upload_image(request)?;
+            self.image_upload_queue
+                .upload_image(request, &mut self.texture_streamer)?;
         }
 
         let results: Vec<_> = self
@@ -55,6 +61,14 @@ impl AssetTypeHandler for ImageAssetTypeHandler {
                 ImageAssetUploadOpResult::UploadComplete(load_op, result_tx, texture) => {
                     log::trace!("Uploading image {:?} complete", load_op.load_handle());
                     let loaded_asset = finish_load_image(asset_manager, texture);
+                    match &loaded_asset {
+                        Ok(image_asset) => self
+                            .texture_streamer
+                            .finish_streamed_upload(load_op.load_handle(), &image_asset.image_view),
+                        Err(_) => self
+                            .texture_streamer
+                            .cancel_streamed_upload(load_op.load_handle()),
+                    }
                     crate::assets::asset_type_handler::handle_load_result(
                         load_op,
                         loaded_asset,
@@ -64,15 +78,21 @@ impl AssetTypeHandler for ImageAssetTypeHandler {
                 }
                 ImageAssetUploadOpResult::UploadError(load_handle) => {
                     log::trace!("Uploading image {:?} failed", load_handle);
-                    // Don't need to do anything - the uploaded should have triggered an error on the load_op
+                    // The upload should have triggered an error on the load_op, we only need to
+                    // drop any mips that were held for streaming
+                    self.texture_streamer.cancel_streamed_upload(load_handle);
                 }
                 ImageAssetUploadOpResult::UploadDrop(load_handle) => {
                     log::trace!("Uploading image {:?} cancelled", load_handle);
-                    // Don't need to do anything - the uploaded should have triggered an error on the load_op
+                    // The upload should have triggered an error on the load_op, we only need to
+                    // drop any mips that were held for streaming
+                    self.texture_streamer.cancel_streamed_upload(load_handle);
                 }
             }
         }
 
+        self.texture_streamer.update(asset_manager)?;
+
         crate::assets::asset_type_handler::handle_commit_requests(
             &mut self.load_queues,
             &mut self.asset_lookup,
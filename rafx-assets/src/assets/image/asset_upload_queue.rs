@@ -1,3 +1,5 @@
+use super::image_processing::Rgba8DownsampleMode;
+use super::texture_streaming::TextureStreamer;
 use super::ImageAsset;
 use super::ImageAssetData;
 use crate::assets::image::ImageAssetDataFormat;
@@ -5,14 +7,17 @@ use crate::assets::load_queue::LoadRequest;
 use crate::assets::upload_asset_op::{UploadAssetOp, UploadAssetOpResult};
 use crate::{ImageAssetDataPayload, ImageAssetDataPayloadSubresources};
 #[cfg(feature = "basis-universal")]
-use basis_universal::{TranscodeParameters, TranscoderTextureFormat};
+use basis_universal::{
+    DecodeFlags, LowLevelUastcTranscoder, SliceParametersUastc, TranscodeParameters,
+    TranscoderBlockFormat, TranscoderTextureFormat,
+};
 use crossbeam_channel::{Receiver, Sender};
-use rafx_api::{RafxFormat, RafxResult, RafxTexture};
+use rafx_api::{RafxFormat, RafxResult, RafxTexture, RafxTextureDimensions};
 use rafx_framework::upload::image_upload::IMAGE_UPLOAD_REQUIRED_SUBRESOURCE_ALIGNMENT;
-use rafx_framework::upload::UploadQueueContext;
 use rafx_framework::upload::{
     GpuImageData, GpuImageDataColorSpace, GpuImageDataLayer, GpuImageDataMipLevel,
 };
+use rafx_framework::upload::{UploadPriority, UploadQueueContext};
 
 pub type ImageAssetUploadOpResult = UploadAssetOpResult<RafxTexture, ImageAsset>;
 
@@ -36,6 +41,8 @@ pub type ImageAssetUploadOpResult = UploadAssetOpResult<RafxTexture, ImageAsset>
 pub fn new_gpu_image_data_from_image_asset_data_subresources(
     width: u32,
     height: u32,
+    depth: u32,
+    dimensions: RafxTextureDimensions,
     format: RafxFormat,
     subresources: ImageAssetDataPayloadSubresources,
 ) -> GpuImageData {
@@ -43,6 +50,7 @@ pub fn new_gpu_image_data_from_image_asset_data_subresources(
     {
         debug_assert_eq!(width, subresources.layers[0].mip_levels[0].width);
         debug_assert_eq!(height, subresources.layers[0].mip_levels[0].height);
+        debug_assert_eq!(depth, subresources.layers[0].mip_levels[0].depth);
         for i in 1..subresources.layers.len() {
             let layers = &subresources.layers;
             let layer_0 = &layers[0];
@@ -50,7 +58,7 @@ pub fn new_gpu_image_data_from_image_asset_data_subresources(
             for j in 1..layer_0.mip_levels.len() {
                 debug_assert_eq!(layer_0.mip_levels[j].width, layers[i].mip_levels[j].width);
                 debug_assert_eq!(layer_0.mip_levels[j].height, layers[i].mip_levels[j].height);
-                debug_assert_eq!(layer_0.mip_levels[j].width, layers[i].mip_levels[j].width);
+                debug_assert_eq!(layer_0.mip_levels[j].depth, layers[i].mip_levels[j].depth);
             }
         }
     }
@@ -65,6 +73,7 @@ pub fn new_gpu_image_data_from_image_asset_data_subresources(
                 .map(|mip_level| GpuImageDataMipLevel {
                     width: mip_level.width,
                     height: mip_level.height,
+                    depth: mip_level.depth,
                     data: mip_level.bytes,
                 })
                 .collect();
@@ -76,11 +85,52 @@ pub fn new_gpu_image_data_from_image_asset_data_subresources(
     GpuImageData {
         width,
         height,
+        depth,
+        dimensions,
         format,
         layers,
     }
 }
 
+// Builds the full mip chain of an image on the CPU. Normally RGBA8 images generate their mips on
+// the GPU, but a streamed image must have all of its mips available so that any part of the chain
+// can be uploaded.
+fn generate_rgba8_mip_chain(
+    image_data: GpuImageData,
+    mode: Rgba8DownsampleMode,
+) -> GpuImageData {
+    let layers = image_data
+        .layers
+        .into_iter()
+        .map(|layer| {
+            let mut mip_levels = layer.mip_levels;
+            loop {
+                let last = mip_levels.last().unwrap();
+                if last.width == 1 && last.height == 1 {
+                    break;
+                }
+
+                let (width, height, data) = super::image_processing::downsample_rgba8(
+                    last.width,
+                    last.height,
+                    &last.data,
+                    mode,
+                );
+                mip_levels.push(GpuImageDataMipLevel {
+                    width,
+                    height,
+                    depth: 1,
+                    data,
+                });
+            }
+
+            GpuImageDataLayer::new(mip_levels)
+        })
+        .collect();
+
+    GpuImageData::new(layers, image_data.format)
+}
+
 pub struct ImageAssetUploadQueue {
     pub upload_queue_context: UploadQueueContext,
 
@@ -122,31 +172,162 @@ impl ImageAssetUploadQueue {
             ImageAssetDataFormat::BC6H_SFloat => RafxFormat::BC6H_SFLOAT_BLOCK,
             ImageAssetDataFormat::BC7_Unorm_Linear => RafxFormat::BC7_UNORM_BLOCK,
             ImageAssetDataFormat::BC7_Unorm_Srgb => RafxFormat::BC7_SRGB_BLOCK,
+            ImageAssetDataFormat::R16G16B16A16_SFloat => RafxFormat::R16G16B16A16_SFLOAT,
+            ImageAssetDataFormat::R32G32B32A32_SFloat => RafxFormat::R32G32B32A32_SFLOAT,
+            ImageAssetDataFormat::ETC2_R8G8B8_UNorm_Linear => RafxFormat::ETC2_R8G8B8_UNORM_BLOCK,
+            ImageAssetDataFormat::ETC2_R8G8B8_UNorm_Srgb => RafxFormat::ETC2_R8G8B8_SRGB_BLOCK,
+            ImageAssetDataFormat::ETC2_R8G8B8A1_UNorm_Linear => {
+                RafxFormat::ETC2_R8G8B8A1_UNORM_BLOCK
+            }
+            ImageAssetDataFormat::ETC2_R8G8B8A1_UNorm_Srgb => RafxFormat::ETC2_R8G8B8A1_SRGB_BLOCK,
+            ImageAssetDataFormat::ETC2_R8G8B8A8_UNorm_Linear => {
+                RafxFormat::ETC2_R8G8B8A8_UNORM_BLOCK
+            }
+            ImageAssetDataFormat::ETC2_R8G8B8A8_UNorm_Srgb => RafxFormat::ETC2_R8G8B8A8_SRGB_BLOCK,
+            ImageAssetDataFormat::EAC_R11_UNorm => RafxFormat::EAC_R11_UNORM_BLOCK,
+            ImageAssetDataFormat::EAC_R11_SNorm => RafxFormat::EAC_R11_SNORM_BLOCK,
+            ImageAssetDataFormat::EAC_R11G11_UNorm => RafxFormat::EAC_R11G11_UNORM_BLOCK,
+            ImageAssetDataFormat::EAC_R11G11_SNorm => RafxFormat::EAC_R11G11_SNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_4x4_UNorm_Linear => RafxFormat::ASTC_4X4_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_4x4_UNorm_Srgb => RafxFormat::ASTC_4X4_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_5x4_UNorm_Linear => RafxFormat::ASTC_5X4_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_5x4_UNorm_Srgb => RafxFormat::ASTC_5X4_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_5x5_UNorm_Linear => RafxFormat::ASTC_5X5_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_5x5_UNorm_Srgb => RafxFormat::ASTC_5X5_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_6x5_UNorm_Linear => RafxFormat::ASTC_6X5_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_6x5_UNorm_Srgb => RafxFormat::ASTC_6X5_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_6x6_UNorm_Linear => RafxFormat::ASTC_6X6_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_6x6_UNorm_Srgb => RafxFormat::ASTC_6X6_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_8x5_UNorm_Linear => RafxFormat::ASTC_8X5_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_8x5_UNorm_Srgb => RafxFormat::ASTC_8X5_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_8x6_UNorm_Linear => RafxFormat::ASTC_8X6_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_8x6_UNorm_Srgb => RafxFormat::ASTC_8X6_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_8x8_UNorm_Linear => RafxFormat::ASTC_8X8_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_8x8_UNorm_Srgb => RafxFormat::ASTC_8X8_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_10x5_UNorm_Linear => RafxFormat::ASTC_10X5_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_10x5_UNorm_Srgb => RafxFormat::ASTC_10X5_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_10x6_UNorm_Linear => RafxFormat::ASTC_10X6_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_10x6_UNorm_Srgb => RafxFormat::ASTC_10X6_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_10x8_UNorm_Linear => RafxFormat::ASTC_10X8_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_10x8_UNorm_Srgb => RafxFormat::ASTC_10X8_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_10x10_UNorm_Linear => RafxFormat::ASTC_10X10_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_10x10_UNorm_Srgb => RafxFormat::ASTC_10X10_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_12x10_UNorm_Linear => RafxFormat::ASTC_12X10_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_12x10_UNorm_Srgb => RafxFormat::ASTC_12X10_SRGB_BLOCK,
+            ImageAssetDataFormat::ASTC_12x12_UNorm_Linear => RafxFormat::ASTC_12X12_UNORM_BLOCK,
+            ImageAssetDataFormat::ASTC_12x12_UNorm_Srgb => RafxFormat::ASTC_12X12_SRGB_BLOCK,
 
             // We choose format of basis at runtime depending on what our hardware supports
             ImageAssetDataFormat::Basis_Linear => unimplemented!(),
             ImageAssetDataFormat::Basis_Srgb => unimplemented!(),
+            ImageAssetDataFormat::UASTC_Linear => unimplemented!(),
+            ImageAssetDataFormat::UASTC_Srgb => unimplemented!(),
         }
     }
 
+    // Unlike .basis files, UASTC blocks don't need any data from the rest of the file to be
+    // transcoded, so each subresource is transcoded on its own
+    #[cfg(feature = "basis-universal")]
+    fn transcode_uastc_subresources(
+        &self,
+        color_space: GpuImageDataColorSpace,
+        generate_mips: bool,
+        subresources: ImageAssetDataPayloadSubresources,
+    ) -> RafxResult<GpuImageData> {
+        let (rafx_format, block_format) = if generate_mips {
+            // We can't do runtime mip generation with compresed formats, fall back to uncompressed data
+            (color_space.rgba8(), TranscoderBlockFormat::RGBA32)
+        } else if self.astc4x4_supported {
+            (color_space.astc4x4(), TranscoderBlockFormat::ASTC_4x4)
+        } else if self.bc7_supported {
+            (color_space.bc7(), TranscoderBlockFormat::BC7)
+        } else {
+            (color_space.rgba8(), TranscoderBlockFormat::RGBA32)
+        };
+
+        log::trace!(
+            "Transcoding UASTC format: {:?} block format: {:?} layers: {}",
+            rafx_format,
+            block_format,
+            subresources.layers.len()
+        );
+
+        let transcoder = LowLevelUastcTranscoder::new();
+        let mut layers = Vec::with_capacity(subresources.layers.len());
+        for layer in subresources.layers {
+            if layer.mip_levels.len() > 1 && generate_mips {
+                Err("UASTC image asset configured to generate mips at runtime but has more than one mip layer stored")?;
+            }
+
+            let mut levels = Vec::with_capacity(layer.mip_levels.len());
+            for mip_level in layer.mip_levels {
+                let level_data = transcoder
+                    .transcode_slice(
+                        &mip_level.bytes,
+                        SliceParametersUastc {
+                            num_blocks_x: (mip_level.width + 3) / 4,
+                            num_blocks_y: (mip_level.height + 3) / 4,
+                            has_alpha: true,
+                            original_width: mip_level.width,
+                            original_height: mip_level.height,
+                        },
+                        DecodeFlags::HIGH_QUALITY,
+                        block_format,
+                    )
+                    .map_err(|e| format!("Failed to transcode UASTC image data: {:?}", e))?;
+
+                levels.push(GpuImageDataMipLevel {
+                    width: mip_level.width,
+                    height: mip_level.height,
+                    depth: 1,
+                    data: level_data,
+                });
+            }
+
+            layers.push(GpuImageDataLayer::new(levels));
+        }
+
+        Ok(GpuImageData::new(layers, rafx_format))
+    }
+
     pub fn upload_image(
         &self,
         request: LoadRequest<ImageAssetData, ImageAsset>,
+        texture_streamer: &mut TextureStreamer,
     ) -> RafxResult<()> {
-        let generate_mips = request.asset.generate_mips_at_runtime;
+        let mut generate_mips = request.asset.generate_mips_at_runtime;
 
         let t0 = rafx_base::Instant::now();
-        let image_data = match request.asset.data {
+        let mut image_data = match request.asset.data {
             ImageAssetDataPayload::Subresources(subresources) => {
                 profiling::scope!("prepare upload image");
+                match request.asset.format {
+                    #[cfg(not(feature = "basis-universal"))]
+                    ImageAssetDataFormat::UASTC_Linear | ImageAssetDataFormat::UASTC_Srgb => {
+                        Err("Can't upload UASTC image data, the crate was not built with the basis-universal feature")?
+                    }
+                    #[cfg(feature = "basis-universal")]
+                    ImageAssetDataFormat::UASTC_Linear | ImageAssetDataFormat::UASTC_Srgb => {
+                        let color_space = match request.asset.format {
+                            ImageAssetDataFormat::UASTC_Linear => GpuImageDataColorSpace::Linear,
+                            ImageAssetDataFormat::UASTC_Srgb => GpuImageDataColorSpace::Srgb,
+                            _ => unreachable!(),
+                        };
 
-                let rafx_format = Self::get_rafx_format(request.asset.format);
-                new_gpu_image_data_from_image_asset_data_subresources(
-                    request.asset.width,
-                    request.asset.height,
-                    rafx_format,
-                    subresources,
-                )
+                        self.transcode_uastc_subresources(color_space, generate_mips, subresources)?
+                    }
+                    _ => {
+                        let rafx_format = Self::get_rafx_format(request.asset.format);
+                        new_gpu_image_data_from_image_asset_data_subresources(
+                            request.asset.width,
+                            request.asset.height,
+                            request.asset.depth,
+                            request.asset.dimensions,
+                            rafx_format,
+                            subresources,
+                        )
+                    }
+                }
             }
             ImageAssetDataPayload::SingleBuffer(_single_buffer) => {
                 profiling::scope!("prepare upload image");
@@ -240,6 +421,7 @@ impl ImageAssetUploadQueue {
                                 levels.push(GpuImageDataMipLevel {
                                     width: level_description.original_width,
                                     height: level_description.original_height,
+                                    depth: 1,
                                     data: level_data,
                                 });
                             }
@@ -253,6 +435,33 @@ impl ImageAssetUploadQueue {
                 }
             }
         };
+
+        let resource_type = request.asset.resource_type;
+        if request.asset.allow_mip_streaming
+            && texture_streamer.can_stream_image(&image_data, resource_type)
+        {
+            if generate_mips {
+                let downsample_mode = match image_data.format {
+                    RafxFormat::R8G8B8A8_SRGB => Some(Rgba8DownsampleMode::Srgb),
+                    RafxFormat::R8G8B8A8_UNORM => Some(Rgba8DownsampleMode::Linear),
+                    _ => None,
+                };
+
+                if let Some(downsample_mode) = downsample_mode {
+                    profiling::scope!("generate mips for streaming");
+                    image_data = generate_rgba8_mip_chain(image_data, downsample_mode);
+                    generate_mips = false;
+                }
+            }
+
+            if !generate_mips && image_data.mip_level_count() > 1 {
+                image_data = texture_streamer.begin_streamed_upload(
+                    request.load_handle,
+                    image_data,
+                    resource_type,
+                );
+            }
+        }
         let t1 = rafx_base::Instant::now();
 
         #[cfg(debug_assertions)]
@@ -274,8 +483,9 @@ impl ImageAssetUploadQueue {
         self.upload_queue_context.upload_new_image(
             op,
             image_data,
-            request.asset.resource_type,
+            resource_type,
             generate_mips,
+            UploadPriority::Normal,
         )
     }
 }
@@ -1,4 +1,4 @@
-use rafx_api::{RafxResourceType, RafxResult};
+use rafx_api::{RafxResourceType, RafxResult, RafxTextureDimensions};
 use rafx_framework::upload::GpuImageDataColorSpace;
 use rafx_framework::{ImageResource, ImageViewResource, ResourceArc};
 use serde::{Deserialize, Serialize};
@@ -69,6 +69,59 @@ impl ImageAssetBasisCompressionSettings {
     }
 }
 
+//NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ImageAssetBcFormat {
+    // RGB with 1-bit alpha, 4 bits per pixel
+    BC1,
+    // RGBA, 8 bits per pixel
+    BC3,
+    // Single channel (R), 4 bits per pixel
+    BC4,
+    // Two channels (RG), 8 bits per pixel
+    BC5,
+    // Same as BC5, but RG is treated as the XY of a tangent-space normal when generating mips so
+    // that they are renormalized. The shader must reconstruct Z.
+    BC5NormalMap,
+    // RGBA, 8 bits per pixel, best quality but slowest to encode
+    BC7,
+}
+
+// Presets for the encoder, slower presets produce higher quality. Only BC7 has settings to tune,
+// the other formats always use the same encoder.
+//NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ImageAssetBcQuality {
+    UltraFast,
+    VeryFast,
+    Fast,
+    Basic,
+    Slow,
+}
+
+//NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ImageAssetBcCompressionSettings {
+    pub format: ImageAssetBcFormat,
+    pub quality: ImageAssetBcQuality,
+}
+
+impl ImageAssetBcCompressionSettings {
+    pub fn default_color() -> Self {
+        ImageAssetBcCompressionSettings {
+            format: ImageAssetBcFormat::BC7,
+            quality: ImageAssetBcQuality::Basic,
+        }
+    }
+
+    pub fn default_normal_map() -> Self {
+        ImageAssetBcCompressionSettings {
+            format: ImageAssetBcFormat::BC5NormalMap,
+            quality: ImageAssetBcQuality::Basic,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 #[allow(non_camel_case_types)]
 pub enum ImageAssetDataFormat {
@@ -90,6 +143,50 @@ pub enum ImageAssetDataFormat {
     BC6H_SFloat,
     BC7_Unorm_Linear,
     BC7_Unorm_Srgb,
+    ETC2_R8G8B8_UNorm_Linear,
+    ETC2_R8G8B8_UNorm_Srgb,
+    ETC2_R8G8B8A1_UNorm_Linear,
+    ETC2_R8G8B8A1_UNorm_Srgb,
+    ETC2_R8G8B8A8_UNorm_Linear,
+    ETC2_R8G8B8A8_UNorm_Srgb,
+    EAC_R11_UNorm,
+    EAC_R11_SNorm,
+    EAC_R11G11_UNorm,
+    EAC_R11G11_SNorm,
+    ASTC_4x4_UNorm_Linear,
+    ASTC_4x4_UNorm_Srgb,
+    ASTC_5x4_UNorm_Linear,
+    ASTC_5x4_UNorm_Srgb,
+    ASTC_5x5_UNorm_Linear,
+    ASTC_5x5_UNorm_Srgb,
+    ASTC_6x5_UNorm_Linear,
+    ASTC_6x5_UNorm_Srgb,
+    ASTC_6x6_UNorm_Linear,
+    ASTC_6x6_UNorm_Srgb,
+    ASTC_8x5_UNorm_Linear,
+    ASTC_8x5_UNorm_Srgb,
+    ASTC_8x6_UNorm_Linear,
+    ASTC_8x6_UNorm_Srgb,
+    ASTC_8x8_UNorm_Linear,
+    ASTC_8x8_UNorm_Srgb,
+    ASTC_10x5_UNorm_Linear,
+    ASTC_10x5_UNorm_Srgb,
+    ASTC_10x6_UNorm_Linear,
+    ASTC_10x6_UNorm_Srgb,
+    ASTC_10x8_UNorm_Linear,
+    ASTC_10x8_UNorm_Srgb,
+    ASTC_10x10_UNorm_Linear,
+    ASTC_10x10_UNorm_Srgb,
+    ASTC_12x10_UNorm_Linear,
+    ASTC_12x10_UNorm_Srgb,
+    ASTC_12x12_UNorm_Linear,
+    ASTC_12x12_UNorm_Srgb,
+    // UASTC 4x4 blocks (i.e. from a KTX2 file) that are transcoded at runtime to a format the
+    // hardware supports
+    UASTC_Linear,
+    UASTC_Srgb,
+    R16G16B16A16_SFloat,
+    R32G32B32A32_SFloat,
 }
 
 //NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
@@ -97,12 +194,33 @@ pub enum ImageAssetDataFormat {
 pub enum ImageAssetDataFormatConfig {
     Uncompressed,
     BasisCompressed(ImageAssetBasisCompressionSettings),
+    // Encoded to a BCn format at import time. Runtime mip generation is not possible for
+    // compressed formats, so mips are precomputed unless mip generation is disabled.
+    BcCompressed(ImageAssetBcCompressionSettings),
+}
+
+//NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ImageAssetHdrDataFormatConfig {
+    Float16,
+    Float32,
+}
+
+//NOTE: This is serialized in image asset options, so may require asset schema change if modifying it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ImageAssetProjection {
+    Flat,
+    // Converts an equirectangular (latitude/longitude) panorama into a cubemap. If a face size is
+    // not given, it is half the height of the panorama (so that texel density is roughly the same)
+    EquirectangularToCubemap { face_size: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ImageAssetDataMipLevel {
     pub width: u32,
     pub height: u32,
+    // Number of depth slices in bytes, 1 unless this is a 3D image
+    pub depth: u32,
     #[serde(with = "serde_bytes")]
     pub bytes: Vec<u8>,
 }
@@ -135,9 +253,16 @@ pub enum ImageAssetDataPayload {
 pub struct ImageAssetData {
     pub width: u32,
     pub height: u32,
+    // Greater than 1 only for 3D images, which have a single layer
+    pub depth: u32,
     pub format: ImageAssetDataFormat,
     pub resource_type: RafxResourceType,
+    pub dimensions: RafxTextureDimensions,
     pub generate_mips_at_runtime: bool,
+    // If true, the texture streamer may keep only the smaller mips of this image resident and load
+    // the larger ones on demand. Only consumers that look up the resident image view (i.e. the
+    // material DB) see the streamed mips, so this should only be set for images used that way.
+    pub allow_mip_streaming: bool,
     pub data: ImageAssetDataPayload,
 }
 
@@ -148,7 +273,8 @@ impl std::fmt::Debug for ImageAssetData {
     ) -> std::fmt::Result {
         f.debug_struct("Point")
             .field("width", &self.width)
-            .field("width", &self.height)
+            .field("height", &self.height)
+            .field("depth", &self.depth)
             .field("format", &self.format)
             .finish()
     }
@@ -201,6 +327,7 @@ impl ImageAssetData {
                 let mip = ImageAssetDataMipLevel {
                     width,
                     height,
+                    depth: 1,
                     bytes: raw_rgba32.to_vec(),
                 };
 
@@ -216,9 +343,12 @@ impl ImageAssetData {
                 Ok(ImageAssetData {
                     width,
                     height,
+                    depth: 1,
                     format,
                     generate_mips_at_runtime,
+                    allow_mip_streaming: false,
                     resource_type,
+                    dimensions: RafxTextureDimensions::Dim2D,
                     data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources {
                         layers: vec![layer],
                     }),
@@ -276,15 +406,156 @@ impl ImageAssetData {
                 Ok(ImageAssetData {
                     width,
                     height,
+                    depth: 1,
                     format,
                     generate_mips_at_runtime,
+                    allow_mip_streaming: false,
                     resource_type,
+                    dimensions: RafxTextureDimensions::Dim2D,
                     data: ImageAssetDataPayload::SingleBuffer(ImageAssetDataPayloadSingleBuffer {
                         buffer: compressed_basis_data.to_vec(),
                     }),
                 })
             }
+            #[cfg(not(feature = "intel_tex"))]
+            ImageAssetDataFormatConfig::BcCompressed(_) => Err(
+                "Can't BC compress the image, the crate was not built with the intel_tex feature",
+            )?,
+            #[cfg(feature = "intel_tex")]
+            ImageAssetDataFormatConfig::BcCompressed(settings) => {
+                let generate_mips = mip_generation != ImageAssetMipGeneration::NoMips;
+                let (format, mip_levels) = super::bc_compression::compress_bc_mip_chain(
+                    width,
+                    height,
+                    color_space,
+                    &settings,
+                    generate_mips,
+                    raw_rgba32,
+                );
+
+                Ok(ImageAssetData {
+                    width,
+                    height,
+                    depth: 1,
+                    format,
+                    generate_mips_at_runtime: false,
+                    allow_mip_streaming: false,
+                    resource_type,
+                    dimensions: RafxTextureDimensions::Dim2D,
+                    data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources {
+                        layers: vec![ImageAssetDataLayer { mip_levels }],
+                    }),
+                })
+            }
+        }
+    }
+}
+
+impl ImageAssetData {
+    /// Creates an image asset from float data (4 f32 per pixel). Unlike from_raw_rgba32, mipmaps
+    /// can be precomputed, and cubemaps are always stored with precomputed mips unless mips are
+    /// disabled.
+    pub fn from_raw_rgba_f32(
+        width: u32,
+        height: u32,
+        format_config: ImageAssetHdrDataFormatConfig,
+        mip_generation: ImageAssetMipGeneration,
+        projection: ImageAssetProjection,
+        raw_rgba_f32: &[f32],
+    ) -> RafxResult<ImageAssetData> {
+        if raw_rgba_f32.len() != (width * height * 4) as usize {
+            Err(format!(
+                "Float image data has {} values, expected {} for a {}x{} RGBA image",
+                raw_rgba_f32.len(),
+                width * height * 4,
+                width,
+                height
+            ))?;
         }
+
+        let (format, to_bytes): (_, fn(&[f32]) -> Vec<u8>) = match format_config {
+            ImageAssetHdrDataFormatConfig::Float16 => (
+                ImageAssetDataFormat::R16G16B16A16_SFloat,
+                super::image_processing::rgba_f32_to_f16_bytes,
+            ),
+            ImageAssetHdrDataFormatConfig::Float32 => (
+                ImageAssetDataFormat::R32G32B32A32_SFloat,
+                super::image_processing::rgba_f32_to_bytes,
+            ),
+        };
+
+        let (layer_size, layer_data, resource_type) = match projection {
+            ImageAssetProjection::Flat => (
+                (width, height),
+                vec![raw_rgba_f32.to_vec()],
+                RafxResourceType::TEXTURE,
+            ),
+            ImageAssetProjection::EquirectangularToCubemap { face_size } => {
+                let face_size = face_size.unwrap_or(height / 2).max(1);
+                let faces = super::image_processing::equirectangular_to_cubemap(
+                    width,
+                    height,
+                    raw_rgba_f32,
+                    face_size,
+                );
+                (
+                    (face_size, face_size),
+                    faces,
+                    RafxResourceType::TEXTURE_CUBE,
+                )
+            }
+        };
+
+        let precompute_mips = match mip_generation {
+            ImageAssetMipGeneration::NoMips => false,
+            ImageAssetMipGeneration::Precomupted => true,
+            ImageAssetMipGeneration::Runtime => resource_type == RafxResourceType::TEXTURE_CUBE,
+        };
+        let generate_mips_at_runtime =
+            mip_generation == ImageAssetMipGeneration::Runtime && !precompute_mips;
+
+        let layers = layer_data
+            .into_iter()
+            .map(|data| {
+                let (mut mip_width, mut mip_height) = layer_size;
+                let mut mip_data = data;
+                let mut mip_levels = vec![];
+                loop {
+                    mip_levels.push(ImageAssetDataMipLevel {
+                        width: mip_width,
+                        height: mip_height,
+                        depth: 1,
+                        bytes: (to_bytes)(&mip_data),
+                    });
+
+                    if !precompute_mips || (mip_width == 1 && mip_height == 1) {
+                        break;
+                    }
+
+                    let (next_width, next_height, next_data) =
+                        super::image_processing::downsample_rgba_f32(
+                            mip_width, mip_height, &mip_data,
+                        );
+                    mip_width = next_width;
+                    mip_height = next_height;
+                    mip_data = next_data;
+                }
+
+                ImageAssetDataLayer { mip_levels }
+            })
+            .collect();
+
+        Ok(ImageAssetData {
+            width: layer_size.0,
+            height: layer_size.1,
+            depth: 1,
+            format,
+            generate_mips_at_runtime,
+            allow_mip_streaming: false,
+            resource_type,
+            dimensions: RafxTextureDimensions::Dim2D,
+            data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources { layers }),
+        })
     }
 }
 
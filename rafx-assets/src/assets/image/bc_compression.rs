@@ -0,0 +1,313 @@
+use super::image_processing::{downsample_rgba8, Rgba8DownsampleMode};
+use crate::assets::image::{
+    ImageAssetBcCompressionSettings, ImageAssetBcFormat, ImageAssetBcQuality,
+    ImageAssetColorSpaceConfig, ImageAssetDataFormat, ImageAssetDataMipLevel,
+};
+
+// The encoder works on whole 4x4 blocks, so the surface is padded by repeating the last row and
+// column. The padding is never sampled, it just keeps the edge blocks from bleeding in garbage.
+fn pad_to_block_size(
+    width: u32,
+    height: u32,
+    data: &[u8],
+    bytes_per_pixel: usize,
+) -> (u32, u32, Vec<u8>) {
+    let padded_width = (width + 3) / 4 * 4;
+    let padded_height = (height + 3) / 4 * 4;
+    if padded_width == width && padded_height == height {
+        return (width, height, data.to_vec());
+    }
+
+    let mut padded = Vec::with_capacity((padded_width * padded_height) as usize * bytes_per_pixel);
+    for y in 0..padded_height {
+        let src_y = y.min(height - 1);
+        for x in 0..padded_width {
+            let src_x = x.min(width - 1);
+            let offset = (src_y * width + src_x) as usize * bytes_per_pixel;
+            padded.extend_from_slice(&data[offset..offset + bytes_per_pixel]);
+        }
+    }
+
+    (padded_width, padded_height, padded)
+}
+
+fn extract_channels(
+    rgba: &[u8],
+    channels: &[usize],
+) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|pixel| channels.iter().map(move |channel| pixel[*channel]))
+        .collect()
+}
+
+fn bc7_settings(
+    quality: ImageAssetBcQuality,
+    has_alpha: bool,
+) -> intel_tex::bc7::EncodeSettings {
+    use intel_tex::bc7;
+    match (quality, has_alpha) {
+        (ImageAssetBcQuality::UltraFast, false) => bc7::opaque_ultra_fast_settings(),
+        (ImageAssetBcQuality::VeryFast, false) => bc7::opaque_very_fast_settings(),
+        (ImageAssetBcQuality::Fast, false) => bc7::opaque_fast_settings(),
+        (ImageAssetBcQuality::Basic, false) => bc7::opaque_basic_settings(),
+        (ImageAssetBcQuality::Slow, false) => bc7::opaque_slow_settings(),
+        (ImageAssetBcQuality::UltraFast, true) => bc7::alpha_ultra_fast_settings(),
+        (ImageAssetBcQuality::VeryFast, true) => bc7::alpha_very_fast_settings(),
+        (ImageAssetBcQuality::Fast, true) => bc7::alpha_fast_settings(),
+        (ImageAssetBcQuality::Basic, true) => bc7::alpha_basic_settings(),
+        (ImageAssetBcQuality::Slow, true) => bc7::alpha_slow_settings(),
+    }
+}
+
+fn compress_level(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    settings: &ImageAssetBcCompressionSettings,
+    bc7_settings: &intel_tex::bc7::EncodeSettings,
+) -> Vec<u8> {
+    match settings.format {
+        ImageAssetBcFormat::BC1 | ImageAssetBcFormat::BC3 | ImageAssetBcFormat::BC7 => {
+            let (width, height, data) = pad_to_block_size(width, height, rgba, 4);
+            let surface = intel_tex::RgbaSurface {
+                data: &data,
+                width,
+                height,
+                stride: width * 4,
+            };
+
+            match settings.format {
+                ImageAssetBcFormat::BC1 => intel_tex::bc1::compress_blocks(&surface),
+                ImageAssetBcFormat::BC3 => intel_tex::bc3::compress_blocks(&surface),
+                _ => intel_tex::bc7::compress_blocks(bc7_settings, &surface),
+            }
+        }
+        ImageAssetBcFormat::BC4 => {
+            let r = extract_channels(rgba, &[0]);
+            let (width, height, data) = pad_to_block_size(width, height, &r, 1);
+            intel_tex::bc4::compress_blocks(&intel_tex::RSurface {
+                data: &data,
+                width,
+                height,
+                stride: width,
+            })
+        }
+        ImageAssetBcFormat::BC5 | ImageAssetBcFormat::BC5NormalMap => {
+            let rg = extract_channels(rgba, &[0, 1]);
+            let (width, height, data) = pad_to_block_size(width, height, &rg, 2);
+            intel_tex::bc5::compress_blocks(&intel_tex::RgSurface {
+                data: &data,
+                width,
+                height,
+                stride: width * 2,
+            })
+        }
+    }
+}
+
+// Compresses the image and (optionally) a full mip chain. Mips are generated from the
+// uncompressed data so that compression artifacts don't accumulate down the chain.
+pub(crate) fn compress_bc_mip_chain(
+    width: u32,
+    height: u32,
+    color_space: ImageAssetColorSpaceConfig,
+    settings: &ImageAssetBcCompressionSettings,
+    generate_mips: bool,
+    raw_rgba32: &[u8],
+) -> (ImageAssetDataFormat, Vec<ImageAssetDataMipLevel>) {
+    let is_srgb = match color_space {
+        ImageAssetColorSpaceConfig::Srgb => true,
+        ImageAssetColorSpaceConfig::Linear => false,
+    };
+
+    // BC4 and BC5 don't have sRGB variants, they are always read as linear data
+    let (format, downsample_mode) = match settings.format {
+        ImageAssetBcFormat::BC1 if is_srgb => (
+            ImageAssetDataFormat::BC1_UNorm_Srgb,
+            Rgba8DownsampleMode::Srgb,
+        ),
+        ImageAssetBcFormat::BC1 => (
+            ImageAssetDataFormat::BC1_UNorm_Linear,
+            Rgba8DownsampleMode::Linear,
+        ),
+        ImageAssetBcFormat::BC3 if is_srgb => (
+            ImageAssetDataFormat::BC3_UNorm_Srgb,
+            Rgba8DownsampleMode::Srgb,
+        ),
+        ImageAssetBcFormat::BC3 => (
+            ImageAssetDataFormat::BC3_UNorm_Linear,
+            Rgba8DownsampleMode::Linear,
+        ),
+        ImageAssetBcFormat::BC7 if is_srgb => (
+            ImageAssetDataFormat::BC7_Unorm_Srgb,
+            Rgba8DownsampleMode::Srgb,
+        ),
+        ImageAssetBcFormat::BC7 => (
+            ImageAssetDataFormat::BC7_Unorm_Linear,
+            Rgba8DownsampleMode::Linear,
+        ),
+        ImageAssetBcFormat::BC4 => (ImageAssetDataFormat::BC4_UNorm, Rgba8DownsampleMode::Linear),
+        ImageAssetBcFormat::BC5 => (ImageAssetDataFormat::BC5_UNorm, Rgba8DownsampleMode::Linear),
+        ImageAssetBcFormat::BC5NormalMap => (
+            ImageAssetDataFormat::BC5_UNorm,
+            Rgba8DownsampleMode::NormalMap,
+        ),
+    };
+
+    let has_alpha = raw_rgba32.chunks_exact(4).any(|pixel| pixel[3] != 255);
+    let bc7_settings = bc7_settings(settings.quality, has_alpha);
+
+    let mut mip_width = width;
+    let mut mip_height = height;
+    let mut mip_data = raw_rgba32.to_vec();
+    let mut mip_levels = vec![];
+    loop {
+        log::debug!(
+            "Compressing {}x{} mip to {:?}",
+            mip_width,
+            mip_height,
+            format
+        );
+        mip_levels.push(ImageAssetDataMipLevel {
+            width: mip_width,
+            height: mip_height,
+            depth: 1,
+            bytes: compress_level(mip_width, mip_height, &mip_data, settings, &bc7_settings),
+        });
+
+        if !generate_mips || (mip_width == 1 && mip_height == 1) {
+            break;
+        }
+
+        let (next_width, next_height, next_data) =
+            downsample_rgba8(mip_width, mip_height, &mip_data, downsample_mode);
+        mip_width = next_width;
+        mip_height = next_height;
+        mip_data = next_data;
+    }
+
+    (format, mip_levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_image(
+        width: u32,
+        height: u32,
+        alpha: u8,
+    ) -> Vec<u8> {
+        (0..width * height)
+            .flat_map(|i| vec![(i * 7) as u8, (i * 13) as u8, (i * 29) as u8, alpha])
+            .collect()
+    }
+
+    fn compress(
+        format: ImageAssetBcFormat,
+        color_space: ImageAssetColorSpaceConfig,
+        generate_mips: bool,
+    ) -> (ImageAssetDataFormat, Vec<ImageAssetDataMipLevel>) {
+        compress_bc_mip_chain(
+            5,
+            3,
+            color_space,
+            &ImageAssetBcCompressionSettings {
+                format,
+                quality: ImageAssetBcQuality::UltraFast,
+            },
+            generate_mips,
+            &test_image(5, 3, 128),
+        )
+    }
+
+    #[test]
+    fn test_pad_to_block_size() {
+        let data: Vec<u8> = (0..5 * 3).collect();
+        let (width, height, padded) = pad_to_block_size(5, 3, &data, 1);
+        assert_eq!((width, height), (8, 4));
+        assert_eq!(&padded[0..8], &[0, 1, 2, 3, 4, 4, 4, 4]);
+        // The last row is repeated
+        assert_eq!(&padded[16..24], &[10, 11, 12, 13, 14, 14, 14, 14]);
+        assert_eq!(&padded[24..32], &[10, 11, 12, 13, 14, 14, 14, 14]);
+
+        // Block aligned data is left alone
+        let data = test_image(4, 8, 255);
+        assert_eq!(pad_to_block_size(4, 8, &data, 4), (4, 8, data));
+    }
+
+    #[test]
+    fn test_compressed_sizes() {
+        // A 5x3 image is padded to 2x1 blocks
+        for (format, bytes_per_block) in &[
+            (ImageAssetBcFormat::BC1, 8),
+            (ImageAssetBcFormat::BC3, 16),
+            (ImageAssetBcFormat::BC4, 8),
+            (ImageAssetBcFormat::BC5, 16),
+            (ImageAssetBcFormat::BC5NormalMap, 16),
+            (ImageAssetBcFormat::BC7, 16),
+        ] {
+            let (_, mip_levels) = compress(*format, ImageAssetColorSpaceConfig::Linear, false);
+            assert_eq!(mip_levels.len(), 1);
+            assert_eq!(mip_levels[0].width, 5);
+            assert_eq!(mip_levels[0].height, 3);
+            assert_eq!(mip_levels[0].bytes.len(), 2 * bytes_per_block);
+        }
+    }
+
+    #[test]
+    fn test_mip_chain() {
+        let (_, mip_levels) = compress(
+            ImageAssetBcFormat::BC7,
+            ImageAssetColorSpaceConfig::Srgb,
+            true,
+        );
+        let sizes: Vec<_> = mip_levels
+            .iter()
+            .map(|mip_level| (mip_level.width, mip_level.height, mip_level.bytes.len()))
+            .collect();
+        // Every mip level takes at least one block
+        assert_eq!(sizes, vec![(5, 3, 32), (2, 1, 16), (1, 1, 16)]);
+    }
+
+    #[test]
+    fn test_formats() {
+        use ImageAssetColorSpaceConfig::{Linear, Srgb};
+        let format = |bc_format, color_space| compress(bc_format, color_space, false).0;
+
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC1, Srgb),
+            ImageAssetDataFormat::BC1_UNorm_Srgb
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC1, Linear),
+            ImageAssetDataFormat::BC1_UNorm_Linear
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC3, Srgb),
+            ImageAssetDataFormat::BC3_UNorm_Srgb
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC7, Srgb),
+            ImageAssetDataFormat::BC7_Unorm_Srgb
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC7, Linear),
+            ImageAssetDataFormat::BC7_Unorm_Linear
+        ));
+
+        // BC4 and BC5 are always linear
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC4, Srgb),
+            ImageAssetDataFormat::BC4_UNorm
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC5, Srgb),
+            ImageAssetDataFormat::BC5_UNorm
+        ));
+        assert!(matches!(
+            format(ImageAssetBcFormat::BC5NormalMap, Linear),
+            ImageAssetDataFormat::BC5_UNorm
+        ));
+    }
+}
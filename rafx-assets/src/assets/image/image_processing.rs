@@ -0,0 +1,316 @@
+// CPU-side image processing done at import time. Float images have 4 f32 per pixel, 8-bit images
+// have 4 u8 per pixel
+
+// Directions for texel (u, v) in [-1, 1] of each cube face, in the face order and orientation
+// vulkan expects (+X, -X, +Y, -Y, +Z, -Z)
+fn cube_face_direction(
+    face_index: usize,
+    u: f32,
+    v: f32,
+) -> [f32; 3] {
+    match face_index {
+        0 => [1.0, -v, -u],
+        1 => [-1.0, -v, u],
+        2 => [u, 1.0, v],
+        3 => [u, -1.0, -v],
+        4 => [u, -v, 1.0],
+        5 => [-u, -v, -1.0],
+        _ => unreachable!(),
+    }
+}
+
+// Bilinear sample that wraps horizontally and clamps vertically, which is what an equirectangular
+// panorama needs
+fn sample_equirectangular(
+    width: u32,
+    height: u32,
+    data: &[f32],
+    s: f32,
+    t: f32,
+) -> [f32; 4] {
+    let x = s * width as f32 - 0.5;
+    let y = (t * height as f32 - 0.5).max(0.0).min(height as f32 - 1.0);
+
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let fx = x - x0;
+    let fy = y - y0;
+
+    let x0 = (x0 as i64).rem_euclid(width as i64) as usize;
+    let x1 = (x0 + 1) % width as usize;
+    let y0 = y0 as usize;
+    let y1 = (y0 + 1).min(height as usize - 1);
+
+    let texel = |x: usize, y: usize| {
+        let offset = (y * width as usize + x) * 4;
+        &data[offset..offset + 4]
+    };
+
+    let mut result = [0.0; 4];
+    for channel in 0..4 {
+        let top = texel(x0, y0)[channel] * (1.0 - fx) + texel(x1, y0)[channel] * fx;
+        let bottom = texel(x0, y1)[channel] * (1.0 - fx) + texel(x1, y1)[channel] * fx;
+        result[channel] = top * (1.0 - fy) + bottom * fy;
+    }
+
+    result
+}
+
+// Produces the six faces of a cubemap from an equirectangular (latitude/longitude) panorama. The
+// panorama's vertical axis becomes +Y and its center column faces -Z. Cubemaps are Y up, the skybox
+// rotates them into the Z up world when drawing.
+pub(crate) fn equirectangular_to_cubemap(
+    width: u32,
+    height: u32,
+    data: &[f32],
+    face_size: u32,
+) -> Vec<Vec<f32>> {
+    (0..6)
+        .map(|face_index| {
+            let mut face = Vec::with_capacity((face_size * face_size * 4) as usize);
+            for y in 0..face_size {
+                for x in 0..face_size {
+                    let u = 2.0 * (x as f32 + 0.5) / face_size as f32 - 1.0;
+                    let v = 2.0 * (y as f32 + 0.5) / face_size as f32 - 1.0;
+                    let [dx, dy, dz] = cube_face_direction(face_index, u, v);
+                    let length = (dx * dx + dy * dy + dz * dz).sqrt();
+
+                    let longitude = dx.atan2(-dz);
+                    let latitude = (dy / length).asin();
+                    let s = 0.5 + longitude / (2.0 * std::f32::consts::PI);
+                    let t = 0.5 - latitude / std::f32::consts::PI;
+
+                    face.extend_from_slice(&sample_equirectangular(width, height, data, s, t));
+                }
+            }
+
+            face
+        })
+        .collect()
+}
+
+// Box filters the image down to half size (rounding down, minimum of 1 pixel)
+pub(crate) fn downsample_rgba_f32(
+    width: u32,
+    height: u32,
+    data: &[f32],
+) -> (u32, u32, Vec<f32>) {
+    let mip_width = (width / 2).max(1);
+    let mip_height = (height / 2).max(1);
+
+    let mut mip = Vec::with_capacity((mip_width * mip_height * 4) as usize);
+    for y in 0..mip_height {
+        for x in 0..mip_width {
+            let mut sum = [0.0; 4];
+            let mut count = 0.0;
+            for src_y in (y * 2)..((y * 2 + 2).min(height)) {
+                for src_x in (x * 2)..((x * 2 + 2).min(width)) {
+                    let offset = ((src_y * width + src_x) * 4) as usize;
+                    for channel in 0..4 {
+                        sum[channel] += data[offset + channel];
+                    }
+                    count += 1.0;
+                }
+            }
+
+            for channel in 0..4 {
+                mip.push(sum[channel] / count);
+            }
+        }
+    }
+
+    (mip_width, mip_height, mip)
+}
+
+pub(crate) fn rgba_f32_to_bytes(data: &[f32]) -> Vec<u8> {
+    data.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect()
+}
+
+pub(crate) fn rgba_f32_to_f16_bytes(data: &[f32]) -> Vec<u8> {
+    data.iter()
+        .flat_map(|x| half::f16::from_f32(*x).to_bits().to_le_bytes().to_vec())
+        .collect()
+}
+
+// How 8-bit RGBA data is interpreted when generating mips on the CPU
+#[cfg_attr(not(feature = "intel_tex"), allow(dead_code))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Rgba8DownsampleMode {
+    Linear,
+    // RGB is converted to linear before filtering, alpha is always linear
+    Srgb,
+    // RG hold the XY of a unit length tangent-space normal (Z is reconstructed by the shader).
+    // Averaged normals are renormalized so that mips don't get flatter as they get smaller
+    NormalMap,
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    let value = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (value * 255.0).round().max(0.0).min(255.0) as u8
+}
+
+fn unorm_to_u8(value: f32) -> u8 {
+    (value * 255.0).round().max(0.0).min(255.0) as u8
+}
+
+fn decode_normal(pixel: &[u8]) -> [f32; 3] {
+    let x = pixel[0] as f32 / 255.0 * 2.0 - 1.0;
+    let y = pixel[1] as f32 / 255.0 * 2.0 - 1.0;
+    let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+    [x, y, z]
+}
+
+// Box filters the image down to half size (rounding down, minimum of 1 pixel)
+pub(crate) fn downsample_rgba8(
+    width: u32,
+    height: u32,
+    data: &[u8],
+    mode: Rgba8DownsampleMode,
+) -> (u32, u32, Vec<u8>) {
+    let mip_width = (width / 2).max(1);
+    let mip_height = (height / 2).max(1);
+
+    let mut mip = Vec::with_capacity((mip_width * mip_height * 4) as usize);
+    for y in 0..mip_height {
+        for x in 0..mip_width {
+            let mut sum = [0.0; 4];
+            let mut count = 0.0;
+            for src_y in (y * 2)..((y * 2 + 2).min(height)) {
+                for src_x in (x * 2)..((x * 2 + 2).min(width)) {
+                    let offset = ((src_y * width + src_x) * 4) as usize;
+                    let pixel = &data[offset..offset + 4];
+                    match mode {
+                        Rgba8DownsampleMode::Linear => {
+                            for channel in 0..4 {
+                                sum[channel] += pixel[channel] as f32 / 255.0;
+                            }
+                        }
+                        Rgba8DownsampleMode::Srgb => {
+                            for channel in 0..3 {
+                                sum[channel] += srgb_to_linear(pixel[channel]);
+                            }
+                            sum[3] += pixel[3] as f32 / 255.0;
+                        }
+                        Rgba8DownsampleMode::NormalMap => {
+                            let normal = decode_normal(pixel);
+                            for channel in 0..3 {
+                                sum[channel] += normal[channel];
+                            }
+                            sum[3] += pixel[3] as f32 / 255.0;
+                        }
+                    }
+                    count += 1.0;
+                }
+            }
+
+            match mode {
+                Rgba8DownsampleMode::Linear => {
+                    for channel in 0..4 {
+                        mip.push(unorm_to_u8(sum[channel] / count));
+                    }
+                }
+                Rgba8DownsampleMode::Srgb => {
+                    for channel in 0..3 {
+                        mip.push(linear_to_srgb(sum[channel] / count));
+                    }
+                    mip.push(unorm_to_u8(sum[3] / count));
+                }
+                Rgba8DownsampleMode::NormalMap => {
+                    let length = (sum[0] * sum[0] + sum[1] * sum[1] + sum[2] * sum[2]).sqrt();
+                    let normal = if length > 0.0 {
+                        [sum[0] / length, sum[1] / length, sum[2] / length]
+                    } else {
+                        [0.0, 0.0, 1.0]
+                    };
+                    for channel in 0..3 {
+                        mip.push(unorm_to_u8(normal[channel] * 0.5 + 0.5));
+                    }
+                    mip.push(unorm_to_u8(sum[3] / count));
+                }
+            }
+        }
+    }
+
+    (mip_width, mip_height, mip)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_direction_eq(
+        actual: [f32; 3],
+        expected: [f32; 3],
+    ) {
+        for i in 0..3 {
+            assert!(
+                (actual[i] - expected[i]).abs() < 0.0001,
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    // An 8x4 panorama where the red channel is the column and the green channel is the row
+    fn test_panorama() -> Vec<f32> {
+        let mut data = Vec::default();
+        for y in 0..4 {
+            for x in 0..8 {
+                data.extend_from_slice(&[x as f32, y as f32, 0.0, 1.0]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_cube_face_direction() {
+        // Face centers
+        assert_direction_eq(cube_face_direction(0, 0.0, 0.0), [1.0, 0.0, 0.0]);
+        assert_direction_eq(cube_face_direction(1, 0.0, 0.0), [-1.0, 0.0, 0.0]);
+        assert_direction_eq(cube_face_direction(2, 0.0, 0.0), [0.0, 1.0, 0.0]);
+        assert_direction_eq(cube_face_direction(3, 0.0, 0.0), [0.0, -1.0, 0.0]);
+        assert_direction_eq(cube_face_direction(4, 0.0, 0.0), [0.0, 0.0, 1.0]);
+        assert_direction_eq(cube_face_direction(5, 0.0, 0.0), [0.0, 0.0, -1.0]);
+
+        // The top row (v = -1) of the side faces points up
+        for face_index in [0, 1, 4, 5].iter() {
+            assert_eq!(cube_face_direction(*face_index, 0.0, -1.0)[1], 1.0);
+        }
+
+        // The top-left corner of +Z is toward -X, +Y
+        assert_direction_eq(cube_face_direction(4, -1.0, -1.0), [-1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_equirectangular_to_cubemap_poles() {
+        let faces = equirectangular_to_cubemap(8, 4, &test_panorama(), 1);
+        assert_eq!(faces.len(), 6);
+
+        // +Y samples the top row of the panorama and -Y the bottom row
+        assert_eq!(faces[2][1], 0.0);
+        assert_eq!(faces[3][1], 3.0);
+    }
+
+    #[test]
+    fn test_equirectangular_to_cubemap_horizon() {
+        let faces = equirectangular_to_cubemap(8, 4, &test_panorama(), 1);
+
+        // -Z samples the center of the panorama and +X is a quarter turn to the right of it
+        assert_eq!(faces[5][0..2], [3.5, 1.5]);
+        assert_eq!(faces[0][0..2], [5.5, 1.5]);
+    }
+}
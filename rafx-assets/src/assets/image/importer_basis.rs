@@ -6,7 +6,7 @@ use crate::ImageAssetDataFormat;
 use basis_universal::BasisTextureType;
 use distill::importer::{ImportedAsset, Importer, ImporterValue};
 use distill::{core::AssetUuid, importer::ImportOp};
-use rafx_api::RafxResourceType;
+use rafx_api::{RafxResourceType, RafxTextureDimensions};
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use type_uuid::*;
@@ -27,7 +27,7 @@ impl Importer for BasisImageImporter {
     where
         Self: Sized,
     {
-        1
+        3
     }
 
     fn version(&self) -> u32 {
@@ -97,9 +97,12 @@ impl Importer for BasisImageImporter {
         let asset_data = ImageAssetData {
             width: level_info.original_width,
             height: level_info.original_height,
+            depth: 1,
             format: ImageAssetDataFormat::Basis_Srgb,
             generate_mips_at_runtime: false,
+            allow_mip_streaming: false,
             resource_type,
+            dimensions: RafxTextureDimensions::Dim2D,
             data: ImageAssetDataPayload::SingleBuffer(ImageAssetDataPayloadSingleBuffer {
                 buffer: bytes,
             }),
@@ -0,0 +1,201 @@
+use crate::assets::image::{
+    ImageAssetData, ImageAssetDataFormat, ImageAssetDataLayer, ImageAssetDataMipLevel,
+    ImageAssetDataPayload, ImageAssetDataPayloadSubresources, ImageAssetHdrDataFormatConfig,
+};
+use crate::distill::importer::ImportSource;
+use distill::importer::{Error, ImportedAsset, Importer, ImporterValue};
+use distill::{core::AssetUuid, importer::ImportOp};
+use rafx_api::{RafxResourceType, RafxResult, RafxTextureDimensions};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::*;
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "c6a4e0a5-3f1b-4b0e-9a52-6f2d8e1c7b94"]
+pub struct CubeLutImporterOptions {
+    pub data_format: ImageAssetHdrDataFormatConfig,
+}
+
+impl Default for CubeLutImporterOptions {
+    fn default() -> Self {
+        CubeLutImporterOptions {
+            data_format: ImageAssetHdrDataFormatConfig::Float16,
+        }
+    }
+}
+
+// Returns the size of each edge of the cube and 4 f32 per entry (alpha is always 1.0), with red
+// changing fastest, then green, then blue. This is the same order as the texels of a 3D image.
+fn parse_cube_lut(text: &str) -> RafxResult<(u32, Vec<f32>)> {
+    let mut size = None;
+    let mut data = Vec::new();
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap();
+        match keyword {
+            // The title is quoted and may contain spaces, nothing to do with it
+            "TITLE" => {}
+            "LUT_3D_SIZE" => {
+                let lut_size = tokens
+                    .next()
+                    .and_then(|x| x.parse::<u32>().ok())
+                    .filter(|x| *x >= 2)
+                    .ok_or_else(|| format!("Invalid LUT_3D_SIZE on line {}", line_index + 1))?;
+                size = Some(lut_size);
+                data.reserve((lut_size * lut_size * lut_size * 4) as usize);
+            }
+            "LUT_1D_SIZE" => Err("1D .cube LUTs are not supported, only 3D LUTs can be imported")?,
+            "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                // Only the default domain is supported. The shader samples the LUT with [0, 1]
+                // texture coordinates.
+                let expected = if keyword == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                for _ in 0..3 {
+                    let value = tokens
+                        .next()
+                        .and_then(|x| x.parse::<f32>().ok())
+                        .ok_or_else(|| format!("Invalid {} on line {}", keyword, line_index + 1))?;
+                    if value != expected {
+                        Err(format!(
+                            "{} on line {} is not {}, only the default domain is supported",
+                            keyword,
+                            line_index + 1,
+                            expected
+                        ))?;
+                    }
+                }
+            }
+            _ => {
+                if size.is_none() {
+                    Err(format!(
+                        "Unexpected '{}' on line {} before LUT_3D_SIZE",
+                        keyword,
+                        line_index + 1
+                    ))?;
+                }
+
+                let r = keyword.parse::<f32>();
+                let g = tokens.next().map(|x| x.parse::<f32>());
+                let b = tokens.next().map(|x| x.parse::<f32>());
+                match (r, g, b) {
+                    (Ok(r), Some(Ok(g)), Some(Ok(b))) => data.extend_from_slice(&[r, g, b, 1.0]),
+                    _ => Err(format!("Invalid LUT entry on line {}", line_index + 1))?,
+                }
+            }
+        }
+    }
+
+    let size = size.ok_or("The .cube file does not have a LUT_3D_SIZE")?;
+    let expected_len = (size * size * size * 4) as usize;
+    if data.len() != expected_len {
+        Err(format!(
+            "The .cube file has {} entries, expected {} for a LUT_3D_SIZE of {}",
+            data.len() / 4,
+            expected_len / 4,
+            size
+        ))?;
+    }
+
+    Ok((size, data))
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Default)]
+#[uuid = "0e5b9d3c-8a7f-4c61-b2d4-95f1a3e7c208"]
+pub struct CubeLutImporterState(Option<AssetUuid>);
+
+// Imports color grading LUTs in the Adobe/Resolve .cube format as 3D images
+#[derive(TypeUuid)]
+#[uuid = "4b8f2e61-d9c3-47a5-8e1f-3c7a6b5d9e02"]
+pub struct CubeLutImporter;
+impl Importer for CubeLutImporter {
+    fn version_static() -> u32
+    where
+        Self: Sized,
+    {
+        2
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    type Options = CubeLutImporterOptions;
+
+    type State = CubeLutImporterState;
+
+    fn default_options(
+        &self,
+        _import_source: ImportSource,
+    ) -> Option<Self::Options> {
+        Some(CubeLutImporterOptions::default())
+    }
+
+    /// Reads the given bytes and produces assets.
+    #[profiling::function]
+    fn import(
+        &self,
+        _op: &mut ImportOp,
+        source: &mut dyn Read,
+        options: &Self::Options,
+        state: &mut Self::State,
+    ) -> distill::importer::Result<ImporterValue> {
+        let id = state
+            .0
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+        *state = CubeLutImporterState(Some(id));
+        let mut text = String::new();
+        source.read_to_string(&mut text)?;
+
+        log::trace!("import with options {:?}", options);
+
+        let (size, data) = parse_cube_lut(&text).map_err(|e| Error::Boxed(Box::new(e)))?;
+
+        let (format, bytes) = match options.data_format {
+            ImageAssetHdrDataFormatConfig::Float16 => (
+                ImageAssetDataFormat::R16G16B16A16_SFloat,
+                super::image_processing::rgba_f32_to_f16_bytes(&data),
+            ),
+            ImageAssetHdrDataFormatConfig::Float32 => (
+                ImageAssetDataFormat::R32G32B32A32_SFloat,
+                super::image_processing::rgba_f32_to_bytes(&data),
+            ),
+        };
+
+        // LUTs are sampled with linear filtering at a single level, so never have mips
+        let asset_data = ImageAssetData {
+            width: size,
+            height: size,
+            depth: size,
+            format,
+            resource_type: RafxResourceType::TEXTURE,
+            dimensions: RafxTextureDimensions::Dim3D,
+            generate_mips_at_runtime: false,
+            allow_mip_streaming: false,
+            data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources {
+                layers: vec![ImageAssetDataLayer {
+                    mip_levels: vec![ImageAssetDataMipLevel {
+                        width: size,
+                        height: size,
+                        depth: size,
+                        bytes,
+                    }],
+                }],
+            }),
+        };
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: vec![],
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(asset_data),
+            }],
+        })
+    }
+}
@@ -5,7 +5,7 @@ use crate::{ImageAssetDataFormat, ImageAssetDataPayloadSubresources};
 use ddsfile::DxgiFormat;
 use distill::importer::{Error, ImportedAsset, Importer, ImporterValue};
 use distill::{core::AssetUuid, importer::ImportOp};
-use rafx_api::RafxResourceType;
+use rafx_api::{RafxResourceType, RafxTextureDimensions};
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use type_uuid::*;
@@ -23,7 +23,7 @@ impl Importer for DdsImageImporter {
     where
         Self: Sized,
     {
-        1
+        4
     }
 
     fn version(&self) -> u32 {
@@ -75,6 +75,8 @@ impl Importer for DdsImageImporter {
                 //DxgiFormat::BC7_Typeless => {}
                 DxgiFormat::BC7_UNorm => ImageAssetDataFormat::BC7_Unorm_Linear,
                 DxgiFormat::BC7_UNorm_sRGB => ImageAssetDataFormat::BC7_Unorm_Srgb,
+                DxgiFormat::R16G16B16A16_Float => ImageAssetDataFormat::R16G16B16A16_SFloat,
+                DxgiFormat::R32G32B32A32_Float => ImageAssetDataFormat::R32G32B32A32_SFloat,
                 _ => unimplemented!(),
             }
         } else {
@@ -130,6 +132,7 @@ impl Importer for DdsImageImporter {
                 mip_levels_asset_data.push(ImageAssetDataMipLevel {
                     width: mip_width,
                     height: mip_height,
+                    depth: 1,
                     bytes: mip_data,
                 });
 
@@ -151,9 +154,12 @@ impl Importer for DdsImageImporter {
         let asset_data = ImageAssetData {
             width,
             height,
+            depth: 1,
             format,
             generate_mips_at_runtime: false,
+            allow_mip_streaming: false,
             resource_type: RafxResourceType::TEXTURE,
+            dimensions: RafxTextureDimensions::Dim2D,
             data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources {
                 layers: layers_asset_data,
             }),
@@ -0,0 +1,205 @@
+use crate::assets::image::{ImageAssetData, ImageAssetHdrDataFormatConfig, ImageAssetProjection};
+use crate::distill::importer::ImportSource;
+use crate::ImageAssetMipGeneration;
+use distill::importer::{Error, ImportedAsset, Importer, ImporterValue};
+use distill::{core::AssetUuid, importer::ImportOp};
+use rafx_api::RafxResult;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::*;
+
+// File formats that are decoded to float data rather than 8-bit RGBA
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HdrImageFileFormat {
+    // Radiance HDR (.hdr)
+    Hdr,
+    // OpenEXR (.exr), requires the exr feature
+    Exr,
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "3da7035b-a5d8-489c-a1a0-18e9cf1f3318"]
+pub struct HdrImageImporterOptions {
+    pub mip_generation: ImageAssetMipGeneration,
+    pub data_format: ImageAssetHdrDataFormatConfig,
+    pub projection: ImageAssetProjection,
+}
+
+impl Default for HdrImageImporterOptions {
+    fn default() -> Self {
+        HdrImageImporterOptions {
+            mip_generation: ImageAssetMipGeneration::Runtime,
+            data_format: ImageAssetHdrDataFormatConfig::Float16,
+            projection: ImageAssetProjection::Flat,
+        }
+    }
+}
+
+// Returns width, height and 4 f32 per pixel. Alpha is 1.0 if the file does not have it
+fn decode_radiance_hdr(bytes: &[u8]) -> RafxResult<(u32, u32, Vec<f32>)> {
+    let decoder = image::codecs::hdr::HdrDecoder::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Failed to read HDR image: {}", e))?;
+    let metadata = decoder.metadata();
+    let pixels = decoder
+        .read_image_hdr()
+        .map_err(|e| format!("Failed to decode HDR image: {}", e))?;
+
+    let mut data = Vec::with_capacity(pixels.len() * 4);
+    for pixel in pixels {
+        data.extend_from_slice(&[pixel.0[0], pixel.0[1], pixel.0[2], 1.0]);
+    }
+
+    Ok((metadata.width, metadata.height, data))
+}
+
+#[cfg(feature = "exr")]
+fn decode_open_exr(bytes: &[u8]) -> RafxResult<(u32, u32, Vec<f32>)> {
+    use exr::prelude::*;
+
+    struct ExrPixels {
+        width: usize,
+        height: usize,
+        data: Vec<f32>,
+    }
+
+    let image = read()
+        .no_deep_data()
+        .largest_resolution_level()
+        .rgba_channels(
+            |resolution, _: &RgbaChannels| ExrPixels {
+                width: resolution.width(),
+                height: resolution.height(),
+                data: vec![0.0; resolution.width() * resolution.height() * 4],
+            },
+            |pixels: &mut ExrPixels, position, (r, g, b, a): (f32, f32, f32, f32)| {
+                let offset = (position.y() * pixels.width + position.x()) * 4;
+                pixels.data[offset..offset + 4].copy_from_slice(&[r, g, b, a]);
+            },
+        )
+        .first_valid_layer()
+        .all_attributes()
+        .from_buffered(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Failed to read EXR image: {}", e))?;
+
+    let pixels = image.layer_data.channel_data.pixels;
+    Ok((pixels.width as u32, pixels.height as u32, pixels.data))
+}
+
+#[cfg(not(feature = "exr"))]
+fn decode_open_exr(_bytes: &[u8]) -> RafxResult<(u32, u32, Vec<f32>)> {
+    Err("Cannot import EXR image, crate not built with exr feature")?
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Default)]
+#[uuid = "1899f752-7fbf-49b6-8708-e5f4ecd762ea"]
+pub struct HdrImageImporterState(Option<AssetUuid>);
+
+// Imports HDR images at full range. Files with "_equirect." in the name (i.e. sky_equirect.hdr)
+// default to being converted from an equirectangular panorama to a cubemap, which is what
+// SkyboxResource expects. This can be changed in the .meta file.
+#[derive(TypeUuid)]
+#[uuid = "57523ebb-ce25-4611-b195-5622941b645a"]
+pub struct HdrImageImporter(pub HdrImageFileFormat);
+impl Importer for HdrImageImporter {
+    fn version_static() -> u32
+    where
+        Self: Sized,
+    {
+        4
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    type Options = HdrImageImporterOptions;
+
+    type State = HdrImageImporterState;
+
+    fn default_options(
+        &self,
+        import_source: ImportSource,
+    ) -> Option<Self::Options> {
+        let mut options = HdrImageImporterOptions::default();
+        if let ImportSource::File(path) = import_source {
+            if let Some(file_name) = path.file_name() {
+                if file_name
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .contains("_equirect.")
+                {
+                    options.projection =
+                        ImageAssetProjection::EquirectangularToCubemap { face_size: None };
+                }
+            }
+        }
+
+        Some(options)
+    }
+
+    /// Reads the given bytes and produces assets.
+    #[profiling::function]
+    fn import(
+        &self,
+        _op: &mut ImportOp,
+        source: &mut dyn Read,
+        options: &Self::Options,
+        state: &mut Self::State,
+    ) -> distill::importer::Result<ImporterValue> {
+        let id = state
+            .0
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+        *state = HdrImageImporterState(Some(id));
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        log::trace!("import with options {:?}", options);
+
+        let (width, height, data) = match self.0 {
+            HdrImageFileFormat::Hdr => decode_radiance_hdr(&bytes),
+            HdrImageFileFormat::Exr => decode_open_exr(&bytes),
+        }
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+
+        let asset_data = ImageAssetData::from_raw_rgba_f32(
+            width,
+            height,
+            options.data_format,
+            options.mip_generation,
+            options.projection,
+            &data,
+        )
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: vec![],
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(asset_data),
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_radiance_hdr() {
+        let pixels = [image::Rgb([1.0, 0.5, 0.25]), image::Rgb([0.0, 0.0, 0.0])];
+        let mut bytes = Vec::default();
+        image::codecs::hdr::HdrEncoder::new(&mut bytes)
+            .encode(&pixels, 2, 1)
+            .unwrap();
+
+        let (width, height, data) = decode_radiance_hdr(&bytes).unwrap();
+        assert_eq!(width, 2);
+        assert_eq!(height, 1);
+        // RGBE has 8 bits of precision per channel, these values are exact. Alpha is added as 1.0
+        assert_eq!(data, vec![1.0, 0.5, 0.25, 1.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+}
@@ -78,6 +78,9 @@ pub struct ImageImporterOptions {
     pub mip_generation: ImageAssetMipGeneration,
     pub color_space: ImageAssetColorSpaceConfig,
     pub data_format: ImageAssetDataFormatConfig,
+    // Defaulted so that existing .meta files without this field still deserialize
+    #[serde(default)]
+    pub allow_mip_streaming: bool,
 }
 
 impl Default for ImageImporterOptions {
@@ -86,6 +89,7 @@ impl Default for ImageImporterOptions {
             mip_generation: ImageAssetMipGeneration::NoMips,
             color_space: ImageAssetColorSpaceConfig::Linear,
             data_format: ImageAssetDataFormatConfig::Uncompressed,
+            allow_mip_streaming: false,
         }
     }
 }
@@ -95,6 +99,7 @@ pub struct ImageImporterRuleOptions {
     pub mip_generation: ImageAssetMipGeneration,
     pub color_space: ImageAssetColorSpaceConfig,
     pub data_format: ImageAssetDataFormatConfig,
+    pub allow_mip_streaming: bool,
 }
 
 pub trait ImageImporterRule: Send + Sync {
@@ -172,7 +177,7 @@ impl Importer for ImageImporter {
     where
         Self: Sized,
     {
-        7
+        9
     }
 
     fn version(&self) -> u32 {
@@ -196,6 +201,7 @@ impl Importer for ImageImporter {
                             mip_generation: options.mip_generation,
                             data_format: options.data_format,
                             color_space: options.color_space,
+                            allow_mip_streaming: options.allow_mip_streaming,
                         });
                     }
                 }
@@ -206,6 +212,7 @@ impl Importer for ImageImporter {
             mip_generation: self.1.default.mip_generation,
             data_format: self.1.default.data_format,
             color_space: self.1.default.color_space,
+            allow_mip_streaming: self.1.default.allow_mip_streaming,
         });
     }
 
@@ -232,7 +239,7 @@ impl Importer for ImageImporter {
         let decoded_image = image::load_from_memory_with_format(&bytes, self.0.into())
             .map_err(|e| Error::Boxed(Box::new(e)))?;
         let (width, height) = decoded_image.dimensions();
-        let asset_data = ImageAssetData::from_raw_rgba32(
+        let mut asset_data = ImageAssetData::from_raw_rgba32(
             width,
             height,
             options.color_space,
@@ -242,6 +249,7 @@ impl Importer for ImageImporter {
             decoded_image.into_rgba8().as_bytes(),
         )
         .unwrap();
+        asset_data.allow_mip_streaming = options.allow_mip_streaming;
 
         Ok(ImporterValue {
             assets: vec![ImportedAsset {
@@ -0,0 +1,1139 @@
+use crate::assets::image::{
+    ImageAssetData, ImageAssetDataLayer, ImageAssetDataMipLevel, ImageAssetDataPayload,
+    ImageAssetDataPayloadSingleBuffer,
+};
+use crate::{ImageAssetDataFormat, ImageAssetDataPayloadSubresources};
+use distill::importer::{Error, ImportedAsset, Importer, ImporterValue};
+use distill::{core::AssetUuid, importer::ImportOp};
+use rafx_api::{RafxResourceType, RafxResult, RafxTextureDimensions};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::Read;
+use type_uuid::*;
+
+// See https://github.khronos.org/KTX-Specification/ for the file layout
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const KTX2_HEADER_SIZE: usize = 80;
+const KTX2_LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+const KTX2_SUPERCOMPRESSION_NONE: u32 = 0;
+const KTX2_SUPERCOMPRESSION_BASIS_LZ: u32 = 1;
+const KTX2_SUPERCOMPRESSION_ZSTD: u32 = 2;
+
+// Values from the Khronos Data Format Specification, used when the vkFormat is VK_FORMAT_UNDEFINED
+const KHR_DF_MODEL_ETC1S: u8 = 163;
+const KHR_DF_MODEL_UASTC: u8 = 166;
+const KHR_DF_TRANSFER_SRGB: u8 = 2;
+
+// BasisLZ supercompression global data, see the KTX2 specification
+const BASIS_LZ_GLOBAL_DATA_HEADER_SIZE: usize = 20;
+const BASIS_LZ_IMAGE_DESC_SIZE: usize = 20;
+
+// The .basis file layout, see basisu_file_headers.h in basis_universal
+const BASIS_FILE_SIGNATURE: u16 = ((b'B' as u16) << 8) | b's' as u16;
+const BASIS_FILE_VERSION: u16 = 0x13;
+const BASIS_FILE_HEADER_SIZE: usize = 77;
+const BASIS_SLICE_DESC_SIZE: usize = 23;
+const BASIS_TEX_FORMAT_ETC1S: u8 = 0;
+const BASIS_TEX_TYPE_2D: u8 = 0;
+const BASIS_TEX_TYPE_2D_ARRAY: u8 = 1;
+const BASIS_TEX_TYPE_CUBEMAP_ARRAY: u8 = 2;
+const BASIS_HEADER_FLAG_ETC1S: u16 = 1;
+const BASIS_HEADER_FLAG_HAS_ALPHA_SLICES: u16 = 4;
+const BASIS_HEADER_FLAG_SRGB: u16 = 16;
+const BASIS_SLICE_DESC_FLAG_HAS_ALPHA: u8 = 1;
+
+fn read_u32(
+    bytes: &[u8],
+    offset: usize,
+) -> RafxResult<u32> {
+    let data = bytes
+        .get(offset..offset + 4)
+        .ok_or("KTX2 file is truncated")?;
+    Ok(u32::from_le_bytes([data[0], data[1], data[2], data[3]]))
+}
+
+fn read_u64(
+    bytes: &[u8],
+    offset: usize,
+) -> RafxResult<u64> {
+    let low = read_u32(bytes, offset)? as u64;
+    let high = read_u32(bytes, offset + 4)? as u64;
+    Ok(low | (high << 32))
+}
+
+fn format_from_vk_format(vk_format: u32) -> Option<ImageAssetDataFormat> {
+    use ImageAssetDataFormat::*;
+    let format = match vk_format {
+        37 => RGBA32_Linear,
+        43 => RGBA32_Srgb,
+        97 => R16G16B16A16_SFloat,
+        109 => R32G32B32A32_SFloat,
+        // BC1 with and without alpha are read the same way
+        131 | 133 => BC1_UNorm_Linear,
+        132 | 134 => BC1_UNorm_Srgb,
+        135 => BC2_UNorm_Linear,
+        136 => BC2_UNorm_Srgb,
+        137 => BC3_UNorm_Linear,
+        138 => BC3_UNorm_Srgb,
+        139 => BC4_UNorm,
+        140 => BC4_SNorm,
+        141 => BC5_UNorm,
+        142 => BC5_SNorm,
+        143 => BC6H_UFloat,
+        144 => BC6H_SFloat,
+        145 => BC7_Unorm_Linear,
+        146 => BC7_Unorm_Srgb,
+        147 => ETC2_R8G8B8_UNorm_Linear,
+        148 => ETC2_R8G8B8_UNorm_Srgb,
+        149 => ETC2_R8G8B8A1_UNorm_Linear,
+        150 => ETC2_R8G8B8A1_UNorm_Srgb,
+        151 => ETC2_R8G8B8A8_UNorm_Linear,
+        152 => ETC2_R8G8B8A8_UNorm_Srgb,
+        153 => EAC_R11_UNorm,
+        154 => EAC_R11_SNorm,
+        155 => EAC_R11G11_UNorm,
+        156 => EAC_R11G11_SNorm,
+        157 => ASTC_4x4_UNorm_Linear,
+        158 => ASTC_4x4_UNorm_Srgb,
+        159 => ASTC_5x4_UNorm_Linear,
+        160 => ASTC_5x4_UNorm_Srgb,
+        161 => ASTC_5x5_UNorm_Linear,
+        162 => ASTC_5x5_UNorm_Srgb,
+        163 => ASTC_6x5_UNorm_Linear,
+        164 => ASTC_6x5_UNorm_Srgb,
+        165 => ASTC_6x6_UNorm_Linear,
+        166 => ASTC_6x6_UNorm_Srgb,
+        167 => ASTC_8x5_UNorm_Linear,
+        168 => ASTC_8x5_UNorm_Srgb,
+        169 => ASTC_8x6_UNorm_Linear,
+        170 => ASTC_8x6_UNorm_Srgb,
+        171 => ASTC_8x8_UNorm_Linear,
+        172 => ASTC_8x8_UNorm_Srgb,
+        173 => ASTC_10x5_UNorm_Linear,
+        174 => ASTC_10x5_UNorm_Srgb,
+        175 => ASTC_10x6_UNorm_Linear,
+        176 => ASTC_10x6_UNorm_Srgb,
+        177 => ASTC_10x8_UNorm_Linear,
+        178 => ASTC_10x8_UNorm_Srgb,
+        179 => ASTC_10x10_UNorm_Linear,
+        180 => ASTC_10x10_UNorm_Srgb,
+        181 => ASTC_12x10_UNorm_Linear,
+        182 => ASTC_12x10_UNorm_Srgb,
+        183 => ASTC_12x12_UNorm_Linear,
+        184 => ASTC_12x12_UNorm_Srgb,
+        _ => return None,
+    };
+
+    Some(format)
+}
+
+// Basis data in a KTX2 file has no vkFormat, the data format descriptor says what it is
+fn format_from_data_format_descriptor(
+    bytes: &[u8],
+    dfd_byte_offset: usize,
+) -> RafxResult<ImageAssetDataFormat> {
+    // Skip dfdTotalSize and the first two words of the basic descriptor block
+    let model_word = read_u32(bytes, dfd_byte_offset + 12)?;
+    let color_model = (model_word & 0xFF) as u8;
+    let transfer_function = ((model_word >> 16) & 0xFF) as u8;
+
+    match color_model {
+        KHR_DF_MODEL_UASTC => {
+            if transfer_function == KHR_DF_TRANSFER_SRGB {
+                Ok(ImageAssetDataFormat::UASTC_Srgb)
+            } else {
+                Ok(ImageAssetDataFormat::UASTC_Linear)
+            }
+        }
+        KHR_DF_MODEL_ETC1S => {
+            if transfer_function == KHR_DF_TRANSFER_SRGB {
+                Ok(ImageAssetDataFormat::Basis_Srgb)
+            } else {
+                Ok(ImageAssetDataFormat::Basis_Linear)
+            }
+        }
+        _ => Err(format!(
+            "KTX2 file has VK_FORMAT_UNDEFINED and unsupported color model {}",
+            color_model
+        ))?,
+    }
+}
+
+fn decode_level_data<'a>(
+    supercompression_scheme: u32,
+    level_data: &'a [u8],
+    uncompressed_byte_length: usize,
+) -> RafxResult<Cow<'a, [u8]>> {
+    match supercompression_scheme {
+        KTX2_SUPERCOMPRESSION_NONE => Ok(Cow::Borrowed(level_data)),
+        #[cfg(feature = "zstd")]
+        KTX2_SUPERCOMPRESSION_ZSTD => {
+            let decompressed = zstd::bulk::decompress(level_data, uncompressed_byte_length)
+                .map_err(|e| format!("Failed to decompress zstd KTX2 level data: {}", e))?;
+            if decompressed.len() != uncompressed_byte_length {
+                Err(format!(
+                    "KTX2 level decompressed to {} bytes, expected {}",
+                    decompressed.len(),
+                    uncompressed_byte_length
+                ))?;
+            }
+
+            Ok(Cow::Owned(decompressed))
+        }
+        #[cfg(not(feature = "zstd"))]
+        KTX2_SUPERCOMPRESSION_ZSTD => {
+            let _ = uncompressed_byte_length;
+            Err("KTX2 file uses zstd supercompression, but the crate was not built with the zstd feature")?
+        }
+        KTX2_SUPERCOMPRESSION_BASIS_LZ => {
+            Err("KTX2 file uses BasisLZ supercompression for data that isn't ETC1S")?
+        }
+        _ => Err(format!(
+            "KTX2 file uses unsupported supercompression scheme {}",
+            supercompression_scheme
+        ))?,
+    }
+}
+
+// CRC-16 as computed by basis_universal for the checksums in .basis files
+fn basis_crc16(data: &[u8]) -> u16 {
+    let mut crc = !0u16;
+    for &byte in data {
+        let q = (byte as u16) ^ (crc >> 8);
+        let k = (q >> 4) ^ q;
+        crc = (crc << 8) ^ k ^ (k << 5) ^ (k << 12);
+    }
+    !crc
+}
+
+// Appends the low byte_count bytes of value, little endian, failing if it doesn't fit
+fn push_basis_uint(
+    bytes: &mut Vec<u8>,
+    value: usize,
+    byte_count: usize,
+) -> RafxResult<()> {
+    if byte_count < std::mem::size_of::<usize>() && value >> (byte_count * 8) != 0 {
+        Err(format!(
+            "KTX2 ETC1S data is too large for a .basis file ({} does not fit in {} bytes)",
+            value, byte_count
+        ))?;
+    }
+
+    bytes.extend_from_slice(&value.to_le_bytes()[0..byte_count]);
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+struct BasisSlice<'a> {
+    image_index: usize,
+    level_index: usize,
+    is_alpha: bool,
+    width: u32,
+    height: u32,
+    data: &'a [u8],
+}
+
+// Reads the offset and length of a slice from a BasisLZ image description. Offsets are relative to
+// the start of the mip level.
+fn basis_lz_slice_data<'a>(
+    global_data: &[u8],
+    offset: usize,
+    level_data: &'a [u8],
+) -> RafxResult<&'a [u8]> {
+    let slice_byte_offset = read_u32(global_data, offset)? as usize;
+    let slice_byte_length = read_u32(global_data, offset + 4)? as usize;
+    let slice_data = slice_byte_offset
+        .checked_add(slice_byte_length)
+        .and_then(|slice_end| level_data.get(slice_byte_offset..slice_end))
+        .ok_or("KTX2 ETC1S slice is truncated")?;
+    Ok(slice_data)
+}
+
+// ETC1S slices in a KTX2 file are compressed against codebooks that are shared by the whole file
+// and stored in the supercompression global data. The basis-universal transcoder only consumes
+// .basis files, so the codebooks and slices are repackaged into one. Images are ordered like the
+// subresources of other image assets (all faces of layer 0, then layer 1...)
+fn basis_file_from_basis_lz(
+    bytes: &[u8],
+    width: u32,
+    height: u32,
+    subresource_layer_count: usize,
+    is_cubemap: bool,
+    level_count: u32,
+    is_srgb: bool,
+) -> RafxResult<Vec<u8>> {
+    let sgd_byte_offset = read_u64(bytes, 64)? as usize;
+    let sgd_byte_length = read_u64(bytes, 72)? as usize;
+    let global_data = sgd_byte_offset
+        .checked_add(sgd_byte_length)
+        .and_then(|sgd_end| bytes.get(sgd_byte_offset..sgd_end))
+        .ok_or("KTX2 file is truncated in the BasisLZ global data")?;
+
+    if global_data.len() < BASIS_LZ_GLOBAL_DATA_HEADER_SIZE {
+        Err("KTX2 file has BasisLZ supercompression but no global data")?;
+    }
+
+    let endpoint_count = u16::from_le_bytes([global_data[0], global_data[1]]) as usize;
+    let selector_count = u16::from_le_bytes([global_data[2], global_data[3]]) as usize;
+    let endpoints_byte_length = read_u32(global_data, 4)? as usize;
+    let selectors_byte_length = read_u32(global_data, 8)? as usize;
+    let tables_byte_length = read_u32(global_data, 12)? as usize;
+
+    // Image descriptions are ordered by mip level, then layer, then face
+    let endpoints_offset = (level_count as usize)
+        .checked_mul(subresource_layer_count)
+        .and_then(|x| x.checked_mul(BASIS_LZ_IMAGE_DESC_SIZE))
+        .and_then(|x| x.checked_add(BASIS_LZ_GLOBAL_DATA_HEADER_SIZE))
+        .ok_or("KTX2 BasisLZ global data is truncated")?;
+    let selectors_offset = endpoints_offset + endpoints_byte_length;
+    let tables_offset = selectors_offset + selectors_byte_length;
+    let tables_end = tables_offset + tables_byte_length;
+    if tables_end > global_data.len() {
+        Err("KTX2 BasisLZ global data is truncated")?;
+    }
+
+    let mut slices = Vec::default();
+    let mut has_alpha = None;
+    for level_index in 0..level_count as usize {
+        let level_index_offset = KTX2_HEADER_SIZE + level_index * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+        let level_byte_offset = read_u64(bytes, level_index_offset)? as usize;
+        let level_byte_length = read_u64(bytes, level_index_offset + 8)? as usize;
+        let level_data = level_byte_offset
+            .checked_add(level_byte_length)
+            .and_then(|level_end| bytes.get(level_byte_offset..level_end))
+            .ok_or_else(|| format!("KTX2 file is truncated in mip level {}", level_index))?;
+
+        for image_index in 0..subresource_layer_count {
+            let image_desc_offset = BASIS_LZ_GLOBAL_DATA_HEADER_SIZE
+                + (level_index * subresource_layer_count + image_index) * BASIS_LZ_IMAGE_DESC_SIZE;
+
+            let rgb_data = basis_lz_slice_data(global_data, image_desc_offset + 4, level_data)?;
+            let alpha_data = basis_lz_slice_data(global_data, image_desc_offset + 12, level_data)?;
+
+            // Either every image has an alpha slice or none do
+            let image_has_alpha = !alpha_data.is_empty();
+            if *has_alpha.get_or_insert(image_has_alpha) != image_has_alpha {
+                Err("KTX2 ETC1S data has alpha slices for only some images")?;
+            }
+
+            let slice = BasisSlice {
+                image_index,
+                level_index,
+                is_alpha: false,
+                width: (width >> level_index).max(1),
+                height: (height >> level_index).max(1),
+                data: rgb_data,
+            };
+
+            slices.push(slice);
+            if image_has_alpha {
+                slices.push(BasisSlice {
+                    is_alpha: true,
+                    data: alpha_data,
+                    ..slice
+                });
+            }
+        }
+    }
+
+    // Slices are stored image by image, with the mip levels of each image in order
+    slices.sort_by_key(|slice| (slice.image_index, slice.level_index, slice.is_alpha));
+
+    let slice_descs_offset = BASIS_FILE_HEADER_SIZE;
+    let endpoint_codebook_offset = slice_descs_offset + slices.len() * BASIS_SLICE_DESC_SIZE;
+    let selector_codebook_offset = endpoint_codebook_offset + endpoints_byte_length;
+    let tables_file_offset = selector_codebook_offset + selectors_byte_length;
+    let mut slice_data_offset = tables_file_offset + tables_byte_length;
+
+    let mut data = Vec::default();
+    for slice in &slices {
+        push_basis_uint(&mut data, slice.image_index, 3)?;
+        push_basis_uint(&mut data, slice.level_index, 1)?;
+        push_basis_uint(
+            &mut data,
+            if slice.is_alpha {
+                BASIS_SLICE_DESC_FLAG_HAS_ALPHA
+            } else {
+                0
+            } as usize,
+            1,
+        )?;
+        push_basis_uint(&mut data, slice.width as usize, 2)?;
+        push_basis_uint(&mut data, slice.height as usize, 2)?;
+        push_basis_uint(&mut data, (slice.width as usize + 3) / 4, 2)?;
+        push_basis_uint(&mut data, (slice.height as usize + 3) / 4, 2)?;
+        push_basis_uint(&mut data, slice_data_offset, 4)?;
+        push_basis_uint(&mut data, slice.data.len(), 4)?;
+        push_basis_uint(&mut data, basis_crc16(slice.data) as usize, 2)?;
+        slice_data_offset += slice.data.len();
+    }
+
+    data.extend_from_slice(&global_data[endpoints_offset..tables_end]);
+    for slice in &slices {
+        data.extend_from_slice(slice.data);
+    }
+
+    let mut flags = BASIS_HEADER_FLAG_ETC1S;
+    if has_alpha == Some(true) {
+        flags |= BASIS_HEADER_FLAG_HAS_ALPHA_SLICES;
+    }
+    if is_srgb {
+        flags |= BASIS_HEADER_FLAG_SRGB;
+    }
+
+    let tex_type = if is_cubemap {
+        BASIS_TEX_TYPE_CUBEMAP_ARRAY
+    } else if subresource_layer_count > 1 {
+        BASIS_TEX_TYPE_2D_ARRAY
+    } else {
+        BASIS_TEX_TYPE_2D
+    };
+
+    // Everything in the header after the header CRC
+    let mut header = Vec::with_capacity(BASIS_FILE_HEADER_SIZE);
+    push_basis_uint(&mut header, data.len(), 4)?;
+    push_basis_uint(&mut header, basis_crc16(&data) as usize, 2)?;
+    push_basis_uint(&mut header, slices.len(), 3)?;
+    push_basis_uint(&mut header, subresource_layer_count, 3)?;
+    push_basis_uint(&mut header, BASIS_TEX_FORMAT_ETC1S as usize, 1)?;
+    push_basis_uint(&mut header, flags as usize, 2)?;
+    push_basis_uint(&mut header, tex_type as usize, 1)?;
+    // Microseconds per frame, reserved and user data
+    push_basis_uint(&mut header, 0, 3)?;
+    push_basis_uint(&mut header, 0, 4)?;
+    push_basis_uint(&mut header, 0, 4)?;
+    push_basis_uint(&mut header, 0, 4)?;
+    push_basis_uint(&mut header, endpoint_count, 2)?;
+    push_basis_uint(&mut header, endpoint_codebook_offset, 4)?;
+    push_basis_uint(&mut header, endpoints_byte_length, 3)?;
+    push_basis_uint(&mut header, selector_count, 2)?;
+    push_basis_uint(&mut header, selector_codebook_offset, 4)?;
+    push_basis_uint(&mut header, selectors_byte_length, 3)?;
+    push_basis_uint(&mut header, tables_file_offset, 4)?;
+    push_basis_uint(&mut header, tables_byte_length, 4)?;
+    push_basis_uint(&mut header, slice_descs_offset, 4)?;
+    // Extended data
+    push_basis_uint(&mut header, 0, 4)?;
+    push_basis_uint(&mut header, 0, 4)?;
+
+    let mut basis_file = Vec::with_capacity(BASIS_FILE_HEADER_SIZE + data.len());
+    push_basis_uint(&mut basis_file, BASIS_FILE_SIGNATURE as usize, 2)?;
+    push_basis_uint(&mut basis_file, BASIS_FILE_VERSION as usize, 2)?;
+    push_basis_uint(&mut basis_file, BASIS_FILE_HEADER_SIZE, 2)?;
+    push_basis_uint(&mut basis_file, basis_crc16(&header) as usize, 2)?;
+    basis_file.extend_from_slice(&header);
+    debug_assert_eq!(basis_file.len(), BASIS_FILE_HEADER_SIZE);
+    basis_file.extend_from_slice(&data);
+
+    Ok(basis_file)
+}
+
+/// Reads a KTX2 file. Each array layer and cubemap face becomes a layer in the image asset's
+/// subresources, in the order a cubemap/array image expects (all faces of layer 0, then layer 1...).
+/// 3D images have a single layer.
+///
+/// UASTC data is transcoded at runtime one subresource at a time. ETC1S (BasisLZ supercompressed)
+/// data depends on codebooks shared by the whole file, so it is repackaged as a .basis file and
+/// transcoded like one.
+pub fn image_asset_data_from_ktx2(bytes: &[u8]) -> RafxResult<ImageAssetData> {
+    if bytes.len() < KTX2_HEADER_SIZE || bytes[0..12] != KTX2_IDENTIFIER {
+        Err("File is not a KTX2 file")?;
+    }
+
+    let vk_format = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 20)?;
+    let height = read_u32(bytes, 24)?.max(1);
+    let depth = read_u32(bytes, 28)?.max(1);
+    let array_layer_count = read_u32(bytes, 32)?.max(1);
+    let face_count = read_u32(bytes, 36)?;
+    let stored_level_count = read_u32(bytes, 40)?;
+    let supercompression_scheme = read_u32(bytes, 44)?;
+    let dfd_byte_offset = read_u32(bytes, 48)? as usize;
+
+    if depth > 1 && (array_layer_count > 1 || face_count > 1) {
+        Err("KTX2 file is a 3D image with multiple layers or faces, which is not supported")?;
+    }
+
+    if face_count != 1 && face_count != 6 {
+        Err(format!("KTX2 file has invalid face count {}", face_count))?;
+    }
+
+    if face_count == 6 && width != height {
+        Err(format!(
+            "KTX2 file is a cubemap with non-square faces {}x{}",
+            width, height
+        ))?;
+    }
+
+    let format = if vk_format == 0 {
+        format_from_data_format_descriptor(bytes, dfd_byte_offset)?
+    } else {
+        format_from_vk_format(vk_format)
+            .ok_or_else(|| format!("KTX2 file has unsupported vkFormat {}", vk_format))?
+    };
+
+    // A level count of 0 means mips should be generated at runtime. That is only possible if the
+    // image ends up uncompressed on the GPU
+    let generate_mips_at_runtime = stored_level_count == 0;
+    if generate_mips_at_runtime {
+        match format {
+            ImageAssetDataFormat::RGBA32_Linear
+            | ImageAssetDataFormat::RGBA32_Srgb
+            | ImageAssetDataFormat::UASTC_Linear
+            | ImageAssetDataFormat::UASTC_Srgb
+            | ImageAssetDataFormat::Basis_Linear
+            | ImageAssetDataFormat::Basis_Srgb => {}
+            _ => Err(format!(
+                "KTX2 file requests runtime mip generation, which is not supported for {:?}",
+                format
+            ))?,
+        }
+    }
+    let level_count = stored_level_count.max(1);
+
+    let level_index_end = KTX2_HEADER_SIZE + level_count as usize * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+    if level_index_end > bytes.len() {
+        Err(format!(
+            "KTX2 file is truncated, it has {} mip levels but the level index does not fit",
+            level_count
+        ))?;
+    }
+
+    if depth > 1 {
+        match format {
+            ImageAssetDataFormat::UASTC_Linear | ImageAssetDataFormat::UASTC_Srgb => {
+                Err("KTX2 file is a 3D image with UASTC data, which is not supported")?
+            }
+            ImageAssetDataFormat::Basis_Linear | ImageAssetDataFormat::Basis_Srgb => {
+                Err("KTX2 file is a 3D image with ETC1S data, which is not supported")?
+            }
+            _ if generate_mips_at_runtime => {
+                Err("KTX2 file is a 3D image and requests runtime mip generation, which is not supported")?
+            }
+            _ => {}
+        }
+    }
+
+    log::trace!(
+        "w: {} h: {} d: {} layers: {} faces: {} mips: {} format: {:?} vk_format: {} supercompression: {}",
+        width,
+        height,
+        depth,
+        array_layer_count,
+        face_count,
+        level_count,
+        format,
+        vk_format,
+        supercompression_scheme
+    );
+
+    let subresource_layer_count = array_layer_count
+        .checked_mul(face_count)
+        .ok_or("KTX2 file has too many layers")? as usize;
+
+    let resource_type = if face_count == 6 {
+        RafxResourceType::TEXTURE_CUBE
+    } else {
+        RafxResourceType::TEXTURE
+    };
+
+    // ETC1S data can't be split into subresources, it's transcoded from a .basis file at runtime
+    if matches!(
+        format,
+        ImageAssetDataFormat::Basis_Linear | ImageAssetDataFormat::Basis_Srgb
+    ) {
+        if supercompression_scheme != KTX2_SUPERCOMPRESSION_BASIS_LZ {
+            Err("KTX2 file contains ETC1S data that isn't BasisLZ supercompressed")?;
+        }
+
+        let basis_file = basis_file_from_basis_lz(
+            bytes,
+            width,
+            height,
+            subresource_layer_count,
+            face_count == 6,
+            level_count,
+            matches!(format, ImageAssetDataFormat::Basis_Srgb),
+        )?;
+
+        return Ok(ImageAssetData {
+            width,
+            height,
+            depth,
+            format,
+            generate_mips_at_runtime,
+            allow_mip_streaming: false,
+            resource_type,
+            dimensions: RafxTextureDimensions::Dim2D,
+            data: ImageAssetDataPayload::SingleBuffer(ImageAssetDataPayloadSingleBuffer {
+                buffer: basis_file,
+            }),
+        });
+    }
+
+    let mut layers_asset_data: Vec<_> = (0..subresource_layer_count)
+        .map(|_| ImageAssetDataLayer {
+            mip_levels: Vec::with_capacity(level_count as usize),
+        })
+        .collect();
+
+    for level_index in 0..level_count {
+        let level_index_offset =
+            KTX2_HEADER_SIZE + level_index as usize * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+        let byte_offset = read_u64(bytes, level_index_offset)? as usize;
+        let byte_length = read_u64(bytes, level_index_offset + 8)? as usize;
+        let uncompressed_byte_length = read_u64(bytes, level_index_offset + 16)? as usize;
+
+        let level_data = byte_offset
+            .checked_add(byte_length)
+            .and_then(|byte_end| bytes.get(byte_offset..byte_end))
+            .ok_or_else(|| format!("KTX2 file is truncated in mip level {}", level_index))?;
+        let level_data = decode_level_data(
+            supercompression_scheme,
+            level_data,
+            uncompressed_byte_length,
+        )?;
+
+        // Each level is tightly packed with all the faces of layer 0, followed by layer 1, etc. For
+        // 3D images, the depth slices of a level are kept together in a single subresource.
+        if level_data.len() % subresource_layer_count != 0 {
+            Err(format!(
+                "KTX2 mip level {} has {} bytes, which can't be split evenly between {} layers",
+                level_index,
+                level_data.len(),
+                subresource_layer_count
+            ))?;
+        }
+        let subresource_size = level_data.len() / subresource_layer_count;
+
+        let mip_width = (width >> level_index).max(1);
+        let mip_height = (height >> level_index).max(1);
+        let mip_depth = (depth >> level_index).max(1);
+
+        for (layer_index, layer) in layers_asset_data.iter_mut().enumerate() {
+            let subresource_offset = layer_index * subresource_size;
+            layer.mip_levels.push(ImageAssetDataMipLevel {
+                width: mip_width,
+                height: mip_height,
+                depth: mip_depth,
+                bytes: level_data[subresource_offset..(subresource_offset + subresource_size)]
+                    .to_vec(),
+            });
+        }
+    }
+
+    let dimensions = if depth > 1 {
+        RafxTextureDimensions::Dim3D
+    } else {
+        RafxTextureDimensions::Dim2D
+    };
+
+    Ok(ImageAssetData {
+        width,
+        height,
+        depth,
+        format,
+        generate_mips_at_runtime,
+        allow_mip_streaming: false,
+        resource_type,
+        dimensions,
+        data: ImageAssetDataPayload::Subresources(ImageAssetDataPayloadSubresources {
+            layers: layers_asset_data,
+        }),
+    })
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Default)]
+#[uuid = "9dd49f6d-fb43-4729-973c-0c8dd92ec581"]
+pub struct Ktx2ImageImporterState(Option<AssetUuid>);
+
+#[derive(TypeUuid)]
+#[uuid = "5db3ec1d-8f1b-4b9d-a6d0-d2a9918647f7"]
+pub struct Ktx2ImageImporter;
+
+impl Importer for Ktx2ImageImporter {
+    fn version_static() -> u32
+    where
+        Self: Sized,
+    {
+        5
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    type Options = ();
+
+    type State = Ktx2ImageImporterState;
+
+    /// Reads the given bytes and produces assets.
+    #[profiling::function]
+    fn import(
+        &self,
+        _op: &mut ImportOp,
+        source: &mut dyn Read,
+        _options: &Self::Options,
+        state: &mut Self::State,
+    ) -> distill::importer::Result<ImporterValue> {
+        let id = state
+            .0
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+        *state = Ktx2ImageImporterState(Some(id));
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let asset_data =
+            image_asset_data_from_ktx2(&bytes).map_err(|e| Error::Boxed(Box::new(e)))?;
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: vec![],
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(asset_data),
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+
+    struct TestKtx2 {
+        vk_format: u32,
+        width: u32,
+        height: u32,
+        depth: u32,
+        layer_count: u32,
+        face_count: u32,
+        supercompression_scheme: u32,
+        color_model: u8,
+        supercompression_global_data: Vec<u8>,
+        // Data for each stored mip level, largest first
+        levels: Vec<Vec<u8>>,
+    }
+
+    impl Default for TestKtx2 {
+        fn default() -> Self {
+            TestKtx2 {
+                vk_format: VK_FORMAT_R8G8B8A8_UNORM,
+                width: 1,
+                height: 1,
+                depth: 0,
+                layer_count: 0,
+                face_count: 1,
+                supercompression_scheme: KTX2_SUPERCOMPRESSION_NONE,
+                color_model: 0,
+                supercompression_global_data: vec![],
+                levels: vec![vec![0; 4]],
+            }
+        }
+    }
+
+    impl TestKtx2 {
+        fn to_bytes(&self) -> Vec<u8> {
+            let level_index_size = self.levels.len() * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+            let dfd_byte_offset = KTX2_HEADER_SIZE + level_index_size;
+            let dfd_byte_length = 28;
+            let sgd_byte_offset = dfd_byte_offset + dfd_byte_length;
+            let sgd_byte_length = self.supercompression_global_data.len();
+            let mut level_byte_offset = sgd_byte_offset + sgd_byte_length;
+
+            let mut bytes = KTX2_IDENTIFIER.to_vec();
+            for value in &[
+                self.vk_format,
+                1,
+                self.width,
+                self.height,
+                self.depth,
+                self.layer_count,
+                self.face_count,
+                self.levels.len() as u32,
+                self.supercompression_scheme,
+                dfd_byte_offset as u32,
+                dfd_byte_length as u32,
+                // Key/value data
+                0,
+                0,
+            ] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            // Supercompression global data
+            bytes.extend_from_slice(&(sgd_byte_offset as u64).to_le_bytes());
+            bytes.extend_from_slice(&(sgd_byte_length as u64).to_le_bytes());
+            assert_eq!(bytes.len(), KTX2_HEADER_SIZE);
+
+            for level in &self.levels {
+                bytes.extend_from_slice(&(level_byte_offset as u64).to_le_bytes());
+                bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+                bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+                level_byte_offset += level.len();
+            }
+
+            // Only the color model and transfer function of the data format descriptor are read
+            let mut dfd = vec![0; dfd_byte_length];
+            dfd[12] = self.color_model;
+            dfd[14] = KHR_DF_TRANSFER_SRGB;
+            bytes.extend_from_slice(&dfd);
+            bytes.extend_from_slice(&self.supercompression_global_data);
+
+            for level in &self.levels {
+                bytes.extend_from_slice(level);
+            }
+
+            bytes
+        }
+    }
+
+    fn subresources(asset_data: &ImageAssetData) -> &ImageAssetDataPayloadSubresources {
+        match &asset_data.data {
+            ImageAssetDataPayload::Subresources(subresources) => subresources,
+            ImageAssetDataPayload::SingleBuffer(_) => panic!("Expected subresources"),
+        }
+    }
+
+    #[test]
+    fn test_2d_mip_chain() {
+        let level0: Vec<u8> = (0..4 * 4 * 4).collect();
+        let level1: Vec<u8> = (100..100 + 2 * 2 * 4).collect();
+        let level2 = vec![200, 201, 202, 203];
+        let bytes = TestKtx2 {
+            width: 4,
+            height: 4,
+            levels: vec![level0.clone(), level1.clone(), level2.clone()],
+            ..Default::default()
+        }
+        .to_bytes();
+
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert_eq!(asset_data.width, 4);
+        assert_eq!(asset_data.height, 4);
+        assert_eq!(asset_data.depth, 1);
+        assert!(matches!(
+            asset_data.format,
+            ImageAssetDataFormat::RGBA32_Linear
+        ));
+        assert!(!asset_data.generate_mips_at_runtime);
+        assert_eq!(asset_data.resource_type, RafxResourceType::TEXTURE);
+        assert_eq!(asset_data.dimensions, RafxTextureDimensions::Dim2D);
+
+        let layers = &subresources(&asset_data).layers;
+        assert_eq!(layers.len(), 1);
+        let mip_levels = &layers[0].mip_levels;
+        assert_eq!(mip_levels.len(), 3);
+        for (mip_level, (size, data)) in
+            mip_levels
+                .iter()
+                .zip(&[(4, level0), (2, level1), (1, level2)])
+        {
+            assert_eq!(mip_level.width, *size);
+            assert_eq!(mip_level.height, *size);
+            assert_eq!(mip_level.depth, 1);
+            assert_eq!(&mip_level.bytes, data);
+        }
+    }
+
+    #[test]
+    fn test_cubemap_array_layer_order() {
+        // 2 layers of 6 faces, each 1x1 with a distinct value
+        let level0: Vec<u8> = (0..12).flat_map(|i| vec![i; 4]).collect();
+        let bytes = TestKtx2 {
+            layer_count: 2,
+            face_count: 6,
+            levels: vec![level0],
+            ..Default::default()
+        }
+        .to_bytes();
+
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert_eq!(asset_data.resource_type, RafxResourceType::TEXTURE_CUBE);
+
+        let layers = &subresources(&asset_data).layers;
+        assert_eq!(layers.len(), 12);
+        for (i, layer) in layers.iter().enumerate() {
+            assert_eq!(layer.mip_levels.len(), 1);
+            assert_eq!(layer.mip_levels[0].bytes, vec![i as u8; 4]);
+        }
+    }
+
+    #[test]
+    fn test_3d_image() {
+        let level0: Vec<u8> = (0..2 * 2 * 2 * 4).collect();
+        let level1 = vec![1, 2, 3, 4];
+        let bytes = TestKtx2 {
+            width: 2,
+            height: 2,
+            depth: 2,
+            levels: vec![level0.clone(), level1],
+            ..Default::default()
+        }
+        .to_bytes();
+
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert_eq!(asset_data.depth, 2);
+        assert_eq!(asset_data.dimensions, RafxTextureDimensions::Dim3D);
+
+        let layers = &subresources(&asset_data).layers;
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].mip_levels[0].depth, 2);
+        assert_eq!(layers[0].mip_levels[0].bytes, level0);
+        assert_eq!(layers[0].mip_levels[1].depth, 1);
+    }
+
+    #[test]
+    fn test_uastc_and_runtime_mips() {
+        let bytes = TestKtx2 {
+            vk_format: 0,
+            color_model: KHR_DF_MODEL_UASTC,
+            levels: vec![vec![0; 16]],
+            ..Default::default()
+        }
+        .to_bytes();
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert!(matches!(
+            asset_data.format,
+            ImageAssetDataFormat::UASTC_Srgb
+        ));
+
+        // A stored level count of 0 requests mip generation at runtime, but the first level is
+        // still stored
+        let mut bytes = TestKtx2::default().to_bytes();
+        bytes[40..44].copy_from_slice(&0u32.to_le_bytes());
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert!(asset_data.generate_mips_at_runtime);
+        assert_eq!(subresources(&asset_data).layers[0].mip_levels.len(), 1);
+    }
+
+    #[test]
+    fn test_basis_crc16() {
+        assert_eq!(basis_crc16(b"123456789"), 0xD64E);
+    }
+
+    #[test]
+    fn test_etc1s_repackaged_as_basis_file() {
+        // 2 layers with 2 mip levels, each image with a 1 byte color slice and a 1 byte alpha
+        // slice. Slice data is 10 * level + 2 * layer, plus 1 for alpha.
+        let levels: Vec<Vec<u8>> = vec![vec![10, 11, 12, 13], vec![20, 21, 22, 23]];
+        let endpoints = [1, 2, 3];
+        let selectors = [4, 5];
+        let tables = [6];
+
+        let mut global_data = Vec::default();
+        global_data.extend_from_slice(&2u16.to_le_bytes());
+        global_data.extend_from_slice(&3u16.to_le_bytes());
+        for length in &[endpoints.len(), selectors.len(), tables.len(), 0] {
+            global_data.extend_from_slice(&(*length as u32).to_le_bytes());
+        }
+        for _level in 0..2 {
+            for layer in 0..2u32 {
+                // Flags, then offset and length of the color and alpha slices within the level
+                for value in &[0, layer * 2, 1, layer * 2 + 1, 1] {
+                    global_data.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        global_data.extend_from_slice(&endpoints);
+        global_data.extend_from_slice(&selectors);
+        global_data.extend_from_slice(&tables);
+
+        let bytes = TestKtx2 {
+            vk_format: 0,
+            width: 8,
+            height: 4,
+            layer_count: 2,
+            color_model: KHR_DF_MODEL_ETC1S,
+            supercompression_scheme: KTX2_SUPERCOMPRESSION_BASIS_LZ,
+            supercompression_global_data: global_data,
+            levels,
+            ..Default::default()
+        }
+        .to_bytes();
+
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert!(matches!(
+            asset_data.format,
+            ImageAssetDataFormat::Basis_Srgb
+        ));
+        assert_eq!(asset_data.resource_type, RafxResourceType::TEXTURE);
+        let basis = match &asset_data.data {
+            ImageAssetDataPayload::SingleBuffer(single_buffer) => &single_buffer.buffer,
+            ImageAssetDataPayload::Subresources(_) => panic!("Expected a single buffer"),
+        };
+
+        let read = |offset: usize, byte_count: usize| {
+            let mut value = [0; 4];
+            value[0..byte_count].copy_from_slice(&basis[offset..offset + byte_count]);
+            u32::from_le_bytes(value) as usize
+        };
+
+        // Header
+        assert_eq!(&basis[0..2], b"sB");
+        assert_eq!(read(2, 2), BASIS_FILE_VERSION as usize);
+        assert_eq!(read(4, 2), BASIS_FILE_HEADER_SIZE);
+        assert_eq!(
+            read(6, 2),
+            basis_crc16(&basis[8..BASIS_FILE_HEADER_SIZE]) as usize
+        );
+        assert_eq!(read(8, 4), basis.len() - BASIS_FILE_HEADER_SIZE);
+        assert_eq!(
+            read(12, 2),
+            basis_crc16(&basis[BASIS_FILE_HEADER_SIZE..]) as usize
+        );
+        // Slices, images, format, flags and texture type
+        assert_eq!(read(14, 3), 8);
+        assert_eq!(read(17, 3), 2);
+        assert_eq!(read(20, 1), BASIS_TEX_FORMAT_ETC1S as usize);
+        assert_eq!(
+            read(21, 2),
+            (BASIS_HEADER_FLAG_ETC1S | BASIS_HEADER_FLAG_HAS_ALPHA_SLICES | BASIS_HEADER_FLAG_SRGB)
+                as usize
+        );
+        assert_eq!(read(23, 1), BASIS_TEX_TYPE_2D_ARRAY as usize);
+
+        // Codebooks and tables are copied from the global data
+        assert_eq!(read(39, 2), 2);
+        let endpoints_offset = read(41, 4);
+        assert_eq!(read(45, 3), endpoints.len());
+        assert_eq!(&basis[endpoints_offset..endpoints_offset + 3], &endpoints);
+        assert_eq!(read(48, 2), 3);
+        let selectors_offset = read(50, 4);
+        assert_eq!(read(54, 3), selectors.len());
+        assert_eq!(&basis[selectors_offset..selectors_offset + 2], &selectors);
+        let tables_offset = read(57, 4);
+        assert_eq!(read(61, 4), tables.len());
+        assert_eq!(&basis[tables_offset..tables_offset + 1], &tables);
+        assert_eq!(read(65, 4), BASIS_FILE_HEADER_SIZE);
+
+        // Slices are ordered by image, then level, with the alpha slice after the color slice
+        let expected_slices = [
+            (0, 0, false, 10),
+            (0, 0, true, 11),
+            (0, 1, false, 20),
+            (0, 1, true, 21),
+            (1, 0, false, 12),
+            (1, 0, true, 13),
+            (1, 1, false, 22),
+            (1, 1, true, 23),
+        ];
+        for (slice_index, (image_index, level_index, is_alpha, data)) in
+            expected_slices.iter().enumerate()
+        {
+            let slice_desc = BASIS_FILE_HEADER_SIZE + slice_index * BASIS_SLICE_DESC_SIZE;
+            assert_eq!(read(slice_desc, 3), *image_index);
+            assert_eq!(read(slice_desc + 3, 1), *level_index);
+            assert_eq!(read(slice_desc + 4, 1), *is_alpha as usize);
+            // Original size and size in blocks
+            assert_eq!(read(slice_desc + 5, 2), 8 >> level_index);
+            assert_eq!(read(slice_desc + 7, 2), 4 >> level_index);
+            assert_eq!(read(slice_desc + 9, 2), 2 >> level_index);
+            assert_eq!(read(slice_desc + 11, 2), 1);
+
+            let slice_offset = read(slice_desc + 13, 4);
+            assert_eq!(read(slice_desc + 17, 4), 1);
+            assert_eq!(basis[slice_offset], *data);
+            assert_eq!(read(slice_desc + 21, 2), basis_crc16(&[*data]) as usize);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_data_is_rejected() {
+        let etc1s = TestKtx2 {
+            vk_format: 0,
+            color_model: KHR_DF_MODEL_ETC1S,
+            ..Default::default()
+        };
+        assert!(image_asset_data_from_ktx2(&etc1s.to_bytes()).is_err());
+
+        let basis_lz = TestKtx2 {
+            supercompression_scheme: KTX2_SUPERCOMPRESSION_BASIS_LZ,
+            ..Default::default()
+        };
+        assert!(image_asset_data_from_ktx2(&basis_lz.to_bytes()).is_err());
+
+        let unknown_vk_format = TestKtx2 {
+            vk_format: 1000,
+            ..Default::default()
+        };
+        assert!(image_asset_data_from_ktx2(&unknown_vk_format.to_bytes()).is_err());
+
+        let non_square_cubemap = TestKtx2 {
+            width: 2,
+            face_count: 6,
+            levels: vec![vec![0; 2 * 4 * 6]],
+            ..Default::default()
+        };
+        assert!(image_asset_data_from_ktx2(&non_square_cubemap.to_bytes()).is_err());
+
+        let uneven_layers = TestKtx2 {
+            layer_count: 2,
+            levels: vec![vec![0; 5]],
+            ..Default::default()
+        };
+        assert!(image_asset_data_from_ktx2(&uneven_layers.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_malformed_file_is_rejected() {
+        let bytes = TestKtx2::default().to_bytes();
+
+        let mut bad_identifier = bytes.clone();
+        bad_identifier[1] = 0;
+        assert!(image_asset_data_from_ktx2(&bad_identifier).is_err());
+
+        assert!(image_asset_data_from_ktx2(&bytes[..KTX2_HEADER_SIZE]).is_err());
+        assert!(image_asset_data_from_ktx2(&bytes[..bytes.len() - 1]).is_err());
+
+        // Level index that doesn't fit in the file
+        let mut bad_level_count = bytes.clone();
+        bad_level_count[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(image_asset_data_from_ktx2(&bad_level_count).is_err());
+
+        // Level range that overflows
+        let mut bad_level_range = bytes.clone();
+        bad_level_range[KTX2_HEADER_SIZE..KTX2_HEADER_SIZE + 8]
+            .copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(image_asset_data_from_ktx2(&bad_level_range).is_err());
+
+        // Layer count that overflows when multiplied by the face count
+        let mut bad_layer_count = TestKtx2 {
+            face_count: 6,
+            ..Default::default()
+        }
+        .to_bytes();
+        bad_layer_count[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(image_asset_data_from_ktx2(&bad_layer_count).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_supercompression() {
+        let level0: Vec<u8> = (0..4 * 4 * 4).map(|x| (x / 8) as u8).collect();
+        let compressed = zstd::bulk::compress(&level0, 0).unwrap();
+        let mut bytes = TestKtx2 {
+            width: 4,
+            height: 4,
+            supercompression_scheme: KTX2_SUPERCOMPRESSION_ZSTD,
+            levels: vec![compressed],
+            ..Default::default()
+        }
+        .to_bytes();
+        // uncompressedByteLength of level 0
+        bytes[KTX2_HEADER_SIZE + 16..KTX2_HEADER_SIZE + 24]
+            .copy_from_slice(&(level0.len() as u64).to_le_bytes());
+
+        let asset_data = image_asset_data_from_ktx2(&bytes).unwrap();
+        assert_eq!(
+            subresources(&asset_data).layers[0].mip_levels[0].bytes,
+            level0
+        );
+    }
+}
@@ -9,9 +9,26 @@ mod asset_upload_queue;
 mod asset_type_handler;
 pub use asset_type_handler::*;
 
+mod image_processing;
+
+mod texture_streaming;
+pub use texture_streaming::*;
+
+#[cfg(feature = "intel_tex")]
+mod bc_compression;
+
 mod importer_image;
 pub use importer_image::*;
 
+mod importer_hdr;
+pub use importer_hdr::*;
+
+mod importer_cube_lut;
+pub use importer_cube_lut::*;
+
+mod importer_ktx2;
+pub use importer_ktx2::*;
+
 #[cfg(feature = "basis-universal")]
 mod importer_basis;
 #[cfg(feature = "basis-universal")]
@@ -0,0 +1,708 @@
+use crate::AssetManager;
+use crossbeam_channel::{Receiver, Sender};
+use distill::loader::LoadHandle;
+use fnv::FnvHashMap;
+use rafx_api::{RafxError, RafxResourceType, RafxResult, RafxTexture};
+use rafx_framework::upload::image_upload::IMAGE_UPLOAD_REQUIRED_SUBRESOURCE_ALIGNMENT;
+use rafx_framework::upload::{GpuImageData, UploadOp, UploadPriority, UploadQueueContext};
+use rafx_framework::{ImageViewResource, ResourceArc, WeakResourceArc};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug)]
+pub struct TextureStreamingConfig {
+    // If false, all images are uploaded with their full mip chain
+    pub enabled: bool,
+    // Streamed images are initially uploaded with only the mips no larger than this, and are never
+    // evicted below it
+    pub initial_max_resident_dimension: u32,
+    // Approximate GPU memory that streamed images may use. When exceeded, the least needed images
+    // drop their largest mips first.
+    pub memory_budget_bytes: u64,
+    // Limits how many streamed images are re-uploaded per frame
+    pub max_streaming_uploads_per_frame: usize,
+    // Number of frames an image can go without being requested before its larger mips are evicted
+    pub eviction_delay_frames: u64,
+    // Images no larger than this are always fully resident
+    pub min_streamed_dimension: u32,
+}
+
+impl Default for TextureStreamingConfig {
+    fn default() -> Self {
+        TextureStreamingConfig {
+            enabled: false,
+            initial_max_resident_dimension: 128,
+            memory_budget_bytes: 512 * 1024 * 1024,
+            max_streaming_uploads_per_frame: 4,
+            eviction_delay_frames: 120,
+            min_streamed_dimension: 256,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TextureStreamingStats {
+    pub streamed_image_count: usize,
+    // Images that currently have all of their mips resident
+    pub fully_resident_image_count: usize,
+    pub resident_bytes: u64,
+    // Bytes that would be resident if every streamed image had all of its mips resident
+    pub full_chain_bytes: u64,
+    pub budget_bytes: u64,
+    pub uploads_in_flight: usize,
+}
+
+/// The largest size (in pixels, along the texture's longest axis) that images were drawn at this
+/// frame. Produced by render features and submitted to the streamer via TextureStreamingContext.
+#[derive(Default)]
+pub struct TextureStreamingFeedback {
+    required_pixels: FnvHashMap<WeakResourceArc<ImageViewResource>, f32>,
+}
+
+impl TextureStreamingFeedback {
+    pub fn record(
+        &mut self,
+        image_view: &ResourceArc<ImageViewResource>,
+        screen_pixels: f32,
+    ) {
+        let required_pixels = self
+            .required_pixels
+            .entry(image_view.downgrade())
+            .or_insert(0.0);
+        *required_pixels = required_pixels.max(screen_pixels);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.required_pixels.is_empty()
+    }
+}
+
+struct TextureStreamingContextInner {
+    config: TextureStreamingConfig,
+    feedback_tx: Sender<TextureStreamingFeedback>,
+    feedback_rx: Receiver<TextureStreamingFeedback>,
+    // Maps the image view of a streamed image asset to the view that has the most mips resident.
+    // Images that only have their initial mips resident are not in the map.
+    resident_image_views:
+        Mutex<FnvHashMap<WeakResourceArc<ImageViewResource>, ResourceArc<ImageViewResource>>>,
+    stats: Mutex<TextureStreamingStats>,
+}
+
+/// Shared with render features so that they can submit feedback and look up which image view
+/// currently has the most mips resident for a streamed image asset.
+#[derive(Clone)]
+pub struct TextureStreamingContext {
+    inner: Arc<TextureStreamingContextInner>,
+}
+
+impl TextureStreamingContext {
+    pub fn new(config: TextureStreamingConfig) -> Self {
+        let (feedback_tx, feedback_rx) = crossbeam_channel::unbounded();
+
+        let inner = TextureStreamingContextInner {
+            stats: Mutex::new(TextureStreamingStats {
+                budget_bytes: config.memory_budget_bytes,
+                ..Default::default()
+            }),
+            config,
+            feedback_tx,
+            feedback_rx,
+            resident_image_views: Default::default(),
+        };
+
+        TextureStreamingContext {
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn config(&self) -> &TextureStreamingConfig {
+        &self.inner.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.config.enabled
+    }
+
+    /// Returns the view that should be bound for the given image asset's view. This is the view
+    /// passed in unless larger mips of it have been streamed in.
+    pub fn resident_image_view(
+        &self,
+        image_view: &ResourceArc<ImageViewResource>,
+    ) -> ResourceArc<ImageViewResource> {
+        if !self.inner.config.enabled {
+            return image_view.clone();
+        }
+
+        self.inner
+            .resident_image_views
+            .lock()
+            .unwrap()
+            .get(&image_view.downgrade())
+            .cloned()
+            .unwrap_or_else(|| image_view.clone())
+    }
+
+    pub fn submit_feedback(
+        &self,
+        feedback: TextureStreamingFeedback,
+    ) {
+        if self.inner.config.enabled && !feedback.is_empty() {
+            // Can't fail, the receiver is owned by this context too
+            self.inner.feedback_tx.send(feedback).unwrap();
+        }
+    }
+
+    pub fn stats(&self) -> TextureStreamingStats {
+        self.inner.stats.lock().unwrap().clone()
+    }
+}
+
+enum TextureStreamingUploadResult {
+    Complete(WeakResourceArc<ImageViewResource>, u32, RafxTexture),
+    Error(WeakResourceArc<ImageViewResource>),
+}
+
+struct TextureStreamingUploadOp {
+    key: WeakResourceArc<ImageViewResource>,
+    first_mip: u32,
+    result_tx: Sender<TextureStreamingUploadResult>,
+}
+
+impl UploadOp<RafxTexture> for TextureStreamingUploadOp {
+    fn complete(
+        self: Box<Self>,
+        texture: RafxTexture,
+    ) {
+        let _ = self.result_tx.send(TextureStreamingUploadResult::Complete(
+            self.key,
+            self.first_mip,
+            texture,
+        ));
+    }
+
+    fn error(
+        self: Box<Self>,
+        error: RafxError,
+    ) {
+        log::error!(
+            "Failed to stream mip {} of an image: {:?}",
+            self.first_mip,
+            error
+        );
+        let _ = self
+            .result_tx
+            .send(TextureStreamingUploadResult::Error(self.key));
+    }
+
+    // The image asset was unloaded before the upload started
+    fn is_cancelled(&self) -> bool {
+        self.key.upgrade().is_none()
+    }
+}
+
+struct StreamedImage {
+    // Every mip of the image, kept on the CPU so that any part of the chain can be re-uploaded
+    image_data: GpuImageData,
+    resource_type: RafxResourceType,
+    // Size in bytes of the mip chain starting at each mip
+    mip_chain_sizes: Vec<u64>,
+    // The first mip of the image referenced by the asset itself
+    initial_mip: u32,
+    resident_mip: u32,
+    pending_mip: Option<u32>,
+    required_pixels: f32,
+    last_requested_frame: Option<u64>,
+}
+
+impl StreamedImage {
+    fn new(
+        image_data: GpuImageData,
+        resource_type: RafxResourceType,
+        initial_mip: u32,
+    ) -> Self {
+        let mip_count = image_data.mip_level_count();
+        let mut mip_chain_sizes = vec![0; mip_count];
+        let mut size = 0;
+        for mip in (0..mip_count).rev() {
+            size += rafx_base::memory::round_size_up_to_alignment_u64(
+                image_data.layers[0].mip_levels[mip].data.len() as u64,
+                IMAGE_UPLOAD_REQUIRED_SUBRESOURCE_ALIGNMENT,
+            );
+            mip_chain_sizes[mip] = size;
+        }
+
+        StreamedImage {
+            image_data,
+            resource_type,
+            mip_chain_sizes,
+            initial_mip,
+            resident_mip: initial_mip,
+            pending_mip: None,
+            required_pixels: 0.0,
+            last_requested_frame: None,
+        }
+    }
+
+    fn max_dimension(&self) -> u32 {
+        self.image_data.width.max(self.image_data.height)
+    }
+
+    // Smallest mip index that is still at least as large as the size it's drawn at
+    fn required_mip(&self) -> u32 {
+        let required_pixels = self.required_pixels.max(1.0);
+        let ratio = self.max_dimension() as f32 / required_pixels;
+        if ratio <= 1.0 {
+            0
+        } else {
+            (ratio.log2().floor() as u32).min(self.initial_mip)
+        }
+    }
+}
+
+/// Keeps the larger mips of images with allow_mip_streaming set resident only while they are
+/// drawn large enough on screen to need them, within a memory budget.
+pub struct TextureStreamer {
+    context: TextureStreamingContext,
+    feedback_rx: Receiver<TextureStreamingFeedback>,
+    upload_result_tx: Sender<TextureStreamingUploadResult>,
+    upload_result_rx: Receiver<TextureStreamingUploadResult>,
+    // Full image data for images still being loaded, moved into streamed_images when the asset's
+    // image view exists
+    pending_images: FnvHashMap<LoadHandle, (GpuImageData, RafxResourceType, u32)>,
+    streamed_images: FnvHashMap<WeakResourceArc<ImageViewResource>, StreamedImage>,
+    frame_index: u64,
+}
+
+impl TextureStreamer {
+    pub fn new(context: TextureStreamingContext) -> Self {
+        let feedback_rx = context.inner.feedback_rx.clone();
+        let (upload_result_tx, upload_result_rx) = crossbeam_channel::unbounded();
+
+        TextureStreamer {
+            context,
+            feedback_rx,
+            upload_result_tx,
+            upload_result_rx,
+            pending_images: Default::default(),
+            streamed_images: Default::default(),
+            frame_index: 0,
+        }
+    }
+
+    fn config(&self) -> &TextureStreamingConfig {
+        &self.context.inner.config
+    }
+
+    /// Returns true if the image is worth streaming. Streaming also requires the full mip chain to
+    /// be available on the CPU.
+    pub fn can_stream_image(
+        &self,
+        image_data: &GpuImageData,
+        resource_type: RafxResourceType,
+    ) -> bool {
+        let config = self.config();
+        config.enabled
+            && resource_type == RafxResourceType::TEXTURE
+            && image_data.layers.len() == 1
+            && image_data.depth == 1
+            && image_data.width.max(image_data.height) > config.min_streamed_dimension
+    }
+
+    /// Takes ownership of the full image data of an image that is about to be loaded and returns
+    /// the smaller mips that should be uploaded initially
+    pub fn begin_streamed_upload(
+        &mut self,
+        load_handle: LoadHandle,
+        image_data: GpuImageData,
+        resource_type: RafxResourceType,
+    ) -> GpuImageData {
+        let max_resident_dimension = self.config().initial_max_resident_dimension;
+        let mip_levels = &image_data.layers[0].mip_levels;
+        let initial_mip = mip_levels
+            .iter()
+            .position(|mip_level| mip_level.width.max(mip_level.height) <= max_resident_dimension)
+            .unwrap_or(mip_levels.len() - 1);
+
+        let initial_image_data = image_data.mip_chain_from(initial_mip);
+        self.pending_images
+            .insert(load_handle, (image_data, resource_type, initial_mip as u32));
+        initial_image_data
+    }
+
+    /// Called once the image asset exists so that the streamer can track it by its image view
+    pub fn finish_streamed_upload(
+        &mut self,
+        load_handle: LoadHandle,
+        image_view: &ResourceArc<ImageViewResource>,
+    ) {
+        if let Some((image_data, resource_type, initial_mip)) =
+            self.pending_images.remove(&load_handle)
+        {
+            self.streamed_images.insert(
+                image_view.downgrade(),
+                StreamedImage::new(image_data, resource_type, initial_mip),
+            );
+        }
+    }
+
+    pub fn cancel_streamed_upload(
+        &mut self,
+        load_handle: LoadHandle,
+    ) {
+        self.pending_images.remove(&load_handle);
+    }
+
+    #[profiling::function]
+    pub fn update(
+        &mut self,
+        asset_manager: &AssetManager,
+    ) -> RafxResult<()> {
+        if !self.config().enabled {
+            return Ok(());
+        }
+
+        self.frame_index += 1;
+        self.receive_feedback();
+        self.handle_upload_results(asset_manager)?;
+        self.remove_dropped_images();
+        let desired_mips =
+            calculate_desired_mips(&self.streamed_images, self.frame_index, self.config());
+        self.request_mip_changes(&desired_mips, &asset_manager.upload_queue_context())?;
+        self.update_stats();
+        Ok(())
+    }
+
+    fn receive_feedback(&mut self) {
+        for feedback in self.feedback_rx.try_iter() {
+            for (image_view, required_pixels) in feedback.required_pixels {
+                if let Some(streamed_image) = self.streamed_images.get_mut(&image_view) {
+                    // Feedback from several views in the same frame is combined by taking the max
+                    if streamed_image.last_requested_frame == Some(self.frame_index) {
+                        streamed_image.required_pixels =
+                            streamed_image.required_pixels.max(required_pixels);
+                    } else {
+                        streamed_image.required_pixels = required_pixels;
+                    }
+                    streamed_image.last_requested_frame = Some(self.frame_index);
+                }
+            }
+        }
+    }
+
+    fn handle_upload_results(
+        &mut self,
+        asset_manager: &AssetManager,
+    ) -> RafxResult<()> {
+        let results: Vec<_> = self.upload_result_rx.try_iter().collect();
+        for result in results {
+            match result {
+                TextureStreamingUploadResult::Complete(key, first_mip, texture) => {
+                    let streamed_image = match self.streamed_images.get_mut(&key) {
+                        Some(streamed_image) => streamed_image,
+                        // The image asset was unloaded while the upload was in flight
+                        None => continue,
+                    };
+
+                    streamed_image.pending_mip = None;
+                    streamed_image.resident_mip = first_mip;
+
+                    let image = asset_manager.resources().insert_image(texture);
+                    let image_view = asset_manager
+                        .resources()
+                        .get_or_create_image_view(&image, None)?;
+
+                    log::trace!(
+                        "Streamed image {:?} now has mips {}.. resident",
+                        key,
+                        first_mip
+                    );
+
+                    self.context
+                        .inner
+                        .resident_image_views
+                        .lock()
+                        .unwrap()
+                        .insert(key, image_view);
+                }
+                TextureStreamingUploadResult::Error(key) => {
+                    if let Some(streamed_image) = self.streamed_images.get_mut(&key) {
+                        streamed_image.pending_mip = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_dropped_images(&mut self) {
+        let mut resident_image_views = self.context.inner.resident_image_views.lock().unwrap();
+        self.streamed_images.retain(|key, _| {
+            let is_alive = key.upgrade().is_some();
+            if !is_alive {
+                resident_image_views.remove(key);
+            }
+            is_alive
+        });
+    }
+
+    fn request_mip_changes(
+        &mut self,
+        desired_mips: &FnvHashMap<WeakResourceArc<ImageViewResource>, u32>,
+        upload_queue_context: &UploadQueueContext,
+    ) -> RafxResult<()> {
+        let mut uploads_in_flight = self
+            .streamed_images
+            .values()
+            .filter(|x| x.pending_mip.is_some())
+            .count();
+        let max_uploads = self.config().max_streaming_uploads_per_frame;
+
+        // Handle the images that are drawn largest first
+        let mut changes: Vec<_> = desired_mips
+            .iter()
+            .filter(|(key, &desired_mip)| {
+                let streamed_image = &self.streamed_images[*key];
+                streamed_image.pending_mip.is_none() && streamed_image.resident_mip != desired_mip
+            })
+            .map(|(key, &desired_mip)| (key.clone(), desired_mip))
+            .collect();
+        changes.sort_by(|a, b| {
+            let a = self.streamed_images[&a.0].required_pixels;
+            let b = self.streamed_images[&b.0].required_pixels;
+            b.total_cmp(&a)
+        });
+
+        for (key, desired_mip) in changes {
+            let streamed_image = self.streamed_images.get_mut(&key).unwrap();
+            if desired_mip == streamed_image.initial_mip {
+                // The asset's own image view already has these mips, just stop using the larger
+                // image. It is destroyed once the GPU is no longer using it.
+                streamed_image.resident_mip = desired_mip;
+                self.context
+                    .inner
+                    .resident_image_views
+                    .lock()
+                    .unwrap()
+                    .remove(&key);
+                continue;
+            }
+
+            if uploads_in_flight >= max_uploads {
+                continue;
+            }
+
+            log::trace!(
+                "Streaming image {:?} from mip {} to mip {}",
+                key,
+                streamed_image.resident_mip,
+                desired_mip
+            );
+
+            let image_data = streamed_image
+                .image_data
+                .mip_chain_from(desired_mip as usize);
+            let op = Box::new(TextureStreamingUploadOp {
+                key,
+                first_mip: desired_mip,
+                result_tx: self.upload_result_tx.clone(),
+            });
+            upload_queue_context.upload_new_image(
+                op,
+                image_data,
+                streamed_image.resource_type,
+                false,
+                // The initial mips are already resident, so these can wait behind loading assets
+                UploadPriority::Low,
+            )?;
+
+            streamed_image.pending_mip = Some(desired_mip);
+            uploads_in_flight += 1;
+        }
+
+        Ok(())
+    }
+
+    fn update_stats(&self) {
+        let mut stats = TextureStreamingStats {
+            streamed_image_count: self.streamed_images.len(),
+            budget_bytes: self.config().memory_budget_bytes,
+            ..Default::default()
+        };
+
+        for streamed_image in self.streamed_images.values() {
+            if streamed_image.resident_mip == 0 {
+                stats.fully_resident_image_count += 1;
+            }
+            if streamed_image.pending_mip.is_some() {
+                stats.uploads_in_flight += 1;
+            }
+            stats.resident_bytes +=
+                streamed_image.mip_chain_sizes[streamed_image.resident_mip as usize];
+            stats.full_chain_bytes += streamed_image.mip_chain_sizes[0];
+        }
+
+        *self.context.inner.stats.lock().unwrap() = stats;
+    }
+}
+
+// Picks the first mip each image should have resident: the mip it is drawn at if it was requested
+// recently, otherwise its initial mip. If that exceeds the budget, the images drawn smallest relative
+// to their size give up a mip at a time.
+fn calculate_desired_mips<K: Clone + Eq + Hash>(
+    streamed_images: &FnvHashMap<K, StreamedImage>,
+    frame_index: u64,
+    config: &TextureStreamingConfig,
+) -> FnvHashMap<K, u32> {
+    let mut desired_mips = FnvHashMap::default();
+    let mut total_bytes = 0;
+    for (key, streamed_image) in streamed_images {
+        let is_requested = streamed_image
+            .last_requested_frame
+            .map(|frame| frame_index - frame <= config.eviction_delay_frames)
+            .unwrap_or(false);
+
+        let desired_mip = if is_requested {
+            streamed_image.required_mip()
+        } else {
+            streamed_image.initial_mip
+        };
+
+        total_bytes += streamed_image.mip_chain_sizes[desired_mip as usize];
+        desired_mips.insert(key.clone(), desired_mip);
+    }
+
+    if total_bytes > config.memory_budget_bytes {
+        // Drop a mip at a time from the images that are drawn smallest relative to their size
+        // until we are within budget or nothing more can be evicted
+        let mut candidates: Vec<_> = streamed_images
+            .iter()
+            .map(|(key, streamed_image)| {
+                let importance =
+                    streamed_image.required_pixels / streamed_image.max_dimension() as f32;
+                (key, streamed_image, importance)
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let mut evicted_any = true;
+        while total_bytes > config.memory_budget_bytes && evicted_any {
+            evicted_any = false;
+            for (key, streamed_image, _) in &candidates {
+                let desired_mip = desired_mips.get_mut(*key).unwrap();
+                if *desired_mip < streamed_image.initial_mip {
+                    total_bytes -= streamed_image.mip_chain_sizes[*desired_mip as usize];
+                    *desired_mip += 1;
+                    total_bytes += streamed_image.mip_chain_sizes[*desired_mip as usize];
+                    evicted_any = true;
+
+                    if total_bytes <= config.memory_budget_bytes {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    desired_mips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rafx_api::RafxFormat;
+    use rafx_framework::upload::{GpuImageDataLayer, GpuImageDataMipLevel};
+
+    // A square RGBA8 image with a full mip chain, with mips no larger than 128 initially resident
+    fn streamed_image(
+        dimension: u32,
+        required_pixels: f32,
+        last_requested_frame: Option<u64>,
+    ) -> StreamedImage {
+        let mut mip_levels = Vec::default();
+        let mut mip_dimension = dimension;
+        loop {
+            mip_levels.push(GpuImageDataMipLevel {
+                width: mip_dimension,
+                height: mip_dimension,
+                depth: 1,
+                data: vec![0; (mip_dimension * mip_dimension * 4) as usize],
+            });
+
+            if mip_dimension == 1 {
+                break;
+            }
+            mip_dimension /= 2;
+        }
+
+        let initial_mip = (dimension / 128).trailing_zeros();
+        let image_data = GpuImageData::new(
+            vec![GpuImageDataLayer::new(mip_levels)],
+            RafxFormat::R8G8B8A8_UNORM,
+        );
+
+        let mut streamed_image =
+            StreamedImage::new(image_data, RafxResourceType::TEXTURE, initial_mip);
+        streamed_image.required_pixels = required_pixels;
+        streamed_image.last_requested_frame = last_requested_frame;
+        streamed_image
+    }
+
+    fn config(memory_budget_bytes: u64) -> TextureStreamingConfig {
+        TextureStreamingConfig {
+            enabled: true,
+            memory_budget_bytes,
+            eviction_delay_frames: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_desired_mips_within_budget() {
+        let mut streamed_images = FnvHashMap::default();
+        // Drawn at full size
+        streamed_images.insert(0, streamed_image(1024, 2000.0, Some(100)));
+        // Drawn at half size
+        streamed_images.insert(1, streamed_image(1024, 512.0, Some(95)));
+        // Not requested recently enough
+        streamed_images.insert(2, streamed_image(1024, 1024.0, Some(80)));
+        // Never requested
+        streamed_images.insert(3, streamed_image(1024, 0.0, None));
+        // Drawn smaller than the initially resident mips
+        streamed_images.insert(4, streamed_image(1024, 16.0, Some(100)));
+
+        let desired_mips = calculate_desired_mips(&streamed_images, 100, &config(u64::MAX));
+        assert_eq!(desired_mips[&0], 0);
+        assert_eq!(desired_mips[&1], 1);
+        assert_eq!(desired_mips[&2], 3);
+        assert_eq!(desired_mips[&3], 3);
+        assert_eq!(desired_mips[&4], 3);
+    }
+
+    #[test]
+    fn test_desired_mips_eviction_order() {
+        let mut streamed_images = FnvHashMap::default();
+        // Drawn at its full size
+        streamed_images.insert(0, streamed_image(1024, 1024.0, Some(100)));
+        // Drawn at half its size, so it is less important and gives up mips first
+        streamed_images.insert(1, streamed_image(1024, 512.0, Some(100)));
+        let sizes = streamed_images[&0].mip_chain_sizes.clone();
+
+        // Dropping one mip from the less important image is enough
+        let desired_mips =
+            calculate_desired_mips(&streamed_images, 100, &config(sizes[0] + sizes[2]));
+        assert_eq!(desired_mips[&0], 0);
+        assert_eq!(desired_mips[&1], 2);
+
+        // Each image gives up a mip per round, least important first, until within budget
+        let desired_mips =
+            calculate_desired_mips(&streamed_images, 100, &config(sizes[1] + sizes[3]));
+        assert_eq!(desired_mips[&0], 1);
+        assert_eq!(desired_mips[&1], 3);
+
+        // Images are never evicted below their initial mip, even if still over budget
+        let desired_mips = calculate_desired_mips(&streamed_images, 100, &config(0));
+        assert_eq!(desired_mips[&0], 3);
+        assert_eq!(desired_mips[&1], 3);
+    }
+}
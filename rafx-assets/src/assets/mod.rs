@@ -1,11 +1,20 @@
 mod image;
+pub use self::image::image_asset_data_from_ktx2;
 #[cfg(feature = "basis-universal")]
 pub use self::image::BasisImageImporter;
+pub use self::image::CubeLutImporter;
+pub use self::image::CubeLutImporterOptions;
 #[cfg(feature = "ddsfile")]
 pub use self::image::DdsImageImporter;
+pub use self::image::HdrImageFileFormat;
+pub use self::image::HdrImageImporter;
+pub use self::image::HdrImageImporterOptions;
 pub use self::image::ImageAsset;
 pub use self::image::ImageAssetBasisCompressionSettings;
 pub use self::image::ImageAssetBasisCompressionType;
+pub use self::image::ImageAssetBcCompressionSettings;
+pub use self::image::ImageAssetBcFormat;
+pub use self::image::ImageAssetBcQuality;
 pub use self::image::ImageAssetColorSpaceConfig;
 pub use self::image::ImageAssetData;
 pub use self::image::ImageAssetDataFormat;
@@ -13,7 +22,9 @@ pub use self::image::ImageAssetDataFormatConfig;
 pub use self::image::ImageAssetDataPayload;
 pub use self::image::ImageAssetDataPayloadSingleBuffer;
 pub use self::image::ImageAssetDataPayloadSubresources;
+pub use self::image::ImageAssetHdrDataFormatConfig;
 pub use self::image::ImageAssetMipGeneration;
+pub use self::image::ImageAssetProjection;
 pub use self::image::ImageFileFormat;
 pub use self::image::ImageImporter;
 pub use self::image::ImageImporterConfig;
@@ -21,11 +32,18 @@ pub use self::image::ImageImporterOptions;
 pub use self::image::ImageImporterRule;
 pub use self::image::ImageImporterRuleFilenameContains;
 pub use self::image::ImageImporterRuleOptions;
+pub use self::image::Ktx2ImageImporter;
+pub use self::image::TextureStreamingConfig;
+pub use self::image::TextureStreamingContext;
+pub use self::image::TextureStreamingFeedback;
+pub use self::image::TextureStreamingStats;
 
 mod shader;
 pub use shader::ShaderAsset;
 pub use shader::ShaderAssetData;
+pub use shader::ShaderAssetVariantData;
 pub use shader::ShaderImporterCooked;
+pub use shader::ShaderImporterCookedVariants;
 pub use shader::ShaderImporterSpv;
 
 mod graphics_pipeline;
@@ -8,12 +8,23 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use type_uuid::*;
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShaderAssetVariantData {
+    pub name: String,
+    pub shader_module_hash: ShaderModuleHash,
+    pub shader_package: RafxShaderPackage,
+    pub reflection_data: Vec<ReflectedEntryPoint>,
+}
+
 #[derive(TypeUuid, Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[uuid = "e0ae2222-1a44-4022-af95-03c9101ac89e"]
 pub struct ShaderAssetData {
     pub shader_module_hash: ShaderModuleHash,
     pub shader_package: RafxShaderPackage,
     pub reflection_data: Option<Vec<ReflectedEntryPoint>>,
+    // Permutations of the shader compiled with different defines. If non-empty, the above fields
+    // hold the default (first) variant.
+    pub variants: Vec<ShaderAssetVariantData>,
 }
 
 //
@@ -27,6 +38,44 @@ pub struct ShaderAssetData {
 pub struct ShaderAsset {
     pub shader_module: ResourceArc<ShaderModuleResource>,
     pub reflection_data: Arc<FnvHashMap<String, ReflectedEntryPoint>>,
+    pub variants: Arc<FnvHashMap<String, ShaderAsset>>,
+}
+
+impl ShaderAsset {
+    // Returns the permutation of this shader with the given name. Shaders without declared
+    // variants return None for every name.
+    pub fn variant(
+        &self,
+        variant_name: &str,
+    ) -> Option<&ShaderAsset> {
+        self.variants.get(variant_name)
+    }
+
+    fn load_shader_module(
+        asset_manager: &AssetManager,
+        shader_package: &RafxShaderPackage,
+        shader_module_hash: ShaderModuleHash,
+        reflection_data: &[ReflectedEntryPoint],
+    ) -> RafxResult<ShaderAsset> {
+        let mut reflection_data_lookup = FnvHashMap::default();
+        for entry_point in reflection_data {
+            let old = reflection_data_lookup.insert(
+                entry_point.rafx_api_reflection.entry_point_name.clone(),
+                entry_point.clone(),
+            );
+            assert!(old.is_none());
+        }
+
+        let shader_module = asset_manager
+            .resources()
+            .get_or_create_shader_module(shader_package, Some(shader_module_hash))?;
+
+        Ok(ShaderAsset {
+            shader_module,
+            reflection_data: Arc::new(reflection_data_lookup),
+            variants: Default::default(),
+        })
+    }
 }
 
 pub struct ShaderLoadHandler;
@@ -38,26 +87,28 @@ impl DefaultAssetTypeLoadHandler<ShaderAssetData, ShaderAsset> for ShaderLoadHan
         asset_data: ShaderAssetData,
         _load_handle: LoadHandle,
     ) -> RafxResult<ShaderAsset> {
-        let mut reflection_data_lookup = FnvHashMap::default();
-        if let Some(reflection_data) = &asset_data.reflection_data {
-            for entry_point in reflection_data {
-                let old = reflection_data_lookup.insert(
-                    entry_point.rafx_api_reflection.entry_point_name.clone(),
-                    entry_point.clone(),
-                );
-                assert!(old.is_none());
-            }
+        let mut variants = FnvHashMap::default();
+        for variant in &asset_data.variants {
+            let variant_asset = ShaderAsset::load_shader_module(
+                asset_manager,
+                &variant.shader_package,
+                variant.shader_module_hash,
+                &variant.reflection_data,
+            )?;
+
+            let old = variants.insert(variant.name.clone(), variant_asset);
+            assert!(old.is_none());
         }
 
-        let shader_module = asset_manager.resources().get_or_create_shader_module(
+        let mut shader_asset = ShaderAsset::load_shader_module(
+            asset_manager,
             &asset_data.shader_package,
-            Some(asset_data.shader_module_hash),
+            asset_data.shader_module_hash,
+            asset_data.reflection_data.as_deref().unwrap_or(&[]),
         )?;
+        shader_asset.variants = Arc::new(variants);
 
-        Ok(ShaderAsset {
-            shader_module,
-            reflection_data: Arc::new(reflection_data_lookup),
-        })
+        Ok(shader_asset)
     }
 }
 
@@ -1,8 +1,8 @@
-use crate::assets::shader::ShaderAssetData;
+use crate::assets::shader::{ShaderAssetData, ShaderAssetVariantData};
 use distill::core::AssetUuid;
 use distill::importer::{ImportOp, ImportedAsset, Importer, ImporterValue};
 use rafx_api::{RafxShaderPackage, RafxShaderPackageVulkan};
-use rafx_framework::{CookedShaderPackage, ShaderModuleHash};
+use rafx_framework::{CookedShaderPackage, CookedShaderVariantSet, ShaderModuleHash};
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use type_uuid::*;
@@ -32,7 +32,7 @@ impl Importer for ShaderImporterSpv {
     where
         Self: Sized,
     {
-        5
+        7
     }
 
     fn version(&self) -> u32 {
@@ -73,6 +73,7 @@ impl Importer for ShaderImporterSpv {
             vk: Some(RafxShaderPackageVulkan::SpvBytes(spv_bytes)),
             gles2: None,
             gles3: None,
+            wgsl: None,
         };
 
         let shader_module_hash = ShaderModuleHash::new(&shader_package);
@@ -81,6 +82,7 @@ impl Importer for ShaderImporterSpv {
             shader_module_hash,
             shader_package,
             reflection_data: None,
+            variants: vec![],
         };
 
         Ok(ImporterValue {
@@ -108,7 +110,7 @@ impl Importer for ShaderImporterCooked {
     where
         Self: Sized,
     {
-        5
+        8
     }
 
     fn version(&self) -> u32 {
@@ -152,6 +154,101 @@ impl Importer for ShaderImporterCooked {
             shader_module_hash: cooked_shader.hash,
             shader_package: cooked_shader.shader_package,
             reflection_data: Some(cooked_shader.entry_points),
+            variants: vec![],
+        };
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id: asset_id,
+                search_tags: vec![],
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(shader_asset),
+            }],
+        })
+    }
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Default)]
+#[uuid = "5e9f3c41-4f26-4b5b-9a7d-1c8f0e6b2a73"]
+pub struct ShaderImporterCookedVariantsState(Option<AssetUuid>);
+
+// Imports a set of shader permutations produced by the shader processor for shaders that declare
+// variants. All permutations are loaded into a single ShaderAsset.
+#[derive(TypeUuid)]
+#[uuid = "a3d0b7e2-9c14-4c6e-8f51-2b7e6d90c4f8"]
+pub struct ShaderImporterCookedVariants;
+impl Importer for ShaderImporterCookedVariants {
+    fn version_static() -> u32
+    where
+        Self: Sized,
+    {
+        3
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    type Options = ();
+
+    type State = ShaderImporterCookedVariantsState;
+
+    /// Reads the given bytes and produces assets.
+    #[profiling::function]
+    fn import(
+        &self,
+        _op: &mut ImportOp,
+        source: &mut dyn Read,
+        _options: &Self::Options,
+        state: &mut Self::State,
+    ) -> distill::importer::Result<ImporterValue> {
+        let asset_id = state
+            .0
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+        *state = ShaderImporterCookedVariantsState(Some(asset_id));
+
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let variant_set: CookedShaderVariantSet = coerce_result_string(
+            bincode::deserialize::<CookedShaderVariantSet>(&bytes)
+                .map_err(|x| format!("Failed to deserialize cooked shader variants: {:?}", x)),
+        )?;
+
+        let default_variant = coerce_result_string(
+            variant_set
+                .default_variant()
+                .ok_or_else(|| "Cooked shader variant set has no variants".to_string()),
+        )?;
+
+        log::trace!(
+            "Import shader asset {:?} with {} variants",
+            asset_id,
+            variant_set.variants.len(),
+        );
+
+        let shader_module_hash = default_variant.hash;
+        let shader_package = default_variant.shader_package.clone();
+        let reflection_data = default_variant.entry_points.clone();
+
+        let variants = variant_set
+            .variants
+            .into_iter()
+            .map(|variant| ShaderAssetVariantData {
+                name: variant.name,
+                shader_module_hash: variant.package.hash,
+                shader_package: variant.package.shader_package,
+                reflection_data: variant.package.entry_points,
+            })
+            .collect();
+
+        let shader_asset = ShaderAssetData {
+            shader_module_hash,
+            shader_package,
+            reflection_data: Some(reflection_data),
+            variants,
         };
 
         Ok(ImporterValue {
@@ -23,6 +23,7 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
         mip_generation: ImageAssetMipGeneration::Runtime,
         color_space: ImageAssetColorSpaceConfig::Srgb,
         data_format: ImageAssetDataFormatConfig::Uncompressed,
+        allow_mip_streaming: false,
     });
 
     for suffix in normal_map_suffix {
@@ -33,6 +34,7 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
                 mip_generation: ImageAssetMipGeneration::Runtime,
                 color_space: ImageAssetColorSpaceConfig::Linear,
                 data_format: ImageAssetDataFormatConfig::Uncompressed,
+                allow_mip_streaming: false,
             },
         );
     }
@@ -45,6 +47,7 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
                 mip_generation: ImageAssetMipGeneration::Runtime,
                 color_space: ImageAssetColorSpaceConfig::Linear,
                 data_format: ImageAssetDataFormatConfig::Uncompressed,
+                allow_mip_streaming: false,
             },
         );
     }
@@ -58,6 +61,7 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
         .with_importer(&["materialinstance"], MaterialInstanceImporter)
         .with_importer(&["compute"], ComputePipelineImporter)
         .with_importer(&["cookedshaderpackage"], ShaderImporterCooked)
+        .with_importer(&["cookedshadervariants"], ShaderImporterCookedVariants)
         .with_importer(
             &["png"],
             ImageImporter(ImageFileFormat::Png, image_importer_config.clone()),
@@ -77,7 +81,10 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
         .with_importer(
             &["bmp"],
             ImageImporter(ImageFileFormat::Bmp, image_importer_config.clone()),
-        );
+        )
+        .with_importer(&["ktx2"], Ktx2ImageImporter)
+        .with_importer(&["hdr"], HdrImageImporter(HdrImageFileFormat::Hdr))
+        .with_importer(&["cube"], CubeLutImporter);
 
     #[cfg(feature = "basis-universal")]
     {
@@ -89,5 +96,10 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
         daemon = daemon.with_importer(&["dds"], DdsImageImporter);
     }
 
+    #[cfg(feature = "exr")]
+    {
+        daemon = daemon.with_importer(&["exr"], HdrImageImporter(HdrImageFileFormat::Exr));
+    }
+
     daemon
 }
@@ -975,6 +975,7 @@ impl RenderGraphBuilder {
             mip_count: texture_def.mip_count,
             layer_count: texture_def.array_length,
             samples: texture_def.sample_count,
+            dimensions: texture_def.dimensions,
         };
 
         let external_image_id = RenderGraphExternalImageId(self.external_images.len());
@@ -1,5 +1,8 @@
 use super::*;
-use rafx_api::{RafxExtents3D, RafxFormat, RafxResourceType, RafxSampleCount, RafxTextureBindType};
+use rafx_api::{
+    RafxExtents3D, RafxFormat, RafxResourceType, RafxSampleCount, RafxTextureBindType,
+    RafxTextureDimensions,
+};
 
 /// Unique ID for a particular usage (read or write) of a specific image
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -138,7 +141,7 @@ pub struct RenderGraphImageSpecification {
     pub extents: RafxExtents3D,
     pub layer_count: u32,
     pub mip_count: u32,
-    // image type - always 2D
+    pub dimensions: RafxTextureDimensions,
 }
 
 impl RenderGraphImageSpecification {
@@ -162,6 +165,9 @@ impl RenderGraphImageSpecification {
         if self.extents != other.extents {
             return false;
         }
+        if self.dimensions != other.dimensions {
+            return false;
+        }
 
         true
     }
@@ -200,6 +206,9 @@ impl RenderGraphImageSpecification {
         if written.extents != read.extents {
             return false;
         }
+        if written.dimensions != read.dimensions {
+            return false;
+        }
         if (written.resource_type | read.resource_type) != written.resource_type {
             return false;
         }
@@ -218,6 +227,7 @@ pub struct RenderGraphImageConstraint {
     pub extents: Option<RenderGraphImageExtents>,
     pub layer_count: Option<u32>,
     pub mip_count: Option<u32>,
+    pub dimensions: Option<RafxTextureDimensions>,
 }
 
 impl From<RenderGraphImageSpecification> for RenderGraphImageConstraint {
@@ -229,6 +239,7 @@ impl From<RenderGraphImageSpecification> for RenderGraphImageConstraint {
             layer_count: Some(specification.layer_count),
             mip_count: Some(specification.mip_count),
             extents: Some(RenderGraphImageExtents::Custom(specification.extents)),
+            dimensions: Some(specification.dimensions),
         }
     }
 }
@@ -252,6 +263,7 @@ impl RenderGraphImageConstraint {
                     .unwrap_or(RenderGraphImageExtents::MatchSurface)
                     .into_rafx_extents(swapchain_surface_info),
                 resource_type: self.resource_type,
+                dimensions: self.dimensions.unwrap_or(RafxTextureDimensions::Auto),
             })
         }
     }
@@ -284,6 +296,12 @@ impl RenderGraphImageConstraint {
         if self.extents.is_some() && other.extents.is_some() && self.extents != other.extents {
             return false;
         }
+        if self.dimensions.is_some()
+            && other.dimensions.is_some()
+            && self.dimensions != other.dimensions
+        {
+            return false;
+        }
 
         true
     }
@@ -313,6 +331,9 @@ impl RenderGraphImageConstraint {
         if self.extents.is_none() && other.extents.is_some() {
             self.extents = other.extents;
         }
+        if self.dimensions.is_none() && other.dimensions.is_some() {
+            self.dimensions = other.dimensions;
+        }
 
         self.resource_type |= other.resource_type;
 
@@ -363,6 +384,15 @@ impl RenderGraphImageConstraint {
             self.extents = other.extents;
         }
 
+        if self.dimensions.is_some()
+            && other.dimensions.is_some()
+            && self.dimensions != other.dimensions
+        {
+            complete_merge = false;
+        } else if other.dimensions.is_some() {
+            self.dimensions = other.dimensions;
+        }
+
         self.resource_type |= other.resource_type;
 
         complete_merge
@@ -6,7 +6,10 @@ use crate::graph::{
 };
 use crate::{BufferResource, ImageResource, ImageViewResource, ResourceArc, ResourceLookupSet};
 use fnv::FnvHashMap;
-use rafx_api::{RafxBufferDef, RafxDeviceContext, RafxMemoryUsage, RafxResult, RafxTextureDef};
+use rafx_api::{
+    RafxBufferDef, RafxDeviceContext, RafxMemoryCategory, RafxMemoryUsage, RafxResult,
+    RafxTextureDef,
+};
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
@@ -122,14 +125,17 @@ impl RenderGraphCacheInner {
                 buffer_resources.insert(id, cached_buffer.buffer.clone());
             } else {
                 // No unused buffer available, create one
-                let buffer = device_context.create_buffer(&RafxBufferDef {
-                    size: key.specification.size,
-                    //alignment: key.specification.alignment,
-                    memory_usage: RafxMemoryUsage::GpuOnly,
-                    resource_type: key.specification.resource_type,
-                    //initial_state: key.specification.initial_state,
-                    ..Default::default()
-                })?;
+                let buffer = device_context.create_buffer_in_category(
+                    &RafxBufferDef {
+                        size: key.specification.size,
+                        //alignment: key.specification.alignment,
+                        memory_usage: RafxMemoryUsage::GpuOnly,
+                        resource_type: key.specification.resource_type,
+                        //initial_state: key.specification.initial_state,
+                        ..Default::default()
+                    },
+                    RafxMemoryCategory::RenderGraph,
+                )?;
                 let buffer = resources.insert_buffer(buffer);
 
                 log::trace!(
@@ -212,15 +218,18 @@ impl RenderGraphCacheInner {
                 // No unused image available, create one
                 let extents = key.specification.extents;
 
-                let image = device_context.create_texture(&RafxTextureDef {
-                    extents,
-                    array_length: specification.layer_count,
-                    mip_count: specification.mip_count,
-                    format: specification.format,
-                    sample_count: specification.samples,
-                    resource_type: specification.resource_type,
-                    dimensions: Default::default(),
-                })?;
+                let image = device_context.create_texture_in_category(
+                    &RafxTextureDef {
+                        extents,
+                        array_length: specification.layer_count,
+                        mip_count: specification.mip_count,
+                        format: specification.format,
+                        sample_count: specification.samples,
+                        resource_type: specification.resource_type,
+                        dimensions: specification.dimensions,
+                    },
+                    RafxMemoryCategory::RenderGraph,
+                )?;
                 let image = resources.insert_image(image);
 
                 log::trace!(
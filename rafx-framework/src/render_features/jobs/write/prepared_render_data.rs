@@ -20,6 +20,7 @@ pub type SubmitNodeBlocks = FnvHashMap<ViewPhase, ViewPhaseSubmitNodeBlock>;
 pub struct PreparedRenderData<'write> {
     submit_node_blocks: &'write SubmitNodeBlocks,
     write_jobs: Vec<Option<Arc<dyn RenderFeatureWriteJob<'write> + 'write>>>,
+    is_additional_graph: bool,
 }
 
 impl<'write> PreparedRenderData<'write> {
@@ -30,6 +31,20 @@ impl<'write> PreparedRenderData<'write> {
         Self {
             submit_node_blocks,
             write_jobs,
+            is_additional_graph: false,
+        }
+    }
+
+    /// Used when more than one graph is executed in a frame (i.e. one per viewport). Per-frame work
+    /// in `on_begin_execute_graph` only runs for the first graph, so it is skipped for this one.
+    pub fn new_for_additional_graph(
+        submit_node_blocks: &'write SubmitNodeBlocks,
+        write_jobs: Vec<Option<Arc<dyn RenderFeatureWriteJob<'write> + 'write>>>,
+    ) -> Self {
+        Self {
+            submit_node_blocks,
+            write_jobs,
+            is_additional_graph: true,
         }
     }
 
@@ -37,6 +52,10 @@ impl<'write> PreparedRenderData<'write> {
         &self,
         write_context: &mut RenderJobBeginExecuteGraphContext,
     ) -> RafxResult<()> {
+        if self.is_additional_graph {
+            return Ok(());
+        }
+
         for writer in &self.write_jobs {
             if let Some(writer) = writer {
                 writer.on_begin_execute_graph(write_context)?;
@@ -12,6 +12,7 @@ pub use render_views::RenderView;
 pub use render_views::RenderViewCount;
 pub use render_views::RenderViewDepthRange;
 pub use render_views::RenderViewIndex;
+pub use render_views::RenderViewMatrices;
 pub use render_views::RenderViewSet;
 
 mod jobs;
@@ -58,7 +59,7 @@ pub mod render_features_prelude {
         RenderFeatureViewSubmitPacket, RenderFeatureWriteJob, RenderObjectHandle, RenderObjectId,
         RenderObjectInstanceId, RenderObjectInstanceObjectIds, RenderObjectInstancePerViewId,
         RenderObjectsMap, RenderPhase, RenderPhaseIndex, RenderRegistry, RenderRegistryBuilder,
-        RenderView, RenderViewIndex, RenderViewSet, RenderViewSubmitNodeCount,
+        RenderView, RenderViewIndex, RenderViewMatrices, RenderViewSet, RenderViewSubmitNodeCount,
         RenderViewVisibilityQuery, SubmitNode, SubmitNodeBlocks, SubmitNodeId, SubmitNodeSortKey,
         SubmitPacketData, ViewFrameIndex, ViewPacketSize, ViewPhase, ViewPhaseSubmitNodeBlock,
         ViewVisibilityJob, VisibleRenderObjects,
@@ -4,7 +4,7 @@ use crate::render_features::{
     RenderPhase, RenderPhaseIndex, RenderPhaseMask, ViewFrameIndex,
 };
 use crate::visibility::ViewFrustumArc;
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec2, Vec3};
 use rafx_visibility::{DepthRange, Projection};
 use std::mem::MaybeUninit;
 use std::sync::atomic::Ordering;
@@ -57,12 +57,81 @@ impl RenderViewSet {
         )
     }
 
+    /// Create a view that is drawn every frame with a projection offset by `jitter_amount` (in NDC)
+    /// and remembers the matrices the same view was drawn with in the previous frame. This is
+    /// needed for effects that reproject between frames (TAA, motion vectors)
+    pub fn create_view_with_history(
+        &self,
+        view_frustum: ViewFrustumArc,
+        eye_position: Vec3,
+        view: Mat4,
+        proj: Mat4,
+        extents: (u32, u32),
+        depth_range: RenderViewDepthRange,
+        render_phase_mask: RenderPhaseMask,
+        render_feature_mask: RenderFeatureMask,
+        render_feature_flag_mask: RenderFeatureFlagMask,
+        debug_name: String,
+        jitter_amount: Vec2,
+        previous_frame: Option<RenderViewMatrices>,
+    ) -> RenderView {
+        let view_index = self.view_count.fetch_add(1, Ordering::Release);
+        RenderView::new_with_history(
+            view_frustum,
+            view_index,
+            eye_position,
+            view,
+            proj,
+            extents,
+            depth_range,
+            render_phase_mask,
+            render_feature_mask,
+            render_feature_flag_mask,
+            debug_name,
+            self.frame_index,
+            jitter_amount,
+            previous_frame,
+        )
+    }
+
+    pub fn frame_index(&self) -> usize {
+        self.frame_index
+    }
+
     pub fn view_count(&self) -> RenderViewCount {
         self.view_count.load(Ordering::Acquire)
     }
 }
 
 ////////////////// Views //////////////////
+
+/// The matrices a view is drawn with in a single frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderViewMatrices {
+    pub view: Mat4,
+    pub proj: Mat4,
+    // proj with the frame's subpixel jitter applied. Equal to proj if the view is not jittered
+    pub jittered_proj: Mat4,
+}
+
+impl RenderViewMatrices {
+    pub fn view_proj(&self) -> Mat4 {
+        self.proj * self.view
+    }
+
+    pub fn jittered_view_proj(&self) -> Mat4 {
+        self.jittered_proj * self.view
+    }
+}
+
+// Same math as add_jitter() in taa_jitter.glsl: clip.xy += jitter * clip.w
+fn jitter_projection(
+    proj: Mat4,
+    jitter_amount: Vec2,
+) -> Mat4 {
+    Mat4::from_translation(jitter_amount.extend(0.0)) * proj
+}
+
 pub struct RenderViewInner {
     view_frustum: ViewFrustumArc,
     eye_position: Vec3,
@@ -80,6 +149,8 @@ pub struct RenderViewInner {
     render_feature_flag_mask: RenderFeatureFlagMask,
     debug_name: String,
     frame_index: usize,
+    jitter_amount: Vec2,
+    previous_frame: Option<RenderViewMatrices>,
 }
 
 #[derive(Clone)]
@@ -203,6 +274,40 @@ impl RenderView {
         render_feature_flag_mask: RenderFeatureFlagMask,
         debug_name: String,
         frame_index: usize,
+    ) -> RenderView {
+        Self::new_with_history(
+            view_frustum,
+            view_index,
+            eye_position,
+            view,
+            proj,
+            extents,
+            depth_range,
+            render_phase_mask,
+            render_feature_mask,
+            render_feature_flag_mask,
+            debug_name,
+            frame_index,
+            Vec2::ZERO,
+            None,
+        )
+    }
+
+    pub fn new_with_history(
+        view_frustum: ViewFrustumArc,
+        view_index: RenderViewIndex,
+        eye_position: Vec3,
+        view: Mat4,
+        proj: Mat4,
+        extents: (u32, u32),
+        depth_range: RenderViewDepthRange,
+        render_phase_mask: RenderPhaseMask,
+        render_feature_mask: RenderFeatureMask,
+        render_feature_flag_mask: RenderFeatureFlagMask,
+        debug_name: String,
+        frame_index: usize,
+        jitter_amount: Vec2,
+        previous_frame: Option<RenderViewMatrices>,
     ) -> RenderView {
         let view_dir = Self::view_mat4_to_view_dir(&view);
 
@@ -222,6 +327,8 @@ impl RenderView {
             render_feature_flag_mask,
             debug_name,
             frame_index,
+            jitter_amount,
+            previous_frame,
         };
 
         RenderView {
@@ -257,6 +364,49 @@ impl RenderView {
         self.inner.view_proj
     }
 
+    /// The subpixel offset applied to the projection this frame, in NDC
+    pub fn jitter_amount(&self) -> Vec2 {
+        self.inner.jitter_amount
+    }
+
+    pub fn jittered_projection_matrix(&self) -> Mat4 {
+        jitter_projection(self.inner.proj, self.inner.jitter_amount)
+    }
+
+    pub fn jittered_view_proj(&self) -> Mat4 {
+        self.jittered_projection_matrix() * self.inner.view
+    }
+
+    pub fn matrices(&self) -> RenderViewMatrices {
+        RenderViewMatrices {
+            view: self.inner.view,
+            proj: self.inner.proj,
+            jittered_proj: self.jittered_projection_matrix(),
+        }
+    }
+
+    /// The matrices this view was drawn with in the previous frame. None if the view was not drawn
+    /// in the previous frame, or was not created with history.
+    pub fn previous_frame(&self) -> Option<&RenderViewMatrices> {
+        self.inner.previous_frame.as_ref()
+    }
+
+    /// The previous frame's unjittered view_proj, or the current one if there is no history
+    pub fn previous_view_proj(&self) -> Mat4 {
+        self.inner
+            .previous_frame
+            .map(|x| x.view_proj())
+            .unwrap_or(self.inner.view_proj)
+    }
+
+    /// The previous frame's jittered view_proj, or the current one if there is no history
+    pub fn previous_jittered_view_proj(&self) -> Mat4 {
+        self.inner
+            .previous_frame
+            .map(|x| x.jittered_view_proj())
+            .unwrap_or_else(|| self.jittered_view_proj())
+    }
+
     pub fn view_index(&self) -> RenderViewIndex {
         self.inner.view_index
     }
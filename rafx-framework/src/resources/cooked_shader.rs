@@ -14,6 +14,36 @@ pub struct CookedShaderPackage {
     pub entry_points: Vec<ReflectedEntryPoint>,
 }
 
+// One permutation of a shader, compiled with a particular set of preprocessor defines
+#[derive(Serialize, Deserialize)]
+pub struct CookedShaderVariant {
+    pub name: String,
+    pub defines: Vec<String>,
+    pub package: CookedShaderPackage,
+}
+
+// An import format for shaders that declare permutations. The first variant is the default.
+#[derive(Serialize, Deserialize)]
+pub struct CookedShaderVariantSet {
+    pub variants: Vec<CookedShaderVariant>,
+}
+
+impl CookedShaderVariantSet {
+    pub fn find_variant(
+        &self,
+        variant_name: &str,
+    ) -> Option<&CookedShaderPackage> {
+        self.variants
+            .iter()
+            .find(|x| x.name == variant_name)
+            .map(|x| &x.package)
+    }
+
+    pub fn default_variant(&self) -> Option<&CookedShaderPackage> {
+        self.variants.first().map(|x| &x.package)
+    }
+}
+
 impl CookedShaderPackage {
     pub fn find_entry_point(
         &self,
@@ -76,6 +106,6 @@ impl CookedShaderPackage {
         }
 
         let reflected_shader = ReflectedShader::new(resources, &shader_modules, &entry_points)?;
-        reflected_shader.load_material_pass(resources, fixed_function_state)
+        reflected_shader.load_material_pass(resources, fixed_function_state, None)
     }
 }
@@ -0,0 +1,477 @@
+use super::{add_to_frame_in_flight_index, FrameInFlightIndex};
+use super::{MAX_FRAMES_IN_FLIGHT, MAX_FRAMES_IN_FLIGHT_PLUS_1};
+use crate::resources::resource_lookup::DescriptorSetLayoutResource;
+use crate::{BufferResource, ImageViewResource, ResourceArc};
+use fnv::FnvHashMap;
+use rafx_api::{
+    RafxCommandBuffer, RafxDescriptorElements, RafxDescriptorKey, RafxDescriptorSetArray,
+    RafxDescriptorSetArrayDef, RafxDescriptorSetHandle, RafxDescriptorUpdate, RafxDeviceContext,
+    RafxResult,
+};
+use std::collections::VecDeque;
+use std::num::Wrapping;
+
+struct PendingSlotFree<T>
+where
+    T: std::fmt::Debug + Clone,
+{
+    index: u32,
+    // Held until the GPU can no longer be reading the descriptor that points to it
+    _resource: ResourceArc<T>,
+    live_until_frame: Wrapping<u32>,
+}
+
+// Hands out stable indices into a single bindless binding. Indices are reference counted so that
+// adding the same resource twice returns the same index. Freed indices are not reused until
+// MAX_FRAMES_IN_FLIGHT frames have passed so that in-flight command buffers never see a descriptor
+// change underneath them.
+struct BindlessSlots<T>
+where
+    T: std::fmt::Debug + Clone,
+{
+    binding: u32,
+    capacity: u32,
+    lookup: FnvHashMap<ResourceArc<T>, (u32, u32)>,
+    free_list: Vec<u32>,
+    next_unused_index: u32,
+    pending_frees: VecDeque<PendingSlotFree<T>>,
+}
+
+impl<T> BindlessSlots<T>
+where
+    T: std::fmt::Debug + Clone,
+{
+    fn new(
+        binding: u32,
+        capacity: u32,
+    ) -> Self {
+        BindlessSlots {
+            binding,
+            capacity,
+            lookup: Default::default(),
+            free_list: Default::default(),
+            next_unused_index: 0,
+            pending_frees: Default::default(),
+        }
+    }
+
+    fn index_of(
+        &self,
+        resource: &ResourceArc<T>,
+    ) -> Option<u32> {
+        self.lookup.get(resource).map(|(index, _)| *index)
+    }
+
+    // Returns the index and true if the descriptor at that index needs to be written
+    fn add(
+        &mut self,
+        resource: &ResourceArc<T>,
+    ) -> RafxResult<(u32, bool)> {
+        if let Some((index, ref_count)) = self.lookup.get_mut(resource) {
+            *ref_count += 1;
+            return Ok((*index, false));
+        }
+
+        let index = if let Some(index) = self.free_list.pop() {
+            index
+        } else if self.next_unused_index < self.capacity {
+            self.next_unused_index += 1;
+            self.next_unused_index - 1
+        } else {
+            return Err(format!(
+                "Bindless resource table binding {} is full (capacity {})",
+                self.binding, self.capacity
+            ))?;
+        };
+
+        self.lookup.insert(resource.clone(), (index, 1));
+        Ok((index, true))
+    }
+
+    fn remove(
+        &mut self,
+        resource: &ResourceArc<T>,
+        frame_index: Wrapping<u32>,
+    ) {
+        let remaining = if let Some((_, ref_count)) = self.lookup.get_mut(resource) {
+            *ref_count -= 1;
+            *ref_count
+        } else {
+            log::warn!(
+                "Tried to remove a resource from bindless binding {} that was not added",
+                self.binding
+            );
+            return;
+        };
+
+        if remaining == 0 {
+            let (resource, (index, _)) = self.lookup.remove_entry(resource).unwrap();
+            self.pending_frees.push_back(PendingSlotFree {
+                index,
+                _resource: resource,
+                live_until_frame: frame_index + Wrapping(MAX_FRAMES_IN_FLIGHT as u32),
+            });
+        }
+    }
+
+    fn on_frame_complete(
+        &mut self,
+        frame_index: Wrapping<u32>,
+    ) {
+        // Pending frees are pushed in frame order, so the expired ones are all at the front. Same
+        // wrapping comparison as ResourceDropSink.
+        while let Some(pending_free) = self.pending_frees.front() {
+            if pending_free.live_until_frame - frame_index > Wrapping(std::u32::MAX / 2) {
+                let pending_free = self.pending_frees.pop_front().unwrap();
+                self.free_list.push(pending_free.index);
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+fn find_bindless_binding_capacity(
+    descriptor_set_layout: &ResourceArc<DescriptorSetLayoutResource>,
+    binding: u32,
+) -> RafxResult<u32> {
+    let binding_def = descriptor_set_layout
+        .get_raw()
+        .descriptor_set_layout_def
+        .bindings
+        .iter()
+        .find(|x| x.resource.binding == binding)
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "Descriptor set layout does not contain binding {} for the bindless resource table",
+                binding
+            )
+        })?;
+
+    if !binding_def.bindless {
+        return Err(format!(
+            "Binding {} used by the bindless resource table was not created as bindless",
+            binding
+        ))?;
+    }
+
+    Ok(binding_def.resource.element_count_normalized())
+}
+
+/// A descriptor set containing large, partially-bound arrays of textures and/or buffers.
+/// Resources are added to the table once and referred to by index from shaders, instead of
+/// writing a descriptor set per draw. Requires a descriptor set layout whose bindings were marked
+/// bindless (see `MaterialPass::new_with_bindless_slots`).
+///
+/// The set may also contain regular bindings that change every frame (see `set_buffer`), so the
+/// table keeps a copy of the set for each frame in flight. Bindless writes go to every copy.
+///
+/// Each frame, call `update()`, then add/remove resources and set per-frame bindings, then
+/// `flush()` before recording command buffers that bind the table. The table must not be dropped
+/// while the GPU may still be using it.
+pub struct BindlessResourceTable {
+    descriptor_set_layout: ResourceArc<DescriptorSetLayoutResource>,
+    descriptor_set_array: RafxDescriptorSetArray,
+    textures: Option<BindlessSlots<ImageViewResource>>,
+    buffers: Option<BindlessSlots<BufferResource>>,
+    // Buffers written to regular bindings of each copy of the set, held until the copy is reused
+    frame_buffers: Vec<Vec<ResourceArc<BufferResource>>>,
+    has_queued_writes: bool,
+    frame_index: Wrapping<u32>,
+    frame_in_flight_index: FrameInFlightIndex,
+}
+
+impl BindlessResourceTable {
+    pub fn new(
+        device_context: &RafxDeviceContext,
+        descriptor_set_layout: ResourceArc<DescriptorSetLayoutResource>,
+        texture_binding: Option<u32>,
+        buffer_binding: Option<u32>,
+    ) -> RafxResult<Self> {
+        let textures = if let Some(binding) = texture_binding {
+            let capacity = find_bindless_binding_capacity(&descriptor_set_layout, binding)?;
+            Some(BindlessSlots::new(binding, capacity))
+        } else {
+            None
+        };
+
+        let buffers = if let Some(binding) = buffer_binding {
+            let capacity = find_bindless_binding_capacity(&descriptor_set_layout, binding)?;
+            Some(BindlessSlots::new(binding, capacity))
+        } else {
+            None
+        };
+
+        let descriptor_set_array =
+            device_context.create_descriptor_set_array(&RafxDescriptorSetArrayDef {
+                root_signature: &descriptor_set_layout.get_raw().root_signature,
+                set_index: descriptor_set_layout.get_raw().set_index,
+                array_length: MAX_FRAMES_IN_FLIGHT_PLUS_1,
+            })?;
+
+        Ok(BindlessResourceTable {
+            descriptor_set_layout,
+            descriptor_set_array,
+            textures,
+            buffers,
+            frame_buffers: vec![Vec::default(); MAX_FRAMES_IN_FLIGHT_PLUS_1],
+            has_queued_writes: false,
+            frame_index: Wrapping(0),
+            frame_in_flight_index: 0,
+        })
+    }
+
+    pub fn descriptor_set_layout(&self) -> &ResourceArc<DescriptorSetLayoutResource> {
+        &self.descriptor_set_layout
+    }
+
+    pub fn texture_index(
+        &self,
+        image_view: &ResourceArc<ImageViewResource>,
+    ) -> Option<u32> {
+        self.textures.as_ref()?.index_of(image_view)
+    }
+
+    pub fn buffer_index(
+        &self,
+        buffer: &ResourceArc<BufferResource>,
+    ) -> Option<u32> {
+        self.buffers.as_ref()?.index_of(buffer)
+    }
+
+    /// Returns the index shaders should use to access the texture. Adding the same image view
+    /// again returns the same index, and it must be removed as many times as it was added.
+    pub fn add_texture(
+        &mut self,
+        image_view: &ResourceArc<ImageViewResource>,
+    ) -> RafxResult<u32> {
+        let textures = self
+            .textures
+            .as_mut()
+            .ok_or("Bindless resource table was not created with a texture binding")?;
+
+        let (index, needs_write) = textures.add(image_view)?;
+        if needs_write {
+            for array_index in 0..MAX_FRAMES_IN_FLIGHT_PLUS_1 as u32 {
+                self.descriptor_set_array
+                    .queue_descriptor_set_update(&RafxDescriptorUpdate {
+                        array_index,
+                        descriptor_key: RafxDescriptorKey::Binding(textures.binding),
+                        elements: RafxDescriptorElements {
+                            textures: Some(&[&image_view.get_raw().image.get_raw().image]),
+                            ..Default::default()
+                        },
+                        dst_element_offset: index,
+                        texture_bind_type: image_view.get_raw().texture_bind_type,
+                    })?;
+            }
+            self.has_queued_writes = true;
+        }
+
+        Ok(index)
+    }
+
+    /// Returns the index shaders should use to access the buffer. Adding the same buffer again
+    /// returns the same index, and it must be removed as many times as it was added.
+    pub fn add_buffer(
+        &mut self,
+        buffer: &ResourceArc<BufferResource>,
+    ) -> RafxResult<u32> {
+        let buffers = self
+            .buffers
+            .as_mut()
+            .ok_or("Bindless resource table was not created with a buffer binding")?;
+
+        let (index, needs_write) = buffers.add(buffer)?;
+        if needs_write {
+            for array_index in 0..MAX_FRAMES_IN_FLIGHT_PLUS_1 as u32 {
+                self.descriptor_set_array
+                    .queue_descriptor_set_update(&RafxDescriptorUpdate {
+                        array_index,
+                        descriptor_key: RafxDescriptorKey::Binding(buffers.binding),
+                        elements: RafxDescriptorElements {
+                            buffers: Some(&[&*buffer.get_raw().buffer]),
+                            ..Default::default()
+                        },
+                        dst_element_offset: index,
+                        texture_bind_type: None,
+                    })?;
+            }
+            self.has_queued_writes = true;
+        }
+
+        Ok(index)
+    }
+
+    /// The index is not reused until MAX_FRAMES_IN_FLIGHT frames have passed
+    pub fn remove_texture(
+        &mut self,
+        image_view: &ResourceArc<ImageViewResource>,
+    ) {
+        if let Some(textures) = &mut self.textures {
+            textures.remove(image_view, self.frame_index);
+        }
+    }
+
+    /// The index is not reused until MAX_FRAMES_IN_FLIGHT frames have passed
+    pub fn remove_buffer(
+        &mut self,
+        buffer: &ResourceArc<BufferResource>,
+    ) {
+        if let Some(buffers) = &mut self.buffers {
+            buffers.remove(buffer, self.frame_index);
+        }
+    }
+
+    /// Sets a regular (not bindless) buffer binding for the current frame only. Unlike bindless
+    /// elements, it must be set again every frame.
+    pub fn set_buffer(
+        &mut self,
+        binding: u32,
+        buffer: &ResourceArc<BufferResource>,
+    ) -> RafxResult<()> {
+        self.descriptor_set_array
+            .queue_descriptor_set_update(&RafxDescriptorUpdate {
+                array_index: self.frame_in_flight_index,
+                descriptor_key: RafxDescriptorKey::Binding(binding),
+                elements: RafxDescriptorElements {
+                    buffers: Some(&[&*buffer.get_raw().buffer]),
+                    ..Default::default()
+                },
+                dst_element_offset: 0,
+                texture_bind_type: None,
+            })?;
+        self.frame_buffers[self.frame_in_flight_index as usize].push(buffer.clone());
+        self.has_queued_writes = true;
+        Ok(())
+    }
+
+    /// Call once per frame, before adding or removing resources for the frame
+    pub fn update(&mut self) {
+        self.frame_index += Wrapping(1);
+        if let Some(textures) = &mut self.textures {
+            textures.on_frame_complete(self.frame_index);
+        }
+        if let Some(buffers) = &mut self.buffers {
+            buffers.on_frame_complete(self.frame_index);
+        }
+
+        // The GPU finished with this copy of the set MAX_FRAMES_IN_FLIGHT frames ago
+        self.frame_in_flight_index = add_to_frame_in_flight_index(self.frame_in_flight_index, 1);
+        self.frame_buffers[self.frame_in_flight_index as usize].clear();
+    }
+
+    /// Writes all queued descriptor updates. Call before recording any command buffers that bind
+    /// the table.
+    pub fn flush(&mut self) -> RafxResult<()> {
+        if self.has_queued_writes {
+            self.descriptor_set_array.flush_descriptor_set_updates()?;
+            self.has_queued_writes = false;
+        }
+
+        Ok(())
+    }
+
+    /// The copy of the set used by the current frame. Command buffers recorded this frame may bind
+    /// it after the table itself is no longer accessible.
+    pub fn descriptor_set_handle(&self) -> RafxDescriptorSetHandle {
+        self.descriptor_set_array
+            .handle(self.frame_in_flight_index)
+            .unwrap()
+    }
+
+    pub fn bind(
+        &self,
+        command_buffer: &RafxCommandBuffer,
+    ) -> RafxResult<()> {
+        command_buffer
+            .cmd_bind_descriptor_set(&self.descriptor_set_array, self.frame_in_flight_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resources::resource_arc::{ResourceId, ResourceWithHash};
+    use crossbeam_channel::{Receiver, Sender};
+
+    fn test_resource(
+        id: u64,
+        drop_tx: &Sender<ResourceWithHash<u64>>,
+    ) -> ResourceArc<u64> {
+        ResourceArc::new(id, ResourceId(id), drop_tx.clone())
+    }
+
+    fn dropped_resources(drop_rx: &Receiver<ResourceWithHash<u64>>) -> Vec<u64> {
+        drop_rx.try_iter().map(|x| x.resource).collect()
+    }
+
+    #[test]
+    fn test_indices_are_allocated_in_order_until_full() {
+        let (drop_tx, _drop_rx) = crossbeam_channel::unbounded();
+        let mut slots = BindlessSlots::new(0, 2);
+
+        let a = test_resource(1, &drop_tx);
+        let b = test_resource(2, &drop_tx);
+        let c = test_resource(3, &drop_tx);
+        assert_eq!(slots.add(&a).unwrap(), (0, true));
+        assert_eq!(slots.add(&b).unwrap(), (1, true));
+        assert_eq!(slots.index_of(&a), Some(0));
+        assert_eq!(slots.index_of(&b), Some(1));
+        assert_eq!(slots.index_of(&c), None);
+        assert!(slots.add(&c).is_err());
+    }
+
+    #[test]
+    fn test_indices_are_ref_counted() {
+        let (drop_tx, drop_rx) = crossbeam_channel::unbounded();
+        let mut slots = BindlessSlots::new(0, 4);
+        let frame_index = Wrapping(10);
+
+        let a = test_resource(1, &drop_tx);
+        assert_eq!(slots.add(&a).unwrap(), (0, true));
+        // Adding again returns the same index, and the descriptor does not need to be written
+        assert_eq!(slots.add(&a).unwrap(), (0, false));
+
+        slots.remove(&a, frame_index);
+        assert_eq!(slots.index_of(&a), Some(0));
+        assert!(slots.pending_frees.is_empty());
+
+        slots.remove(&a, frame_index);
+        assert_eq!(slots.index_of(&a), None);
+        assert_eq!(slots.pending_frees.len(), 1);
+
+        // The slots still hold the resource until the pending free expires
+        drop(a);
+        assert!(dropped_resources(&drop_rx).is_empty());
+    }
+
+    #[test]
+    fn test_freed_indices_are_reused_after_frames_in_flight() {
+        let (drop_tx, drop_rx) = crossbeam_channel::unbounded();
+        let mut slots = BindlessSlots::new(0, 4);
+        let mut frame_index = Wrapping(u32::MAX - 1);
+
+        let a = test_resource(1, &drop_tx);
+        let b = test_resource(2, &drop_tx);
+        assert_eq!(slots.add(&a).unwrap(), (0, true));
+        slots.remove(&a, frame_index);
+        drop(a);
+
+        // Until MAX_FRAMES_IN_FLIGHT frames have passed, index 0 may still be in use by the GPU
+        for _ in 0..MAX_FRAMES_IN_FLIGHT {
+            frame_index += Wrapping(1);
+            slots.on_frame_complete(frame_index);
+            assert!(dropped_resources(&drop_rx).is_empty());
+        }
+        assert_eq!(slots.add(&b).unwrap(), (1, true));
+
+        frame_index += Wrapping(1);
+        slots.on_frame_complete(frame_index);
+        assert_eq!(dropped_resources(&drop_rx), vec![1]);
+
+        let c = test_resource(3, &drop_tx);
+        assert_eq!(slots.add(&c).unwrap(), (0, true));
+    }
+}
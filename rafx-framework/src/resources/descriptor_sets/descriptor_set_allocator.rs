@@ -108,6 +108,17 @@ impl DescriptorSetAllocator {
         descriptor_set_layout: &ResourceArc<DescriptorSetLayoutResource>,
         write_set: DescriptorSetWriteSet,
     ) -> RafxResult<DescriptorSetArc> {
+        #[cfg(debug_assertions)]
+        {
+            let layout = descriptor_set_layout.get_raw();
+            crate::validate_descriptor_set_writes(
+                layout.debug_name.as_deref(),
+                layout.set_index,
+                &layout.descriptor_set_layout_def,
+                &write_set,
+            )?;
+        }
+
         Self::get_or_create_pool_for_layout(
             &mut self.pools,
             &self.device_context,
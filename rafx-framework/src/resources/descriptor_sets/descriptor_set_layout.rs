@@ -14,6 +14,11 @@ pub struct DescriptorSetLayoutBinding {
     // and automatically bind it - this makes binding data easy to do without having to manage
     // buffers.
     pub internal_buffer_per_descriptor_size: Option<u32>,
+
+    // If true, the binding is created as a bindless (update-after-bind, partially bound) array.
+    // Only valid for texture/buffer arrays and only when the device supports bindless.
+    #[serde(default)]
+    pub bindless: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
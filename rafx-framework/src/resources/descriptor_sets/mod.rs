@@ -41,6 +41,9 @@ pub use descriptor_set_allocator::DescriptorSetAllocatorMetrics;
 pub use descriptor_set_allocator::DescriptorSetInitializer;
 pub use descriptor_set_allocator::DescriptorSetPoolMetrics;
 
+mod bindless_resource_table;
+pub use bindless_resource_table::BindlessResourceTable;
+
 mod descriptor_set_allocator_manager;
 use crate::{DescriptorSetLayoutResource, ResourceArc};
 pub(super) use descriptor_set_allocator_manager::DescriptorSetAllocatorManager;
@@ -1,7 +1,7 @@
 use crate::{
     DescriptorSetWriteSet, FixedFunctionState, MaterialPassResource, MaterialPassVertexInput,
     RafxResult, ReflectedEntryPoint, ReflectedShader, ResourceArc, ResourceContext,
-    ShaderModuleResource, SlotNameLookup,
+    ShaderModuleResource, SlotNameLookup, VertexDataSetLayout,
 };
 use rafx_api::RafxShaderStageFlags;
 use serde::{Deserialize, Serialize};
@@ -61,12 +61,54 @@ impl MaterialPass {
         fixed_function_state: Arc<FixedFunctionState>,
         shader_modules: Vec<ResourceArc<ShaderModuleResource>>,
         entry_points: &[&ReflectedEntryPoint],
+        debug_name: Option<&str>,
     ) -> RafxResult<MaterialPass> {
-        let reflected_shader =
+        MaterialPass::new_with_bindless_slots(
+            resource_context,
+            fixed_function_state,
+            shader_modules,
+            entry_points,
+            &[],
+            debug_name,
+        )
+    }
+
+    /// Like `new`, but the bindings assigned to the given slot names are created as bindless
+    /// descriptors. If the device does not support bindless, the slots are bound normally.
+    pub fn new_with_bindless_slots(
+        resource_context: &ResourceContext,
+        fixed_function_state: Arc<FixedFunctionState>,
+        shader_modules: Vec<ResourceArc<ShaderModuleResource>>,
+        entry_points: &[&ReflectedEntryPoint],
+        bindless_slots: &[String],
+        debug_name: Option<&str>,
+    ) -> RafxResult<MaterialPass> {
+        let mut reflected_shader =
             ReflectedShader::new(resource_context.resources(), &shader_modules, entry_points)?;
 
-        let material_pass = reflected_shader
-            .load_material_pass(resource_context.resources(), fixed_function_state)?;
+        if !bindless_slots.is_empty() {
+            if resource_context
+                .device_context()
+                .device_info()
+                .supports_bindless
+            {
+                reflected_shader
+                    .metadata
+                    .mark_bindless_slots(bindless_slots)?;
+            } else {
+                log::info!(
+                    "Bindless is not supported or enabled, slots {:?} in material pass {:?} will be bound normally",
+                    bindless_slots,
+                    debug_name
+                );
+            }
+        }
+
+        let material_pass = reflected_shader.load_material_pass(
+            resource_context.resources(),
+            fixed_function_state,
+            debug_name,
+        )?;
 
         let vertex_inputs = reflected_shader
             .metadata
@@ -102,6 +144,61 @@ impl MaterialPass {
 
         pass_descriptor_set_writes
     }
+
+    pub fn debug_name(&self) -> Option<String> {
+        self.inner
+            .material_pass_resource
+            .get_raw()
+            .debug_name
+            .clone()
+    }
+
+    /// Returns a descriptive error if the vertex layout does not provide data for every vertex
+    /// input of this pass in a compatible format
+    pub fn validate_vertex_data_set_layout(
+        &self,
+        vertex_data_set_layout: &VertexDataSetLayout,
+    ) -> RafxResult<()> {
+        super::validate_vertex_data_set_layout(
+            self.debug_name().as_deref(),
+            &self.vertex_inputs,
+            vertex_data_set_layout,
+        )
+    }
+
+    /// Returns a descriptive error if any of the writes (one write set per descriptor set layout,
+    /// like those returned by create_uninitialized_write_sets_for_material_pass) do not match the
+    /// bindings declared by this pass's shaders
+    pub fn validate_write_sets(
+        &self,
+        write_sets: &[DescriptorSetWriteSet],
+    ) -> RafxResult<()> {
+        let descriptor_set_layouts = &self
+            .inner
+            .material_pass_resource
+            .get_raw()
+            .descriptor_set_layouts;
+        if write_sets.len() != descriptor_set_layouts.len() {
+            Err(format!(
+                "Material pass '{}' has {} descriptor set layouts but {} write sets were provided",
+                self.debug_name().as_deref().unwrap_or("<unnamed>"),
+                descriptor_set_layouts.len(),
+                write_sets.len()
+            ))?;
+        }
+
+        for (layout, write_set) in descriptor_set_layouts.iter().zip(write_sets) {
+            let layout = layout.get_raw();
+            super::validate_descriptor_set_writes(
+                self.debug_name().as_deref(),
+                layout.set_index,
+                &layout.descriptor_set_layout_def,
+                write_set,
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Deref for MaterialPass {
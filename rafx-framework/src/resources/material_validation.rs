@@ -0,0 +1,400 @@
+//
+// Checks data that is paired with a material pass at runtime against the pass's reflection data.
+// Without these checks, mismatches show up as backend validation errors or garbage rendering.
+//
+use crate::resources::descriptor_sets::{
+    DescriptorSetElementWrite, DescriptorSetWriteElementBufferData,
+    DescriptorSetWriteElementImageValue,
+};
+use crate::{
+    DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetWriteSet,
+    MaterialPassVertexInput, VertexDataSetLayout,
+};
+use rafx_api::{RafxFormat, RafxResourceType, RafxResult, RafxTextureBindType};
+
+// Passes loaded from assets are named, but passes created by hand or descriptor sets flushed outside
+// of a material may not be
+fn describe_material_pass(material_name: Option<&str>) -> String {
+    match material_name {
+        Some(material_name) => format!("Material pass '{}'", material_name),
+        None => "Material pass".to_string(),
+    }
+}
+
+fn slot_display_name(binding: &DescriptorSetLayoutBinding) -> &str {
+    binding.resource.name.as_deref().unwrap_or("<unnamed>")
+}
+
+// The kind of number a shader reads from a vertex attribute in this format. Normalized and scaled
+// formats are converted to floats by the GPU
+fn vertex_format_number_kind(format: RafxFormat) -> &'static str {
+    if format.is_sint() {
+        "signed integer"
+    } else if format.is_uint() {
+        "unsigned integer"
+    } else {
+        "float"
+    }
+}
+
+/// Verifies that the vertex layout provides every input the material pass reads, in a format the
+/// shader can read. It is ok for the vertex layout to have additional members.
+pub fn validate_vertex_data_set_layout(
+    material_name: Option<&str>,
+    vertex_inputs: &[MaterialPassVertexInput],
+    vertex_data_set_layout: &VertexDataSetLayout,
+) -> RafxResult<()> {
+    for vertex_input in vertex_inputs {
+        let member = match vertex_data_set_layout.member(&vertex_input.semantic) {
+            Some(member) => member,
+            None => {
+                let mut available_semantics: Vec<_> =
+                    vertex_data_set_layout.members().keys().collect();
+                available_semantics.sort();
+                Err(format!(
+                    "{} vertex input '{}' (location {}) requires semantic {}, but the vertex layout only provides {:?}",
+                    describe_material_pass(material_name),
+                    vertex_input.gl_attribute_name,
+                    vertex_input.location,
+                    vertex_input.semantic,
+                    available_semantics
+                ))?
+            }
+        };
+
+        if vertex_input.format.is_undefined() {
+            continue;
+        }
+
+        let expected_kind = vertex_format_number_kind(vertex_input.format);
+        let provided_kind = vertex_format_number_kind(member.format);
+        if expected_kind != provided_kind {
+            Err(format!(
+                "{} vertex input '{}' (semantic {}) is read as a {} ({:?}), but the vertex layout provides it as {:?}",
+                describe_material_pass(material_name),
+                vertex_input.gl_attribute_name,
+                vertex_input.semantic,
+                expected_kind,
+                vertex_input.format,
+                member.format
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that every write in the set targets a binding that exists in the layout, within the
+/// binding's array size, with a resource of the type the binding expects.
+pub fn validate_descriptor_set_writes(
+    material_name: Option<&str>,
+    set_index: u32,
+    layout: &DescriptorSetLayout,
+    write_set: &DescriptorSetWriteSet,
+) -> RafxResult<()> {
+    for (key, write) in &write_set.elements {
+        let binding = layout
+            .bindings
+            .iter()
+            .find(|x| x.resource.binding == key.dst_binding)
+            .ok_or_else(|| {
+                format!(
+                    "{} descriptor set {} has a write to binding {}, but the shader does not declare that binding",
+                    describe_material_pass(material_name),
+                    set_index,
+                    key.dst_binding
+                )
+            })?;
+
+        let element_count = binding.resource.element_count_normalized() as usize;
+        if key.array_index >= element_count {
+            Err(format!(
+                "{} slot '{}' (set {} binding {}) is written at array index {}, but the shader declares {} element(s)",
+                describe_material_pass(material_name),
+                slot_display_name(binding),
+                set_index,
+                key.dst_binding,
+                key.array_index,
+                element_count
+            ))?;
+        }
+
+        if write.descriptor_type != binding.resource.resource_type {
+            Err(format!(
+                "{} slot '{}' (set {} binding {}) is a {:?}, but the write is for a {:?}",
+                describe_material_pass(material_name),
+                slot_display_name(binding),
+                set_index,
+                key.dst_binding,
+                binding.resource.resource_type,
+                write.descriptor_type
+            ))?;
+        }
+
+        validate_descriptor_set_element_write(material_name, set_index, binding, write)?;
+    }
+
+    Ok(())
+}
+
+fn validate_descriptor_set_element_write(
+    material_name: Option<&str>,
+    set_index: u32,
+    binding: &DescriptorSetLayoutBinding,
+    write: &DescriptorSetElementWrite,
+) -> RafxResult<()> {
+    let what_to_bind = super::descriptor_sets::what_to_bind(write);
+
+    if let Some(DescriptorSetWriteElementImageValue::Resource(image_view)) =
+        &write.image_info.image_view
+    {
+        if !what_to_bind.bind_images {
+            Err(format!(
+                "{} slot '{}' (set {} binding {}) is a {:?} and can't be bound to an image",
+                describe_material_pass(material_name),
+                slot_display_name(binding),
+                set_index,
+                binding.resource.binding,
+                binding.resource.resource_type
+            ))?;
+        }
+
+        // Storage images must be bound with an unordered access view (a single mip level). Sampling
+        // from a UAV is fine.
+        let expects_read_write = binding
+            .resource
+            .resource_type
+            .intersects(RafxResourceType::TEXTURE_READ_WRITE);
+        let is_shader_resource_view = match image_view.get_raw().texture_bind_type {
+            Some(RafxTextureBindType::Srv) | Some(RafxTextureBindType::SrvStencil) => true,
+            _ => false,
+        };
+
+        if expects_read_write && is_shader_resource_view {
+            Err(format!(
+                "{} slot '{}' (set {} binding {}) is a {:?}, but the image view is bound as {:?}",
+                describe_material_pass(material_name),
+                slot_display_name(binding),
+                set_index,
+                binding.resource.binding,
+                binding.resource.resource_type,
+                image_view.get_raw().texture_bind_type
+            ))?;
+        }
+    }
+
+    if write.image_info.sampler.is_some() && write.has_immutable_sampler {
+        Err(format!(
+            "{} slot '{}' (set {} binding {}) uses an immutable sampler, it can't be assigned a sampler",
+            describe_material_pass(material_name),
+            slot_display_name(binding),
+            set_index,
+            binding.resource.binding
+        ))?;
+    }
+
+    if let Some(buffer) = &write.buffer_info.buffer {
+        if !what_to_bind.bind_buffers {
+            Err(format!(
+                "{} slot '{}' (set {} binding {}) is a {:?} and can't be bound to a buffer",
+                describe_material_pass(material_name),
+                slot_display_name(binding),
+                set_index,
+                binding.resource.binding,
+                binding.resource.resource_type
+            ))?;
+        }
+
+        if let DescriptorSetWriteElementBufferData::Data(data) = buffer {
+            match binding.internal_buffer_per_descriptor_size {
+                Some(size) if data.len() > size as usize => Err(format!(
+                    "{} slot '{}' (set {} binding {}) was given {} bytes of data, but the shader declares {} bytes",
+                    describe_material_pass(material_name),
+                    slot_display_name(binding),
+                    set_index,
+                    binding.resource.binding,
+                    data.len(),
+                    size
+                ))?,
+                Some(_) => {}
+                None => Err(format!(
+                    "{} slot '{}' (set {} binding {}) was given buffer data, but it does not have an internal buffer (is it missing @[internal_buffer]?)",
+                    describe_material_pass(material_name),
+                    slot_display_name(binding),
+                    set_index,
+                    binding.resource.binding
+                ))?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resources::descriptor_sets::{
+        create_uninitialized_write_set_for_layout, DescriptorSetElementKey,
+    };
+    use crate::VertexDataLayout;
+    use rafx_api::{RafxPrimitiveTopology, RafxShaderResource, RafxVertexAttributeRate};
+
+    #[derive(Default, Clone, Copy, Debug)]
+    #[repr(C)]
+    struct TestVertex {
+        position: [f32; 3],
+        color: [u8; 4],
+    }
+
+    fn test_layout() -> VertexDataSetLayout {
+        VertexDataLayout::build_vertex_layout(
+            &TestVertex::default(),
+            RafxVertexAttributeRate::Vertex,
+            |builder, vertex| {
+                builder.add_member(&vertex.position, "POSITION", RafxFormat::R32G32B32_SFLOAT);
+                builder.add_member(&vertex.color, "COLOR", RafxFormat::R8G8B8A8_UNORM);
+            },
+        )
+        .into_set(RafxPrimitiveTopology::TriangleList)
+    }
+
+    fn vertex_input(
+        semantic: &str,
+        format: RafxFormat,
+    ) -> MaterialPassVertexInput {
+        MaterialPassVertexInput {
+            semantic: semantic.to_string(),
+            location: 0,
+            gl_attribute_name: semantic.to_lowercase(),
+            format,
+        }
+    }
+
+    #[test]
+    fn test_validate_vertex_data_set_layout() {
+        let layout = test_layout();
+
+        // Normalized data is read as floats
+        let inputs = vec![
+            vertex_input("POSITION", RafxFormat::R32G32B32_SFLOAT),
+            vertex_input("COLOR", RafxFormat::R32G32B32A32_SFLOAT),
+        ];
+        assert!(validate_vertex_data_set_layout(Some("test"), &inputs, &layout).is_ok());
+
+        // Unknown formats are not checked
+        let inputs = vec![vertex_input("COLOR", RafxFormat::UNDEFINED)];
+        assert!(validate_vertex_data_set_layout(Some("test"), &inputs, &layout).is_ok());
+
+        let inputs = vec![vertex_input("NORMAL", RafxFormat::R32G32B32_SFLOAT)];
+        assert!(validate_vertex_data_set_layout(Some("test"), &inputs, &layout).is_err());
+
+        let inputs = vec![vertex_input("COLOR", RafxFormat::R32G32B32A32_UINT)];
+        assert!(validate_vertex_data_set_layout(Some("test"), &inputs, &layout).is_err());
+    }
+
+    fn test_descriptor_set_layout() -> DescriptorSetLayout {
+        DescriptorSetLayout {
+            bindings: vec![
+                DescriptorSetLayoutBinding {
+                    resource: RafxShaderResource {
+                        resource_type: RafxResourceType::UNIFORM_BUFFER,
+                        binding: 0,
+                        name: Some("per_view_data".to_string()),
+                        ..Default::default()
+                    },
+                    immutable_samplers: None,
+                    internal_buffer_per_descriptor_size: Some(16),
+                    bindless: false,
+                },
+                DescriptorSetLayoutBinding {
+                    resource: RafxShaderResource {
+                        resource_type: RafxResourceType::TEXTURE,
+                        binding: 1,
+                        element_count: 2,
+                        name: Some("textures".to_string()),
+                        ..Default::default()
+                    },
+                    immutable_samplers: None,
+                    internal_buffer_per_descriptor_size: None,
+                    bindless: false,
+                },
+            ],
+        }
+    }
+
+    fn set_buffer_data(
+        write_set: &mut DescriptorSetWriteSet,
+        dst_binding: u32,
+        data: Vec<u8>,
+    ) {
+        let key = DescriptorSetElementKey {
+            dst_binding,
+            array_index: 0,
+        };
+        write_set.elements.get_mut(&key).unwrap().buffer_info.buffer =
+            Some(DescriptorSetWriteElementBufferData::Data(data));
+    }
+
+    #[test]
+    fn test_validate_descriptor_set_writes() {
+        let layout = test_descriptor_set_layout();
+        let uninitialized_write_set = create_uninitialized_write_set_for_layout(&layout);
+        assert!(
+            validate_descriptor_set_writes(Some("test"), 0, &layout, &uninitialized_write_set)
+                .is_ok()
+        );
+
+        // Data that fits in the internal buffer
+        let mut write_set = uninitialized_write_set.clone();
+        set_buffer_data(&mut write_set, 0, vec![0; 16]);
+        assert!(validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set).is_ok());
+
+        // Data larger than the internal buffer
+        let mut write_set = uninitialized_write_set.clone();
+        set_buffer_data(&mut write_set, 0, vec![0; 32]);
+        let error = validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'test'"));
+        assert!(error.contains("'per_view_data'"));
+
+        // Data for a slot without an internal buffer
+        let mut write_set = uninitialized_write_set.clone();
+        set_buffer_data(&mut write_set, 1, vec![0; 16]);
+        assert!(validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set).is_err());
+
+        // A binding the layout does not declare
+        let mut write_set = uninitialized_write_set.clone();
+        let write = write_set.elements.values().next().unwrap().clone();
+        write_set.elements.insert(
+            DescriptorSetElementKey {
+                dst_binding: 2,
+                array_index: 0,
+            },
+            write,
+        );
+        assert!(validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set).is_err());
+
+        // An array index past the end of the array
+        let mut write_set = uninitialized_write_set.clone();
+        let key = DescriptorSetElementKey {
+            dst_binding: 1,
+            array_index: 0,
+        };
+        let write = write_set.elements[&key].clone();
+        write_set.elements.insert(
+            DescriptorSetElementKey {
+                dst_binding: 1,
+                array_index: 2,
+            },
+            write,
+        );
+        assert!(validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set).is_err());
+
+        // A write for a different resource type than the binding
+        let mut write_set = uninitialized_write_set.clone();
+        write_set.elements.get_mut(&key).unwrap().descriptor_type = RafxResourceType::BUFFER;
+        assert!(validate_descriptor_set_writes(Some("test"), 0, &layout, &write_set).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 mod resource_arc;
 pub use resource_arc::ResourceArc;
 pub(crate) use resource_arc::ResourceId;
+pub use resource_arc::WeakResourceArc;
 
 mod resource_lookup;
 pub use resource_lookup::BufferKey;
@@ -28,6 +29,7 @@ pub use dyn_resources::DynResourceAllocatorSet;
 pub use dyn_resources::DynResourceAllocatorSetProvider;
 
 pub mod descriptor_sets;
+pub use descriptor_sets::BindlessResourceTable;
 pub use descriptor_sets::DescriptorSetAllocator;
 pub use descriptor_sets::DescriptorSetAllocatorMetrics;
 pub use descriptor_sets::DescriptorSetAllocatorProvider;
@@ -54,6 +56,10 @@ mod material;
 pub use material::MaterialPass;
 pub use material::MaterialShaderStage;
 
+mod material_validation;
+pub use material_validation::validate_descriptor_set_writes;
+pub use material_validation::validate_vertex_data_set_layout;
+
 mod pipeline_cache;
 pub use pipeline_cache::GraphicsPipelineCache;
 pub use pipeline_cache::GraphicsPipelineRenderTargetMeta;
@@ -470,30 +470,33 @@ impl GraphicsPipelineCache {
                 })
             }
 
+            let material_pass_resource = material_pass.get_raw();
+            if let Err(e) = super::validate_vertex_data_set_layout(
+                material_pass_resource.debug_name.as_deref(),
+                &material_pass_resource.vertex_inputs,
+                vertex_data_set_layout,
+            ) {
+                log::error!("Vertex data does not support this material. {}", e);
+                log::info!(
+                    "  required inputs:\n{:#?}",
+                    material_pass_resource.vertex_inputs
+                );
+                log::info!(
+                    "  available inputs:\n{:#?}",
+                    vertex_data_set_layout.members()
+                );
+                return Some(Err(e));
+            }
+
             //let mut attribute_descriptions = Vec::default();
             let mut vertex_layout_attributes =
-                Vec::with_capacity(material_pass.get_raw().vertex_inputs.len());
+                Vec::with_capacity(material_pass_resource.vertex_inputs.len());
 
-            for vertex_input in &*material_pass.get_raw().vertex_inputs {
+            for vertex_input in &*material_pass_resource.vertex_inputs {
+                // Validation above ensures every input is present
                 let member = vertex_data_set_layout
                     .member(&vertex_input.semantic)
-                    .ok_or_else(|| {
-                        let error_message = format!(
-                            "Vertex data does not support this material. Missing data {}",
-                            vertex_input.semantic
-                        );
-                        log::error!("{}", error_message);
-                        log::info!(
-                            "  required inputs:\n{:#?}",
-                            material_pass.get_raw().vertex_inputs
-                        );
-                        log::info!(
-                            "  available inputs:\n{:#?}",
-                            vertex_data_set_layout.members()
-                        );
-                        error_message
-                    })
-                    .ok()?;
+                    .unwrap();
 
                 vertex_layout_attributes.push(RafxVertexLayoutAttribute {
                     location: vertex_input.location,
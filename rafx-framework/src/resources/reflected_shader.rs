@@ -6,8 +6,8 @@ use crate::{
 };
 use fnv::{FnvHashMap, FnvHashSet};
 use rafx_api::{
-    RafxImmutableSamplerKey, RafxResult, RafxSamplerDef, RafxShaderResource, RafxShaderStageFlags,
-    RafxShaderStageReflection,
+    RafxBindlessDescriptorKey, RafxFormat, RafxImmutableSamplerKey, RafxResourceType, RafxResult,
+    RafxSamplerDef, RafxShaderResource, RafxShaderStageFlags, RafxShaderStageReflection,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -40,6 +40,7 @@ impl Into<DescriptorSetLayoutBinding> for ReflectedDescriptorSetLayoutBinding {
             resource: self.resource.clone(),
             immutable_samplers: self.immutable_samplers.clone(),
             internal_buffer_per_descriptor_size: self.internal_buffer_per_descriptor_size,
+            bindless: false,
         }
     }
 }
@@ -55,6 +56,10 @@ pub struct ReflectedVertexInput {
     pub name: String,
     pub semantic: String,
     pub location: u32,
+    // The format that exactly matches the input's type (i.e. R32G32B32_SFLOAT for a vec3). Vertex
+    // data may use a different format as long as the shader reads the same kind of number from it.
+    // UNDEFINED if the type is not known.
+    pub format: RafxFormat,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -98,6 +103,7 @@ impl ReflectedShaderMetadata {
                         semantic: x.semantic.clone(),
                         location: x.location,
                         gl_attribute_name: x.name.clone(),
+                        format: x.format,
                     })
                     .collect();
 
@@ -231,6 +237,55 @@ impl ReflectedShaderMetadata {
             slot_name_lookup,
         })
     }
+
+    // Marks every binding assigned to the given slot names as bindless. The caller is responsible
+    // for checking that the device supports bindless descriptors.
+    pub fn mark_bindless_slots(
+        &mut self,
+        slot_names: &[String],
+    ) -> RafxResult<()> {
+        for slot_name in slot_names {
+            let locations = self.slot_name_lookup.get(slot_name).ok_or_else(|| {
+                format!(
+                    "Load Material Failed - Bindless slot '{}' does not exist in the pass",
+                    slot_name
+                )
+            })?;
+
+            for location in locations {
+                let binding = self.descriptor_set_layout_defs[location.layout_index as usize]
+                    .bindings
+                    .iter_mut()
+                    .find(|x| x.resource.binding == location.binding_index)
+                    .unwrap();
+
+                let resource_type = binding.resource.resource_type;
+                if resource_type != RafxResourceType::TEXTURE
+                    && resource_type != RafxResourceType::TEXTURE_READ_WRITE
+                    && resource_type != RafxResourceType::BUFFER
+                    && resource_type != RafxResourceType::BUFFER_READ_WRITE
+                {
+                    let error = format!(
+                        "Load Material Failed - Bindless slot '{}' has type {:?}, only textures and buffers can be bindless",
+                        slot_name,
+                        resource_type
+                    );
+                    log::error!("{}", error);
+                    return Err(error)?;
+                }
+
+                log::trace!(
+                    "  Mark slot '{}' set={} binding={} as bindless",
+                    slot_name,
+                    location.layout_index,
+                    location.binding_index
+                );
+                binding.bindless = true;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub struct ReflectedShader {
@@ -287,10 +342,28 @@ impl ReflectedShader {
         Ok((immutable_rafx_sampler_keys, immutable_rafx_sampler_lists))
     }
 
+    pub fn create_bindless_descriptor_keys(
+        descriptor_set_layouts: &[DescriptorSetLayout]
+    ) -> Vec<RafxBindlessDescriptorKey> {
+        let mut bindless_descriptor_keys = Vec::default();
+        for (set_index, descriptor_set_layout_def) in descriptor_set_layouts.iter().enumerate() {
+            for binding in &descriptor_set_layout_def.bindings {
+                if binding.bindless {
+                    bindless_descriptor_keys.push(RafxBindlessDescriptorKey::new(
+                        set_index as u32,
+                        binding.resource.binding,
+                    ));
+                }
+            }
+        }
+        bindless_descriptor_keys
+    }
+
     pub fn load_material_pass(
         &self,
         resources: &ResourceLookupSet,
         fixed_function_state: Arc<FixedFunctionState>,
+        debug_name: Option<&str>,
     ) -> RafxResult<ResourceArc<MaterialPassResource>> {
         let vertex_inputs = self
             .metadata
@@ -308,10 +381,15 @@ impl ReflectedShader {
                 &self.metadata.descriptor_set_layout_defs,
             )?;
 
+        let bindless_descriptor_keys = ReflectedShader::create_bindless_descriptor_keys(
+            &self.metadata.descriptor_set_layout_defs,
+        );
+
         let root_signature = resources.get_or_create_root_signature(
             &[self.shader.clone()],
             &immutable_rafx_sampler_keys,
             &immutable_rafx_sampler_lists,
+            &bindless_descriptor_keys,
         )?;
 
         //
@@ -327,6 +405,7 @@ impl ReflectedShader {
                 &root_signature,
                 set_index as u32,
                 &descriptor_set_layout_def,
+                debug_name,
             )?;
             descriptor_set_layouts.push(descriptor_set_layout);
         }
@@ -340,6 +419,7 @@ impl ReflectedShader {
             descriptor_set_layouts,
             fixed_function_state,
             vertex_inputs.clone(),
+            debug_name,
         )
     }
 
@@ -360,10 +440,15 @@ impl ReflectedShader {
                 &self.metadata.descriptor_set_layout_defs,
             )?;
 
+        let bindless_descriptor_keys = ReflectedShader::create_bindless_descriptor_keys(
+            &self.metadata.descriptor_set_layout_defs,
+        );
+
         let root_signature = resources.get_or_create_root_signature(
             &[self.shader.clone()],
             &immutable_rafx_sampler_keys,
             &immutable_rafx_sampler_lists,
+            &bindless_descriptor_keys,
         )?;
 
         //
@@ -411,6 +496,7 @@ impl ReflectedShader {
                 &root_signature,
                 set_index as u32,
                 &descriptor_set_layout_def,
+                None,
             )?;
             descriptor_set_layouts.push(descriptor_set_layout);
         }
@@ -346,6 +346,7 @@ impl RootSignatureHash {
         shader_hashes: &[ShaderHash],
         immutable_sampler_keys: &[RafxImmutableSamplerKey],
         immutable_sampler_hashes: &[Vec<SamplerHash>],
+        bindless_descriptors: &[RafxBindlessDescriptorKey],
     ) -> Self {
         let mut hasher = FnvHasher::default();
         RafxRootSignatureDef::hash_definition(
@@ -353,6 +354,7 @@ impl RootSignatureHash {
             shader_hashes,
             immutable_sampler_keys,
             immutable_sampler_hashes,
+            bindless_descriptors,
         );
         let hash = hasher.finish();
         RootSignatureHash(hash)
@@ -463,6 +465,8 @@ pub struct MaterialPassVertexInput {
     pub semantic: String,
     pub location: u32,
     pub gl_attribute_name: String,
+    // UNDEFINED if the shader's type for this input is not known
+    pub format: RafxFormat,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -555,6 +559,10 @@ pub struct DescriptorSetLayoutResource {
 
     pub descriptor_set_layout_def: Arc<DescriptorSetLayout>,
     pub key: DescriptorSetLayoutKey,
+
+    // Used in validation errors. This is not part of the key, so if material passes with identical
+    // layouts are loaded with different names, the name of the first one is kept
+    pub debug_name: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -566,6 +574,10 @@ pub struct MaterialPassResource {
 
     pub fixed_function_state: Arc<FixedFunctionState>,
     pub vertex_inputs: Arc<Vec<MaterialPassVertexInput>>,
+
+    // Used in validation errors. This is not part of the key, so if identical passes are loaded
+    // with different names, the name of the first one is kept
+    pub debug_name: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -832,6 +844,7 @@ impl ResourceLookupSet {
         shader_resources: &[ResourceArc<ShaderResource>],
         immutable_sampler_keys: &[RafxImmutableSamplerKey],
         immutable_sampler_resources: &[Vec<ResourceArc<SamplerResource>>],
+        bindless_descriptors: &[RafxBindlessDescriptorKey],
     ) -> RafxResult<ResourceArc<RootSignatureResource>> {
         let shader_hashes: Vec<_> = shader_resources
             .iter()
@@ -847,7 +860,12 @@ impl ResourceLookupSet {
             sampler_hashes.push(hashes);
         }
 
-        let hash = RootSignatureHash::new(&shader_hashes, immutable_sampler_keys, &sampler_hashes);
+        let hash = RootSignatureHash::new(
+            &shader_hashes,
+            immutable_sampler_keys,
+            &sampler_hashes,
+            bindless_descriptors,
+        );
         let key = RootSignatureKey { hash };
 
         self.inner.root_signatures.get_or_create(&key, || {
@@ -879,6 +897,7 @@ impl ResourceLookupSet {
                     .create_root_signature(&RafxRootSignatureDef {
                         shaders: &shaders,
                         immutable_samplers: &immutable_samplers,
+                        bindless_descriptors,
                     })?;
 
             let shaders = shader_resources.iter().cloned().collect();
@@ -907,6 +926,7 @@ impl ResourceLookupSet {
         root_signature: &ResourceArc<RootSignatureResource>,
         set_index: u32,
         descriptor_set_layout_def: &DescriptorSetLayout,
+        debug_name: Option<&str>,
     ) -> RafxResult<ResourceArc<DescriptorSetLayoutResource>> {
         let hash = DescriptorSetLayoutHash::new(
             root_signature.get_raw().key.hash,
@@ -930,6 +950,7 @@ impl ResourceLookupSet {
                 set_index,
                 descriptor_set_layout_def: Arc::new(descriptor_set_layout_def.clone()),
                 key: key.clone(),
+                debug_name: debug_name.map(|x| x.to_string()),
             };
 
             log::trace!("Created descriptor set layout {:?}", resource);
@@ -944,6 +965,7 @@ impl ResourceLookupSet {
         descriptor_sets: Vec<ResourceArc<DescriptorSetLayoutResource>>,
         fixed_function_state: Arc<FixedFunctionState>,
         vertex_inputs: Arc<Vec<MaterialPassVertexInput>>,
+        debug_name: Option<&str>,
     ) -> RafxResult<ResourceArc<MaterialPassResource>> {
         let descriptor_set_hashes: Vec<_> = descriptor_sets
             .iter()
@@ -969,6 +991,7 @@ impl ResourceLookupSet {
                     shader,
                     fixed_function_state,
                     vertex_inputs,
+                    debug_name: debug_name.map(|x| x.to_string()),
                 };
                 Ok(resource)
             })
@@ -996,6 +1019,19 @@ impl ResourceLookupSet {
                 log::trace!("Creating graphics pipeline\n{:#?}", pipeline_key);
 
                 let fixed_function_state = &material_pass.get_raw().fixed_function_state;
+
+                // A material may provide per-target blend states for color attachments that only
+                // some render targets have. Drop the states for attachments that aren't present.
+                let color_formats = render_target_meta.color_formats();
+                let mut blend_state = fixed_function_state.blend_state.clone();
+                if blend_state.independent_blend
+                    && blend_state.render_target_blend_states.len() > color_formats.len()
+                {
+                    blend_state
+                        .render_target_blend_states
+                        .truncate(color_formats.len());
+                }
+
                 let pipeline = self.inner.device_context.create_graphics_pipeline(
                     &RafxGraphicsPipelineDef {
                         root_signature: &material_pass
@@ -1006,14 +1042,14 @@ impl ResourceLookupSet {
 
                         shader: &material_pass.get_raw().shader.get_raw().shader,
 
-                        blend_state: &fixed_function_state.blend_state,
+                        blend_state: &blend_state,
                         depth_state: &fixed_function_state.depth_state,
                         rasterizer_state: &fixed_function_state.rasterizer_state,
 
                         primitive_topology,
                         vertex_layout: &vertex_layout,
 
-                        color_formats: &render_target_meta.color_formats(),
+                        color_formats,
                         depth_stencil_format: render_target_meta.depth_stencil_format(),
                         sample_count: render_target_meta.sample_count(),
                     },
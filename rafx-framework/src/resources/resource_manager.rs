@@ -15,7 +15,7 @@ use crate::resources::dyn_resources::{
     DynResourceAllocatorSetManager, DynResourceAllocatorSetProvider,
 };
 use crate::resources::resource_lookup::ResourceLookupSet;
-use rafx_api::{RafxDeviceContext, RafxResult};
+use rafx_api::{RafxDeviceContext, RafxMemoryStats, RafxResult};
 use std::sync::Arc;
 
 //TODO: Support descriptors that can be different per-view
@@ -27,6 +27,7 @@ pub struct ResourceManagerMetrics {
     pub dyn_resource_metrics: dyn_resources::ResourceMetrics,
     pub resource_metrics: resource_lookup::ResourceMetrics,
     pub graphics_pipeline_cache_metrics: pipeline_cache::GraphicsPipelineCacheMetrics,
+    pub memory_stats: RafxMemoryStats,
 }
 
 struct ResourceContextInner {
@@ -175,11 +176,13 @@ impl ResourceManager {
         let dyn_resource_metrics = self.dyn_resource_allocators.metrics();
         let resource_metrics = self.resources.metrics();
         let graphics_pipeline_cache_metrics = self.graphics_pipeline_cache.metrics();
+        let memory_stats = self.device_context().memory_stats();
 
         ResourceManagerMetrics {
             dyn_resource_metrics,
             resource_metrics,
             graphics_pipeline_cache_metrics,
+            memory_stats,
         }
     }
 
@@ -1,4 +1,4 @@
-use rafx_api::RafxFormat;
+use rafx_api::{RafxFormat, RafxTextureDimensions};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum GpuImageDataColorSpace {
@@ -29,10 +29,14 @@ impl GpuImageDataColorSpace {
     }
 }
 
+// Layers are array layers (6 per cube for cubemaps). For 3D images, there is a single layer and each
+// mip level holds all of its depth slices, tightly packed, in order.
 #[derive(Debug)]
 pub struct GpuImageData {
     pub width: u32,
     pub height: u32,
+    pub depth: u32,
+    pub dimensions: RafxTextureDimensions,
     pub format: RafxFormat,
     pub layers: Vec<GpuImageDataLayer>,
 }
@@ -52,19 +56,55 @@ impl GpuImageData {
                         layers[0].mip_levels[j].height,
                         layers[i].mip_levels[j].height
                     );
-                    debug_assert_eq!(layers[0].mip_levels[j].width, layers[i].mip_levels[j].width);
+                    debug_assert_eq!(layers[0].mip_levels[j].depth, layers[i].mip_levels[j].depth);
                 }
             }
         }
 
+        let depth = layers[0].mip_levels[0].depth;
         GpuImageData {
             width: layers[0].mip_levels[0].width,
             height: layers[0].mip_levels[0].height,
+            depth,
+            dimensions: if depth > 1 {
+                RafxTextureDimensions::Dim3D
+            } else {
+                RafxTextureDimensions::Dim2D
+            },
             format,
             layers,
         }
     }
 
+    /// Creates a 3D image with a single mip level from individual depth slices, each of which must
+    /// be the same size
+    pub fn new_3d_image_from_slices(
+        width: u32,
+        height: u32,
+        format: RafxFormat,
+        slices: Vec<Vec<u8>>,
+    ) -> Self {
+        let depth = slices.len() as u32;
+        #[cfg(debug_assertions)]
+        for slice in &slices {
+            debug_assert_eq!(slice.len(), slices[0].len());
+        }
+
+        GpuImageData {
+            width,
+            height,
+            depth,
+            dimensions: RafxTextureDimensions::Dim3D,
+            format,
+            layers: vec![GpuImageDataLayer::new(vec![GpuImageDataMipLevel {
+                width,
+                height,
+                depth,
+                data: slices.concat(),
+            }])],
+        }
+    }
+
     pub fn new_simple_image_from_bytes(
         width: u32,
         height: u32,
@@ -74,6 +114,8 @@ impl GpuImageData {
         GpuImageData {
             width,
             height,
+            depth: 1,
+            dimensions: RafxTextureDimensions::Dim2D,
             format,
             layers: vec![GpuImageDataLayer::new_single_level(width, height, data)],
         }
@@ -96,18 +138,65 @@ impl GpuImageData {
         bytes_required
     }
 
+    /// Copies the mip chain starting at first_mip into a new image, i.e. the same image with the
+    /// largest first_mip levels dropped. Used to upload a partial mip chain of a larger image.
+    pub fn mip_chain_from(
+        &self,
+        first_mip: usize,
+    ) -> GpuImageData {
+        let layers: Vec<_> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                GpuImageDataLayer::new(
+                    layer.mip_levels[first_mip..]
+                        .iter()
+                        .map(|mip_level| GpuImageDataMipLevel {
+                            width: mip_level.width,
+                            height: mip_level.height,
+                            depth: mip_level.depth,
+                            data: mip_level.data.clone(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let first_level = &layers[0].mip_levels[0];
+        GpuImageData {
+            width: first_level.width,
+            height: first_level.height,
+            depth: first_level.depth,
+            dimensions: self.dimensions,
+            format: self.format,
+            layers,
+        }
+    }
+
+    pub fn mip_level_count(&self) -> usize {
+        self.layers[0].mip_levels.len()
+    }
+
     #[cfg(debug_assertions)]
     pub fn verify_state(&self) {
         let first_layer = &self.layers[0];
         let first_level = &first_layer.mip_levels[0];
         assert_eq!(first_level.width, self.width);
         assert_eq!(first_level.height, self.height);
+        assert_eq!(first_level.depth, self.depth);
+
+        if self.dimensions == RafxTextureDimensions::Dim3D {
+            assert_eq!(self.layers.len(), 1);
+        } else {
+            assert_eq!(self.depth, 1);
+        }
 
         for layer in &self.layers {
             assert_eq!(first_layer.mip_levels.len(), layer.mip_levels.len());
             for (i, level) in layer.mip_levels.iter().enumerate() {
                 assert_eq!(first_layer.mip_levels[i].width, level.width);
                 assert_eq!(first_layer.mip_levels[i].height, level.height);
+                assert_eq!(first_layer.mip_levels[i].depth, level.depth);
             }
         }
     }
@@ -132,6 +221,7 @@ impl GpuImageDataLayer {
             mip_levels: vec![GpuImageDataMipLevel {
                 width,
                 height,
+                depth: 1,
                 data,
             }],
         }
@@ -141,9 +231,22 @@ impl GpuImageDataLayer {
 pub struct GpuImageDataMipLevel {
     pub width: u32,
     pub height: u32,
+    // Number of depth slices in data, 1 unless this is a 3D image
+    pub depth: u32,
     pub data: Vec<u8>,
 }
 
+impl GpuImageDataMipLevel {
+    pub fn slice(
+        &self,
+        slice_index: u32,
+    ) -> &[u8] {
+        let slice_size = self.data.len() / self.depth as usize;
+        let offset = slice_index as usize * slice_size;
+        &self.data[offset..offset + slice_size]
+    }
+}
+
 impl std::fmt::Debug for GpuImageDataMipLevel {
     fn fmt(
         &self,
@@ -152,6 +255,7 @@ impl std::fmt::Debug for GpuImageDataMipLevel {
         f.debug_struct("GpuImageDataMipLevel")
             .field("width", &self.width)
             .field("height", &self.height)
+            .field("depth", &self.depth)
             .field("data_length", &self.data.len())
             .finish()
     }
@@ -53,6 +53,13 @@ pub fn enqueue_load_image(
         Err(RafxUploadError::BufferFull)?;
     }
 
+    // Mipmap generation blits one slice at a time, so it can't produce the mips of a 3D image
+    if params.generate_mips && image_data.dimensions == RafxTextureDimensions::Dim3D {
+        Err(RafxUploadError::Other(
+            "Mipmaps can't be generated at runtime for 3D images, they must be provided".into(),
+        ))?;
+    }
+
     //
     // Determine mip count
     //
@@ -97,14 +104,14 @@ pub fn enqueue_load_image(
         extents: RafxExtents3D {
             width: image_data.width,
             height: image_data.height,
-            depth: 1,
+            depth: image_data.depth,
         },
         array_length: layer_count,
         mip_count,
         sample_count: RafxSampleCount::SampleCount1,
         format: image_data.format,
         resource_type: params.resource_type,
-        dimensions: RafxTextureDimensions::Dim2D,
+        dimensions: image_data.dimensions,
     })?;
 
     //
@@ -136,6 +143,7 @@ pub fn enqueue_load_image(
             dst_layer_index as usize
         };
 
+        // Each copy writes every depth slice of the mip level
         for level_index in 0..image_data.layers[src_layer_index].mip_levels.len() {
             upload
                 .transfer_command_buffer()
@@ -153,9 +161,10 @@ pub fn enqueue_load_image(
     }
 
     log::debug!(
-        "upload image {}x{} format {:?} layers: {} levels: {} generate mips: {} resource type: {:?}",
+        "upload image {}x{}x{} format {:?} layers: {} levels: {} generate mips: {} resource type: {:?}",
         image_data.width,
         image_data.height,
+        image_data.depth,
         image_data.format,
         layer_count,
         mip_count,
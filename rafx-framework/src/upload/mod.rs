@@ -9,6 +9,8 @@ pub use gpu_image_data::GpuImageDataMipLevel;
 
 mod upload_queue;
 pub use upload_queue::UploadOp;
+pub use upload_queue::UploadPriority;
 pub use upload_queue::UploadQueue;
 pub use upload_queue::UploadQueueConfig;
 pub use upload_queue::UploadQueueContext;
+pub use upload_queue::UploadQueueMetrics;
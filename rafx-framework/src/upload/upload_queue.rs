@@ -7,6 +7,7 @@ use rafx_api::{
     extra::upload::*, RafxBuffer, RafxDeviceContext, RafxError, RafxQueue, RafxResourceType,
     RafxResult, RafxTexture,
 };
+use std::collections::VecDeque;
 
 pub trait UploadOp<ResourceT>: Send + Sync {
     fn complete(
@@ -18,6 +19,43 @@ pub trait UploadOp<ResourceT>: Send + Sync {
         self: Box<Self>,
         error: RafxError,
     );
+
+    // Returns true if whoever requested the upload no longer needs it. Uploads that are cancelled
+    // before they start are dropped without calling complete() or error().
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+// All pending uploads of a higher priority are started before any uploads of a lower priority.
+// Uploads of the same priority start in the order they were requested.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UploadPriority {
+    // Resources that are needed as soon as possible, i.e. to render the current frame
+    High,
+    Normal,
+    // Resources that are nice to have, i.e. higher detail mips of streamed textures
+    Low,
+}
+
+impl UploadPriority {
+    pub const COUNT: usize = 3;
+
+    pub const ALL: [UploadPriority; UploadPriority::COUNT] = [
+        UploadPriority::High,
+        UploadPriority::Normal,
+        UploadPriority::Low,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Default for UploadPriority {
+    fn default() -> Self {
+        UploadPriority::Normal
+    }
 }
 
 type ImageUploadOp = Box<dyn UploadOp<RafxTexture>>;
@@ -55,7 +93,22 @@ enum PendingUpload {
     ExistingBuffer(PendingExistingBufferUpload),
 }
 
+// A pending upload as sent by UploadQueueContext
+struct QueuedUpload {
+    upload: PendingUpload,
+    priority: UploadPriority,
+    queued_time: rafx_base::Instant,
+}
+
 impl PendingUpload {
+    fn is_cancelled(&self) -> bool {
+        match self {
+            PendingUpload::Image(image) => image.upload_op.is_cancelled(),
+            PendingUpload::Buffer(buffer) => buffer.upload_op.is_cancelled(),
+            PendingUpload::ExistingBuffer(buffer) => buffer.upload_op.is_cancelled(),
+        }
+    }
+
     // Ok(None) = upload enqueue
     // Ok(Some) = upload not enqueued because there was not enough room
     // Err = Vulkan error
@@ -290,6 +343,8 @@ struct InProgressUploadBatchDebugInfo {
     start_time: rafx_base::Instant,
     size: u64,
     resource_count: usize,
+    // When each upload in the batch was requested, used to measure latency
+    queued_times: Vec<rafx_base::Instant>,
 }
 
 // A single upload which may contain multiple images
@@ -390,6 +445,54 @@ pub struct UploadQueueConfig {
     pub max_bytes_per_upload: usize,
     pub max_concurrent_uploads: usize,
     pub max_new_uploads_in_single_frame: usize,
+    // Limits the bytes of new uploads started in a single frame to avoid hitches when many
+    // resources load at once. At least one upload is always started (if there is room for it) so
+    // that uploads larger than this still make progress.
+    pub max_bytes_per_frame: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UploadQueueMetrics {
+    // Uploads that have been requested but not started, indexed by UploadPriority::index()
+    pub pending_upload_counts: [usize; UploadPriority::COUNT],
+    pub pending_bytes: u64,
+    // How long the oldest pending upload has been waiting
+    pub oldest_pending_upload_age_ms: f32,
+    pub upload_batches_in_progress: usize,
+    pub bytes_started_last_update: u64,
+    pub started_upload_count: u64,
+    pub completed_upload_count: u64,
+    pub cancelled_upload_count: u64,
+    // Moving averages of the time from an upload being requested to it being started, and to it
+    // being complete
+    pub average_wait_ms: f32,
+    pub average_latency_ms: f32,
+}
+
+// Weight of the newest sample in the moving averages of UploadQueueMetrics
+const UPLOAD_LATENCY_SMOOTHING: f32 = 0.1;
+
+fn update_moving_average(
+    average: &mut f32,
+    sample: f32,
+    is_first_sample: bool,
+) {
+    if is_first_sample {
+        *average = sample;
+    } else {
+        *average += (sample - *average) * UPLOAD_LATENCY_SMOOTHING;
+    }
+}
+
+// Running totals reported by UploadQueueMetrics
+#[derive(Default)]
+struct UploadQueueCounters {
+    bytes_started_this_frame: u64,
+    started_upload_count: u64,
+    completed_upload_count: u64,
+    cancelled_upload_count: u64,
+    average_wait_ms: f32,
+    average_latency_ms: f32,
 }
 
 //
@@ -399,10 +502,11 @@ pub struct UploadQueue {
     device_context: RafxDeviceContext,
     config: UploadQueueConfig,
 
-    pending_upload_tx: Sender<PendingUpload>,
-    pending_upload_rx: Receiver<PendingUpload>,
-    // If we fail to upload due to size limitation, keep the failed upload here to retry later
-    next_upload: Option<PendingUpload>,
+    pending_upload_tx: Sender<QueuedUpload>,
+    pending_upload_rx: Receiver<QueuedUpload>,
+    // Uploads received from pending_upload_rx that have not been started, indexed by
+    // UploadPriority::index(). If an upload doesn't fit, it stays at the front of its queue.
+    pending_uploads: [VecDeque<QueuedUpload>; UploadPriority::COUNT],
 
     // These are uploads that are currently in progress
     uploads_in_progress: Vec<InProgressUploadBatch>,
@@ -413,6 +517,8 @@ pub struct UploadQueue {
     transfer_queue: RafxQueue,
 
     next_upload_id: usize,
+
+    counters: UploadQueueCounters,
 }
 
 impl UploadQueue {
@@ -435,11 +541,12 @@ impl UploadQueue {
             upload_buffer_pool,
             pending_upload_tx,
             pending_upload_rx,
-            next_upload: None,
+            pending_uploads: Default::default(),
             uploads_in_progress: Default::default(),
             next_upload_id: 1,
             graphics_queue,
             transfer_queue,
+            counters: Default::default(),
         })
     }
 
@@ -450,55 +557,90 @@ impl UploadQueue {
     }
 
     pub fn update(&mut self) -> RafxResult<()> {
+        self.counters.bytes_started_this_frame = 0;
+        self.receive_pending_uploads();
         self.start_new_upload_batches()?;
         self.update_existing_upload_batches();
         Ok(())
     }
 
+    pub fn metrics(&self) -> UploadQueueMetrics {
+        let mut metrics = UploadQueueMetrics {
+            upload_batches_in_progress: self.uploads_in_progress.len(),
+            bytes_started_last_update: self.counters.bytes_started_this_frame,
+            started_upload_count: self.counters.started_upload_count,
+            completed_upload_count: self.counters.completed_upload_count,
+            cancelled_upload_count: self.counters.cancelled_upload_count,
+            average_wait_ms: self.counters.average_wait_ms,
+            average_latency_ms: self.counters.average_latency_ms,
+            ..Default::default()
+        };
+
+        for (priority_index, pending_uploads) in self.pending_uploads.iter().enumerate() {
+            metrics.pending_upload_counts[priority_index] = pending_uploads.len();
+            for queued_upload in pending_uploads {
+                metrics.pending_bytes += queued_upload.upload.required_bytes() as u64;
+                let age_ms = queued_upload.queued_time.elapsed().as_secs_f32() * 1000.0;
+                metrics.oldest_pending_upload_age_ms =
+                    metrics.oldest_pending_upload_age_ms.max(age_ms);
+            }
+        }
+
+        metrics
+    }
+
+    fn receive_pending_uploads(&mut self) {
+        for queued_upload in self.pending_upload_rx.try_iter() {
+            self.pending_uploads[queued_upload.priority.index()].push_back(queued_upload);
+        }
+    }
+
+    fn has_pending_uploads(&self) -> bool {
+        self.pending_uploads.iter().any(|x| !x.is_empty())
+    }
+
     fn gather_pending_uploads_for_single_upload_batch(
         &mut self,
         upload: &mut RafxTransferUpload,
-    ) -> RafxResult<Vec<InFlightUpload>> {
+    ) -> RafxResult<(Vec<InFlightUpload>, Vec<rafx_base::Instant>)> {
+        let device_context = &self.device_context;
         let mut in_flight_uploads = vec![];
 
-        // If we had a pending image upload from before, try to upload it now
-        self.next_upload = if let Some(next_upload) = self.next_upload.take() {
-            next_upload.try_enqueue_upload(&self.device_context, upload, &mut in_flight_uploads)?
-        } else {
-            None
-        };
-
-        // The first image we tried to upload failed. Log an error since we aren't making forward progress
-        if let Some(next_upload) = &self.next_upload {
-            log::error!(
-                "Resource of {} bytes has repeatedly exceeded the available room in the upload buffer. ({} of {} bytes free)",
-                next_upload.required_bytes(),
-                upload.bytes_free(),
-                upload.buffer_size()
-            );
-            return Ok(vec![]);
-        }
+        let queued_times = gather_pending_uploads(
+            &mut self.pending_uploads,
+            &mut self.counters,
+            self.config.max_bytes_per_frame as u64,
+            |pending_upload| {
+                let not_enqueued = pending_upload.try_enqueue_upload(
+                    device_context,
+                    upload,
+                    &mut in_flight_uploads,
+                )?;
+
+                if let Some(not_enqueued) = &not_enqueued {
+                    if in_flight_uploads.is_empty() {
+                        // The upload buffer was empty, so we aren't making forward progress
+                        log::error!(
+                            "Resource of {} bytes has repeatedly exceeded the available room in the upload buffer. ({} of {} bytes free)",
+                            not_enqueued.required_bytes(),
+                            upload.bytes_free(),
+                            upload.buffer_size()
+                        );
+                    } else {
+                        log::debug!(
+                            "Resource of {} bytes exceeds the available room in the upload buffer. ({} of {} bytes free)",
+                            not_enqueued.required_bytes(),
+                            upload.bytes_free(),
+                            upload.buffer_size(),
+                        );
+                    }
+                }
 
-        let rx = self.pending_upload_rx.clone();
-        for pending_upload in rx.try_iter() {
-            self.next_upload = pending_upload.try_enqueue_upload(
-                &self.device_context,
-                upload,
-                &mut in_flight_uploads,
-            )?;
-
-            if let Some(next_upload) = &self.next_upload {
-                log::debug!(
-                    "Resource of {} bytes exceeds the available room in the upload buffer. ({} of {} bytes free)",
-                    next_upload.required_bytes(),
-                    upload.bytes_free(),
-                    upload.buffer_size(),
-                );
-                break;
-            }
-        }
+                Ok(not_enqueued)
+            },
+        )?;
 
-        Ok(in_flight_uploads)
+        Ok((in_flight_uploads, queued_times))
     }
 
     fn try_start_single_upload_batch(&mut self) -> RafxResult<bool> {
@@ -510,7 +652,8 @@ impl UploadQueue {
             Some(&mut self.upload_buffer_pool),
         )?;
 
-        let in_flight_uploads = self.gather_pending_uploads_for_single_upload_batch(&mut upload)?;
+        let (in_flight_uploads, queued_times) =
+            self.gather_pending_uploads_for_single_upload_batch(&mut upload)?;
 
         if !in_flight_uploads.is_empty() {
             let upload_id = self.next_upload_id;
@@ -530,6 +673,7 @@ impl UploadQueue {
                 resource_count: in_flight_uploads.len(),
                 size: upload.bytes_written(),
                 start_time: rafx_base::Instant::now(),
+                queued_times,
             };
 
             self.uploads_in_progress.push(InProgressUploadBatch::new(
@@ -546,7 +690,7 @@ impl UploadQueue {
 
     fn start_new_upload_batches(&mut self) -> RafxResult<()> {
         for _ in 0..self.config.max_new_uploads_in_single_frame {
-            if self.pending_upload_rx.is_empty() && self.next_upload.is_none() {
+            if !self.has_pending_uploads() {
                 return Ok(());
             }
 
@@ -585,6 +729,16 @@ impl UploadQueue {
                         debug_info.upload_id
                     );
 
+                    for queued_time in &debug_info.queued_times {
+                        let latency_ms = queued_time.elapsed().as_secs_f32() * 1000.0;
+                        update_moving_average(
+                            &mut self.counters.average_latency_ms,
+                            latency_ms,
+                            self.counters.completed_upload_count == 0,
+                        );
+                        self.counters.completed_upload_count += 1;
+                    }
+
                     self.uploads_in_progress.swap_remove(i);
                 }
                 InProgressUploadBatchPollResult::Error => {
@@ -610,31 +764,104 @@ impl UploadQueue {
     }
 }
 
+// Starts pending uploads, higher priorities first, by passing them to try_enqueue. Stops when
+// try_enqueue hands an upload back because it didn't fit, or when the bytes started this frame would
+// exceed max_bytes_per_frame. Cancelled uploads are dropped. Returns when each started upload was
+// queued.
+fn gather_pending_uploads(
+    pending_uploads: &mut [VecDeque<QueuedUpload>; UploadPriority::COUNT],
+    counters: &mut UploadQueueCounters,
+    max_bytes_per_frame: u64,
+    mut try_enqueue: impl FnMut(PendingUpload) -> RafxResult<Option<PendingUpload>>,
+) -> RafxResult<Vec<rafx_base::Instant>> {
+    let mut queued_times = vec![];
+
+    for &priority in &UploadPriority::ALL {
+        let pending_uploads = &mut pending_uploads[priority.index()];
+        while let Some(queued_upload) = pending_uploads.pop_front() {
+            if queued_upload.upload.is_cancelled() {
+                // Dropping the upload op lets it clean up
+                counters.cancelled_upload_count += 1;
+                continue;
+            }
+
+            let required_bytes = queued_upload.upload.required_bytes() as u64;
+            if counters.bytes_started_this_frame > 0
+                && counters.bytes_started_this_frame + required_bytes > max_bytes_per_frame
+            {
+                log::trace!(
+                    "Upload budget of {} bytes for this frame reached, deferring remaining uploads",
+                    max_bytes_per_frame
+                );
+                pending_uploads.push_front(queued_upload);
+                return Ok(queued_times);
+            }
+
+            let queued_time = queued_upload.queued_time;
+            if let Some(not_enqueued) = try_enqueue(queued_upload.upload)? {
+                pending_uploads.push_front(QueuedUpload {
+                    upload: not_enqueued,
+                    priority,
+                    queued_time,
+                });
+                return Ok(queued_times);
+            }
+
+            let wait_ms = queued_time.elapsed().as_secs_f32() * 1000.0;
+            update_moving_average(
+                &mut counters.average_wait_ms,
+                wait_ms,
+                counters.started_upload_count == 0,
+            );
+            counters.started_upload_count += 1;
+            counters.bytes_started_this_frame += required_bytes;
+            queued_times.push(queued_time);
+        }
+    }
+
+    Ok(queued_times)
+}
+
 #[derive(Clone)]
 pub struct UploadQueueContext {
-    pending_upload_tx: Sender<PendingUpload>,
+    pending_upload_tx: Sender<QueuedUpload>,
 }
 
 impl UploadQueueContext {
+    fn send(
+        &self,
+        upload: PendingUpload,
+        priority: UploadPriority,
+    ) -> Result<(), crossbeam_channel::SendError<QueuedUpload>> {
+        self.pending_upload_tx.send(QueuedUpload {
+            upload,
+            priority,
+            queued_time: rafx_base::Instant::now(),
+        })
+    }
+
     pub fn upload_new_image(
         &self,
         upload_op: ImageUploadOp,
         image_data: GpuImageData,
         resource_type: RafxResourceType,
         generate_mips: bool,
+        priority: UploadPriority,
     ) -> RafxResult<()> {
-        self.pending_upload_tx
-            .send(PendingUpload::Image(PendingImageUpload {
+        self.send(
+            PendingUpload::Image(PendingImageUpload {
                 upload_op,
                 image_data,
                 resource_type,
                 generate_mips,
-            }))
-            .map_err(|_err| {
-                let error = format!("Could not enqueue image upload");
-                log::error!("{}", error);
-                RafxError::StringError(error)
-            })
+            }),
+            priority,
+        )
+        .map_err(|_err| {
+            let error = format!("Could not enqueue image upload");
+            log::error!("{}", error);
+            RafxError::StringError(error)
+        })
     }
 
     pub fn upload_new_buffer(
@@ -642,18 +869,21 @@ impl UploadQueueContext {
         upload_op: BufferUploadOp,
         resource_type: RafxResourceType,
         data: Vec<u8>,
+        priority: UploadPriority,
     ) -> RafxResult<()> {
-        self.pending_upload_tx
-            .send(PendingUpload::Buffer(PendingBufferUpload {
+        self.send(
+            PendingUpload::Buffer(PendingBufferUpload {
                 upload_op,
                 resource_type,
                 data,
-            }))
-            .map_err(|_err| {
-                let error = format!("Could not enqueue buffer upload");
-                log::error!("{}", error);
-                RafxError::StringError(error)
-            })
+            }),
+            priority,
+        )
+        .map_err(|_err| {
+            let error = format!("Could not enqueue buffer upload");
+            log::error!("{}", error);
+            RafxError::StringError(error)
+        })
     }
 
     pub fn upload_to_existing_buffer(
@@ -663,19 +893,178 @@ impl UploadQueueContext {
         data: Vec<u8>,
         dst_buffer: ResourceArc<BufferResource>,
         dst_byte_offset: u64,
+        priority: UploadPriority,
     ) -> RafxResult<()> {
-        self.pending_upload_tx
-            .send(PendingUpload::ExistingBuffer(PendingExistingBufferUpload {
+        self.send(
+            PendingUpload::ExistingBuffer(PendingExistingBufferUpload {
                 upload_op,
                 resource_type,
                 data,
                 dst_buffer,
                 dst_byte_offset,
-            }))
-            .map_err(|_err| {
-                let error = format!("Could not enqueue buffer upload");
-                log::error!("{}", error);
-                RafxError::StringError(error)
-            })
+            }),
+            priority,
+        )
+        .map_err(|_err| {
+            let error = format!("Could not enqueue buffer upload");
+            log::error!("{}", error);
+            RafxError::StringError(error)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestUploadOp {
+        cancelled: bool,
+    }
+
+    impl UploadOp<RafxBuffer> for TestUploadOp {
+        fn complete(
+            self: Box<Self>,
+            _resource: RafxBuffer,
+        ) {
+        }
+
+        fn error(
+            self: Box<Self>,
+            _error: RafxError,
+        ) {
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.cancelled
+        }
+    }
+
+    #[derive(Default)]
+    struct TestQueue {
+        pending_uploads: [VecDeque<QueuedUpload>; UploadPriority::COUNT],
+        counters: UploadQueueCounters,
+    }
+
+    impl TestQueue {
+        // The first byte of the data identifies the upload
+        fn push(
+            &mut self,
+            id: u8,
+            size: usize,
+            priority: UploadPriority,
+            cancelled: bool,
+        ) {
+            self.pending_uploads[priority.index()].push_back(QueuedUpload {
+                upload: PendingUpload::Buffer(PendingBufferUpload {
+                    upload_op: Box::new(TestUploadOp { cancelled }),
+                    resource_type: RafxResourceType::BUFFER,
+                    data: vec![id; size],
+                }),
+                priority,
+                queued_time: rafx_base::Instant::now(),
+            });
+        }
+
+        // Returns the ids of the uploads that were started. Uploads with an id in full are handed
+        // back as if they didn't fit in the upload buffer.
+        fn gather(
+            &mut self,
+            max_bytes_per_frame: u64,
+            full: &[u8],
+        ) -> Vec<u8> {
+            let mut started = vec![];
+            let queued_times = gather_pending_uploads(
+                &mut self.pending_uploads,
+                &mut self.counters,
+                max_bytes_per_frame,
+                |pending_upload| {
+                    let id = match &pending_upload {
+                        PendingUpload::Buffer(buffer) => buffer.data[0],
+                        _ => unreachable!(),
+                    };
+
+                    if full.contains(&id) {
+                        Ok(Some(pending_upload))
+                    } else {
+                        started.push(id);
+                        Ok(None)
+                    }
+                },
+            )
+            .unwrap();
+
+            assert_eq!(queued_times.len(), started.len());
+            started
+        }
+
+        fn pending_count(&self) -> usize {
+            self.pending_uploads.iter().map(|x| x.len()).sum()
+        }
+    }
+
+    #[test]
+    fn test_higher_priority_uploads_start_first() {
+        let mut queue = TestQueue::default();
+        queue.push(1, 10, UploadPriority::Low, false);
+        queue.push(2, 10, UploadPriority::Normal, false);
+        queue.push(3, 10, UploadPriority::High, false);
+        queue.push(4, 10, UploadPriority::Normal, false);
+
+        assert_eq!(queue.gather(u64::MAX, &[]), vec![3, 2, 4, 1]);
+        assert_eq!(queue.counters.started_upload_count, 4);
+        assert_eq!(queue.counters.bytes_started_this_frame, 40);
+        assert_eq!(queue.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_bytes_per_frame_limit() {
+        let mut queue = TestQueue::default();
+        queue.push(1, 60, UploadPriority::High, false);
+        queue.push(2, 60, UploadPriority::Normal, false);
+        queue.push(3, 30, UploadPriority::Low, false);
+
+        // The second upload would exceed the limit, so it stays pending
+        assert_eq!(queue.gather(100, &[]), vec![1]);
+        assert_eq!(queue.counters.bytes_started_this_frame, 60);
+        assert_eq!(queue.gather(100, &[]), Vec::<u8>::new());
+        assert_eq!(queue.pending_count(), 2);
+
+        // Next frame
+        queue.counters.bytes_started_this_frame = 0;
+        assert_eq!(queue.gather(100, &[]), vec![2, 3]);
+        assert_eq!(queue.counters.bytes_started_this_frame, 90);
+
+        // An upload larger than the limit still starts if nothing else has this frame
+        queue.counters.bytes_started_this_frame = 0;
+        queue.push(4, 200, UploadPriority::Normal, false);
+        queue.push(5, 10, UploadPriority::Normal, false);
+        assert_eq!(queue.gather(100, &[]), vec![4]);
+        assert_eq!(queue.pending_count(), 1);
+    }
+
+    #[test]
+    fn test_uploads_that_do_not_fit_stay_at_front() {
+        let mut queue = TestQueue::default();
+        queue.push(1, 10, UploadPriority::High, false);
+        queue.push(2, 10, UploadPriority::High, false);
+        queue.push(3, 10, UploadPriority::Normal, false);
+
+        assert_eq!(queue.gather(u64::MAX, &[2]), vec![1]);
+        assert_eq!(queue.gather(u64::MAX, &[]), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_cancelled_uploads_are_dropped() {
+        let mut queue = TestQueue::default();
+        queue.push(1, 10, UploadPriority::High, true);
+        queue.push(2, 10, UploadPriority::Normal, false);
+        queue.push(3, 1000, UploadPriority::Normal, true);
+        queue.push(4, 10, UploadPriority::Low, false);
+
+        // Cancelled uploads don't count against the limit
+        assert_eq!(queue.gather(50, &[]), vec![2, 4]);
+        assert_eq!(queue.counters.cancelled_upload_count, 2);
+        assert_eq!(queue.counters.started_upload_count, 2);
+        assert_eq!(queue.pending_count(), 0);
     }
 }
@@ -26,6 +26,9 @@ layout(set=0,binding=0) uniform Config
     uint image_width;
     uint image_height;
     float sharpen_amount;
+    // When the output size differs from the image size, the image is upscaled to the output size
+    uint output_width;
+    uint output_height;
 } config;
 
 // @[export]
@@ -77,11 +80,11 @@ void main()
 {
     uvec4 const0;
     uvec4 const1;
-    CasSetup(const0, const1, config.sharpen_amount, config.image_width, config.image_width, config.image_width, config.image_width);
+    CasSetup(const0, const1, config.sharpen_amount, config.image_width, config.image_height, config.output_width, config.output_height);
 
     // Do remapping of local xy in workgroup for a more PS-like swizzle pattern.
     AU2 gxy = ARmp8x8(gl_LocalInvocationID.x)+AU2(gl_WorkGroupID.x<<4u,gl_WorkGroupID.y<<4u);
-    bool sharpenOnly = true;
+    bool sharpenOnly = config.image_width == config.output_width && config.image_height == config.output_height;
 
 #if CAS_SAMPLE_FP16
 
@@ -0,0 +1,15 @@
+
+// Weighted blended order-independent transparency, see "Weighted Blended Order-Independent
+// Transparency" (McGuire and Bavoil, 2013) http://jcgt.org/published/0002/02/09/
+//
+// The accumulation target is additively blended (ONE, ONE) and the revealage target is
+// multiplicatively blended (ZERO, ONE_MINUS_SRC_COLOR), so draw order doesn't matter.
+void oit_write(vec4 color, out vec4 out_accum, out float out_revealage) {
+    // We use reverse Z, so gl_FragCoord.z is 1.0 at the near plane and approaches 0.0 far away.
+    // This is equation 10 from the paper with z flipped accordingly.
+    float z = gl_FragCoord.z;
+    float weight = color.a * clamp(3000.0 * z * z * z, 0.01, 3000.0);
+
+    out_accum = vec4(color.rgb * color.a, color.a) * weight;
+    out_revealage = color.a;
+}
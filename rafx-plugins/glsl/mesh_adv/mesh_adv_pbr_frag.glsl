@@ -88,7 +88,7 @@ vec4 normal_map(
 ) {
     // Sample the normal and unflatten it from the texture (i.e. convert
     // range of [0, 1] to [-1, 1])
-    vec3 normal = texture(sampler2D(all_material_textures[normal_texture], smp), uv, per_view_data.mip_bias).xyz;
+    vec3 normal = texture(sampler2D(all_material_textures[MATERIAL_TEXTURE_INDEX(normal_texture)], smp), uv, per_view_data.mip_bias).xyz;
     normal = normal * 2.0 - 1.0;
     normal.z = 0.0;
     normal.z = sqrt(1.0 - dot(normal, normal));
@@ -1160,6 +1160,28 @@ uint hash_light_list(uint light_cluster_index) {
     return hash;
 }
 
+#ifdef PBR_SURFACE_OUTPUT
+#include "../util/normal_encoding.glsl"
+
+// View-space normal (octahedral encoded), roughness and metalness of the shaded surface. Used by
+// screen-space effects like SSR that run after the forward pass
+layout (location = 1) out vec4 out_surface;
+#endif
+
+// Attenuates the color by the fog between the eye and the surface and adds the light scattered
+// towards the eye along the way
+vec4 apply_volumetric_fog(vec4 color) {
+    if (!volumetric_fog.enabled) {
+        return color;
+    }
+
+    vec2 uv = gl_FragCoord.xy / vec2(per_view_data.viewport_width, per_view_data.viewport_height);
+    float depth_vs = max(-in_position_vs.z, volumetric_fog.near_z);
+    float w = log(depth_vs / volumetric_fog.near_z) / log(volumetric_fog.far_z / volumetric_fog.near_z);
+    vec4 fog = texture(sampler3D(volumetric_fog_texture, smp_volumetric_fog), vec3(uv, w));
+    return vec4(color.rgb * fog.a + fog.rgb, color.a);
+}
+
 vec4 pbr_main() {
     uint material_index = all_draw_data.draw_data[in_instance_index].material_index;
     MaterialDbEntry per_material_data = all_materials.materials[material_index];
@@ -1171,7 +1193,7 @@ vec4 pbr_main() {
 
 #ifdef PBR_TEXTURES
     if (per_material_data.color_texture != -1) {
-        vec4 sampled_color = texture(sampler2D(all_material_textures[per_material_data.color_texture], smp), in_uv, per_view_data.mip_bias);
+        vec4 sampled_color = texture(sampler2D(all_material_textures[MATERIAL_TEXTURE_INDEX(per_material_data.color_texture)], smp), in_uv, per_view_data.mip_bias);
         if (per_material_data.base_color_texture_has_alpha_channel) {
             base_color *= sampled_color;
         } else {
@@ -1189,7 +1211,7 @@ vec4 pbr_main() {
 
 #ifdef PBR_TEXTURES
     if (per_material_data.emissive_texture != -1) {
-        emissive_color *= texture(sampler2D(all_material_textures[per_material_data.emissive_texture], smp), in_uv, per_view_data.mip_bias);
+        emissive_color *= texture(sampler2D(all_material_textures[MATERIAL_TEXTURE_INDEX(per_material_data.emissive_texture)], smp), in_uv, per_view_data.mip_bias);
     }
 #endif
 
@@ -1199,7 +1221,7 @@ vec4 pbr_main() {
 
 #ifdef PBR_TEXTURES
     if (per_material_data.metallic_roughness_texture != -1) {
-        vec4 sampled = texture(sampler2D(all_material_textures[per_material_data.metallic_roughness_texture], smp), in_uv, per_view_data.mip_bias);
+        vec4 sampled = texture(sampler2D(all_material_textures[MATERIAL_TEXTURE_INDEX(per_material_data.metallic_roughness_texture)], smp), in_uv, per_view_data.mip_bias);
         metalness *= sampled.b;
         roughness *= sampled.g;
     }
@@ -1230,6 +1252,10 @@ vec4 pbr_main() {
 
     //TOOD: AO
 
+#ifdef PBR_SURFACE_OUTPUT
+    out_surface = vec4(octahedral_encode(normal_vs), roughness, metalness);
+#endif
+
     vec3 eye_position_vs = vec3(0, 0, 0);
     vec3 surface_to_eye_vs = normalize(eye_position_vs - in_position_vs);
 
@@ -1244,6 +1270,8 @@ vec4 pbr_main() {
         ambient_factor
     );
 
+    out_color = apply_volumetric_fog(out_color);
+
     // LIGHT COUNT
     //uint light_count = light_bin_output.data.offsets[get_light_cluster_index()].count;
     //out_color = vec4(vec3(light_count / 32.0), 1.0);
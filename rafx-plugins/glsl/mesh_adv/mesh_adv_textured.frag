@@ -2,6 +2,9 @@
 #extension GL_ARB_separate_shader_objects : enable
 #extension GL_ARB_shading_language_420pack : enable
 #extension GL_ARB_shader_draw_parameters : enable
+#ifdef MESH_ADV_BINDLESS
+#extension GL_EXT_nonuniform_qualifier : require
+#endif
 
 #define PBR_TEXTURES
 #include "mesh_adv_pbr_bindings.glsl"
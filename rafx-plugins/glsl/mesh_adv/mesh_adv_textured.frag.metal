@@ -155,6 +155,13 @@ struct MaterialDbEntry_1
     char _m0_final_padding[8];
 };
 
+struct VolumetricFog
+{
+    float near_z;
+    float far_z;
+    uint enabled;
+};
+
 struct AllMaterials
 {
     MaterialDbEntry_1 materials[1];
@@ -181,6 +188,8 @@ struct spvDescriptorSetBuffer0
 struct spvDescriptorSetBuffer1
 {
     texture2d<float> ssao_texture [[id(0)]];
+    texture3d<float> volumetric_fog_texture [[id(1)]];
+    constant VolumetricFog* volumetric_fog [[id(3)]];
 };
 
 struct spvDescriptorSetBuffer2
@@ -860,7 +869,21 @@ float4 pbr_path(thread const float3& surface_to_eye_vs, thread const float4& bas
 }
 
 static inline __attribute__((always_inline))
-float4 pbr_main(thread sampler smp, constant PerViewData& per_view_data, thread float4& in_position_ws, thread float3& in_position_vs, thread float3& in_normal_vs, thread depth2d<float> shadow_map_atlas, thread sampler smp_depth_nearest, thread float3x3& in_model_view, thread sampler smp_depth_linear, device AllLights& all_lights, device LightBinOutput& light_bin_output, device AllDrawData& all_draw_data, constant spvDescriptorSetBuffer3& spvDescriptorSet3, thread uint& in_instance_index, thread float4& gl_FragCoord, thread float2& in_uv, thread texture2d<float> ssao_texture, thread float3& in_tangent_vs, thread float3& in_binormal_vs)
+float4 apply_volumetric_fog(thread const float4& color, constant VolumetricFog& volumetric_fog, thread float4& gl_FragCoord, constant PerViewData& per_view_data, thread float3& in_position_vs, thread texture3d<float> volumetric_fog_texture, thread sampler smp_volumetric_fog)
+{
+    if (volumetric_fog.enabled == 0u)
+    {
+        return color;
+    }
+    float2 uv = gl_FragCoord.xy / float2(float(per_view_data.viewport_width), float(per_view_data.viewport_height));
+    float depth_vs = fast::max(-in_position_vs.z, volumetric_fog.near_z);
+    float w = log(depth_vs / volumetric_fog.near_z) / log(volumetric_fog.far_z / volumetric_fog.near_z);
+    float4 fog = volumetric_fog_texture.sample(smp_volumetric_fog, float3(uv, w));
+    return float4((color.xyz * fog.w) + fog.xyz, color.w);
+}
+
+static inline __attribute__((always_inline))
+float4 pbr_main(thread sampler smp, constant PerViewData& per_view_data, thread float4& in_position_ws, thread float3& in_position_vs, thread float3& in_normal_vs, thread depth2d<float> shadow_map_atlas, thread sampler smp_depth_nearest, thread float3x3& in_model_view, thread sampler smp_depth_linear, device AllLights& all_lights, device LightBinOutput& light_bin_output, device AllDrawData& all_draw_data, constant spvDescriptorSetBuffer3& spvDescriptorSet3, thread uint& in_instance_index, thread float4& gl_FragCoord, thread float2& in_uv, thread texture2d<float> ssao_texture, constant VolumetricFog& volumetric_fog, thread texture3d<float> volumetric_fog_texture, thread sampler smp_volumetric_fog, thread float3& in_tangent_vs, thread float3& in_binormal_vs)
 {
     uint material_index = all_draw_data.draw_data[in_instance_index].material_index;
     MaterialDbEntry per_material_data;
@@ -934,6 +957,8 @@ float4 pbr_main(thread sampler smp, constant PerViewData& per_view_data, thread
     uint param_9 = light_cluster_index;
     float param_10 = ambient_factor;
     float4 out_color = pbr_path(param_3, param_4, param_5, param_6, param_7, param_8, param_9, param_10, per_view_data, in_position_ws, in_position_vs, in_normal_vs, shadow_map_atlas, smp_depth_nearest, in_model_view, smp_depth_linear, all_lights, light_bin_output, all_draw_data, in_instance_index, *spvDescriptorSet3.all_materials);
+    float4 param_12 = out_color;
+    out_color = apply_volumetric_fog(param_12, volumetric_fog, gl_FragCoord, per_view_data, in_position_vs, volumetric_fog_texture, smp_volumetric_fog);
     return out_color;
 }
 
@@ -942,12 +967,13 @@ fragment main0_out main0(main0_in in [[stage_in]], constant spvDescriptorSetBuff
     constexpr sampler smp(filter::linear, mip_filter::linear, address::repeat, compare_func::never, max_anisotropy(16));
     constexpr sampler smp_depth_nearest(mip_filter::nearest, compare_func::greater, max_anisotropy(1), lod_clamp(0.0, 0.0));
     constexpr sampler smp_depth_linear(filter::linear, mip_filter::linear, compare_func::greater, max_anisotropy(1));
+    constexpr sampler smp_volumetric_fog(filter::linear, mip_filter::nearest, compare_func::never, max_anisotropy(1));
     main0_out out = {};
     float3x3 in_model_view = {};
     in_model_view[0] = in.in_model_view_0;
     in_model_view[1] = in.in_model_view_1;
     in_model_view[2] = in.in_model_view_2;
-    out.out_color = pbr_main(smp, (*spvDescriptorSet0.per_view_data), in.in_position_ws, in.in_position_vs, in.in_normal_vs, spvDescriptorSet0.shadow_map_atlas, smp_depth_nearest, in_model_view, smp_depth_linear, (*spvDescriptorSet0.all_lights), (*spvDescriptorSet0.light_bin_output), (*spvDescriptorSet2.all_draw_data), spvDescriptorSet3, in.in_instance_index, gl_FragCoord, in.in_uv, spvDescriptorSet1.ssao_texture, in.in_tangent_vs, in.in_binormal_vs);
+    out.out_color = pbr_main(smp, (*spvDescriptorSet0.per_view_data), in.in_position_ws, in.in_position_vs, in.in_normal_vs, spvDescriptorSet0.shadow_map_atlas, smp_depth_nearest, in_model_view, smp_depth_linear, (*spvDescriptorSet0.all_lights), (*spvDescriptorSet0.light_bin_output), (*spvDescriptorSet2.all_draw_data), spvDescriptorSet3, in.in_instance_index, gl_FragCoord, in.in_uv, spvDescriptorSet1.ssao_texture, (*spvDescriptorSet1.volumetric_fog), spvDescriptorSet1.volumetric_fog_texture, smp_volumetric_fog, in.in_tangent_vs, in.in_binormal_vs);
     return out;
 }
 
@@ -0,0 +1,14 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+#extension GL_ARB_shading_language_420pack : enable
+
+#include "mesh_adv_pbr_bindings.glsl"
+#include "mesh_adv_pbr_frag.glsl"
+#include "mesh_adv_oit.glsl"
+
+layout (location = 0) out vec4 out_accum;
+layout (location = 1) out float out_revealage;
+
+void main() {
+    oit_write(pbr_main(), out_accum, out_revealage);
+}
@@ -16,6 +16,7 @@ layout (location = 3) in vec3 in_binormal;
 layout (location = 4) in vec2 in_uv;
 
 // @[semantic("MODELMATRIX")]
+// @[per_instance]
 layout (location = 5) in mat4 in_model_matrix; // Uses locations 4-7. The semantic will be named `MODELMATRIX0` through `MODELMATRIX3`.
 // layout (location = 6) in mat4 in_model_matrix;
 // layout (location = 7) in mat4 in_model_matrix;
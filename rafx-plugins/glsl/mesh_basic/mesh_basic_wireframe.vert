@@ -8,6 +8,7 @@
 layout (location = 0) in vec3 in_pos;
 
 // @[semantic("MODELMATRIX")]
+// @[per_instance]
 layout (location = 1) in mat4 in_model_matrix; // Uses locations 1-4. The semantic will be named `MODELMATRIX0` through `MODELMATRIX3`.
 // layout (location = 2) in mat4 in_model_matrix;
 // layout (location = 3) in mat4 in_model_matrix;
@@ -2,6 +2,7 @@
 #extension GL_ARB_separate_shader_objects : enable
 #include "../post_shared/tonemapping.glsl"
 #include "luma_histogram_types.glsl"
+#include "color_grading.glsl"
 
 // @[export]
 layout (set = 0, binding = 0) uniform texture2D in_color;
@@ -31,6 +32,17 @@ layout (set = 0, binding = 3) uniform Config {
     int tonemapper_type;
     int output_color_space;
     float max_color_component_value;
+    bool enable_color_grading;
+    vec3 lift;
+    float saturation;
+    vec3 gamma;
+    // 0 uses only lut_a, 1 uses only lut_b
+    float lut_blend;
+    vec3 gain;
+    // How much of the LUT result is mixed into the graded color. 0 skips sampling the LUTs
+    float lut_contribution;
+    // Per-channel scale applied in LMS space
+    vec3 white_balance;
 } config;
 
 layout(set = 0, binding = 4) buffer HistogramResultBuffer
@@ -38,6 +50,24 @@ layout(set = 0, binding = 4) buffer HistogramResultBuffer
     HistogramResult result;
 } histogram_result;
 
+// @[export]
+layout (set = 0, binding = 5) uniform texture3D lut_a;
+
+// @[export]
+layout (set = 0, binding = 6) uniform texture3D lut_b;
+
+// @[immutable_samplers([
+//         (
+//             mag_filter: Linear,
+//             min_filter: Linear,
+//             mip_map_mode: Nearest,
+//             address_mode_u: ClampToEdge,
+//             address_mode_v: ClampToEdge,
+//             address_mode_w: ClampToEdge,
+//         )
+// ])]
+layout (set = 0, binding = 7) uniform sampler smp_lut;
+
 layout (location = 0) in vec2 inUV;
 
 layout (location = 0) out vec4 out_sdr;
@@ -70,6 +100,20 @@ void main()
         histogram_result.result.max_luminosity_interpolated
     );
 
+    if (config.enable_color_grading) {
+        color_srgb_linear = color_grading_white_balance(color_srgb_linear, config.white_balance);
+        color_srgb_linear = color_grading_lift_gamma_gain(color_srgb_linear, config.lift, config.gamma, config.gain);
+        color_srgb_linear = color_grading_saturation(color_srgb_linear, config.saturation);
+
+        if (config.lut_contribution > 0.0) {
+            vec3 color_srgb = linear_to_srgb(clamp(color_srgb_linear, 0.0, 1.0));
+            vec3 graded_a = color_grading_sample_lut(lut_a, smp_lut, color_srgb);
+            vec3 graded_b = color_grading_sample_lut(lut_b, smp_lut, color_srgb);
+            vec3 graded = srgb_to_linear(mix(graded_a, graded_b, config.lut_blend));
+            color_srgb_linear = mix(color_srgb_linear, graded, config.lut_contribution);
+        }
+    }
+
     switch (config.output_color_space)
     {
         case OUTPUT_COLOR_SPACE_SRGB:
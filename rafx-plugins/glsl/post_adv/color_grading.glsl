@@ -0,0 +1,43 @@
+// Color grading applied after tonemapping. All functions take and return linear sRGB.
+
+// Source: Unity post processing (ColorUtilities.LIN_2_LMS_MAT / LMS_2_LIN_MAT). These are written
+// row by row, so they are used as row vector * matrix
+const mat3 LINEAR_SRGB_TO_LMS = mat3(
+    3.90405e-1, 5.49941e-1, 8.92632e-3,
+    7.08416e-2, 9.63172e-1, 1.35775e-3,
+    2.31082e-2, 1.28021e-1, 9.36245e-1
+);
+
+const mat3 LMS_TO_LINEAR_SRGB = mat3(
+    2.85847e+0, -1.62879e+0, -2.48910e-2,
+    -2.10182e-1, 1.15820e+0, 3.24281e-4,
+    -4.18120e-2, -1.18169e-1, 1.06867e+0
+);
+
+// The per-channel LMS scale is computed on the CPU from temperature and tint
+vec3 color_grading_white_balance(vec3 color, vec3 lms_scale) {
+    vec3 lms = color * LINEAR_SRGB_TO_LMS;
+    lms *= lms_scale;
+    return lms * LMS_TO_LINEAR_SRGB;
+}
+
+// Lift raises the shadows, gain scales the highlights and gamma bends the midtones. A lift of 0
+// and gamma/gain of 1 leave the color unchanged.
+vec3 color_grading_lift_gamma_gain(vec3 color, vec3 lift, vec3 gamma, vec3 gain) {
+    color = gain * (color + lift * (1.0 - color));
+    return pow(max(color, vec3(0.0)), 1.0 / max(gamma, vec3(0.001)));
+}
+
+vec3 color_grading_saturation(vec3 color, float saturation) {
+    float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    return max(mix(vec3(luma), color, saturation), vec3(0.0));
+}
+
+// LUTs map sRGB-encoded colors to sRGB-encoded colors (the usual convention for .cube files
+// authored in grading tools). The coordinate is remapped so that 0 and 1 land on the centers of
+// the first and last texels.
+vec3 color_grading_sample_lut(texture3D lut, sampler smp, vec3 color_srgb) {
+    float lut_size = float(textureSize(sampler3D(lut, smp), 0).x);
+    vec3 uvw = clamp(color_srgb, 0.0, 1.0) * ((lut_size - 1.0) / lut_size) + 0.5 / lut_size;
+    return textureLod(sampler3D(lut, smp), uvw, 0.0).rgb;
+}
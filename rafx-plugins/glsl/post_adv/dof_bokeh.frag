@@ -0,0 +1,89 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+// Gathers a bokeh disc at half resolution. Samples are taken on a golden angle spiral out to the
+// max CoC radius, and each sample only contributes if its own CoC is large enough to reach the
+// pixel being shaded (scatter-as-gather). Samples behind the center pixel have their CoC limited
+// to that of the center pixel so that a blurry background doesn't bleed over a sharp foreground.
+//
+// Based on "Bokeh depth of field in a single pass" by Dennis Gustafsson
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D prefilter_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 1) uniform sampler smp_nearest;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Linear,
+//         min_filter: Linear,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 2) uniform sampler smp_linear;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 3) uniform Config {
+    // In half-res pixels
+    float max_coc_radius;
+    // Spacing between rings of the spiral, in half-res pixels. Smaller values take more samples
+    float radius_step;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+// rgb: blurred color, a: signed CoC radius of the center pixel in half-res pixels
+layout (location = 0) out vec4 out_image;
+
+const float GOLDEN_ANGLE = 2.39996323;
+const int MAX_SAMPLES = 256;
+
+void main() {
+    vec2 texel_size = 1.0 / vec2(textureSize(sampler2D(prefilter_tex, smp_nearest), 0));
+
+    vec4 center = texture(sampler2D(prefilter_tex, smp_nearest), inUV);
+    float center_coc = center.a;
+    float center_size = abs(center_coc);
+
+    vec3 color = center.rgb;
+    float total = 1.0;
+
+    float radius_step = max(config.radius_step, 0.1);
+    float radius = radius_step;
+    float angle = 0.0;
+    for (int i = 0; i < MAX_SAMPLES && radius < config.max_coc_radius; ++i) {
+        vec2 sample_uv = inUV + vec2(cos(angle), sin(angle)) * texel_size * radius;
+        vec4 s = textureLod(sampler2D(prefilter_tex, smp_linear), sample_uv, 0.0);
+
+        float sample_size = abs(s.a);
+        if (s.a > center_coc) {
+            // The sample is behind the center pixel
+            sample_size = clamp(sample_size, 0.0, center_size * 2.0);
+        }
+
+        // Samples whose disc doesn't reach this pixel contribute the running average instead, so
+        // that they don't darken the result
+        float m = smoothstep(radius - 0.5, radius + 0.5, sample_size);
+        color += mix(color / total, s.rgb, m);
+        total += 1.0;
+
+        angle += GOLDEN_ANGLE;
+        radius += radius_step / radius;
+    }
+
+    out_image = vec4(color / total, center_coc);
+}
@@ -0,0 +1,25 @@
+// Circle of confusion for a thin lens camera.
+//
+// The CoC diameter on the sensor for an object at distance d when the lens is focused at distance
+// s is: (f^2 / (N * (s - f))) * |d - s| / d, where f is the focal length and N is the f-number.
+// Everything that doesn't depend on d (including the conversion from sensor units to pixels) is
+// folded into coc_scale on the CPU.
+//
+// Returns the signed CoC radius in pixels. Negative values are in front of the focus distance
+// (near field), positive values are behind it (far field). This means the CoC increases
+// monotonically with distance, which the bokeh gather relies on to sort samples.
+float coc_radius(float view_distance, float focus_distance, float coc_scale, float max_coc_radius) {
+    float coc = coc_scale * (1.0 - focus_distance / max(view_distance, 0.0001));
+    return clamp(coc, -max_coc_radius, max_coc_radius);
+}
+
+// Distance from the camera to the surface in the depth buffer. Reverse Z, so 0 is infinitely far.
+float view_distance_from_depth(mat4 proj_inv, vec2 uv, float depth) {
+    if (depth <= 0.0) {
+        return 1.0e20;
+    }
+
+    vec4 pos_cs = vec4((uv * 2.0 - 1.0) * vec2(1, -1), depth, 1.0);
+    vec4 pos_vs = proj_inv * pos_cs;
+    return -pos_vs.z / pos_vs.w;
+}
@@ -0,0 +1,70 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+#include "dof_coc.glsl"
+
+// Blends the half-res bokeh over the full-res color. In-focus pixels keep the full-res color.
+// Blurred foreground is allowed to cover in-focus pixels behind it using the half-res CoC.
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D color_tex;
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D depth_tex;
+// @[export]
+layout (set = 0, binding = 2) uniform texture2D bokeh_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 3) uniform sampler smp_nearest;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Linear,
+//         min_filter: Linear,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 4) uniform sampler smp_linear;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 5) uniform Config {
+    mat4 proj_inv;
+    float focus_distance;
+    // Converts the thin lens CoC to a radius in full-res pixels
+    float coc_scale;
+    // In full-res pixels
+    float max_coc_radius;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_image;
+
+void main() {
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    vec4 color = texelFetch(sampler2D(color_tex, smp_nearest), texel, 0);
+    float depth = texelFetch(sampler2D(depth_tex, smp_nearest), texel, 0).r;
+    float view_distance = view_distance_from_depth(config.proj_inv, inUV, depth);
+    float coc = coc_radius(view_distance, config.focus_distance, config.coc_scale, config.max_coc_radius);
+
+    vec4 bokeh = texture(sampler2D(bokeh_tex, smp_linear), inUV);
+
+    // The bokeh CoC is in half-res pixels. Only the near field is allowed to spread, the far field
+    // stays behind whatever is in front of it.
+    float near_coc = max(-bokeh.a * 2.0, 0.0);
+    float blur_amount = smoothstep(0.5, 1.5, max(abs(coc), near_coc));
+
+    out_image = vec4(mix(color.rgb, bokeh.rgb, blur_amount), color.a);
+}
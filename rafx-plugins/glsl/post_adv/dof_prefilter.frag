@@ -0,0 +1,74 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+#include "dof_coc.glsl"
+
+// Downsamples the color to half resolution and computes the CoC for each half-res texel. The
+// bokeh gather runs on the output of this pass.
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D color_tex;
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D depth_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 2) uniform sampler smp_nearest;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 3) uniform Config {
+    mat4 proj_inv;
+    float focus_distance;
+    // Converts the thin lens CoC to a radius in half-res pixels
+    float coc_scale;
+    // In half-res pixels
+    float max_coc_radius;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+// rgb: color, a: signed CoC radius in half-res pixels
+layout (location = 0) out vec4 out_image;
+
+void main() {
+    ivec2 full_res_size = textureSize(sampler2D(color_tex, smp_nearest), 0);
+    ivec2 base = ivec2(gl_FragCoord.xy) * 2;
+
+    vec3 color = vec3(0.0);
+    float color_weight = 0.0;
+    float coc = 0.0;
+    for (int y = 0; y < 2; ++y) {
+        for (int x = 0; x < 2; ++x) {
+            ivec2 texel = min(base + ivec2(x, y), full_res_size - 1);
+            vec2 uv = (vec2(texel) + 0.5) / vec2(full_res_size);
+            float depth = texelFetch(sampler2D(depth_tex, smp_nearest), texel, 0).r;
+            float view_distance = view_distance_from_depth(config.proj_inv, uv, depth);
+            float sample_coc = coc_radius(view_distance, config.focus_distance, config.coc_scale, config.max_coc_radius);
+
+            // Weighting by CoC keeps sharp foreground texels from bleeding into the blurred
+            // background and vice versa. Luma weighting suppresses fireflies, which would otherwise
+            // become bright flickering bokeh discs.
+            vec3 sample_color = texelFetch(sampler2D(color_tex, smp_nearest), texel, 0).rgb;
+            float luma = dot(sample_color, vec3(0.2126, 0.7152, 0.0722));
+            float weight = (abs(sample_coc) + 0.001) / (1.0 + luma);
+            color += sample_color * weight;
+            color_weight += weight;
+
+            // Keep whichever CoC is most blurred so that edges of blurred objects aren't eroded
+            if (abs(sample_coc) > abs(coc)) {
+                coc = sample_coc;
+            }
+        }
+    }
+
+    out_image = vec4(color / color_weight, coc);
+}
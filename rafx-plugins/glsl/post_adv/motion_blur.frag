@@ -0,0 +1,126 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+// Blurs each pixel along its screen-space velocity. The blur covers the distance the surface moved
+// while the shutter was open, centered on its current position. Samples that are in front of the
+// center pixel only contribute if they are moving fast enough to cover it, so that a fast moving
+// background doesn't smear over a static foreground.
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D color_tex;
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D depth_tex;
+// @[export]
+layout (set = 0, binding = 2) uniform texture2D velocity_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 3) uniform sampler smp_nearest;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Linear,
+//         min_filter: Linear,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 4) uniform sampler smp_linear;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 5) uniform Config {
+    mat4 current_view_proj_inv;
+    mat4 previous_view_proj;
+    // Fraction of the frame the shutter is open for (shutter angle / 360)
+    float velocity_scale;
+    // In pixels
+    float max_blur_radius;
+    uint sample_count;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_image;
+
+// Returns velocity in pixels, scaled by the shutter
+vec2 velocity_px(vec2 uv, ivec2 texel, vec2 viewport_size) {
+    vec2 velocity_ndc = texelFetch(sampler2D(velocity_tex, smp_nearest), texel, 0).rg;
+    if (velocity_ndc.x > 9000000.0 && velocity_ndc.y > 9000000.0) {
+        //
+        // There is no velocity data, reproject using our current and previous view/projection matrices
+        //
+        // Reverse Z with an infinite far plane can't be unprojected at 0 (the sky), so push it
+        // very far away instead. It will then only be moved by camera rotation.
+        float depth = max(texelFetch(sampler2D(depth_tex, smp_nearest), texel, 0).r, 0.000001);
+        vec2 fragcoord_ndc = uv * 2.0 - 1.0;
+        fragcoord_ndc.y *= -1.0;
+        vec4 position_ws = config.current_view_proj_inv * vec4(fragcoord_ndc, depth, 1.0);
+        position_ws /= position_ws.w;
+        vec4 previous_position_ndc = config.previous_view_proj * vec4(position_ws.xyz, 1.0);
+        previous_position_ndc /= previous_position_ndc.w;
+        velocity_ndc = fragcoord_ndc - previous_position_ndc.xy;
+    }
+
+    vec2 velocity = velocity_ndc * vec2(0.5, -0.5) * viewport_size * config.velocity_scale;
+    float len = length(velocity);
+    if (len > config.max_blur_radius) {
+        velocity *= config.max_blur_radius / len;
+    }
+
+    return velocity;
+}
+
+void main() {
+    ivec2 viewport_size_i = textureSize(sampler2D(color_tex, smp_nearest), 0);
+    vec2 viewport_size = vec2(viewport_size_i);
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+
+    vec4 center_color = texelFetch(sampler2D(color_tex, smp_nearest), texel, 0);
+    vec2 center_velocity = velocity_px(inUV, texel, viewport_size);
+    float center_speed = length(center_velocity);
+    if (center_speed < 0.5 || config.sample_count == 0) {
+        out_image = center_color;
+        return;
+    }
+
+    float center_depth = texelFetch(sampler2D(depth_tex, smp_nearest), texel, 0).r;
+
+    vec3 color = center_color.rgb;
+    float total_weight = 1.0;
+    for (uint i = 0; i < config.sample_count; ++i) {
+        // Spread samples evenly over [-0.5, 0.5] of the blur vector, skipping the center
+        float t = (float(i) + 0.5) / float(config.sample_count) - 0.5;
+        vec2 offset_px = center_velocity * t;
+        vec2 sample_uv = inUV + offset_px / viewport_size;
+        ivec2 sample_texel = clamp(ivec2(sample_uv * viewport_size), ivec2(0), viewport_size_i - 1);
+
+        float sample_depth = texelFetch(sampler2D(depth_tex, smp_nearest), sample_texel, 0).r;
+        float distance_px = length(offset_px);
+
+        float weight;
+        if (sample_depth > center_depth) {
+            // Reverse Z, the sample is in front of the center pixel. It only blurs over us if it
+            // is moving far enough to reach us.
+            float sample_speed = length(velocity_px(sample_uv, sample_texel, viewport_size));
+            weight = clamp(sample_speed * 0.5 - distance_px + 1.0, 0.0, 1.0);
+        } else {
+            weight = clamp(center_speed * 0.5 - distance_px + 1.0, 0.0, 1.0);
+        }
+
+        color += textureLod(sampler2D(color_tex, smp_linear), sample_uv, 0.0).rgb * weight;
+        total_weight += weight;
+    }
+
+    out_image = vec4(color / total_weight, center_color.a);
+}
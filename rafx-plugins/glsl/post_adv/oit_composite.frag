@@ -0,0 +1,47 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+// Resolves the weighted blended OIT targets written by the transparent mesh passes. The output is
+// alpha blended over the opaque color (SRC_ALPHA, ONE_MINUS_SRC_ALPHA)
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D accum_tex;
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D revealage_tex;
+
+// @[immutable_samplers([
+//         (
+//             mag_filter: Nearest,
+//             min_filter: Nearest,
+//             mip_map_mode: Nearest,
+//             address_mode_u: ClampToEdge,
+//             address_mode_v: ClampToEdge,
+//             address_mode_w: ClampToEdge,
+//         )
+// ])]
+layout (set = 0, binding = 2) uniform sampler smp;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_color;
+
+void main()
+{
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    float revealage = texelFetch(sampler2D(revealage_tex, smp), texel, 0).r;
+
+    // Nothing transparent was drawn to this pixel
+    if (revealage >= 1.0) {
+        discard;
+    }
+
+    vec4 accum = texelFetch(sampler2D(accum_tex, smp), texel, 0);
+
+    // Avoid overflow if a lot of high-weight fragments were accumulated
+    if (isinf(max(max(abs(accum.r), abs(accum.g)), abs(accum.b)))) {
+        accum.rgb = vec3(accum.a);
+    }
+
+    vec3 average_color = accum.rgb / max(accum.a, 0.00001);
+    out_color = vec4(average_color, 1.0 - revealage);
+}
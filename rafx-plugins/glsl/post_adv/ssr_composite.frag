@@ -0,0 +1,30 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+// Adds the accumulated reflections to the opaque color. Reflections are already premultiplied by
+// fresnel and confidence, so this is drawn with additive blending (ONE, ONE)
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D reflection_tex;
+
+// @[immutable_samplers([
+//         (
+//             mag_filter: Nearest,
+//             min_filter: Nearest,
+//             mip_map_mode: Nearest,
+//             address_mode_u: ClampToEdge,
+//             address_mode_v: ClampToEdge,
+//             address_mode_w: ClampToEdge,
+//         )
+// ])]
+layout (set = 0, binding = 1) uniform sampler smp;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_color;
+
+void main()
+{
+    vec3 reflection = texelFetch(sampler2D(reflection_tex, smp), ivec2(gl_FragCoord.xy), 0).rgb;
+    out_color = vec4(reflection, 0.0);
+}
@@ -0,0 +1,113 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+// Accumulates the traced reflections over several frames. Reflections are noisy because the rays
+// are jittered by roughness and only a single ray is traced per pixel. History is reprojected the
+// same way as in taa.frag and clamped to the neighborhood of the current frame to avoid ghosting.
+
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D history_tex;
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D current_tex;
+// @[export]
+layout (set = 0, binding = 2) uniform texture2D velocity_tex;
+// @[export]
+layout (set = 0, binding = 3) uniform texture2D depth_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 4) uniform sampler smp_nearest;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Linear,
+//         min_filter: Linear,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 5) uniform sampler smp_bilinear;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 6) uniform Config {
+    mat4 current_view_proj_inv;
+    mat4 previous_view_proj;
+    bool has_history_data;
+    float history_weight;
+    uint viewport_width;
+    uint viewport_height;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_image;
+
+void main() {
+    vec2 texel_size = 1.0 / vec2(config.viewport_width, config.viewport_height);
+
+    //
+    // Neighborhood of the current frame, used to clamp history
+    //
+    vec4 current = vec4(0.0);
+    vec4 neighborhood_min = vec4(1000000.0);
+    vec4 neighborhood_max = vec4(-1000000.0);
+    for (int y = -1; y <= 1; ++y) {
+        for (int x = -1; x <= 1; ++x) {
+            vec2 sample_uv = clamp(inUV + vec2(x, y) * texel_size, 0.0, 1.0);
+            vec4 value = texture(sampler2D(current_tex, smp_nearest), sample_uv);
+            neighborhood_min = min(neighborhood_min, value);
+            neighborhood_max = max(neighborhood_max, value);
+            if (x == 0 && y == 0) {
+                current = value;
+            }
+        }
+    }
+
+    if (!config.has_history_data) {
+        out_image = current;
+        return;
+    }
+
+    float depth = texture(sampler2D(depth_tex, smp_nearest), inUV).r;
+    if (depth <= 0.0) {
+        out_image = current;
+        return;
+    }
+
+    vec2 velocity_ndc = texture(sampler2D(velocity_tex, smp_nearest), inUV).rg;
+    if (velocity_ndc.x > 9000000.0 && velocity_ndc.y > 9000000.0) {
+        //
+        // There is no velocity data, reproject using our current and previous view/projection matrices
+        //
+        vec2 viewport_size = vec2(config.viewport_width, config.viewport_height);
+        vec2 fragcoord_ndc = (gl_FragCoord.xy / viewport_size) * 2.0 - 1.0;
+        fragcoord_ndc.y *= -1.0;
+        vec4 new_position_ndc = vec4(fragcoord_ndc, depth, 1.0);
+        vec4 position_ws = config.current_view_proj_inv * new_position_ndc;
+        position_ws /= position_ws.w;
+        vec4 previous_position_ndc = config.previous_view_proj * vec4(position_ws.xyz, 1.0);
+        previous_position_ndc /= previous_position_ndc.w;
+        velocity_ndc = fragcoord_ndc - previous_position_ndc.xy;
+    }
+
+    vec2 history_sample_uv = inUV - (velocity_ndc * vec2(0.5, -0.5)); // ndc -> uv
+    if (history_sample_uv.x < 0.0 || history_sample_uv.x > 1.0 || history_sample_uv.y < 0.0 || history_sample_uv.y > 1.0) {
+        out_image = current;
+        return;
+    }
+
+    vec4 history = texture(sampler2D(history_tex, smp_bilinear), history_sample_uv);
+    history = clamp(history, neighborhood_min, neighborhood_max);
+    out_image = mix(current, history, config.history_weight);
+}
@@ -0,0 +1,223 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+#include "../util/normal_encoding.glsl"
+
+// Traces reflection rays against the depth pyramid and returns the color of the opaque pass at the
+// hit point. The output is premultiplied by a confidence value (alpha) that fades out rays that
+// leave the screen, run out of steps, or start on surfaces too rough for a mirror-like reflection.
+// Where confidence is zero, the forward lighting's specular term is all that remains.
+
+// Mip 0 is the depth buffer. Other mips are (farthest, closest) depth in (r, g)
+// @[export]
+layout (set = 0, binding = 0) uniform texture2D depth_mip_slices[16];
+// @[export]
+layout (set = 0, binding = 1) uniform texture2D surface_tex;
+// @[export]
+layout (set = 0, binding = 2) uniform texture2D color_tex;
+// @[export]
+layout (set = 0, binding = 3) uniform texture2D noise_tex;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Nearest,
+//         min_filter: Nearest,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 4) uniform sampler smp_nearest;
+
+// @[immutable_samplers([
+//     (
+//         mag_filter: Linear,
+//         min_filter: Linear,
+//         mip_map_mode: Nearest,
+//         address_mode_u: ClampToEdge,
+//         address_mode_v: ClampToEdge,
+//         address_mode_w: ClampToEdge,
+//     )
+// ])]
+layout (set = 0, binding = 5) uniform sampler smp_linear;
+
+// @[export]
+// @[internal_buffer]
+layout (set = 0, binding = 6) uniform Config {
+    mat4 proj;
+    mat4 proj_inv;
+    uint viewport_width;
+    uint viewport_height;
+    uint depth_mip_slice_count;
+    uint max_steps;
+    float max_distance;
+    float thickness;
+    float max_roughness;
+    float roughness_fade_start;
+    float edge_fade;
+    float intensity;
+    uint frame_index;
+} config;
+
+layout (location = 0) in vec2 inUV;
+
+layout (location = 0) out vec4 out_image;
+
+// See ssao.frag
+float depth_vs(float depth) {
+    return -1.0 * (config.proj[3][2] / (depth - config.proj[2][2]));
+}
+
+vec3 pos_vs(vec2 uv, float depth) {
+    vec4 pos_cs = vec4((uv * 2.0 - 1.0) * vec2(1, -1), depth, 1.0);
+    vec4 pos_vs = config.proj_inv * pos_cs;
+    return pos_vs.xyz / pos_vs.w;
+}
+
+// Returns (uv, ndc depth)
+vec3 project_vs(vec3 p) {
+    vec4 pos_cs = config.proj * vec4(p, 1.0);
+    pos_cs.xyz /= pos_cs.w;
+    return vec3(pos_cs.xy * vec2(0.5, -0.5) + 0.5, pos_cs.z);
+}
+
+vec2 mip_size(uint level) {
+    return vec2(max(uvec2(1), uvec2(config.viewport_width, config.viewport_height) >> level));
+}
+
+// Closest depth within a cell at the given level. Reverse Z, so closest is the largest value.
+float closest_depth(uint level, ivec2 cell) {
+    vec4 value = texelFetch(sampler2D(depth_mip_slices[level], smp_nearest), cell, 0);
+    return level == 0 ? value.r : value.g;
+}
+
+// Ray parameter where the ray leaves the given cell, nudged slightly so that it lands in the next
+// cell
+float cell_exit_t(vec2 origin, vec2 dir, ivec2 cell, vec2 size) {
+    vec2 safe_dir = vec2(
+        abs(dir.x) < 0.000001 ? 0.000001 : dir.x,
+        abs(dir.y) < 0.000001 ? 0.000001 : dir.y
+    );
+    vec2 boundary = (vec2(cell) + step(vec2(0.0), safe_dir)) / size;
+    vec2 t = (boundary - origin) / safe_dir;
+    float nudge = 0.01 / (max(size.x, size.y) * max(abs(safe_dir.x), abs(safe_dir.y)));
+    return min(t.x, t.y) + nudge;
+}
+
+void main() {
+    out_image = vec4(0.0);
+
+    ivec2 pixel = ivec2(gl_FragCoord.xy);
+    float depth = texelFetch(sampler2D(depth_mip_slices[0], smp_nearest), pixel, 0).r;
+    if (depth <= 0.0) {
+        // Nothing was drawn here
+        return;
+    }
+
+    vec4 surface = texelFetch(sampler2D(surface_tex, smp_nearest), pixel, 0);
+    float roughness = surface.z;
+    float metalness = surface.w;
+    if (roughness >= config.max_roughness) {
+        return;
+    }
+
+    vec3 normal_vs = octahedral_decode(surface.xy);
+    vec3 P = pos_vs(inUV, depth);
+    vec3 V = normalize(P);
+
+    //
+    // Perturb the normal by roughness so that glossy surfaces get blurry reflections once temporally
+    // accumulated
+    //
+    vec2 noise_texture_size = textureSize(sampler2D(noise_tex, smp_nearest), 0);
+    uvec2 noise_offset = uvec2(config.frame_index * 7, config.frame_index * 13);
+    uvec2 noise_pixel = (uvec2(pixel) + noise_offset) % uvec2(noise_texture_size);
+    vec3 noise_value = texelFetch(sampler2D(noise_tex, smp_nearest), ivec2(noise_pixel), 0).rgb * 2.0 - 1.0;
+    vec3 N = normalize(normal_vs + noise_value * roughness * roughness);
+
+    vec3 R = reflect(V, N);
+    float n_dot_v = clamp(dot(normal_vs, -V), 0.0, 1.0);
+
+    //
+    // Clip the ray against the near plane so that the projected end point is valid
+    //
+    float ray_length = config.max_distance;
+    float near_plane_z = depth_vs(1.0);
+    if (P.z + R.z * ray_length > near_plane_z) {
+        ray_length = (near_plane_z - P.z) / R.z * 0.99;
+    }
+
+    vec3 ray_start = vec3(inUV, depth);
+    vec3 ray_end = project_vs(P + R * ray_length);
+    vec3 ray_dir = ray_end - ray_start;
+
+    //
+    // Hierarchical trace. While the ray is in front of the closest depth of a cell we can skip the
+    // whole cell and move up a level. Otherwise move down a level until we reach full resolution,
+    // where we check whether the ray actually passed behind the surface within the thickness
+    //
+    uint max_level = config.depth_mip_slice_count - 1;
+    uint level = 0;
+    float t = cell_exit_t(ray_start.xy, ray_dir.xy, pixel, mip_size(0));
+    bool hit = false;
+    vec2 hit_uv = vec2(0.0);
+    for (uint i = 0; i < config.max_steps; ++i) {
+        if (t > 1.0) {
+            break;
+        }
+
+        vec3 p = ray_start + ray_dir * t;
+        if (p.x < 0.0 || p.x > 1.0 || p.y < 0.0 || p.y > 1.0) {
+            break;
+        }
+
+        vec2 size = mip_size(level);
+        ivec2 cell = ivec2(p.xy * size);
+        float closest = closest_depth(level, cell);
+        float t_exit = cell_exit_t(ray_start.xy, ray_dir.xy, cell, size);
+        float exit_z = ray_start.z + ray_dir.z * min(t_exit, 1.0);
+
+        // Reverse Z, so the farthest point of the ray segment in this cell is the smaller value
+        float ray_far_z = min(p.z, exit_z);
+        if (ray_far_z > closest) {
+            t = t_exit;
+            level = min(level + 1, max_level);
+        } else if (level > 0) {
+            level -= 1;
+        } else {
+            // If the ray entered the cell in front of the surface, it crossed it within this cell.
+            // Otherwise it's behind the surface and we only accept it if it's within the thickness
+            bool crossed = p.z >= closest;
+            if (crossed || (depth_vs(closest) - depth_vs(p.z)) < config.thickness) {
+                hit = true;
+                hit_uv = p.xy;
+                break;
+            }
+
+            t = t_exit;
+        }
+    }
+
+    if (!hit) {
+        return;
+    }
+
+    //
+    // Fade out reflections that are near the edge of the screen, near the end of the ray, or on
+    // surfaces that are getting too rough
+    //
+    vec2 edge_distance = min(hit_uv, 1.0 - hit_uv);
+    float edge_fade = clamp(min(edge_distance.x, edge_distance.y) / max(config.edge_fade, 0.0001), 0.0, 1.0);
+    float distance_fade = 1.0 - t * t;
+    float roughness_fade = 1.0 - smoothstep(config.roughness_fade_start, config.max_roughness, roughness);
+    float confidence = edge_fade * distance_fade * roughness_fade;
+
+    // We don't have albedo here, so metals use a white F0. This is a reasonable approximation for
+    // the polished surfaces that get reflections at all.
+    float f0 = mix(0.04, 1.0, metalness);
+    float fresnel = f0 + (1.0 - f0) * pow(1.0 - n_dot_v, 5.0);
+
+    vec3 hit_color = textureLod(sampler2D(color_tex, smp_linear), hit_uv, 0.0).rgb;
+    out_image = vec4(hit_color * fresnel * config.intensity * confidence, confidence);
+}
@@ -243,7 +243,7 @@ void main() {
     vec3 m2 = vec3(0.0);
     float m_weight = 0.0;
 
-    // We assume all textures are the same size
+    // We assume all textures except the history are the same size
     vec2 texture_size = textureSize(sampler2D(current_tex, smp_nearest), 0);
     vec2 texel_size = 1.0 / texture_size;
     vec3 current_color;
@@ -326,8 +326,10 @@ void main() {
     vec3 history_color = current_color;
     vec2 history_sample_uv = inUV - (velocity_ndc * vec2(0.5, -0.5)); // ndc -> uv
     if (history_sample_uv.x <= 1.0 && history_sample_uv.x >= 0.0 && history_sample_uv.y <= 1.0 && history_sample_uv.y >= 0.0) {
-        // catmull-rom filtering reduces accumulation of blur
-        history_color = sample_history_catmull_rom(history_sample_uv, texel_size);
+        // catmull-rom filtering reduces accumulation of blur. The history may be larger than the current
+        // image when rendering at a dynamic resolution
+        vec2 history_texel_size = 1.0 / textureSize(sampler2D(history_tex, smp_nearest), 0);
+        history_color = sample_history_catmull_rom(history_sample_uv, history_texel_size);
         //history_color = texture(sampler2D(history_tex, smp), history_sample_uv).rgb;
     }
 
@@ -0,0 +1,21 @@
+
+// Octahedral normal encoding, see "A Survey of Efficient Representations for Independent Unit
+// Vectors" (Cigolle et al., 2014) http://jcgt.org/published/0003/02/01/
+vec2 octahedral_wrap(vec2 v) {
+    return (1.0 - abs(v.yx)) * vec2(v.x >= 0.0 ? 1.0 : -1.0, v.y >= 0.0 ? 1.0 : -1.0);
+}
+
+// Encodes a unit vector into [-1, 1]^2
+vec2 octahedral_encode(vec3 n) {
+    n /= (abs(n.x) + abs(n.y) + abs(n.z));
+    n.xy = n.z >= 0.0 ? n.xy : octahedral_wrap(n.xy);
+    return n.xy;
+}
+
+vec3 octahedral_decode(vec2 f) {
+    vec3 n = vec3(f.x, f.y, 1.0 - abs(f.x) - abs(f.y));
+    float t = clamp(-n.z, 0.0, 1.0);
+    n.x += n.x >= 0.0 ? -t : t;
+    n.y += n.y >= 0.0 ? -t : t;
+    return normalize(n);
+}
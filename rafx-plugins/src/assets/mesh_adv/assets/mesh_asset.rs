@@ -87,10 +87,19 @@ pub struct MeshAdvPartAssetData {
     pub index_type: RafxIndexType,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MeshAdvLodAssetData {
+    pub mesh_parts: Vec<MeshAdvPartAssetData>,
+    // This LOD is used while the projected height of the mesh's bounding sphere, as a fraction of
+    // the view's height, is at least this large. The last LOD should use 0.0
+    pub screen_size: f32,
+}
+
 #[derive(TypeUuid, Serialize, Deserialize, Clone)]
 #[uuid = "4c888448-2650-4f56-82dc-71ba81f4295b"]
 pub struct MeshAdvAssetData {
-    pub mesh_parts: Vec<MeshAdvPartAssetData>,
+    // Ordered from most to least detailed. All LODs share the same vertex/index buffers
+    pub lods: Vec<MeshAdvLodAssetData>,
     pub vertex_full_buffer: Handle<MeshAdvBufferAsset>, // Vertex type is MeshVertexFull
     pub vertex_position_buffer: Handle<MeshAdvBufferAsset>, // Vertex type is MeshVertexPosition
     pub index_buffer: Handle<MeshAdvBufferAsset>,       // u16 indices
@@ -118,10 +127,20 @@ pub struct MeshAdvShaderPassIndices {
     pub opaque_backface_untextured: u8,
     //pub opaque_alphaclip_untextured: u8,
     //pub opaque_alphaclip_backface_untextured: u8,
+    // Used instead of the opaque passes when the pipeline needs surface data (i.e. for SSR)
+    pub opaque_surface: u8,
+    pub opaque_surface_backface: u8,
+    pub opaque_surface_untextured: u8,
+    pub opaque_surface_backface_untextured: u8,
     pub transparent: u8,
     pub transparent_backface: u8,
     pub transparent_untextured: u8,
     pub transparent_backface_untextured: u8,
+    // Used instead of the transparent passes when the pipeline uses weighted blended OIT
+    pub transparent_oit: u8,
+    pub transparent_oit_backface: u8,
+    pub transparent_oit_untextured: u8,
+    pub transparent_oit_backface_untextured: u8,
 
     pub wireframe: u8,
 }
@@ -145,10 +164,18 @@ impl MeshAdvShaderPassIndices {
         let opaque_backface_untextured = material.find_pass_index_by_name("opaque_backface_untextured").expect("Mesh shader must have pass named 'opaque_backface'") as u8;
         //let opaque_alphaclip_untextured = material.find_pass_index_by_name("opaque_alphaclip").expect("Mesh shader must have pass named 'opaque_alphaclip'") as u8;
         //let opaque_alphaclip_backface_untextured = material.find_pass_index_by_name("opaque_alphaclip_backface").expect("Mesh shader must have pass named 'opaque_alphaclip_backface'") as u8;
+        let opaque_surface = material.find_pass_index_by_name("opaque_surface").expect("Mesh shader must have pass named 'opaque_surface'") as u8;
+        let opaque_surface_backface = material.find_pass_index_by_name("opaque_surface_backface").expect("Mesh shader must have pass named 'opaque_surface_backface'") as u8;
+        let opaque_surface_untextured = material.find_pass_index_by_name("opaque_surface_untextured").expect("Mesh shader must have pass named 'opaque_surface_untextured'") as u8;
+        let opaque_surface_backface_untextured = material.find_pass_index_by_name("opaque_surface_backface_untextured").expect("Mesh shader must have pass named 'opaque_surface_backface_untextured'") as u8;
         let transparent = material.find_pass_index_by_name("transparent").expect("Mesh shader must have pass named 'transparent'") as u8;
         let transparent_backface = material.find_pass_index_by_name("transparent_backface").expect("Mesh shader must have pass named 'transparent_backface'") as u8;
         let transparent_untextured = material.find_pass_index_by_name("transparent_untextured").expect("Mesh shader must have pass named 'transparent'") as u8;
         let transparent_backface_untextured = material.find_pass_index_by_name("transparent_backface_untextured").expect("Mesh shader must have pass named 'transparent_backface'") as u8;
+        let transparent_oit = material.find_pass_index_by_name("transparent_oit").expect("Mesh shader must have pass named 'transparent_oit'") as u8;
+        let transparent_oit_backface = material.find_pass_index_by_name("transparent_oit_backface").expect("Mesh shader must have pass named 'transparent_oit_backface'") as u8;
+        let transparent_oit_untextured = material.find_pass_index_by_name("transparent_oit_untextured").expect("Mesh shader must have pass named 'transparent_oit_untextured'") as u8;
+        let transparent_oit_backface_untextured = material.find_pass_index_by_name("transparent_oit_backface_untextured").expect("Mesh shader must have pass named 'transparent_oit_backface_untextured'") as u8;
         let wireframe = material.find_pass_index_by_name("wireframe").expect("Mesh shader must have pass named 'wireframe'") as u8;
 
         MeshAdvShaderPassIndices {
@@ -168,10 +195,18 @@ impl MeshAdvShaderPassIndices {
             opaque_backface_untextured,
             //opaque_alphaclip_untextured,
             //opaque_alphaclip_backface_untextured,
+            opaque_surface,
+            opaque_surface_backface,
+            opaque_surface_untextured,
+            opaque_surface_backface_untextured,
             transparent,
             transparent_backface,
             transparent_untextured,
             transparent_backface_untextured,
+            transparent_oit,
+            transparent_oit_backface,
+            transparent_oit_untextured,
+            transparent_oit_backface_untextured,
             wireframe
         }
     }
@@ -181,8 +216,26 @@ impl MeshAdvShaderPassIndices {
         material_data: &MeshAdvMaterialData,
         render_phase_index: RenderPhaseIndex,
         untextured: bool,
+        weighted_blended_oit: bool,
+        write_surface_data: bool,
     ) -> usize {
-        let pass_index = if render_phase_index == OpaqueRenderPhase::render_phase_index() {
+        let pass_index = if render_phase_index == OpaqueRenderPhase::render_phase_index()
+            && write_surface_data
+        {
+            if material_data.backface_culling {
+                if untextured {
+                    self.opaque_surface_untextured
+                } else {
+                    self.opaque_surface
+                }
+            } else {
+                if untextured {
+                    self.opaque_surface_backface_untextured
+                } else {
+                    self.opaque_surface_backface
+                }
+            }
+        } else if render_phase_index == OpaqueRenderPhase::render_phase_index() {
             if material_data.backface_culling {
                 if untextured {
                     self.opaque_untextured
@@ -196,6 +249,22 @@ impl MeshAdvShaderPassIndices {
                     self.opaque_backface
                 }
             }
+        } else if render_phase_index == TransparentRenderPhase::render_phase_index()
+            && weighted_blended_oit
+        {
+            if material_data.backface_culling {
+                if untextured {
+                    self.transparent_oit_untextured
+                } else {
+                    self.transparent_oit
+                }
+            } else {
+                if untextured {
+                    self.transparent_oit_backface_untextured
+                } else {
+                    self.transparent_oit_backface
+                }
+            }
         } else if render_phase_index == TransparentRenderPhase::render_phase_index() {
             if material_data.backface_culling {
                 if untextured {
@@ -240,12 +309,16 @@ impl MeshAdvAssetPart {
         &self,
         view: &RenderView,
         render_phase_index: RenderPhaseIndex,
+        weighted_blended_oit: bool,
+        write_surface_data: bool,
     ) -> usize {
         let untextured = view.feature_flag_is_relevant::<MeshAdvUntexturedRenderFeatureFlag>();
         self.pass_indices.get_material_pass_index(
             self.mesh_material.material_data(),
             render_phase_index,
             untextured,
+            weighted_blended_oit,
+            write_surface_data,
         )
     }
 
@@ -253,18 +326,48 @@ impl MeshAdvAssetPart {
         &self,
         view: &RenderView,
         render_phase_index: RenderPhaseIndex,
+        weighted_blended_oit: bool,
+        write_surface_data: bool,
     ) -> &ResourceArc<MaterialPassResource> {
-        &self.mesh_material.material_asset().passes
-            [self.get_material_pass_index(view, render_phase_index)]
+        &self.mesh_material.material_asset().passes[self.get_material_pass_index(
+            view,
+            render_phase_index,
+            weighted_blended_oit,
+            write_surface_data,
+        )]
         .material_pass_resource
     }
 }
 
-pub struct MeshAdvAssetInner {
+pub struct MeshAdvAssetLod {
     pub mesh_parts: Vec<MeshAdvAssetPart>,
+    pub screen_size: f32,
+}
+
+pub struct MeshAdvAssetInner {
+    pub lods: Vec<MeshAdvAssetLod>,
     pub asset_data: MeshAdvAssetData,
 }
 
+impl MeshAdvAssetInner {
+    // Picks the most detailed LOD whose screen size threshold is met, see
+    // lod_selection::select_lod_index
+    pub fn select_lod(
+        &self,
+        screen_size: f32,
+        previous_lod: Option<usize>,
+        hysteresis: f32,
+    ) -> usize {
+        crate::features::mesh_adv::select_lod_index(
+            self.lods.len(),
+            |lod_index| self.lods[lod_index].screen_size,
+            screen_size,
+            previous_lod,
+            hysteresis,
+        )
+    }
+}
+
 #[derive(TypeUuid, Clone)]
 #[uuid = "8a7afe47-8abc-4383-a7c8-0f09026b3019"]
 pub struct MeshAdvAsset {
@@ -293,43 +396,56 @@ impl DefaultAssetTypeLoadHandler<MeshAdvAssetData, MeshAdvAsset> for MeshAdvLoad
             .unwrap()
             .buffer_byte_offset();
 
-        let mesh_parts: Vec<_> = mesh_asset
-            .mesh_parts
+        let lods: Vec<_> = mesh_asset
+            .lods
             .iter()
-            .map(|mesh_part| {
-                println!(
-                    "load asset {:?} {:?}",
-                    mesh_part.mesh_material,
-                    mesh_part.mesh_material.load_handle()
-                );
-                let mesh_material = asset_manager
-                    .latest_asset(&mesh_part.mesh_material)
-                    .unwrap();
-
-                let material_asset = mesh_material.material_asset();
-                let pass_indices = MeshAdvShaderPassIndices::new(&material_asset);
-
-                MeshAdvAssetPart {
-                    mesh_material: mesh_material.clone(),
-                    pass_indices,
-                    vertex_full_buffer_offset_in_bytes: vertex_full_buffer_byte_offset
-                        + mesh_part.vertex_full_buffer_offset_in_bytes,
-                    vertex_full_buffer_size_in_bytes: mesh_part.vertex_full_buffer_size_in_bytes,
-                    vertex_position_buffer_offset_in_bytes: vertex_position_buffer_byte_offset
-                        + mesh_part.vertex_position_buffer_offset_in_bytes,
-                    vertex_position_buffer_size_in_bytes: mesh_part
-                        .vertex_position_buffer_size_in_bytes,
-                    index_buffer_offset_in_bytes: index_buffer_byte_offset
-                        + mesh_part.index_buffer_offset_in_bytes,
-                    index_buffer_size_in_bytes: mesh_part.index_buffer_size_in_bytes,
-                    index_type: mesh_part.index_type,
+            .map(|lod| {
+                let mesh_parts: Vec<_> = lod
+                    .mesh_parts
+                    .iter()
+                    .map(|mesh_part| {
+                        log::trace!(
+                            "load asset {:?} {:?}",
+                            mesh_part.mesh_material,
+                            mesh_part.mesh_material.load_handle()
+                        );
+                        let mesh_material = asset_manager
+                            .latest_asset(&mesh_part.mesh_material)
+                            .unwrap();
+
+                        let material_asset = mesh_material.material_asset();
+                        let pass_indices = MeshAdvShaderPassIndices::new(&material_asset);
+
+                        MeshAdvAssetPart {
+                            mesh_material: mesh_material.clone(),
+                            pass_indices,
+                            vertex_full_buffer_offset_in_bytes: vertex_full_buffer_byte_offset
+                                + mesh_part.vertex_full_buffer_offset_in_bytes,
+                            vertex_full_buffer_size_in_bytes: mesh_part
+                                .vertex_full_buffer_size_in_bytes,
+                            vertex_position_buffer_offset_in_bytes:
+                                vertex_position_buffer_byte_offset
+                                    + mesh_part.vertex_position_buffer_offset_in_bytes,
+                            vertex_position_buffer_size_in_bytes: mesh_part
+                                .vertex_position_buffer_size_in_bytes,
+                            index_buffer_offset_in_bytes: index_buffer_byte_offset
+                                + mesh_part.index_buffer_offset_in_bytes,
+                            index_buffer_size_in_bytes: mesh_part.index_buffer_size_in_bytes,
+                            index_type: mesh_part.index_type,
+                        }
+                    })
+                    .collect();
+
+                MeshAdvAssetLod {
+                    mesh_parts,
+                    screen_size: lod.screen_size,
                 }
             })
             .collect();
 
         let inner = MeshAdvAssetInner {
             asset_data: mesh_asset,
-            mesh_parts,
+            lods,
         };
 
         Ok(MeshAdvAsset {
@@ -8,7 +8,7 @@ use rafx::assets::{
     asset_type_handler, AssetLookup, AssetManager, AssetTypeHandler, DynAssetLookup, LoadQueues,
     LoadRequest, PushBuffer, UploadAssetOp, UploadAssetOpResult,
 };
-use rafx::framework::upload::UploadQueueContext;
+use rafx::framework::upload::{UploadPriority, UploadQueueContext};
 use rafx::framework::ResourceArc;
 use rafx::framework::{BufferResource, RenderResources};
 use rafx::render_feature_renderer_prelude::AssetResource;
@@ -263,6 +263,7 @@ impl MeshAdvBufferAssetUploadQueue {
             request.asset.data,
             dst_buffer,
             dst_byte_offset,
+            UploadPriority::Normal,
         )
     }
 }
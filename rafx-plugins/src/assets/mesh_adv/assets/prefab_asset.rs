@@ -42,11 +42,36 @@ pub struct PrefabAdvAssetDataObjectLight {
     pub spot: Option<PrefabAdvAssetDataObjectLightSpot>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PrefabAdvAssetDataObjectCameraProjection {
+    Perspective {
+        // Vertical field of view in radians
+        y_fov: f32,
+        // If none, the aspect ratio of the viewport should be used
+        aspect_ratio: Option<f32>,
+        z_near: f32,
+        // If none, the projection is infinite
+        z_far: Option<f32>,
+    },
+    Orthographic {
+        x_mag: f32,
+        y_mag: f32,
+        z_near: f32,
+        z_far: f32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefabAdvAssetDataObjectCamera {
+    pub projection: PrefabAdvAssetDataObjectCameraProjection,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PrefabAdvAssetDataObject {
     pub transform: PrefabAdvAssetDataObjectTransform,
     pub model: Option<PrefabAdvAssetDataObjectModel>,
     pub light: Option<PrefabAdvAssetDataObjectLight>,
+    pub camera: Option<PrefabAdvAssetDataObjectCamera>,
 }
 
 #[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
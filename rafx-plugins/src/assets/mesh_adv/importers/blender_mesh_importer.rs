@@ -1,5 +1,6 @@
 use crate::assets::mesh_adv::{
-    MeshAdvAssetData, MeshAdvBufferAssetData, MeshAdvPartAssetData, MeshMaterialAdvAsset,
+    MeshAdvAssetData, MeshAdvBufferAssetData, MeshAdvLodAssetData, MeshAdvPartAssetData,
+    MeshMaterialAdvAsset,
 };
 use crate::features::mesh_adv::{MeshVertexFull, MeshVertexPosition};
 use distill::importer::{ImportedAsset, Importer, ImporterValue};
@@ -73,7 +74,7 @@ impl Importer for MeshAdvBlenderImporter {
     where
         Self: Sized,
     {
-        6
+        7
     }
 
     fn version(&self) -> u32 {
@@ -253,7 +254,10 @@ impl Importer for MeshAdvBlenderImporter {
         };
 
         let asset_data = MeshAdvAssetData {
-            mesh_parts,
+            lods: vec![MeshAdvLodAssetData {
+                mesh_parts,
+                screen_size: 0.0,
+            }],
             vertex_full_buffer: vertex_full_buffer_handle,
             vertex_position_buffer: vertex_position_buffer_handle,
             index_buffer: index_buffer_handle,
@@ -82,7 +82,7 @@ impl Importer for MeshAdvBlenderPrefabImporter {
     where
         Self: Sized,
     {
-        4
+        5
     }
 
     fn version(&self) -> u32 {
@@ -156,6 +156,7 @@ impl Importer for MeshAdvBlenderPrefabImporter {
                 transform,
                 model,
                 light,
+                camera: None,
             });
         }
 
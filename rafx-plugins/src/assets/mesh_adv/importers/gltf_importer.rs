@@ -1,6 +1,11 @@
 use crate::assets::mesh_adv::{
-    MeshAdvAssetData, MeshAdvBufferAssetData, MeshAdvMaterialData, MeshAdvPartAssetData,
-    MeshMaterialAdvAsset, MeshMaterialAdvAssetData,
+    MeshAdvAsset, MeshAdvAssetData, MeshAdvBufferAssetData, MeshAdvLodAssetData,
+    MeshAdvMaterialData, MeshAdvPartAssetData, MeshMaterialAdvAsset, MeshMaterialAdvAssetData,
+    ModelAdvAsset, ModelAdvAssetData, ModelAdvAssetDataLod, PrefabAdvAssetData,
+    PrefabAdvAssetDataObject, PrefabAdvAssetDataObjectCamera,
+    PrefabAdvAssetDataObjectCameraProjection, PrefabAdvAssetDataObjectLight,
+    PrefabAdvAssetDataObjectLightKind, PrefabAdvAssetDataObjectLightSpot,
+    PrefabAdvAssetDataObjectModel, PrefabAdvAssetDataObjectTransform,
 };
 use crate::features::mesh_adv::{MeshVertexFull, MeshVertexPosition};
 use distill::core::AssetUuid;
@@ -21,15 +26,13 @@ use serde::{Deserialize, Serialize};
 use std::io::Read;
 use type_uuid::*;
 
-//TODO: These are extensions that might be interesting to try supporting. In particular, lights,
-// LOD, and clearcoat
+//TODO: These are extensions that might be interesting to try supporting. In particular,
+// clearcoat
 // Good explanations of upcoming extensions here: https://medium.com/@babylonjs/gltf-extensions-in-babylon-js-b3fa56de5483
 //KHR_materials_clearcoat: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_clearcoat/README.md
 //KHR_materials_pbrSpecularGlossiness: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_pbrSpecularGlossiness/README.md
 //KHR_materials_unlit: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_unlit/README.md
-//KHR_lights_punctual (directional, point, spot): https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_lights_punctual/README.md
 //EXT_lights_image_based: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/EXT_lights_image_based/README.md
-//MSFT_lod: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/MSFT_lod/README.md
 //MSFT_packing_normalRoughnessMetallic: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/MSFT_packing_normalRoughnessMetallic/README.md
 // Normal: NG, Roughness: B, Metallic: A
 //MSFT_packing_occlusionRoughnessMetallic: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/MSFT_packing_occlusionRoughnessMetallic/README.md
@@ -101,6 +104,38 @@ struct BufferToImport {
     asset: MeshAdvBufferAssetData,
 }
 
+struct PrefabToImport {
+    id: GltfObjectId,
+    asset: PrefabAdvAssetData,
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "5ee7a7de-5a6c-4e4f-a0c4-5b3b76e5d1c3"]
+pub struct MeshAdvGltfImporterOptions {
+    // Number of LODs to generate (in addition to the original mesh) for meshes that don't have
+    // authored LODs via MSFT_lod. Requires the meshopt feature, otherwise no LODs are generated.
+    // Generated LODs share the original mesh's vertices and only add indices.
+    pub generated_lod_count: u32,
+    // Fraction of triangles to keep in each generated LOD relative to the previous one
+    pub generated_lod_triangle_ratio: f32,
+    // Maximum error (relative to mesh extents) the simplifier may introduce per generated LOD
+    pub generated_lod_target_error: f32,
+    // LOD n is used while the projected screen size is at least this ratio raised to n + 1 (unless
+    // MSFT_screencoverage provides it)
+    pub lod_screen_size_ratio: f32,
+}
+
+impl Default for MeshAdvGltfImporterOptions {
+    fn default() -> Self {
+        MeshAdvGltfImporterOptions {
+            generated_lod_count: 0,
+            generated_lod_triangle_ratio: 0.5,
+            generated_lod_target_error: 0.01,
+            lod_screen_size_ratio: 0.5,
+        }
+    }
+}
+
 // The asset state is stored in this format using Vecs
 #[derive(TypeUuid, Serialize, Deserialize, Default, Clone)]
 #[uuid = "980d063c-1923-42f8-b4fa-b819fbfd8a5e"]
@@ -113,6 +148,10 @@ pub struct MeshAdvGltfImporterStateStable {
     material_instance_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
     mesh_material_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
     mesh_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
+    #[serde(default)]
+    model_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
+    #[serde(default)]
+    prefab_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
 }
 
 impl From<MeshAdvGltfImporterStateUnstable> for MeshAdvGltfImporterStateStable {
@@ -148,6 +187,16 @@ impl From<MeshAdvGltfImporterStateUnstable> for MeshAdvGltfImporterStateStable {
             .into_iter()
             .sorted_by_key(|(id, _uuid)| id.clone())
             .collect();
+        stable.model_asset_uuids = other
+            .model_asset_uuids
+            .into_iter()
+            .sorted_by_key(|(id, _uuid)| id.clone())
+            .collect();
+        stable.prefab_asset_uuids = other
+            .prefab_asset_uuids
+            .into_iter()
+            .sorted_by_key(|(id, _uuid)| id.clone())
+            .collect();
         stable
     }
 }
@@ -163,6 +212,8 @@ pub struct MeshAdvGltfImporterStateUnstable {
     material_instance_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
     mesh_material_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
     mesh_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
+    model_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
+    prefab_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
 }
 
 impl From<MeshAdvGltfImporterStateStable> for MeshAdvGltfImporterStateUnstable {
@@ -175,6 +226,8 @@ impl From<MeshAdvGltfImporterStateStable> for MeshAdvGltfImporterStateUnstable {
             other.material_instance_asset_uuids.into_iter().collect();
         unstable.mesh_material_asset_uuids = other.mesh_material_asset_uuids.into_iter().collect();
         unstable.mesh_asset_uuids = other.mesh_asset_uuids.into_iter().collect();
+        unstable.model_asset_uuids = other.model_asset_uuids.into_iter().collect();
+        unstable.prefab_asset_uuids = other.prefab_asset_uuids.into_iter().collect();
         unstable
     }
 }
@@ -187,14 +240,14 @@ impl Importer for MeshAdvGltfImporter {
     where
         Self: Sized,
     {
-        29
+        33
     }
 
     fn version(&self) -> u32 {
         Self::version_static()
     }
 
-    type Options = ();
+    type Options = MeshAdvGltfImporterOptions;
 
     type State = MeshAdvGltfImporterStateStable;
 
@@ -203,7 +256,7 @@ impl Importer for MeshAdvGltfImporter {
         &self,
         op: &mut ImportOp,
         source: &mut dyn Read,
-        _options: &Self::Options,
+        options: &Self::Options,
         stable_state: &mut Self::State,
     ) -> distill::importer::Result<ImporterValue> {
         let mut unstable_state: MeshAdvGltfImporterStateUnstable = stable_state.clone().into();
@@ -221,6 +274,8 @@ impl Importer for MeshAdvGltfImporter {
 
         let (doc, buffers, images) = result.unwrap();
 
+        let msft_lods = extract_msft_lods(&doc, &bytes);
+
         // Accumulate everything we will import in this list
         let mut imported_assets = Vec::new();
 
@@ -345,6 +400,8 @@ impl Importer for MeshAdvGltfImporter {
             &doc,
             &buffers,
             &mesh_material_index_to_handle,
+            &msft_lods,
+            options,
         )?;
 
         let mut buffer_index_to_handle = vec![];
@@ -373,7 +430,7 @@ impl Importer for MeshAdvGltfImporter {
             });
         }
 
-        //let mut mesh_index_to_uuid_lookup = vec![];
+        let mut mesh_index_to_handle = vec![];
         for mesh_to_import in meshes_to_import {
             // Find the UUID associated with this image or create a new one
             let mesh_uuid = *unstable_state
@@ -388,6 +445,11 @@ impl Importer for MeshAdvGltfImporter {
 
             log::debug!("Importing mesh uuid {:?}", mesh_uuid);
 
+            let mesh_handle = make_handle::<MeshAdvAsset>(mesh_uuid);
+
+            // Push the UUID into the list so that we have an O(1) lookup for mesh index to UUID
+            mesh_index_to_handle.push(mesh_handle);
+
             // Create the asset
             imported_assets.push(ImportedAsset {
                 id: mesh_uuid,
@@ -399,6 +461,74 @@ impl Importer for MeshAdvGltfImporter {
             });
         }
 
+        //
+        // Models (one per mesh, so that scene nodes can reference them from a prefab)
+        //
+        let mut mesh_index_to_model_handle = vec![];
+        for mesh in doc.meshes() {
+            let model_id = mesh
+                .name()
+                .map(|s| GltfObjectId::Name(s.to_string()))
+                .unwrap_or_else(|| GltfObjectId::Index(mesh.index()));
+
+            let model_uuid = *unstable_state
+                .model_asset_uuids
+                .entry(model_id.clone())
+                .or_insert_with(|| op.new_asset_uuid());
+
+            let model_handle = make_handle::<ModelAdvAsset>(model_uuid);
+            mesh_index_to_model_handle.push(model_handle);
+
+            let mut search_tags: Vec<(String, Option<String>)> = vec![];
+            if let GltfObjectId::Name(name) = &model_id {
+                search_tags.push(("name".to_string(), Some(name.clone())));
+            }
+
+            log::debug!("Importing model uuid {:?}", model_uuid);
+
+            let model_asset = ModelAdvAssetData {
+                lods: vec![ModelAdvAssetDataLod {
+                    mesh: mesh_index_to_handle[mesh.index()].clone(),
+                }],
+            };
+
+            imported_assets.push(ImportedAsset {
+                id: model_uuid,
+                search_tags,
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(model_asset),
+            });
+        }
+
+        //
+        // Prefabs (one per scene)
+        //
+        let prefabs_to_import = extract_prefabs_to_import(&doc, &mesh_index_to_model_handle);
+        for prefab_to_import in prefabs_to_import {
+            let prefab_uuid = *unstable_state
+                .prefab_asset_uuids
+                .entry(prefab_to_import.id.clone())
+                .or_insert_with(|| op.new_asset_uuid());
+
+            let mut search_tags: Vec<(String, Option<String>)> = vec![];
+            if let GltfObjectId::Name(name) = &prefab_to_import.id {
+                search_tags.push(("name".to_string(), Some(name.clone())));
+            }
+
+            log::debug!("Importing prefab uuid {:?}", prefab_uuid);
+
+            imported_assets.push(ImportedAsset {
+                id: prefab_uuid,
+                search_tags,
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(prefab_to_import.asset),
+            });
+        }
+
         *stable_state = unstable_state.into();
 
         Ok(ImporterValue {
@@ -487,7 +617,7 @@ fn extract_images_to_import(
         );
 
         let (format, mip_generation) = ImageAssetData::default_format_and_mip_generation();
-        let asset_data = ImageAssetData::from_raw_rgba32(
+        let mut asset_data = ImageAssetData::from_raw_rgba32(
             image_data.width,
             image_data.height,
             color_space,
@@ -497,6 +627,8 @@ fn extract_images_to_import(
             converted_image.as_raw().as_slice(),
         )
         .unwrap();
+        // These images are only sampled through the material DB, which binds the resident image view
+        asset_data.allow_mip_streaming = true;
 
         let id = image
             .name()
@@ -668,12 +800,306 @@ fn extract_materials_to_import(
     materials_to_import
 }
 
+struct MeshPrimitiveToProcess {
+    indices: Vec<u32>,
+    // Progressively simplified copies of indices, one per generated LOD. They reference the same
+    // vertices as indices so that generated LODs don't duplicate vertex data
+    generated_lod_indices: Vec<Vec<u32>>,
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    tex_coords: Vec<[f32; 2]>,
+    mesh_material: Handle<MeshMaterialAdvAsset>,
+}
+
+// Authored LODs have their own primitives. Generated LODs are stored within the primitives of the
+// most detailed LOD (see MeshPrimitiveToProcess::generated_lod_indices)
+struct MeshLodsToProcess {
+    authored_lods: Vec<Vec<MeshPrimitiveToProcess>>,
+    // One per LOD, including generated LODs
+    screen_sizes: Vec<f32>,
+}
+
+// Lower LODs provided by a MSFT_lod extension on a node that references a mesh
+struct GltfMeshLods {
+    lod_mesh_indices: Vec<usize>,
+    screen_coverage: Vec<f32>,
+}
+
+// MSFT_lod is not supported by the gltf crate, so we read it out of the raw json. The extension is
+// declared on nodes, but since LODs are stored in the mesh asset we key the result by the mesh of
+// the node that declares it. MSFT_screencoverage (stored in the node's extras) provides a
+// per-LOD threshold as a fraction of the screen's area. LOD screen sizes are a fraction of the
+// screen's height, so we use its square root.
+fn extract_msft_lods(
+    doc: &gltf::Document,
+    bytes: &[u8],
+) -> FnvHashMap<usize, GltfMeshLods> {
+    let mut mesh_lods = FnvHashMap::default();
+
+    let json_bytes = if bytes.starts_with(b"glTF") {
+        match gltf::binary::Glb::from_slice(bytes) {
+            Ok(glb) => glb.json.into_owned(),
+            Err(_) => return mesh_lods,
+        }
+    } else {
+        bytes.to_vec()
+    };
+
+    let root: serde_json::Value = match serde_json::from_slice(&json_bytes) {
+        Ok(root) => root,
+        Err(_) => return mesh_lods,
+    };
+
+    let json_nodes = match root.get("nodes").and_then(|x| x.as_array()) {
+        Some(json_nodes) => json_nodes,
+        None => return mesh_lods,
+    };
+
+    let nodes: Vec<_> = doc.nodes().collect();
+    for (node_index, json_node) in json_nodes.iter().enumerate() {
+        let lod_node_indices = match json_node
+            .pointer("/extensions/MSFT_lod/ids")
+            .and_then(|x| x.as_array())
+        {
+            Some(lod_node_indices) => lod_node_indices,
+            None => continue,
+        };
+
+        let mesh_index = match nodes.get(node_index).and_then(|x| x.mesh()) {
+            Some(mesh) => mesh.index(),
+            None => continue,
+        };
+
+        let mut lod_mesh_indices = Vec::with_capacity(lod_node_indices.len());
+        for lod_node_index in lod_node_indices {
+            let lod_mesh = lod_node_index
+                .as_u64()
+                .and_then(|x| nodes.get(x as usize))
+                .and_then(|x| x.mesh());
+
+            if let Some(lod_mesh) = lod_mesh {
+                lod_mesh_indices.push(lod_mesh.index());
+            } else {
+                log::warn!(
+                    "MSFT_lod on node {} references node {:?} which does not have a mesh",
+                    node_index,
+                    lod_node_index
+                );
+            }
+        }
+
+        let screen_coverage = json_node
+            .pointer("/extras/MSFT_screencoverage")
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|x| x.as_f64())
+                    .map(|x| (x as f32).max(0.0).sqrt())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        mesh_lods.insert(
+            mesh_index,
+            GltfMeshLods {
+                lod_mesh_indices,
+                screen_coverage,
+            },
+        );
+    }
+
+    mesh_lods
+}
+
+fn read_mesh_primitives(
+    mesh: &gltf::Mesh,
+    buffers: &[GltfBufferData],
+    mesh_material_index_to_handle: &[Handle<MeshMaterialAdvAsset>],
+) -> distill::importer::Result<Vec<MeshPrimitiveToProcess>> {
+    let mut primitives = Vec::with_capacity(mesh.primitives().len());
+
+    for primitive in mesh.primitives() {
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|x| &**x));
+
+        let positions = reader.read_positions();
+        let normals = reader.read_normals();
+        let tex_coords = reader.read_tex_coords(0);
+        let indices = reader.read_indices();
+
+        if let (Some(indices), Some(positions), Some(normals), Some(tex_coords)) =
+            (indices, positions, normals, tex_coords)
+        {
+            let mesh_material = if let Some(material_index) = primitive.material().index() {
+                mesh_material_index_to_handle[material_index].clone()
+            } else {
+                return Err(distill::importer::Error::Boxed(Box::new(
+                    GltfImportError::new("A mesh primitive did not have a material"),
+                )));
+            };
+
+            primitives.push(MeshPrimitiveToProcess {
+                indices: indices.into_u32().collect(),
+                generated_lod_indices: Vec::default(),
+                positions: positions.collect(),
+                normals: normals.collect(),
+                tex_coords: tex_coords.into_f32().collect(),
+                mesh_material,
+            });
+        } else {
+            log::error!(
+                "Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"
+            );
+
+            return Err(distill::importer::Error::Boxed(Box::new(
+                GltfImportError::new("Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"),
+            )));
+        }
+    }
+
+    Ok(primitives)
+}
+
+// Appends a simplified index list to each primitive's generated_lod_indices, keeping roughly the
+// given fraction of triangles of the previous LOD. Returns false (leaving the primitives unchanged)
+// if the mesh could not be simplified meaningfully (i.e. it is already too coarse or simplifying
+// further would distort it too much)
+#[cfg(feature = "meshopt")]
+fn simplify_mesh_primitives(
+    primitives: &mut [MeshPrimitiveToProcess],
+    triangle_ratio: f32,
+    target_error: f32,
+) -> bool {
+    let mut original_index_count = 0;
+    let mut simplified_index_count = 0;
+    let mut simplified_indices = Vec::with_capacity(primitives.len());
+
+    for primitive in primitives.iter() {
+        let vertex_adapter = match meshopt::VertexDataAdapter::new(
+            rafx::base::memory::any_slice_as_bytes(&primitive.positions),
+            std::mem::size_of::<[f32; 3]>(),
+            0,
+        ) {
+            Ok(vertex_adapter) => vertex_adapter,
+            Err(_) => return false,
+        };
+
+        let previous_indices = primitive
+            .generated_lod_indices
+            .last()
+            .unwrap_or(&primitive.indices);
+
+        let target_index_count =
+            ((previous_indices.len() as f32 * triangle_ratio) as usize / 3) * 3;
+        let indices = meshopt::simplify(
+            previous_indices,
+            &vertex_adapter,
+            target_index_count,
+            target_error,
+        );
+
+        original_index_count += previous_indices.len();
+        simplified_index_count += indices.len();
+
+        // A primitive that collapses entirely would leave a hole, so keep it as-is
+        let indices = if indices.is_empty() {
+            previous_indices.clone()
+        } else {
+            indices
+        };
+
+        simplified_indices.push(indices);
+    }
+
+    // Not worth keeping a LOD that doesn't remove a meaningful amount of triangles
+    if simplified_index_count == 0
+        || simplified_index_count as f32 > original_index_count as f32 * 0.9
+    {
+        return false;
+    }
+
+    for (primitive, indices) in primitives.iter_mut().zip(simplified_indices) {
+        primitive.generated_lod_indices.push(indices);
+    }
+
+    true
+}
+
+fn build_mesh_lods(
+    mesh: &gltf::Mesh,
+    doc: &gltf::Document,
+    buffers: &[GltfBufferData],
+    mesh_material_index_to_handle: &[Handle<MeshMaterialAdvAsset>],
+    msft_lods: &FnvHashMap<usize, GltfMeshLods>,
+    options: &MeshAdvGltfImporterOptions,
+) -> distill::importer::Result<MeshLodsToProcess> {
+    let mut authored_lods = Vec::default();
+    authored_lods.push(read_mesh_primitives(
+        mesh,
+        buffers,
+        mesh_material_index_to_handle,
+    )?);
+
+    let mut screen_sizes = Vec::default();
+    if let Some(msft_lod) = msft_lods.get(&mesh.index()) {
+        // The LODs were authored, so use them as-is
+        for &lod_mesh_index in &msft_lod.lod_mesh_indices {
+            let lod_mesh = doc.meshes().nth(lod_mesh_index).unwrap();
+            authored_lods.push(read_mesh_primitives(
+                &lod_mesh,
+                buffers,
+                mesh_material_index_to_handle,
+            )?);
+        }
+
+        for lod_index in 0..authored_lods.len() {
+            screen_sizes.push(
+                msft_lod
+                    .screen_coverage
+                    .get(lod_index)
+                    .copied()
+                    .unwrap_or_else(|| options.lod_screen_size_ratio.powi(lod_index as i32 + 1)),
+            );
+        }
+    } else {
+        #[cfg(feature = "meshopt")]
+        for _ in 0..options.generated_lod_count {
+            if !simplify_mesh_primitives(
+                &mut authored_lods[0],
+                options.generated_lod_triangle_ratio,
+                options.generated_lod_target_error,
+            ) {
+                break;
+            }
+        }
+
+        // Every primitive has the same number of generated LODs
+        let generated_lod_count = authored_lods[0]
+            .first()
+            .map(|x| x.generated_lod_indices.len())
+            .unwrap_or(0);
+
+        for lod_index in 0..(generated_lod_count + 1) {
+            screen_sizes.push(options.lod_screen_size_ratio.powi(lod_index as i32 + 1));
+        }
+    }
+
+    // The least detailed LOD is used for anything smaller than the previous LOD's threshold
+    *screen_sizes.last_mut().unwrap() = 0.0;
+
+    Ok(MeshLodsToProcess {
+        authored_lods,
+        screen_sizes,
+    })
+}
+
 fn extract_meshes_to_import(
     op: &mut ImportOp,
     state: &mut MeshAdvGltfImporterStateUnstable,
     doc: &gltf::Document,
     buffers: &[GltfBufferData],
     mesh_material_index_to_handle: &[Handle<MeshMaterialAdvAsset>],
+    msft_lods: &FnvHashMap<usize, GltfMeshLods>,
+    options: &MeshAdvGltfImporterOptions,
 ) -> distill::importer::Result<(Vec<MeshToImport>, Vec<BufferToImport>)> {
     let mut meshes_to_import = Vec::with_capacity(doc.meshes().len());
     let mut buffers_to_import = Vec::with_capacity(doc.meshes().len() * 2);
@@ -686,90 +1112,84 @@ fn extract_meshes_to_import(
         let mut all_vertices_position = PushBuffer::new(16384);
         let mut all_indices = PushBuffer::new(16384);
 
-        let mut mesh_parts: Vec<MeshAdvPartAssetData> = Vec::with_capacity(mesh.primitives().len());
+        let lods_to_process = build_mesh_lods(
+            &mesh,
+            doc,
+            buffers,
+            mesh_material_index_to_handle,
+            msft_lods,
+            options,
+        )?;
+
+        let mut lods: Vec<_> = lods_to_process
+            .screen_sizes
+            .iter()
+            .map(|&screen_size| MeshAdvLodAssetData {
+                mesh_parts: Vec::default(),
+                screen_size,
+            })
+            .collect();
 
         //
-        // Iterate all mesh parts, building a single vertex and index buffer. Each MeshPart will
-        // hold offsets/lengths to their sections in the vertex/index buffers
+        // Iterate all LODs and their mesh parts, building a single vertex and index buffer. Each
+        // MeshPart will hold offsets/lengths to their sections in the vertex/index buffers.
+        // Generated LODs only add indices that reference the vertices of the most detailed LOD
         //
-        for primitive in mesh.primitives() {
-            let mesh_part = {
-                let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|x| &**x));
-
-                let positions = reader.read_positions();
-                let normals = reader.read_normals();
-                let tex_coords = reader.read_tex_coords(0);
-                let indices = reader.read_indices();
-
-                if let (Some(indices), Some(positions), Some(normals), Some(tex_coords)) =
-                    (indices, positions, normals, tex_coords)
-                {
-                    let part_indices: Vec<u32> = indices.into_u32().collect();
-
-                    let positions: Vec<_> = positions.collect();
-                    let normals: Vec<_> = normals.collect();
-                    let tex_coords: Vec<_> = tex_coords.into_f32().collect();
-
-                    let part_data = super::util::process_mesh_part(
-                        &part_indices,
-                        &positions,
-                        &normals,
-                        &tex_coords,
+        for (lod_index, primitives) in lods_to_process.authored_lods.iter().enumerate() {
+            for primitive in primitives {
+                let (part_data, generated_lod_index_data) =
+                    super::util::process_mesh_part_with_lods(
+                        &primitive.indices,
+                        &primitive.generated_lod_indices,
+                        &primitive.positions,
+                        &primitive.normals,
+                        &primitive.tex_coords,
                         &mut all_vertices_full,
                         &mut all_vertices_position,
                         &mut all_indices,
                     );
 
-                    //
-                    // Positions and indices for the visibility system
-                    //
-                    for index in part_indices {
+                //
+                // Positions and indices for the visibility system. The most detailed LOD is used
+                // so that bounds are consistent regardless of which LOD is drawn
+                //
+                if lod_index == 0 {
+                    for &index in &primitive.indices {
                         all_position_indices.push(index as u32);
                     }
 
-                    for i in 0..positions.len() {
-                        all_positions.push(Vec3::new(
-                            positions[i][0],
-                            positions[i][1],
-                            positions[i][2],
-                        ));
+                    for position in &primitive.positions {
+                        all_positions.push(Vec3::new(position[0], position[1], position[2]));
                     }
+                }
 
-                    let mesh_material = if let Some(material_index) = primitive.material().index() {
-                        mesh_material_index_to_handle[material_index].clone()
-                    } else {
-                        return Err(distill::importer::Error::Boxed(Box::new(
-                            GltfImportError::new("A mesh primitive did not have a material"),
-                        )));
-                    };
-
-                    Some(MeshAdvPartAssetData {
-                        mesh_material,
-                        vertex_full_buffer_offset_in_bytes: part_data
-                            .vertex_full_buffer_offset_in_bytes,
-                        vertex_full_buffer_size_in_bytes: part_data
-                            .vertex_full_buffer_size_in_bytes,
-                        vertex_position_buffer_offset_in_bytes: part_data
-                            .vertex_position_buffer_offset_in_bytes,
-                        vertex_position_buffer_size_in_bytes: part_data
-                            .vertex_position_buffer_size_in_bytes,
-                        index_buffer_offset_in_bytes: part_data.index_buffer_offset_in_bytes,
-                        index_buffer_size_in_bytes: part_data.index_buffer_size_in_bytes,
-                        index_type: part_data.index_type,
-                    })
-                } else {
-                    log::error!(
-                        "Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"
-                    );
+                let mesh_part = MeshAdvPartAssetData {
+                    mesh_material: primitive.mesh_material.clone(),
+                    vertex_full_buffer_offset_in_bytes: part_data
+                        .vertex_full_buffer_offset_in_bytes,
+                    vertex_full_buffer_size_in_bytes: part_data.vertex_full_buffer_size_in_bytes,
+                    vertex_position_buffer_offset_in_bytes: part_data
+                        .vertex_position_buffer_offset_in_bytes,
+                    vertex_position_buffer_size_in_bytes: part_data
+                        .vertex_position_buffer_size_in_bytes,
+                    index_buffer_offset_in_bytes: part_data.index_buffer_offset_in_bytes,
+                    index_buffer_size_in_bytes: part_data.index_buffer_size_in_bytes,
+                    index_type: part_data.index_type,
+                };
 
-                    return Err(distill::importer::Error::Boxed(Box::new(
-                        GltfImportError::new("Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"),
-                    )));
+                for (generated_lod_index, index_data) in
+                    generated_lod_index_data.into_iter().enumerate()
+                {
+                    lods[lod_index + generated_lod_index + 1].mesh_parts.push(
+                        MeshAdvPartAssetData {
+                            index_buffer_offset_in_bytes: index_data.index_buffer_offset_in_bytes,
+                            index_buffer_size_in_bytes: index_data.index_buffer_size_in_bytes,
+                            ..mesh_part.clone()
+                        },
+                    );
                 }
-            };
 
-            if let Some(mesh_part) = mesh_part {
-                mesh_parts.push(mesh_part);
+                lods[lod_index].mesh_parts.push(mesh_part);
             }
         }
 
@@ -851,7 +1271,7 @@ fn extract_meshes_to_import(
         };
 
         let asset = MeshAdvAssetData {
-            mesh_parts,
+            lods,
             vertex_full_buffer: vertex_full_buffer_handle,
             vertex_position_buffer: vertex_position_buffer_handle,
             index_buffer: index_buffer_handle,
@@ -868,10 +1288,11 @@ fn extract_meshes_to_import(
         // Verify that we iterate meshes in order so that our resulting assets are in order
         assert!(mesh.index() == meshes_to_import.len());
         log::debug!(
-            "Importing Mesh name: {:?} index: {} mesh_parts count: {}",
+            "Importing Mesh name: {:?} index: {} lod count: {} mesh_parts count: {}",
             mesh.name(),
             mesh.index(),
-            mesh_to_import.asset.mesh_parts.len()
+            mesh_to_import.asset.lods.len(),
+            mesh_to_import.asset.lods[0].mesh_parts.len()
         );
 
         meshes_to_import.push(mesh_to_import);
@@ -879,3 +1300,259 @@ fn extract_meshes_to_import(
 
     Ok((meshes_to_import, buffers_to_import))
 }
+
+fn extract_prefabs_to_import(
+    doc: &gltf::Document,
+    mesh_index_to_model_handle: &[Handle<ModelAdvAsset>],
+) -> Vec<PrefabToImport> {
+    let mut prefabs_to_import = Vec::with_capacity(doc.scenes().len());
+
+    for scene in doc.scenes() {
+        let mut objects = Vec::default();
+        for node in scene.nodes() {
+            extract_prefab_objects_from_node(
+                &node,
+                glam::Mat4::IDENTITY,
+                mesh_index_to_model_handle,
+                &mut objects,
+            );
+        }
+
+        let id = scene
+            .name()
+            .map(|s| GltfObjectId::Name(s.to_string()))
+            .unwrap_or_else(|| GltfObjectId::Index(scene.index()));
+
+        log::debug!(
+            "Importing Scene name: {:?} index: {} object count: {}",
+            scene.name(),
+            scene.index(),
+            objects.len()
+        );
+
+        prefabs_to_import.push(PrefabToImport {
+            id,
+            asset: PrefabAdvAssetData { objects },
+        });
+    }
+
+    prefabs_to_import
+}
+
+// Prefabs are a flat list of objects, so the node hierarchy is collapsed by baking parent
+// transforms into each object's transform
+fn extract_prefab_objects_from_node(
+    node: &gltf::Node,
+    parent_transform: glam::Mat4,
+    mesh_index_to_model_handle: &[Handle<ModelAdvAsset>],
+    objects: &mut Vec<PrefabAdvAssetDataObject>,
+) {
+    let local_transform = glam::Mat4::from_cols_array_2d(&node.transform().matrix());
+    let world_transform = parent_transform * local_transform;
+
+    let model = node.mesh().map(|mesh| PrefabAdvAssetDataObjectModel {
+        model: mesh_index_to_model_handle[mesh.index()].clone(),
+    });
+
+    let light = node.light().map(|light| {
+        use gltf::khr_lights_punctual::Kind;
+        let (kind, spot) = match light.kind() {
+            Kind::Directional => (PrefabAdvAssetDataObjectLightKind::Directional, None),
+            Kind::Point => (PrefabAdvAssetDataObjectLightKind::Point, None),
+            Kind::Spot {
+                inner_cone_angle,
+                outer_cone_angle,
+            } => (
+                PrefabAdvAssetDataObjectLightKind::Spot,
+                Some(PrefabAdvAssetDataObjectLightSpot {
+                    inner_angle: inner_cone_angle,
+                    outer_angle: outer_cone_angle,
+                }),
+            ),
+        };
+
+        PrefabAdvAssetDataObjectLight {
+            color: light.color().into(),
+            kind,
+            intensity: light.intensity(),
+            range: light.range(),
+            spot,
+        }
+    });
+
+    let camera = node.camera().map(|camera| {
+        use gltf::camera::Projection;
+        let projection = match camera.projection() {
+            Projection::Perspective(perspective) => {
+                PrefabAdvAssetDataObjectCameraProjection::Perspective {
+                    y_fov: perspective.yfov(),
+                    aspect_ratio: perspective.aspect_ratio(),
+                    z_near: perspective.znear(),
+                    z_far: perspective.zfar(),
+                }
+            }
+            Projection::Orthographic(orthographic) => {
+                PrefabAdvAssetDataObjectCameraProjection::Orthographic {
+                    x_mag: orthographic.xmag(),
+                    y_mag: orthographic.ymag(),
+                    z_near: orthographic.znear(),
+                    z_far: orthographic.zfar(),
+                }
+            }
+        };
+
+        PrefabAdvAssetDataObjectCamera { projection }
+    });
+
+    if model.is_some() || light.is_some() || camera.is_some() {
+        let (scale, rotation, position) = world_transform.to_scale_rotation_translation();
+        objects.push(PrefabAdvAssetDataObject {
+            transform: PrefabAdvAssetDataObjectTransform {
+                position,
+                rotation,
+                scale,
+            },
+            model,
+            light,
+            camera,
+        });
+    }
+
+    for child in node.children() {
+        extract_prefab_objects_from_node(
+            &child,
+            world_transform,
+            mesh_index_to_model_handle,
+            objects,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use distill::loader::handle::AssetHandle;
+    use distill::loader::LoadHandle;
+
+    // A parent node without a mesh (rotated 90 degrees around Y, scaled by 2) with a mesh and a
+    // light as children, plus a root node with a mesh that uses a matrix instead of TRS
+    const PREFAB_GLTF: &str = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": [ "KHR_lights_punctual" ],
+        "extensions": {
+            "KHR_lights_punctual": {
+                "lights": [ { "type": "point", "color": [1.0, 1.0, 1.0], "intensity": 2.0 } ]
+            }
+        },
+        "scene": 0,
+        "scenes": [ { "name": "Scene", "nodes": [0, 3] } ],
+        "nodes": [
+            {
+                "name": "Parent",
+                "translation": [1.0, 0.0, 0.0],
+                "rotation": [0.0, 0.70710677, 0.0, 0.70710677],
+                "scale": [2.0, 2.0, 2.0],
+                "children": [1, 2]
+            },
+            { "name": "Child Mesh", "translation": [0.0, 0.0, 1.0], "mesh": 1 },
+            {
+                "name": "Child Light",
+                "translation": [0.0, 1.0, 0.0],
+                "extensions": { "KHR_lights_punctual": { "light": 0 } }
+            },
+            {
+                "name": "Root Mesh",
+                "matrix": [
+                    1.0, 0.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, -5.0, 1.0
+                ],
+                "mesh": 0
+            }
+        ],
+        "meshes": [
+            { "name": "Mesh A", "primitives": [ { "attributes": { "POSITION": 0 } } ] },
+            { "name": "Mesh B", "primitives": [ { "attributes": { "POSITION": 0 } } ] }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [0.0, 0.0, 0.0]
+            }
+        ],
+        "bufferViews": [ { "buffer": 0, "byteLength": 36 } ],
+        "buffers": [
+            {
+                "byteLength": 36,
+                "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_extract_prefabs_bakes_node_hierarchy() {
+        let gltf = gltf::Gltf::from_slice(PREFAB_GLTF.as_bytes()).unwrap();
+
+        // Keep the receiver alive so dropping the handles doesn't fail to send
+        let (tx, _rx) = distill::loader::crossbeam_channel::unbounded();
+        let mesh_index_to_model_handle = vec![
+            Handle::<ModelAdvAsset>::new(tx.clone(), LoadHandle(10)),
+            Handle::<ModelAdvAsset>::new(tx.clone(), LoadHandle(11)),
+        ];
+
+        let prefabs = extract_prefabs_to_import(&gltf.document, &mesh_index_to_model_handle);
+        assert_eq!(prefabs.len(), 1);
+        assert!(prefabs[0].id == GltfObjectId::Name("Scene".to_string()));
+
+        // The parent node has nothing to place, so only its descendants and the root mesh remain
+        let objects = &prefabs[0].asset.objects;
+        assert_eq!(objects.len(), 3);
+
+        // Child mesh: (0, 0, 1) scaled by 2 and rotated onto +X, then offset by the parent
+        let child_mesh = &objects[0];
+        assert_eq!(
+            child_mesh.model.as_ref().unwrap().model.load_handle(),
+            LoadHandle(11)
+        );
+        assert!(child_mesh.light.is_none());
+        assert!(child_mesh
+            .transform
+            .position
+            .abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 0.0001));
+        assert!(child_mesh
+            .transform
+            .scale
+            .abs_diff_eq(Vec3::new(2.0, 2.0, 2.0), 0.0001));
+        assert!((child_mesh.transform.rotation * Vec3::X).abs_diff_eq(-Vec3::Z, 0.0001));
+
+        // Child light: (0, 1, 0) is unaffected by the rotation
+        let child_light = &objects[1];
+        assert!(child_light.model.is_none());
+        assert!(child_light.light.is_some());
+        assert!(child_light
+            .transform
+            .position
+            .abs_diff_eq(Vec3::new(1.0, 2.0, 0.0), 0.0001));
+
+        // Root mesh uses its own matrix only
+        let root_mesh = &objects[2];
+        assert_eq!(
+            root_mesh.model.as_ref().unwrap().model.load_handle(),
+            LoadHandle(10)
+        );
+        assert!(root_mesh
+            .transform
+            .position
+            .abs_diff_eq(Vec3::new(0.0, 0.0, -5.0), 0.0001));
+        assert!(root_mesh.transform.scale.abs_diff_eq(Vec3::ONE, 0.0001));
+        assert!(root_mesh
+            .transform
+            .rotation
+            .abs_diff_eq(glam::Quat::IDENTITY, 0.0001));
+    }
+}
@@ -80,6 +80,13 @@ pub(super) struct MeshPartData {
     pub index_type: RafxIndexType,
 }
 
+// Location of a lower LOD's indices in the index buffer. The LOD uses the same vertices (and index
+// type) as the mesh part it was generated from
+pub(super) struct MeshPartLodIndexData {
+    pub index_buffer_offset_in_bytes: u32,
+    pub index_buffer_size_in_bytes: u32,
+}
+
 // Appends index/vertex data to buffers and returns metadata about the processed mesh part
 pub(super) fn process_mesh_part(
     part_indices: &[u32],
@@ -90,6 +97,31 @@ pub(super) fn process_mesh_part(
     all_vertices_position: &mut PushBuffer,
     all_indices: &mut PushBuffer,
 ) -> MeshPartData {
+    process_mesh_part_with_lods(
+        part_indices,
+        &[],
+        positions,
+        normals,
+        tex_coords,
+        all_vertices_full,
+        all_vertices_position,
+        all_indices,
+    )
+    .0
+}
+
+// Same as process_mesh_part, but also appends index lists for lower LODs that reference the same
+// vertices. Only the indices are appended for those LODs, the vertex data is shared.
+pub(super) fn process_mesh_part_with_lods(
+    part_indices: &[u32],
+    lod_indices: &[Vec<u32>],
+    positions: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    tex_coords: &[[f32; 2]],
+    all_vertices_full: &mut PushBuffer,
+    all_vertices_position: &mut PushBuffer,
+    all_indices: &mut PushBuffer,
+) -> (MeshPartData, Vec<MeshPartLodIndexData>) {
     //
     // Use texcoords to build tangents/binormals
     //
@@ -147,7 +179,7 @@ pub(super) fn process_mesh_part(
     // Optimize vertex/index buffers
     //
     #[cfg(feature = "meshopt")]
-    let (part_indices_data, part_vertices_full, part_vertices_position) = {
+    let (part_indices_data, lod_indices_data, part_vertices_full, part_vertices_position) = {
         //WARNING: meshopt functions mutate values, even if they only take non-mut borrows. This is
         // technically unsound, so we need to be careful here. (And in theory it could become UB if
         // the compiler assumes data won't change)
@@ -174,10 +206,29 @@ pub(super) fn process_mesh_part(
             &remap,
         );
 
-        (part_indices, part_vertices_full, part_vertices_position)
+        // LOD indices only reference vertices used by the full detail indices, so the same remap
+        // applies to them. They are copied first so that the in-place optimization doesn't mutate
+        // the caller's buffers.
+        let lod_indices: Vec<_> = lod_indices
+            .iter()
+            .map(|indices| {
+                let indices = indices.clone();
+                meshopt::optimize_vertex_cache_in_place(&indices, positions.len());
+                meshopt::remap_index_buffer(Some(&indices), indices.len(), &remap)
+            })
+            .collect();
+
+        (
+            part_indices,
+            lod_indices,
+            part_vertices_full,
+            part_vertices_position,
+        )
     };
     #[cfg(feature = "meshopt")]
     let part_indices = &part_indices_data;
+    #[cfg(feature = "meshopt")]
+    let lod_indices = &lod_indices_data;
 
     //
     // Push the optimized vertex info into the combined buffer for the mesh
@@ -203,26 +254,46 @@ pub(super) fn process_mesh_part(
     //
     // Push the optimized index info into the combined buffer for the mesh
     //
+    let part_lod_index_data = push_indices(all_indices, part_indices, index_type);
+
+    let lod_index_data = lod_indices
+        .iter()
+        .map(|indices| push_indices(all_indices, indices, index_type))
+        .collect();
+
+    let part_data = MeshPartData {
+        vertex_full_buffer_offset_in_bytes: vertex_full_offset as u32,
+        vertex_full_buffer_size_in_bytes: vertex_full_size as u32,
+        vertex_position_buffer_offset_in_bytes: vertex_position_offset as u32,
+        vertex_position_buffer_size_in_bytes: vertex_position_size as u32,
+        index_buffer_offset_in_bytes: part_lod_index_data.index_buffer_offset_in_bytes,
+        index_buffer_size_in_bytes: part_lod_index_data.index_buffer_size_in_bytes,
+        index_type,
+    };
+
+    (part_data, lod_index_data)
+}
+
+fn push_indices(
+    all_indices: &mut PushBuffer,
+    indices: &[u32],
+    index_type: RafxIndexType,
+) -> MeshPartLodIndexData {
     let indices_offset = all_indices.len();
     match index_type {
         RafxIndexType::Uint32 => {
-            all_indices.push(&part_indices, 1);
+            all_indices.push(indices, 1);
         }
         RafxIndexType::Uint16 => {
-            for &index in part_indices {
+            for &index in indices {
                 all_indices.push(&[index as u16], 1);
             }
         }
     }
     let indices_size = all_indices.len() - indices_offset;
 
-    MeshPartData {
-        vertex_full_buffer_offset_in_bytes: vertex_full_offset as u32,
-        vertex_full_buffer_size_in_bytes: vertex_full_size as u32,
-        vertex_position_buffer_offset_in_bytes: vertex_position_offset as u32,
-        vertex_position_buffer_size_in_bytes: vertex_position_size as u32,
+    MeshPartLodIndexData {
         index_buffer_offset_in_bytes: indices_offset as u32,
         index_buffer_size_in_bytes: indices_size as u32,
-        index_type,
     }
 }
@@ -1,15 +1,19 @@
 use crate::assets::mesh_adv::{MeshAdvBlendMethod, MeshAdvMaterialData, MeshMaterialAdvAsset};
 use crossbeam_channel::{Receiver, Sender};
 use fnv::FnvHashMap;
-use rafx::api::{RafxBufferDef, RafxError, RafxMemoryUsage, RafxQueueType, RafxResourceType};
-use rafx::assets::UploadAssetOp;
+use rafx::api::{
+    RafxBufferDef, RafxCommandBuffer, RafxDescriptorSetHandle, RafxError, RafxMemoryUsage,
+    RafxQueueType, RafxResourceType,
+};
+use rafx::assets::{TextureStreamingContext, TextureStreamingFeedback, UploadAssetOp};
 use rafx::base::slab::{RawSlab, RawSlabKey};
 use rafx::framework::{
-    BufferResource, DescriptorSetArc, DescriptorSetBindings, DescriptorSetLayoutResource,
-    ImageViewResource, ResourceArc, ResourceContext,
+    BindlessResourceTable, BufferResource, DescriptorSetArc, DescriptorSetBindings,
+    DescriptorSetLayoutResource, ImageViewResource, ResourceArc, ResourceContext, ResourceDropSink,
+    MAX_FRAMES_IN_FLIGHT,
 };
 use rafx::RafxResult;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::shaders::mesh_adv::mesh_adv_textured_frag;
 
@@ -62,6 +66,55 @@ pub struct MaterialTextureMeta {
     ref_count: u32,
 }
 
+// The descriptor set containing all material data and textures for the current frame
+#[derive(Clone)]
+pub enum MaterialDBDescriptorSet {
+    // This frame's copy of the set owned by the material DB's bindless resource table
+    Bindless {
+        descriptor_set_layout: ResourceArc<DescriptorSetLayoutResource>,
+        handle: RafxDescriptorSetHandle,
+    },
+    DescriptorSet(DescriptorSetArc),
+}
+
+impl MaterialDBDescriptorSet {
+    pub fn bind(
+        &self,
+        command_buffer: &RafxCommandBuffer,
+    ) -> RafxResult<()> {
+        match self {
+            MaterialDBDescriptorSet::Bindless {
+                descriptor_set_layout,
+                handle,
+            } => {
+                let descriptor_set_layout = descriptor_set_layout.get_raw();
+                command_buffer.cmd_bind_descriptor_set_handle(
+                    &descriptor_set_layout.root_signature,
+                    descriptor_set_layout.set_index,
+                    handle,
+                )
+            }
+            MaterialDBDescriptorSet::DescriptorSet(descriptor_set) => {
+                descriptor_set.bind(command_buffer)
+            }
+        }
+    }
+}
+
+// Material textures are added to the table once and keep their index until they are dropped or a
+// larger mip is streamed in, instead of rewriting every texture descriptor each frame
+struct MaterialDBBindlessTextures {
+    table: BindlessResourceTable,
+    // The resident image view added to the table for each material texture, by slab index
+    resident_image_views: FnvHashMap<u32, ResourceArc<ImageViewResource>>,
+}
+
+struct MaterialDBBindlessState {
+    textures: Option<MaterialDBBindlessTextures>,
+    // Tables replaced because the material was reloaded may still be bound by frames in flight
+    retired_textures: ResourceDropSink<MaterialDBBindlessTextures>,
+}
+
 pub struct MaterialDB {
     material_entries: RawSlab<MaterialEntry>,
 
@@ -72,10 +125,15 @@ pub struct MaterialDB {
 
     drop_tx: Sender<RawSlabKey<MaterialEntry>>,
     drop_rx: Receiver<RawSlabKey<MaterialEntry>>,
+
+    texture_streaming_context: TextureStreamingContext,
+
+    // update_gpu_resources() is called from the prepare job, which only has shared access
+    bindless_state: Mutex<MaterialDBBindlessState>,
 }
 
 impl MaterialDB {
-    pub fn new() -> MaterialDB {
+    pub fn new(texture_streaming_context: TextureStreamingContext) -> MaterialDB {
         let (drop_tx, drop_rx) = crossbeam_channel::unbounded();
 
         MaterialDB {
@@ -85,6 +143,40 @@ impl MaterialDB {
             image_view_lookup: Default::default(),
             drop_tx,
             drop_rx,
+            texture_streaming_context,
+            bindless_state: Mutex::new(MaterialDBBindlessState {
+                textures: None,
+                retired_textures: ResourceDropSink::new(MAX_FRAMES_IN_FLIGHT as u32),
+            }),
+        }
+    }
+
+    pub fn texture_streaming_context(&self) -> &TextureStreamingContext {
+        &self.texture_streaming_context
+    }
+
+    // Records that the material's textures were drawn covering screen_pixels pixels along their
+    // longest axis, so that the texture streamer can keep the mips needed for that size resident
+    pub fn record_texture_streaming_feedback(
+        &self,
+        material: &MeshAdvMaterial,
+        screen_pixels: f32,
+        feedback: &mut TextureStreamingFeedback,
+    ) {
+        let entry = self
+            .material_entries
+            .get(material.inner.material_key)
+            .unwrap();
+        let texture_keys = [
+            entry.color_texture_key,
+            entry.metallic_roughness_texture_key,
+            entry.normal_texture_key,
+            entry.emissive_texture_key,
+        ];
+
+        for key in texture_keys.iter().flatten() {
+            let meta = self.image_views.get(*key).unwrap();
+            feedback.record(&meta.image, screen_pixels);
         }
     }
 
@@ -193,9 +285,10 @@ impl MaterialDB {
         }
     }
 
-    fn create_all_materials_buffer(
+    fn create_all_materials_buffer<F: Fn(RawSlabKey<MaterialTextureMeta>) -> i32>(
         &self,
         resource_context: &ResourceContext,
+        texture_index: F,
     ) -> RafxResult<ResourceArc<BufferResource>> {
         use mesh_adv_textured_frag::MaterialDbEntryBuffer;
 
@@ -228,22 +321,13 @@ impl MaterialDB {
         for (key, entry) in self.material_entries.iter() {
             let material = &mut all_materials[key.index() as usize];
             let material_data = self.material_data.get(entry.data_key).unwrap();
-            let color_texture = entry
-                .color_texture_key
-                .map(|x| x.index() as i32)
-                .unwrap_or(-1);
+            let color_texture = entry.color_texture_key.map(&texture_index).unwrap_or(-1);
             let metallic_roughness_texture = entry
                 .metallic_roughness_texture_key
-                .map(|x| x.index() as i32)
-                .unwrap_or(-1);
-            let normal_texture = entry
-                .normal_texture_key
-                .map(|x| x.index() as i32)
-                .unwrap_or(-1);
-            let emissive_texture = entry
-                .emissive_texture_key
-                .map(|x| x.index() as i32)
+                .map(&texture_index)
                 .unwrap_or(-1);
+            let normal_texture = entry.normal_texture_key.map(&texture_index).unwrap_or(-1);
+            let emissive_texture = entry.emissive_texture_key.map(&texture_index).unwrap_or(-1);
 
             *material = mesh_adv_textured_frag::MaterialDbEntryBuffer {
                 base_color_factor: material_data.base_color_factor,
@@ -284,6 +368,12 @@ impl MaterialDB {
     }
 
     pub fn destroy(&mut self) {
+        // Called when the renderer is dropped, after the swapchain has waited for all frames in
+        // flight, so nothing can be using the tables anymore
+        let bindless_state = self.bindless_state.get_mut().unwrap();
+        bindless_state.textures = None;
+        bindless_state.retired_textures.destroy().unwrap();
+
         self.material_entries.clear();
         self.material_data.clear();
         self.image_views.clear();
@@ -295,8 +385,123 @@ impl MaterialDB {
         resource_context: &ResourceContext,
         bindless_materials_layout: &ResourceArc<DescriptorSetLayoutResource>,
         invalid_image: &ResourceArc<ImageViewResource>,
-    ) -> RafxResult<DescriptorSetArc> {
-        let all_materials = self.create_all_materials_buffer(resource_context)?;
+    ) -> RafxResult<MaterialDBDescriptorSet> {
+        let textures_are_bindless = bindless_materials_layout
+            .get_raw()
+            .descriptor_set_layout_def
+            .bindings
+            .iter()
+            .any(|x| {
+                x.resource.binding
+                    == mesh_adv_textured_frag::ALL_MATERIAL_TEXTURES_DESCRIPTOR_BINDING_INDEX as u32
+                    && x.bindless
+            });
+
+        // Called once per frame
+        self.bindless_state
+            .lock()
+            .unwrap()
+            .retired_textures
+            .on_frame_complete()?;
+
+        if textures_are_bindless {
+            self.update_bindless_gpu_resources(resource_context, bindless_materials_layout)
+        } else {
+            self.update_descriptor_set_gpu_resources(
+                resource_context,
+                bindless_materials_layout,
+                invalid_image,
+            )
+        }
+    }
+
+    fn update_bindless_gpu_resources(
+        &self,
+        resource_context: &ResourceContext,
+        bindless_materials_layout: &ResourceArc<DescriptorSetLayoutResource>,
+    ) -> RafxResult<MaterialDBDescriptorSet> {
+        let mut bindless_state = self.bindless_state.lock().unwrap();
+        let bindless_state = &mut *bindless_state;
+
+        // The layout changes if the material is reloaded. The old table's descriptor sets may still
+        // be bound by frames in flight, so it is retired instead of dropped.
+        let needs_new_table = bindless_state
+            .textures
+            .as_ref()
+            .map(|x| x.table.descriptor_set_layout() != bindless_materials_layout)
+            .unwrap_or(true);
+        if needs_new_table {
+            let new_textures = MaterialDBBindlessTextures {
+                table: BindlessResourceTable::new(
+                    resource_context.device_context(),
+                    bindless_materials_layout.clone(),
+                    Some(
+                        mesh_adv_textured_frag::ALL_MATERIAL_TEXTURES_DESCRIPTOR_BINDING_INDEX
+                            as u32,
+                    ),
+                    None,
+                )?,
+                resident_image_views: Default::default(),
+            };
+
+            if let Some(old_textures) = bindless_state.textures.replace(new_textures) {
+                bindless_state.retired_textures.retire(old_textures);
+            }
+        }
+
+        let bindless_textures = bindless_state.textures.as_mut().unwrap();
+        let table = &mut bindless_textures.table;
+        table.update();
+
+        // If larger mips of an image have been streamed in, they are in a different image
+        let mut resident_image_views = FnvHashMap::default();
+        for (key, image_view) in self.image_views.iter() {
+            let resident_image_view = self
+                .texture_streaming_context
+                .resident_image_view(&image_view.image);
+            resident_image_views.insert(key.index(), resident_image_view);
+        }
+
+        for (index, old_image_view) in &bindless_textures.resident_image_views {
+            if resident_image_views.get(index) != Some(old_image_view) {
+                table.remove_texture(old_image_view);
+            }
+        }
+
+        for (index, new_image_view) in &resident_image_views {
+            if bindless_textures.resident_image_views.get(index) != Some(new_image_view) {
+                table.add_texture(new_image_view)?;
+            }
+        }
+
+        let all_materials = self.create_all_materials_buffer(resource_context, |key| {
+            let resident_image_view = &resident_image_views[&key.index()];
+            table.texture_index(resident_image_view).unwrap() as i32
+        })?;
+
+        bindless_textures.resident_image_views = resident_image_views;
+
+        table.set_buffer(
+            mesh_adv_textured_frag::ALL_MATERIALS_DESCRIPTOR_BINDING_INDEX as u32,
+            &all_materials,
+        )?;
+        table.flush()?;
+
+        Ok(MaterialDBDescriptorSet::Bindless {
+            descriptor_set_layout: bindless_materials_layout.clone(),
+            handle: table.descriptor_set_handle(),
+        })
+    }
+
+    fn update_descriptor_set_gpu_resources(
+        &self,
+        resource_context: &ResourceContext,
+        bindless_materials_layout: &ResourceArc<DescriptorSetLayoutResource>,
+        invalid_image: &ResourceArc<ImageViewResource>,
+    ) -> RafxResult<MaterialDBDescriptorSet> {
+        // Textures are bound at their slab index
+        let all_materials =
+            self.create_all_materials_buffer(resource_context, |key| key.index() as i32)?;
 
         // Create array of textures
         let mut descriptor_set_allocator = resource_context.create_descriptor_set_allocator();
@@ -308,9 +513,8 @@ impl MaterialDB {
             &all_materials,
         );
 
-        // For now this is necessary because 1) vulkan wants everything bound, unless opting into
-        // certain features 2) there is nothing clearing old bindings from previous frames. We can
-        // end up with textures from previous frames that were dropped being bound. (Even if we
+        // This is necessary because there is nothing clearing old bindings from previous frames. We
+        // can end up with textures from previous frames that were dropped being bound. (Even if we
         // don't try to index them, binding the descriptor set with stale resources can cause UB)
         for i in 0..768 {
             descriptor_set.set_image_at_index(
@@ -320,22 +524,24 @@ impl MaterialDB {
             );
         }
 
-        //println!("set image");
         for (key, image_view) in self.image_views.iter() {
+            // If larger mips of the image have been streamed in, they are in a different image
+            let resident_image_view = self
+                .texture_streaming_context
+                .resident_image_view(&image_view.image);
             descriptor_set.set_image_at_index(
                 mesh_adv_textured_frag::ALL_MATERIAL_TEXTURES_DESCRIPTOR_BINDING_INDEX as u32,
                 key.index() as usize,
-                &image_view.image,
+                &resident_image_view,
             );
         }
-        //println!("finished set image");
 
         descriptor_set.flush(&mut descriptor_set_allocator)?;
         descriptor_set_allocator.flush_changes()?;
 
-        // material data binding set?
-
-        Ok(descriptor_set.descriptor_set().clone())
+        Ok(MaterialDBDescriptorSet::DescriptorSet(
+            descriptor_set.descriptor_set().clone(),
+        ))
     }
 }
 
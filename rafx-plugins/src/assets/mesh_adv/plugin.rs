@@ -61,13 +61,15 @@ impl RendererAssetPlugin for MeshAdvAssetTypeRendererPlugin {
     fn initialize_static_resources(
         &self,
         _renderer_load_context: &RendererLoadContext,
-        _asset_manager: &mut AssetManager,
+        asset_manager: &mut AssetManager,
         _asset_resource: &mut AssetResource,
         _extract_resources: &ExtractResources,
         render_resources: &mut RenderResources,
         _upload: &mut RafxTransferUpload,
     ) -> RafxResult<()> {
-        render_resources.insert(super::material_db::MaterialDB::new());
+        render_resources.insert(super::material_db::MaterialDB::new(
+            asset_manager.texture_streaming_context().clone(),
+        ));
         Ok(())
     }
 
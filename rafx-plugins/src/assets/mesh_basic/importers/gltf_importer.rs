@@ -188,7 +188,7 @@ impl Importer for MeshBasicGltfImporter {
     where
         Self: Sized,
     {
-        29
+        30
     }
 
     fn version(&self) -> u32 {
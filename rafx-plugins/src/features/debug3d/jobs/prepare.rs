@@ -38,8 +38,8 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for Debug3DPrepareJob {
 
             for vertex_pos in &line_list.points {
                 per_frame_submit_data.vertex_list.push(Debug3DVertex {
-                    pos: (*vertex_pos).into(),
-                    color: line_list.color.into(),
+                    in_pos: (*vertex_pos).into(),
+                    in_color: line_list.color.into(),
                 });
             }
 
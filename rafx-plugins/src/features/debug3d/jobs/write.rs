@@ -2,29 +2,18 @@ use rafx::render_feature_write_job_prelude::*;
 
 use super::*;
 use crate::phases::WireframeRenderPhase;
-use rafx::api::{RafxPrimitiveTopology, RafxVertexAttributeRate, RafxVertexBufferBinding};
+use crate::shaders::debug3d::debug3d_vert;
+use rafx::api::{RafxPrimitiveTopology, RafxVertexBufferBinding};
 use rafx::framework::render_features::RenderPhase;
-use rafx::framework::{MaterialPassResource, ResourceArc, VertexDataLayout, VertexDataSetLayout};
+use rafx::framework::{MaterialPassResource, ResourceArc, VertexDataSetLayout};
 use rafx::render_features::{BeginSubmitNodeBatchArgs, RenderSubmitNodeArgs};
 use std::marker::PhantomData;
 
-/// Vertex format for vertices sent to the GPU
-#[derive(Clone, Debug, Copy, Default)]
-#[repr(C)]
-pub struct Debug3DVertex {
-    pub pos: [f32; 3],
-    pub color: [f32; 4],
-}
+/// Vertex format for vertices sent to the GPU, generated from the inputs of debug3d.vert
+pub type Debug3DVertex = debug3d_vert::VertexInput;
 
 lazy_static::lazy_static! {
-    pub static ref DEBUG_VERTEX_LAYOUT : VertexDataSetLayout = {
-        use rafx::api::RafxFormat;
-
-        VertexDataLayout::build_vertex_layout(&Debug3DVertex::default(), RafxVertexAttributeRate::Vertex, |builder, vertex| {
-            builder.add_member(&vertex.pos, "POSITION", RafxFormat::R32G32B32_SFLOAT);
-            builder.add_member(&vertex.color, "COLOR", RafxFormat::R32G32B32A32_SFLOAT);
-        }).into_set(RafxPrimitiveTopology::LineStrip)
-    };
+    pub static ref DEBUG_VERTEX_LAYOUT : VertexDataSetLayout = debug3d_vert::vertex_data_set_layout(RafxPrimitiveTopology::LineStrip);
 }
 
 pub struct Debug3DWriteJob<'write> {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::assets::mesh_adv::material_db::MaterialDBDescriptorSet;
 use crate::assets::mesh_adv::{MeshAdvAsset, MeshAdvShaderPassIndices};
 use crate::components::{
     DirectionalLightComponent, PointLightComponent, SpotLightComponent, TransformComponent,
@@ -12,6 +13,7 @@ use rafx::framework::{
     BufferResource, DescriptorSetArc, ImageViewResource, MaterialPassResource, ResourceArc,
 };
 use rafx::rafx_visibility::geometry::{BoundingSphere, Transform};
+use std::ops::Mul;
 
 pub struct MeshAdvRenderFeatureTypes;
 
@@ -30,6 +32,8 @@ pub struct MeshAdvPerFrameData {
     pub shadow_map_atlas_depth_material_pass: Option<ResourceArc<MaterialPassResource>>,
     pub shadow_map_atlas: ResourceArc<ImageViewResource>,
     pub invalid_image_color: ResourceArc<ImageViewResource>,
+    pub lod_bias: i32,
+    pub lod_hysteresis: f32,
 }
 
 pub struct MeshAdvRenderObjectInstanceData {
@@ -39,6 +43,18 @@ pub struct MeshAdvRenderObjectInstanceData {
     pub bounding_sphere: Option<BoundingSphere>,
 }
 
+impl MeshAdvRenderObjectInstanceData {
+    pub fn world_bounding_sphere(&self) -> Option<BoundingSphere> {
+        self.bounding_sphere.map(|x| {
+            let t = self.transform;
+            BoundingSphere {
+                position: t.translation + t.rotation.mul(x.position * t.scale),
+                radius: x.radius * t.scale.abs().max_element(),
+            }
+        })
+    }
+}
+
 #[derive(Default)]
 pub struct MeshAdvPerViewData {
     //TODO: Replace with arrayvec/tinyvec? These were static arrays but they can get big enough now
@@ -133,7 +149,7 @@ pub struct MeshAdvPerFrameSubmitData {
     pub shadow_map_image_index_remap: FnvHashMap<ShadowViewIndex, usize>,
     pub model_matrix_buffer: TrustCell<Option<ResourceArc<BufferResource>>>,
     pub model_matrix_with_history_buffer: TrustCell<Option<ResourceArc<BufferResource>>>,
-    pub all_materials_descriptor_set: TrustCell<Option<DescriptorSetArc>>,
+    pub all_materials_descriptor_set: TrustCell<Option<MaterialDBDescriptorSet>>,
     pub batched_pass_lookup: AtomicOnceCell<FnvHashMap<MeshAdvBatchedPassKey, usize>>,
     pub batched_passes: AtomicOnceCell<Vec<MeshAdvBatchedPreparedPassInfo>>,
     pub per_batch_descriptor_sets: AtomicOnceCell<Vec<Option<DescriptorSetArc>>>,
@@ -192,6 +208,7 @@ pub struct MeshAdvUnbatchedDrawCall {
     pub render_object_instance_id: RenderObjectInstanceId,
     pub material_pass_resource: ResourceArc<MaterialPassResource>,
     //pub per_material_descriptor_set: Option<DescriptorSetArc>,
+    pub lod_index: usize,
     pub mesh_part_index: usize,
     pub model_matrix_index: usize,
     pub material_index: Option<u32>,
@@ -73,6 +73,15 @@ impl<'extract> ExtractJobEntryPoints<'extract> for MeshAdvExtractJob<'extract> {
             .clone();
         let default_pbr_material_pass_indices =
             MeshAdvShaderPassIndices::new(&default_pbr_material);
+        let (lod_bias, lod_hysteresis) =
+            if let Some(mesh_render_options) = &self.mesh_render_options {
+                (
+                    mesh_render_options.lod_bias,
+                    mesh_render_options.lod_hysteresis,
+                )
+            } else {
+                (0, 0.1)
+            };
         context
             .frame_packet()
             .per_frame_data()
@@ -93,6 +102,8 @@ impl<'extract> ExtractJobEntryPoints<'extract> for MeshAdvExtractJob<'extract> {
                     .ok(),
                 invalid_image_color: self.invalid_image_color.clone(),
                 shadow_map_atlas: self.shadow_map_atlas.shadow_atlas_image_view().clone(),
+                lod_bias,
+                lod_hysteresis,
             });
     }
 
@@ -1,6 +1,5 @@
 use fnv::FnvHashMap;
 use rafx::render_feature_prepare_job_predule::*;
-use std::ops::Mul;
 
 use super::*;
 use crate::phases::{
@@ -30,7 +29,7 @@ use crate::shaders::mesh_adv::lights_bin_comp;
 use crate::shaders::mesh_adv::mesh_adv_textured_frag::LightInListStd430;
 use crate::shaders::mesh_adv::shadow_atlas_depth_vert;
 use mesh_adv_textured_frag::PerViewDataUniform as MeshPerViewFragmentShaderParam;
-use rafx::assets::MaterialAsset;
+use rafx::assets::{MaterialAsset, TextureStreamingFeedback};
 use rafx::renderer::MainViewRenderResource;
 
 const PER_VIEW_DESCRIPTOR_SET_INDEX: u32 =
@@ -59,6 +58,10 @@ pub struct MeshAdvPrepareJob<'prepare> {
     render_objects: MeshAdvRenderObjectSet,
     batched_pass_lookup: AtomicOnceCell<FnvHashMap<MeshAdvBatchedPassKey, usize>>,
     batched_passes: AtomicOnceCell<Vec<MeshAdvBatchedPassInfo>>,
+    lod_selection_resource: ReadBorrow<'prepare, MeshAdvLodSelectionResource>,
+    lod_selections: AtomicOnceCell<FnvHashMap<(ViewFrameIndex, RenderObjectInstanceId), usize>>,
+    lod_bias: i32,
+    lod_hysteresis: f32,
 }
 
 impl<'prepare> MeshAdvPrepareJob<'prepare> {
@@ -137,6 +140,14 @@ impl<'prepare> MeshAdvPrepareJob<'prepare> {
                 render_objects,
                 batched_pass_lookup: AtomicOnceCell::new(),
                 batched_passes: AtomicOnceCell::new(),
+                lod_selection_resource: {
+                    prepare_context
+                        .render_resources
+                        .fetch::<MeshAdvLodSelectionResource>()
+                },
+                lod_selections: AtomicOnceCell::new(),
+                lod_bias: per_frame_data.lod_bias,
+                lod_hysteresis: per_frame_data.lod_hysteresis,
             },
             prepare_context,
             frame_packet,
@@ -211,6 +222,144 @@ impl<'prepare> MeshAdvPrepareJob<'prepare> {
             dyn_descriptor_set.descriptor_set().clone(),
         )
     }
+
+    // Chooses a LOD for every render object instance in every view based on how large its bounding
+    // sphere is on screen. The choice must be made up front so that batch sizes can be counted
+    // before draw data is pushed.
+    fn select_lods(
+        &self,
+        context: &PreparePerFrameContext<'prepare, '_, Self>,
+    ) -> FnvHashMap<(ViewFrameIndex, RenderObjectInstanceId), usize> {
+        profiling::scope!("select lods");
+
+        let previous_lods = self.lod_selection_resource.begin_frame();
+        let mut current_lods =
+            FnvHashMap::with_capacity_and_hasher(previous_lods.len(), Default::default());
+        let mut lod_selections = FnvHashMap::default();
+
+        for view_packet in context.frame_packet().view_packets() {
+            let view = view_packet.view();
+            let view_frustum_id = view.view_frustum().view_frustum_id();
+
+            for object_instance in view_packet.render_object_instances() {
+                let render_object_instance_data = context
+                    .frame_packet()
+                    .render_object_instances_data()
+                    .get(object_instance.render_object_instance_id as usize)
+                    .as_ref()
+                    .unwrap();
+
+                let mesh_asset_inner = &render_object_instance_data.mesh_asset.inner;
+                if mesh_asset_inner.lods.len() <= 1 {
+                    continue;
+                }
+
+                let lod_key = (
+                    view_frustum_id,
+                    object_instance.render_object_instance.render_object_id,
+                );
+
+                let lod_index = if let Some(bounding_sphere) =
+                    render_object_instance_data.world_bounding_sphere()
+                {
+                    let screen_size = projected_screen_size(view, &bounding_sphere);
+                    mesh_asset_inner.select_lod(
+                        screen_size,
+                        previous_lods.get(&lod_key).copied(),
+                        self.lod_hysteresis,
+                    )
+                } else {
+                    0
+                };
+
+                current_lods.insert(lod_key, lod_index);
+
+                // The bias is applied after hysteresis so that changing it takes effect immediately
+                let last_lod = mesh_asset_inner.lods.len() as i32 - 1;
+                let biased_lod_index = (lod_index as i32 + self.lod_bias).max(0).min(last_lod);
+                lod_selections.insert(
+                    (
+                        view_packet.view_frame_index(),
+                        object_instance.render_object_instance_id,
+                    ),
+                    biased_lod_index as usize,
+                );
+            }
+        }
+
+        self.lod_selection_resource.end_frame(current_lods);
+        lod_selections
+    }
+
+    // Tells the texture streamer how large the materials of the selected LODs are drawn so that it
+    // can stream in the mips they need. Must run after select_lods.
+    fn submit_texture_streaming_feedback(
+        &self,
+        context: &PreparePerFrameContext<'prepare, '_, Self>,
+    ) {
+        let texture_streaming_context = self.material_db.texture_streaming_context();
+        if !texture_streaming_context.is_enabled() {
+            return;
+        }
+
+        profiling::scope!("texture streaming feedback");
+
+        let mut texture_streaming_feedback = TextureStreamingFeedback::default();
+        for view_packet in context.frame_packet().view_packets() {
+            let view = view_packet.view();
+
+            // Depth-only views (i.e. shadow maps) don't sample material textures
+            if !view.phase_is_relevant::<OpaqueRenderPhase>()
+                && !view.phase_is_relevant::<TransparentRenderPhase>()
+            {
+                continue;
+            }
+
+            for object_instance in view_packet.render_object_instances() {
+                let render_object_instance_data = context
+                    .frame_packet()
+                    .render_object_instances_data()
+                    .get(object_instance.render_object_instance_id as usize)
+                    .as_ref()
+                    .unwrap();
+
+                // Assumes textures are mapped across the mesh about once. Meshes without bounds
+                // are assumed to fill the view.
+                let screen_size = render_object_instance_data
+                    .world_bounding_sphere()
+                    .map(|bounding_sphere| projected_screen_size(view, &bounding_sphere))
+                    .unwrap_or(1.0);
+                let screen_pixels = screen_size * view.extents_height() as f32;
+
+                let lod_index = self.selected_lod(
+                    view_packet.view_frame_index(),
+                    object_instance.render_object_instance_id,
+                );
+                let mesh_asset_inner = &render_object_instance_data.mesh_asset.inner;
+                for mesh_part in &mesh_asset_inner.lods[lod_index].mesh_parts {
+                    self.material_db.record_texture_streaming_feedback(
+                        &mesh_part.mesh_material.inner.material,
+                        screen_pixels,
+                        &mut texture_streaming_feedback,
+                    );
+                }
+            }
+        }
+
+        texture_streaming_context.submit_feedback(texture_streaming_feedback);
+    }
+
+    fn selected_lod(
+        &self,
+        view_frame_index: ViewFrameIndex,
+        render_object_instance_id: RenderObjectInstanceId,
+    ) -> usize {
+        self.lod_selections
+            .get()
+            .get(&(view_frame_index, render_object_instance_id))
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
@@ -241,6 +390,9 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
             }
         }
 
+        self.lod_selections.set(self.select_lods(context));
+        self.submit_texture_streaming_feedback(context);
+
         //
         // Determine how large our batches will be for anything that can be submitted as a single large batch
         // (anything that doesn't need to be sorted by depth)
@@ -255,7 +407,13 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
                     .get(render_object_instance_id)
                     .as_ref()
                     .unwrap();
-                for mesh_part in &render_object_instance_data.mesh_asset.inner.mesh_parts {
+                let lod_index = self.selected_lod(
+                    view_packet.view_frame_index(),
+                    object_instance.render_object_instance_id,
+                );
+                for mesh_part in
+                    &render_object_instance_data.mesh_asset.inner.lods[lod_index].mesh_parts
+                {
                     let is_transparent = mesh_part.mesh_material.material_data().blend_method
                         != MeshAdvBlendMethod::Opaque;
 
@@ -311,7 +469,12 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
                     };
 
                     let pass = mesh_part
-                        .get_material_pass_resource(view_packet.view(), render_phase_index)
+                        .get_material_pass_resource(
+                            view_packet.view(),
+                            render_phase_index,
+                            self.pipeline_state.use_weighted_blended_oit,
+                            self.pipeline_state.write_surface_data,
+                        )
                         .clone();
 
                     add_batched_pass_count(
@@ -525,18 +688,13 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
                 previous_model_matrix: previous_model,
             });
 
+        //TODO: Do this in the compute shader if possible. For now do it on CPU so I know it
+        // matches frustum culling
         let bounding_sphere = extracted_data
-            .bounding_sphere
-            .map(|x| {
-                //TODO: Do this in the compute shader if possible. For now do it on CPU so I know it
-                // matches frustum culling
-                let t = extracted_data.transform;
-                let position = t.translation + t.rotation.mul(x.position) * t.scale;
-                let radius = x.radius * t.scale.abs().max_element();
-                mesh_culling_comp::BoundingSphereBuffer {
-                    position: position.into(),
-                    radius: radius,
-                }
+            .world_bounding_sphere()
+            .map(|x| mesh_culling_comp::BoundingSphereBuffer {
+                position: x.position.into(),
+                radius: x.radius,
             })
             .unwrap_or_else(|| mesh_culling_comp::BoundingSphereBuffer {
                 position: [0.0, 0.0, 0.0],
@@ -639,9 +797,9 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
             // Iterate all mesh parts and push draw calls into batches. Additionally push submit nodes
             // for transparent meshes as we need to sort these by depth and draw them individually
             //
-            for (mesh_part_index, mesh_part) in extracted_data
-                .mesh_asset
-                .inner
+            let lod_index =
+                self.selected_lod(context.view_frame_index(), render_object_instance_id);
+            for (mesh_part_index, mesh_part) in extracted_data.mesh_asset.inner.lods[lod_index]
                 .mesh_parts
                 .iter()
                 .enumerate()
@@ -699,7 +857,12 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
 
                 if view.phase_index_is_relevant(phase_index) {
                     let material_pass_resource = mesh_part
-                        .get_material_pass_resource(view, phase_index)
+                        .get_material_pass_resource(
+                            view,
+                            phase_index,
+                            self.pipeline_state.use_weighted_blended_oit,
+                            self.pipeline_state.write_surface_data,
+                        )
                         .clone();
 
                     let push_draw_data_result = push_draw_data(
@@ -723,6 +886,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
                             MeshAdvDrawCall::Unbatched(MeshAdvUnbatchedDrawCall {
                                 render_object_instance_id,
                                 material_pass_resource,
+                                lod_index,
                                 mesh_part_index,
                                 model_matrix_index: model_matrix_offset,
                                 material_index: Some(
@@ -818,7 +982,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
                 };
                 per_view_frag_data.viewport_width = view.extents_width();
                 per_view_frag_data.viewport_height = view.extents_height();
-                per_view_frag_data.jitter_amount = self.pipeline_state.jitter_amount.into();
+                per_view_frag_data.jitter_amount = view.jitter_amount().into();
                 per_view_frag_data.mip_bias = self.pipeline_state.forward_pass_mip_bias;
 
                 let mut light_bounds_data = lights_bin_comp::LightsInputListBuffer {
@@ -1031,6 +1195,28 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
             dyn_descriptor_set
                 .flush(&mut descriptor_set_allocator)
                 .unwrap();
+
+            // Volumetric fog is lit the same way as the main view's surfaces
+            let is_main_view = self
+                .main_view_resource
+                .main_view
+                .as_ref()
+                .map(|main_view| main_view.view_index() == view.view_index())
+                .unwrap_or(false);
+            if is_main_view {
+                context
+                    .render_resources()
+                    .fetch_mut::<MeshAdvVolumetricFogRenderResource>()
+                    .lighting_data = Some(VolumetricFogLightingData {
+                    per_view_data: Box::new(per_view_frag_data),
+                    all_lights: all_lights_buffer.clone(),
+                    light_bin_output: self
+                        .light_bin_resource
+                        .output_gpu_buffer(view.frame_index())
+                        .clone(),
+                    shadow_map_atlas: shadow_map_atlas.clone(),
+                });
+            }
             Some(dyn_descriptor_set.descriptor_set().clone())
         } else {
             None
@@ -1098,16 +1284,9 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshAdvPrepareJob<'prepare> {
             per_view_data.current_view_proj_inv = view.view_proj().inverse().to_cols_array_2d();
             per_view_data.viewport_width = view.extents_width();
             per_view_data.viewport_height = view.extents_height();
-            per_view_data.jitter_amount = self.pipeline_state.jitter_amount.into();
-
-            if let Some(previous_main_view_info) = &self.main_view_resource.previous_main_view_info
-            {
-                let previous_view_proj =
-                    previous_main_view_info.projection_matrix * previous_main_view_info.view_matrix;
-                per_view_data.previous_view_proj = previous_view_proj.to_cols_array_2d();
-            } else {
-                per_view_data.previous_view_proj = per_view_data.current_view_proj;
-            }
+            per_view_data.jitter_amount = view.jitter_amount().into();
+            // Unjittered, so velocity only contains camera and object motion
+            per_view_data.previous_view_proj = view.previous_view_proj().to_cols_array_2d();
 
             let per_instance_descriptor_set_layout = &self
                 .depth_material_pass
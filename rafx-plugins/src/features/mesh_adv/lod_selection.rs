@@ -0,0 +1,156 @@
+use fnv::FnvHashMap;
+use rafx::rafx_visibility::geometry::BoundingSphere;
+use rafx::render_features::{RenderObjectId, RenderView};
+use rafx::visibility::ViewFrustumId;
+use std::sync::Mutex;
+
+// Remembers the LOD that was chosen for each render object in each view last frame so that LOD
+// selection can apply hysteresis. Entries that are not refreshed during a frame are dropped.
+#[derive(Default)]
+pub struct MeshAdvLodSelectionResource {
+    previous_lods: Mutex<FnvHashMap<(ViewFrustumId, RenderObjectId), usize>>,
+}
+
+impl MeshAdvLodSelectionResource {
+    // Takes the LODs chosen last frame, leaving the map empty to be refilled with end_frame()
+    pub fn begin_frame(&self) -> FnvHashMap<(ViewFrustumId, RenderObjectId), usize> {
+        std::mem::take(&mut *self.previous_lods.lock().unwrap())
+    }
+
+    pub fn end_frame(
+        &self,
+        current_lods: FnvHashMap<(ViewFrustumId, RenderObjectId), usize>,
+    ) {
+        *self.previous_lods.lock().unwrap() = current_lods;
+    }
+}
+
+// Returns the height of the bounding sphere projected into the view, as a fraction of the view's
+// height. This is the metric that MeshAdvAssetLod::screen_size thresholds are compared against.
+pub fn projected_screen_size(
+    view: &RenderView,
+    bounding_sphere: &BoundingSphere,
+) -> f32 {
+    let projection = view.projection_matrix();
+
+    // proj[1][1] scales view-space y into clip space. For an orthographic projection w is always 1,
+    // for a perspective projection clip w is view-space depth
+    let is_orthographic = projection.w_axis.w == 1.0;
+    if is_orthographic {
+        bounding_sphere.radius * projection.y_axis.y.abs()
+    } else {
+        let distance = (bounding_sphere.position - view.eye_position())
+            .length()
+            .max(0.0001);
+        bounding_sphere.radius * projection.y_axis.y.abs() / distance
+    }
+}
+
+// Picks the most detailed LOD whose screen size threshold is met. LODs are ordered from most to
+// least detailed. If the LOD used previously is provided, every threshold is moved away from it by
+// the hysteresis fraction: adding detail requires being clearly above a more detailed LOD's
+// threshold, and dropping detail requires being clearly below the threshold of the LOD being left.
+// This keeps objects whose size hovers near one or more thresholds from flickering between LODs.
+pub fn select_lod_index<F: Fn(usize) -> f32>(
+    lod_count: usize,
+    lod_screen_size: F,
+    screen_size: f32,
+    previous_lod: Option<usize>,
+    hysteresis: f32,
+) -> usize {
+    let previous_lod = previous_lod.filter(|x| *x < lod_count);
+    for lod_index in 0..lod_count {
+        let threshold = match previous_lod {
+            Some(previous_lod) if lod_index < previous_lod => {
+                lod_screen_size(lod_index) * (1.0 + hysteresis)
+            }
+            Some(_) => lod_screen_size(lod_index) * (1.0 - hysteresis),
+            None => lod_screen_size(lod_index),
+        };
+
+        if screen_size >= threshold {
+            return lod_index;
+        }
+    }
+
+    lod_count.saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOD_SCREEN_SIZES: [f32; 3] = [0.5, 0.25, 0.0];
+
+    fn select(
+        screen_size: f32,
+        previous_lod: Option<usize>,
+    ) -> usize {
+        select_lod_index(
+            LOD_SCREEN_SIZES.len(),
+            |lod_index| LOD_SCREEN_SIZES[lod_index],
+            screen_size,
+            previous_lod,
+            0.1,
+        )
+    }
+
+    #[test]
+    fn test_select_lod_by_screen_size() {
+        assert_eq!(select(1.0, None), 0);
+        assert_eq!(select(0.5, None), 0);
+        assert_eq!(select(0.3, None), 1);
+        assert_eq!(select(0.1, None), 2);
+        assert_eq!(select(0.0, None), 2);
+    }
+
+    #[test]
+    fn test_select_lod_hysteresis() {
+        // Slightly below LOD 0's threshold, keep LOD 0 if it was used last frame
+        assert_eq!(select(0.47, Some(0)), 0);
+        assert_eq!(select(0.47, Some(1)), 1);
+        // Clearly below the threshold, drop detail
+        assert_eq!(select(0.4, Some(0)), 1);
+
+        // Slightly above LOD 0's threshold, keep LOD 1 if it was used last frame
+        assert_eq!(select(0.52, Some(1)), 1);
+        // Clearly above the threshold, add detail
+        assert_eq!(select(0.6, Some(1)), 0);
+    }
+
+    #[test]
+    fn test_select_lod_hysteresis_across_two_thresholds() {
+        let select = |screen_size: f32, previous_lod: Option<usize>| {
+            select_lod_index(
+                3,
+                |lod_index| [0.5, 0.48, 0.0][lod_index],
+                screen_size,
+                previous_lod,
+                0.1,
+            )
+        };
+
+        // The size hovers across both of two close thresholds. Whichever LOD was chosen when the
+        // hovering started is kept.
+        let hovering_screen_sizes = [0.51, 0.47, 0.5, 0.46, 0.52, 0.49, 0.47];
+        for &initial_lod in &[0, 1, 2] {
+            let mut lod = initial_lod;
+            for &screen_size in &hovering_screen_sizes {
+                lod = select(screen_size, Some(lod));
+                assert_eq!(lod, initial_lod);
+            }
+        }
+
+        // Clearly above LOD 1's threshold but not LOD 0's, only one LOD of detail is added
+        assert_eq!(select(0.54, Some(2)), 1);
+        // Clearly below LOD 0's threshold but not LOD 1's, only one LOD of detail is dropped
+        assert_eq!(select(0.44, Some(0)), 1);
+    }
+
+    #[test]
+    fn test_select_lod_invalid_previous_lod() {
+        // A previous LOD that no longer exists (i.e. the asset was reloaded) is ignored
+        assert_eq!(select(0.3, Some(5)), 1);
+        assert_eq!(select_lod_index(0, |_| 0.0, 0.5, Some(0), 0.1), 0);
+    }
+}
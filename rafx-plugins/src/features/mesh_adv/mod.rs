@@ -54,3 +54,9 @@ mod gpu_occlusion_cull;
 pub use gpu_occlusion_cull::*;
 
 pub use shadow_map_atlas::*;
+
+mod lod_selection;
+pub use lod_selection::*;
+
+mod volumetric_fog;
+pub use volumetric_fog::*;
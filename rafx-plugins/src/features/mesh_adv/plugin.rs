@@ -18,6 +18,8 @@ pub struct MeshAdvStaticResources {
     pub shadow_map_atlas_clear_tiles_material: Handle<MaterialAsset>,
     pub lights_bin_compute_pipeline: Handle<ComputePipelineAsset>,
     pub lights_build_lists_compute_pipeline: Handle<ComputePipelineAsset>,
+    pub volumetric_fog_inject_compute_pipeline: Handle<ComputePipelineAsset>,
+    pub volumetric_fog_integrate_compute_pipeline: Handle<ComputePipelineAsset>,
 }
 
 pub struct MeshAdvRendererPlugin {
@@ -120,6 +122,16 @@ impl RenderFeaturePlugin for MeshAdvRendererPlugin {
                 "rafx-plugins/compute_pipelines/lights_build_lists.compute",
             );
 
+        let volumetric_fog_inject_compute_pipeline = asset_resource
+            .load_asset_path::<ComputePipelineAsset, _>(
+                "rafx-plugins/compute_pipelines/volumetric_fog_inject.compute",
+            );
+
+        let volumetric_fog_integrate_compute_pipeline = asset_resource
+            .load_asset_path::<ComputePipelineAsset, _>(
+                "rafx-plugins/compute_pipelines/volumetric_fog_integrate.compute",
+            );
+
         renderer_load_context.wait_for_asset_to_load(
             render_resources,
             asset_manager,
@@ -162,6 +174,20 @@ impl RenderFeaturePlugin for MeshAdvRendererPlugin {
             asset_resource,
             "lights_build_lists.compute",
         )?;
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &volumetric_fog_inject_compute_pipeline,
+            asset_resource,
+            "volumetric_fog_inject.compute",
+        )?;
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &volumetric_fog_integrate_compute_pipeline,
+            asset_resource,
+            "volumetric_fog_integrate.compute",
+        )?;
 
         render_resources.insert(MeshAdvStaticResources {
             default_pbr_material,
@@ -170,11 +196,15 @@ impl RenderFeaturePlugin for MeshAdvRendererPlugin {
             shadow_map_atlas_clear_tiles_material,
             lights_bin_compute_pipeline,
             lights_build_lists_compute_pipeline,
+            volumetric_fog_inject_compute_pipeline,
+            volumetric_fog_integrate_compute_pipeline,
         });
 
         render_resources.insert(MeshAdvShadowMapResource::default());
         render_resources.insert(MeshAdvRenderPipelineState::default());
         render_resources.insert(MeshAdvGpuOcclusionCullRenderResource::default());
+        render_resources.insert(MeshAdvLodSelectionResource::default());
+        render_resources.insert(MeshAdvVolumetricFogRenderResource::default());
 
         render_resources.insert(MeshAdvLightBinRenderResource::new(
             &asset_manager.resources(),
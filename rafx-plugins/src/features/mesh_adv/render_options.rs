@@ -9,6 +9,11 @@ pub struct MeshAdvRenderOptions {
     pub ambient_light: glam::Vec3,
     pub ndf_filter_amount: f32,
     pub use_clustered_lighting: bool,
+    // Added to the LOD chosen by screen size. Positive values favor less detailed LODs
+    pub lod_bias: i32,
+    // How far (as a fraction of the threshold) the screen size must move past a LOD's threshold
+    // before switching away from the LOD used in the previous frame
+    pub lod_hysteresis: f32,
 }
 
 impl Default for MeshAdvRenderOptions {
@@ -20,6 +25,8 @@ impl Default for MeshAdvRenderOptions {
             ambient_light: glam::Vec3::ZERO,
             ndf_filter_amount: 1.0,
             use_clustered_lighting: true,
+            lod_bias: 0,
+            lod_hysteresis: 0.1,
         }
     }
 }
@@ -27,16 +34,22 @@ impl Default for MeshAdvRenderOptions {
 // This is state that's updated by the pipeline (pipeline pushes data to the feature plugin, not
 // the other way around)
 pub struct MeshAdvRenderPipelineState {
-    pub jitter_amount: glam::Vec2,
     pub forward_pass_mip_bias: f32,
+    // If true, transparent meshes use the passes that write weighted blended OIT accumulation and
+    // revealage targets rather than blending directly into the color target
+    pub use_weighted_blended_oit: bool,
+    // If true, opaque meshes use the passes that also write normal/roughness/metalness to a second
+    // color target (read by SSR)
+    pub write_surface_data: bool,
     pub ssao_descriptor_set: Option<DescriptorSetArc>,
 }
 
 impl Default for MeshAdvRenderPipelineState {
     fn default() -> Self {
         MeshAdvRenderPipelineState {
-            jitter_amount: glam::Vec2::ZERO,
             forward_pass_mip_bias: 0.0,
+            use_weighted_blended_oit: false,
+            write_surface_data: false,
             ssao_descriptor_set: None,
         }
     }
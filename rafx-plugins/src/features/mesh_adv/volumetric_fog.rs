@@ -0,0 +1,17 @@
+use crate::shaders::mesh_adv::mesh_adv_textured_frag::PerViewDataUniform;
+use rafx::framework::{BufferResource, ImageViewResource, ResourceArc};
+
+// The lighting used by the main view's forward pass. The volumetric fog pass lights froxels with
+// the same data so that fog matches the lighting on surfaces
+pub struct VolumetricFogLightingData {
+    pub per_view_data: Box<PerViewDataUniform>,
+    pub all_lights: ResourceArc<BufferResource>,
+    pub light_bin_output: ResourceArc<BufferResource>,
+    pub shadow_map_atlas: ResourceArc<ImageViewResource>,
+}
+
+// Written during prepare, read by the volumetric fog pass in the render graph
+#[derive(Default)]
+pub struct MeshAdvVolumetricFogRenderResource {
+    pub lighting_data: Option<VolumetricFogLightingData>,
+}
@@ -5,7 +5,6 @@ use rafx::api::RafxSwapchainColorSpace;
 use rafx::framework::{MaterialPassResource, ResourceArc};
 use rafx::graph::*;
 use rafx::render_features::RenderPhase;
-use rafx::renderer::SwapchainRenderResource;
 
 use super::BasicPipelineContext;
 use super::BloomExtractPass;
@@ -23,7 +22,7 @@ pub(super) fn bloom_combine_pass(
     bloom_combine_material_pass: ResourceArc<MaterialPassResource>,
     bloom_extract_pass: &BloomExtractPass,
     blurred_color: RenderGraphImageUsageId,
-    swapchain_render_resource: &SwapchainRenderResource,
+    max_color_component_value: f32,
 ) -> BloomCombinePass {
     let render_options = context
         .extract_resources
@@ -59,12 +58,7 @@ pub(super) fn bloom_combine_pass(
             .sample_image(node, blurred_color, Default::default(), Default::default());
     context.graph.set_image_name(hdr_image, "hdr");
 
-    let swapchain_color_space = swapchain_render_resource
-        .surface_info()
-        .unwrap()
-        .swapchain_surface_info
-        .color_space;
-    let max_color_component_value = swapchain_render_resource.max_color_component_value;
+    let swapchain_color_space = context.viewport.surface_info.color_space;
 
     context.graph.set_renderpass_callback(node, move |args| {
         // Get the color image from before
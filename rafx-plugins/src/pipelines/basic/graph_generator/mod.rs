@@ -1,6 +1,4 @@
-use rafx::api::{
-    RafxFormat, RafxPrimitiveTopology, RafxResourceState, RafxResult, RafxSampleCount,
-};
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxResult, RafxSampleCount};
 use rafx::framework::VertexDataSetLayout;
 use rafx::framework::{ImageViewResource, ResourceArc};
 use rafx::framework::{RenderResources, ResourceContext};
@@ -23,6 +21,7 @@ use crate::features::mesh_basic::MeshBasicShadowMapResource;
 use crate::pipelines::basic::AntiAliasMethodBasic;
 use bloom_extract_pass::BloomExtractPass;
 use rafx::assets::AssetManager;
+use rafx::renderer::RenderViewportTarget;
 use rafx::renderer::SwapchainRenderResource;
 
 mod bloom_blur_pass;
@@ -58,6 +57,7 @@ struct BasicPipelineContext<'a> {
     resource_context: &'a ResourceContext,
     graph_config: &'a BasicPipelineRenderGraphConfig,
     main_view: &'a RenderView,
+    viewport: &'a RenderViewportTarget,
     extract_resources: &'a ExtractResources<'a>,
     render_resources: &'a RenderResources,
 }
@@ -67,6 +67,7 @@ pub(super) fn generate_render_graph(
     swapchain_image: ResourceArc<ImageViewResource>,
     _rotating_frame_index: usize,
     main_view: RenderView,
+    viewport: &RenderViewportTarget,
     extract_resources: &ExtractResources,
     render_resources: &RenderResources,
 ) -> RafxResult<PreparedRenderGraph> {
@@ -75,7 +76,11 @@ pub(super) fn generate_render_graph(
     let device_context = asset_manager.device_context();
     let resource_context = asset_manager.resource_manager().resource_context();
     let swapchain_render_resource = render_resources.fetch::<SwapchainRenderResource>();
-    let swapchain_info = swapchain_render_resource.surface_info().unwrap();
+    let swapchain_info = swapchain_render_resource
+        .surface_info_for_surface(&viewport.surface_name)
+        .unwrap();
+    // Everything in the graph is rendered at the size of the viewport
+    let swapchain_surface_info = &viewport.surface_info;
     let static_resources = render_resources.fetch::<BasicPipelineStaticResources>();
 
     render_resources
@@ -86,7 +91,7 @@ pub(super) fn generate_render_graph(
         let render_options = extract_resources
             .fetch::<BasicPipelineRenderOptions>()
             .clone();
-        let swapchain_format = swapchain_info.swapchain_surface_info.format;
+        let swapchain_format = swapchain_surface_info.format;
         let sample_count = if render_options.anti_alias_method == AntiAliasMethodBasic::Msaa4x {
             RafxSampleCount::SampleCount4
         } else {
@@ -118,6 +123,7 @@ pub(super) fn generate_render_graph(
         resource_context: &resource_context,
         graph_config: &graph_config,
         main_view: &main_view,
+        viewport,
         render_resources,
         extract_resources,
     };
@@ -125,8 +131,8 @@ pub(super) fn generate_render_graph(
     let swapchain_image_id = graph_context.graph.add_external_image(
         swapchain_image,
         Default::default(),
-        RafxResourceState::PRESENT,
-        RafxResourceState::PRESENT,
+        viewport.image_state,
+        viewport.image_state,
     );
 
     let depth_prepass = depth_prepass::depth_prepass(&mut graph_context);
@@ -176,7 +182,7 @@ pub(super) fn generate_render_graph(
             bloom_combine_material_pass,
             &bloom_extract_pass,
             blurred_color,
-            &*swapchain_render_resource,
+            swapchain_render_resource.max_color_component_value,
         );
 
         bloom_combine_pass.color
@@ -189,13 +195,31 @@ pub(super) fn generate_render_graph(
 
     previous_pass_color = ui_pass::ui_pass(&mut graph_context, previous_pass_color).color;
 
-    graph.write_external_image(swapchain_image_id, previous_pass_color);
+    if viewport.covers_surface() {
+        graph.write_external_image(swapchain_image_id, previous_pass_color);
+    } else {
+        // Draw into the viewport's rect of the swapchain image, keeping whatever earlier viewports
+        // drew to the rest of it
+        let swapchain_image = graph.read_external_image(swapchain_image_id);
+        let (dst_min_uv, dst_max_uv) = viewport.rect_uv();
+        let swapchain_image = graph.blit_image_to_image(
+            "ViewportBlit",
+            RenderGraphQueue::DefaultGraphics,
+            previous_pass_color,
+            glam::Vec2::ZERO,
+            glam::Vec2::ONE,
+            swapchain_image,
+            dst_min_uv,
+            dst_max_uv,
+        );
+        graph.write_external_image(swapchain_image_id, swapchain_image);
+    }
 
     let prepared_render_graph = PreparedRenderGraph::new(
         &device_context,
         &resource_context,
         graph,
-        &swapchain_info.swapchain_surface_info,
+        swapchain_surface_info,
     )?;
 
     render_resources
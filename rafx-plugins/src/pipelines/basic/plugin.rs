@@ -10,7 +10,7 @@ use rafx::distill::loader::handle::Handle;
 use rafx::framework::{ImageViewResource, RenderResources, ResourceArc};
 use rafx::graph::PreparedRenderGraph;
 use rafx::render_features::{ExtractResources, RenderRegistryBuilder, RenderView};
-use rafx::renderer::{RendererLoadContext, RendererPipelinePlugin};
+use rafx::renderer::{RenderViewportTarget, RendererLoadContext, RendererPipelinePlugin};
 
 // A plugin that add demo-specific configuration
 
@@ -107,6 +107,7 @@ impl RendererPipelinePlugin for BasicPipelineRendererPlugin {
         swapchain_image: ResourceArc<ImageViewResource>,
         rotating_frame_index: usize,
         main_view: RenderView,
+        viewport: &RenderViewportTarget,
         extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph> {
@@ -115,6 +116,7 @@ impl RendererPipelinePlugin for BasicPipelineRendererPlugin {
             swapchain_image,
             rotating_frame_index,
             main_view,
+            viewport,
             extract_resources,
             render_resources,
         )
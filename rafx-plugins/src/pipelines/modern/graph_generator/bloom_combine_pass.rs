@@ -2,11 +2,14 @@ use super::ModernPipelineRenderOptions;
 use crate::phases::PostProcessRenderPhase;
 use crate::pipelines::modern::graph_generator::luma_pass::LumaAverageHistogramPass;
 use crate::pipelines::modern::ModernPipelineOutputColorSpace;
-use rafx::api::RafxSwapchainColorSpace;
-use rafx::framework::{DescriptorSetBindings, MaterialPassResource, ResourceArc};
+use rafx::api::{RafxExtents3D, RafxSwapchainColorSpace};
+use rafx::assets::ImageAsset;
+use rafx::distill::loader::handle::Handle;
+use rafx::framework::{
+    DescriptorSetBindings, ImageViewResource, MaterialPassResource, ResourceArc,
+};
 use rafx::graph::*;
 use rafx::render_features::RenderPhase;
-use rafx::renderer::SwapchainRenderResource;
 
 use super::ModernPipelineContext;
 use super::EMPTY_VERTEX_LAYOUT;
@@ -24,7 +27,8 @@ pub(super) fn bloom_combine_pass(
     sdr_image: RenderGraphImageUsageId,
     blurred_color: RenderGraphImageUsageId,
     luma_average_histogram_pass: &LumaAverageHistogramPass,
-    swapchain_render_resource: &SwapchainRenderResource,
+    max_color_component_value: f32,
+    neutral_color_grading_lut: ResourceArc<ImageViewResource>,
 ) -> BloomCombinePass {
     let render_options = context
         .extract_resources
@@ -34,12 +38,19 @@ pub(super) fn bloom_combine_pass(
         .graph
         .add_node("BloomCombine", RenderGraphQueue::DefaultGraphics);
 
+    // This is the first pass that runs at the size of the viewport rather than the render size
+    let output_extents = context.viewport.surface_info.extents;
     let color = context.graph.create_color_attachment(
         node,
         0,
         Default::default(),
         RenderGraphImageConstraint {
             format: Some(context.graph_config.swapchain_format),
+            extents: Some(RenderGraphImageExtents::Custom(RafxExtents3D {
+                width: output_extents.width,
+                height: output_extents.height,
+                depth: 1,
+            })),
             ..Default::default()
         },
         Default::default(),
@@ -64,12 +75,28 @@ pub(super) fn bloom_combine_pass(
         Default::default(),
     );
 
-    let swapchain_color_space = swapchain_render_resource
-        .surface_info()
-        .unwrap()
-        .swapchain_surface_info
-        .color_space;
-    let max_color_component_value = swapchain_render_resource.max_color_component_value;
+    let swapchain_color_space = context.viewport.surface_info.color_space;
+
+    // LUTs that are not set or still loading are replaced with an identity LUT
+    let color_grading_options = render_options.color_grading_options.clone();
+    let lut_image_view = |lut: &Option<Handle<ImageAsset>>| {
+        lut.as_ref()
+            .and_then(|handle| context.asset_manager.committed_asset(handle))
+            .map(|asset| asset.image_view.clone())
+    };
+    let lut_a = lut_image_view(&color_grading_options.lut_a);
+    let lut_b = lut_image_view(&color_grading_options.lut_b);
+    let lut_contribution = if lut_a.is_some() || lut_b.is_some() {
+        color_grading_options.lut_contribution.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let lut_a = lut_a.unwrap_or_else(|| neutral_color_grading_lut.clone());
+    let lut_b = lut_b.unwrap_or(neutral_color_grading_lut);
+    let white_balance = super::super::internal::color_grading::white_balance_lms_scale(
+        color_grading_options.temperature,
+        color_grading_options.tint,
+    );
 
     context.graph.set_renderpass_callback(node, move |args| {
         // Get the color image from before
@@ -112,8 +139,18 @@ pub(super) fn bloom_combine_pass(
                         tonemapper_type: render_options.tonemapper_type as i32,
                         output_color_space: output_color_space as i32,
                         max_color_component_value,
+                        enable_color_grading: render_options.enable_color_grading as u32,
+                        lift: color_grading_options.lift.into(),
+                        saturation: color_grading_options.saturation,
+                        gamma: color_grading_options.gamma.into(),
+                        lut_blend: color_grading_options.lut_blend.clamp(0.0, 1.0),
+                        gain: color_grading_options.gain.into(),
+                        lut_contribution,
+                        white_balance: white_balance.into(),
                         ..Default::default()
                     },
+                    lut_a: &lut_a,
+                    lut_b: &lut_b,
                 },
             )?;
 
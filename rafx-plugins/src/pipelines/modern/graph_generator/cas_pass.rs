@@ -3,7 +3,7 @@ use rafx::graph::*;
 
 use super::ModernPipelineContext;
 use crate::shaders::cas::cas32_comp;
-use rafx::api::{RafxFormat, RafxSampleCount};
+use rafx::api::{RafxExtents3D, RafxFormat, RafxSampleCount};
 
 pub(super) struct CasPass {
     #[allow(dead_code)]
@@ -11,11 +11,13 @@ pub(super) struct CasPass {
     pub(super) color_rt: RenderGraphImageUsageId,
 }
 
+// Sharpens color_rt, upscaling it from the render size to the output size if they differ
 pub(super) fn cas_pass(
     context: &mut ModernPipelineContext,
     cas_pipeline: &ResourceArc<ComputePipelineResource>,
     color_rt: RenderGraphImageUsageId,
-    swapchain_surface_info: &SwapchainSurfaceInfo,
+    render_surface_info: &SwapchainSurfaceInfo,
+    output_surface_info: &SwapchainSurfaceInfo,
 ) -> CasPass {
     let node = context
         .graph
@@ -32,12 +34,20 @@ pub(super) fn cas_pass(
         Default::default(),
     );
 
+    let input_extents = render_surface_info.extents;
+    let output_extents = output_surface_info.extents;
+
     let dst_rt = context.graph.create_storage_image(
         node,
         RenderGraphImageConstraint {
             samples: Some(RafxSampleCount::SampleCount1),
             format: Some(RafxFormat::R16G16B16A16_SFLOAT),
             //format: Some(context.graph_config.color_format),
+            extents: Some(RenderGraphImageExtents::Custom(RafxExtents3D {
+                width: output_extents.width,
+                height: output_extents.height,
+                depth: 1,
+            })),
             ..Default::default()
         },
         Default::default(),
@@ -45,7 +55,6 @@ pub(super) fn cas_pass(
 
     let cas_pipeline = cas_pipeline.clone();
 
-    let swapchain_extents = swapchain_surface_info.extents;
     let sharpening_amount = context.graph_config.sharpening_amount;
 
     context.graph.set_callback(node, move |args| {
@@ -60,15 +69,14 @@ pub(super) fn cas_pass(
         let src_rt = args.graph_context.image_view(src_rt).unwrap();
         let dst_rt = args.graph_context.image_view(dst_rt).unwrap();
 
-        let input_width = swapchain_extents.width;
-        let input_height = swapchain_extents.height;
-
         descriptor_set.set_buffer_data(
             cas32_comp::CONFIG_DESCRIPTOR_BINDING_INDEX as u32,
             &cas32_comp::ConfigUniform {
-                image_width: input_width,
-                image_height: input_height,
+                image_width: input_extents.width,
+                image_height: input_extents.height,
                 sharpen_amount: sharpening_amount,
+                output_width: output_extents.width,
+                output_height: output_extents.height,
                 _padding0: Default::default(),
             },
         );
@@ -82,7 +90,11 @@ pub(super) fn cas_pass(
 
         command_buffer.cmd_bind_pipeline(&*cas_pipeline.get_raw().pipeline)?;
         descriptor_set.bind(command_buffer)?;
-        command_buffer.cmd_dispatch((input_width + 15) / 16, (input_height + 15) / 16, 1)?;
+        command_buffer.cmd_dispatch(
+            (output_extents.width + 15) / 16,
+            (output_extents.height + 15) / 16,
+            1,
+        )?;
 
         Ok(())
     });
@@ -0,0 +1,345 @@
+use crate::pipelines::modern::graph_generator::ModernPipelineContext;
+use crate::pipelines::modern::DepthOfFieldOptions;
+use crate::shaders::post_adv::{dof_bokeh_frag, dof_composite_frag, dof_prefilter_frag};
+use rafx::api::{RafxExtents3D, RafxFormat};
+use rafx::framework::{MaterialPassResource, ResourceArc};
+use rafx::graph::*;
+
+pub(super) struct DepthOfFieldPass {
+    pub(super) color: RenderGraphImageUsageId,
+}
+
+// Radius in pixels of the circle of confusion of an object at infinity, for a view that is
+// `viewport_height` pixels tall. The CoC of an object at distance d is this multiplied by
+// (1 - focus_distance / d).
+fn coc_scale(
+    dof_options: &DepthOfFieldOptions,
+    viewport_height: u32,
+) -> f32 {
+    let focal_length = dof_options.focal_length_mm / 1000.0;
+    let sensor_height = dof_options.sensor_height_mm.max(0.001) / 1000.0;
+    let f_stop = dof_options.f_stop.max(0.1);
+    // Can't focus closer than the focal length
+    let focus_distance = dof_options.focus_distance.max(focal_length + 0.001);
+
+    let coc_diameter = (focal_length * focal_length) / (f_stop * (focus_distance - focal_length));
+    0.5 * coc_diameter / sensor_height * viewport_height as f32
+}
+
+// Physically based depth of field, split into three nodes:
+// - Prefilter: downsample color to half res and compute a signed CoC per texel
+// - Bokeh: gather a bokeh disc at half res
+// - Composite: blend the half res bokeh over the full res color by CoC
+pub(super) fn depth_of_field_pass(
+    context: &mut ModernPipelineContext,
+    dof_options: &DepthOfFieldOptions,
+    dof_prefilter_material_pass: ResourceArc<MaterialPassResource>,
+    dof_bokeh_material_pass: ResourceArc<MaterialPassResource>,
+    dof_composite_material_pass: ResourceArc<MaterialPassResource>,
+    color_rt: RenderGraphImageUsageId,
+    depth_rt: RenderGraphImageUsageId,
+) -> DepthOfFieldPass {
+    let half_extents = RafxExtents3D {
+        width: (context.main_view.extents_width() / 2).max(1),
+        height: (context.main_view.extents_height() / 2).max(1),
+        depth: 1,
+    };
+
+    let prefilter_rt = dof_prefilter_pass(
+        context,
+        dof_options,
+        dof_prefilter_material_pass,
+        color_rt,
+        depth_rt,
+        half_extents,
+    );
+
+    let bokeh_rt = dof_bokeh_pass(
+        context,
+        dof_options,
+        dof_bokeh_material_pass,
+        prefilter_rt,
+        half_extents,
+    );
+
+    let color = dof_composite_pass(
+        context,
+        dof_options,
+        dof_composite_material_pass,
+        color_rt,
+        depth_rt,
+        bokeh_rt,
+    );
+
+    DepthOfFieldPass { color }
+}
+
+fn dof_prefilter_pass(
+    context: &mut ModernPipelineContext,
+    dof_options: &DepthOfFieldOptions,
+    dof_prefilter_material_pass: ResourceArc<MaterialPassResource>,
+    color_rt: RenderGraphImageUsageId,
+    depth_rt: RenderGraphImageUsageId,
+    half_extents: RafxExtents3D,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("DofPrefilter", RenderGraphQueue::DefaultGraphics);
+
+    let color_rt = context.graph.sample_image(
+        node,
+        color_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let depth_rt = context.graph.sample_image(
+        node,
+        depth_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let prefilter_rt = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+            extents: Some(RenderGraphImageExtents::Custom(half_extents)),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(prefilter_rt, "dof_prefilter");
+
+    let focus_distance = dof_options.focus_distance;
+    let coc_scale = coc_scale(dof_options, half_extents.height);
+    let max_coc_radius = dof_options.max_coc_radius * 0.5;
+    let main_view = context.main_view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        let color_tex = args.graph_context.image_view(color_rt).unwrap();
+        let depth_tex = args.graph_context.image_view(depth_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &dof_prefilter_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[dof_prefilter_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            dof_prefilter_frag::DescriptorSet0Args {
+                color_tex: &color_tex,
+                depth_tex: &depth_tex,
+                config: &dof_prefilter_frag::ConfigUniform {
+                    proj_inv: main_view.projection_matrix().inverse().to_cols_array_2d(),
+                    focus_distance,
+                    coc_scale,
+                    max_coc_radius,
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    prefilter_rt
+}
+
+fn dof_bokeh_pass(
+    context: &mut ModernPipelineContext,
+    dof_options: &DepthOfFieldOptions,
+    dof_bokeh_material_pass: ResourceArc<MaterialPassResource>,
+    prefilter_rt: RenderGraphImageUsageId,
+    half_extents: RafxExtents3D,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("DofBokeh", RenderGraphQueue::DefaultGraphics);
+
+    let prefilter_rt = context.graph.sample_image(
+        node,
+        prefilter_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let bokeh_rt = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+            extents: Some(RenderGraphImageExtents::Custom(half_extents)),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(bokeh_rt, "dof_bokeh");
+
+    let max_coc_radius = dof_options.max_coc_radius * 0.5;
+    let radius_step = dof_options.radius_step;
+    context.graph.set_renderpass_callback(node, move |args| {
+        let prefilter_tex = args.graph_context.image_view(prefilter_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &dof_bokeh_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[dof_bokeh_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            dof_bokeh_frag::DescriptorSet0Args {
+                prefilter_tex: &prefilter_tex,
+                config: &dof_bokeh_frag::ConfigUniform {
+                    max_coc_radius,
+                    radius_step,
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    bokeh_rt
+}
+
+fn dof_composite_pass(
+    context: &mut ModernPipelineContext,
+    dof_options: &DepthOfFieldOptions,
+    dof_composite_material_pass: ResourceArc<MaterialPassResource>,
+    color_rt: RenderGraphImageUsageId,
+    depth_rt: RenderGraphImageUsageId,
+    bokeh_rt: RenderGraphImageUsageId,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("DofComposite", RenderGraphQueue::DefaultGraphics);
+
+    let color_rt = context.graph.sample_image(
+        node,
+        color_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let depth_rt = context.graph.sample_image(
+        node,
+        depth_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let bokeh_rt = context.graph.sample_image(
+        node,
+        bokeh_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let color = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(context.graph_config.color_format),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(color, "color");
+
+    let focus_distance = dof_options.focus_distance;
+    let coc_scale = coc_scale(dof_options, context.main_view.extents_height());
+    let max_coc_radius = dof_options.max_coc_radius;
+    let main_view = context.main_view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        let color_tex = args.graph_context.image_view(color_rt).unwrap();
+        let depth_tex = args.graph_context.image_view(depth_rt).unwrap();
+        let bokeh_tex = args.graph_context.image_view(bokeh_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &dof_composite_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[dof_composite_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            dof_composite_frag::DescriptorSet0Args {
+                color_tex: &color_tex,
+                depth_tex: &depth_tex,
+                bokeh_tex: &bokeh_tex,
+                config: &dof_composite_frag::ConfigUniform {
+                    proj_inv: main_view.projection_matrix().inverse().to_cols_array_2d(),
+                    focus_distance,
+                    coc_scale,
+                    max_coc_radius,
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    color
+}
@@ -7,6 +7,8 @@ use rafx::api::{RafxExtents3D, RafxFormat, RafxSampleCount};
 
 pub const MAX_DEPTH_PYRAMID_MIP_LAYERS: u32 = 16;
 
+// Mip 0 is the depth buffer. All other mips store the farthest depth of the covered texels in R
+// and the closest depth in G
 pub(super) struct DepthPyramidPass {
     #[allow(dead_code)]
     pub(super) node: RenderGraphNodeId,
@@ -20,6 +22,7 @@ fn depth_pyramid_mip_pass(
     dst_depth_rt: RenderGraphImageUsageId,
     input_width: u32,
     input_height: u32,
+    src_is_depth: bool,
     node: RenderGraphNodeId,
 ) {
     let depth_pyramid_pipeline = depth_pyramid_pipeline.clone();
@@ -43,6 +46,8 @@ fn depth_pyramid_mip_pass(
                 input_height,
                 odd_width: ((input_width % 2) == 1) as u32,
                 odd_height: ((input_height % 2) == 1) as u32,
+                src_is_depth: src_is_depth as u32,
+                ..Default::default()
             },
         );
         descriptor_set.set_image(
@@ -121,7 +126,7 @@ pub(super) fn depth_pyramid_pass(
                 previous_dst_depth_rt.unwrap(),
                 RenderGraphImageConstraint {
                     samples: Some(RafxSampleCount::SampleCount1),
-                    format: Some(RafxFormat::R32_SFLOAT),
+                    format: Some(RafxFormat::R32G32_SFLOAT),
                     ..Default::default()
                 },
                 Default::default(),
@@ -132,7 +137,7 @@ pub(super) fn depth_pyramid_pass(
             node,
             RenderGraphImageConstraint {
                 samples: Some(RafxSampleCount::SampleCount1),
-                format: Some(RafxFormat::R32_SFLOAT),
+                format: Some(RafxFormat::R32G32_SFLOAT),
                 extents: Some(RenderGraphImageExtents::Custom(RafxExtents3D {
                     width: output_width,
                     height: output_height,
@@ -150,6 +155,7 @@ pub(super) fn depth_pyramid_pass(
             dst_depth_rt,
             input_width,
             input_height,
+            dst_mip_level == 1,
             node,
         );
         previous_node = Some(node);
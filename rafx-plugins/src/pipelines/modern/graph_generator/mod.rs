@@ -1,6 +1,7 @@
 use rafx::api::{
-    RafxExtents3D, RafxFormat, RafxPrimitiveTopology, RafxResourceState, RafxResourceType,
-    RafxResult, RafxSampleCount, RafxTextureDef, RafxTextureDimensions,
+    RafxBufferDef, RafxExtents2D, RafxExtents3D, RafxFormat, RafxMemoryUsage,
+    RafxPrimitiveTopology, RafxQueueType, RafxResourceState, RafxResourceType, RafxResult,
+    RafxSampleCount, RafxTextureDef, RafxTextureDimensions,
 };
 use rafx::framework::VertexDataSetLayout;
 use rafx::framework::{ImageViewResource, ResourceArc};
@@ -23,8 +24,11 @@ use crate::features::debug_pip::DebugPipRenderResource;
 use crate::features::mesh_adv::{MeshAdvRenderPipelineState, ShadowMapAtlas};
 use crate::pipelines::modern::{
     AntiAliasMethodAdv, ModernPipelineMeshCullingDebugData, ModernPipelineTonemapDebugData,
+    TransparencyMethodAdv,
 };
+use crate::shaders::post_adv::luma_average_histogram_comp;
 use rafx::assets::AssetManager;
+use rafx::renderer::RenderViewportTarget;
 use rafx::renderer::SwapchainRenderResource;
 use rafx::renderer::TimeRenderResource;
 
@@ -48,6 +52,16 @@ mod mesh_culling;
 
 mod depth_pyramid;
 
+mod oit_pass;
+
+mod ssr_pass;
+
+mod volumetric_fog_pass;
+
+mod depth_of_field_pass;
+
+mod motion_blur_pass;
+
 lazy_static::lazy_static! {
     pub static ref EMPTY_VERTEX_LAYOUT : VertexDataSetLayout = {
         VertexDataSetLayout::new(vec![], RafxPrimitiveTopology::TriangleList)
@@ -62,7 +76,12 @@ pub struct ModernPipelineRenderGraphConfig {
     pub samples: RafxSampleCount,
     pub enable_hdr: bool,
     pub enable_ssao: bool,
+    pub enable_ssr: bool,
     pub enable_bloom: bool,
+    pub enable_oit: bool,
+    pub enable_volumetric_fog: bool,
+    pub enable_depth_of_field: bool,
+    pub enable_motion_blur: bool,
     pub show_surfaces: bool,
     pub blur_pass_count: usize,
     pub jitter_amount: glam::Vec2,
@@ -77,6 +96,7 @@ struct ModernPipelineContext<'a> {
     asset_manager: &'a AssetManager,
     graph_config: &'a ModernPipelineRenderGraphConfig,
     main_view: &'a RenderView,
+    viewport: &'a RenderViewportTarget,
     extract_resources: &'a ExtractResources<'a>,
     render_resources: &'a RenderResources,
 }
@@ -86,6 +106,7 @@ pub(super) fn generate_render_graph(
     swapchain_image: ResourceArc<ImageViewResource>,
     rotating_frame_index: usize,
     main_view: RenderView,
+    viewport: &RenderViewportTarget,
     extract_resources: &ExtractResources,
     render_resources: &RenderResources,
 ) -> RafxResult<PreparedRenderGraph> {
@@ -93,28 +114,35 @@ pub(super) fn generate_render_graph(
     let device_context = asset_manager.device_context();
     let resource_context = asset_manager.resource_manager().resource_context();
     let swapchain_render_resource = render_resources.fetch::<SwapchainRenderResource>();
-    let swapchain_info = swapchain_render_resource.surface_info().unwrap();
-    let swapchain_extents = swapchain_info.swapchain_surface_info.extents;
+    let swapchain_info = swapchain_render_resource
+        .surface_info_for_surface(&viewport.surface_name)
+        .unwrap();
+    // The final image is the size of the viewport
+    let swapchain_surface_info = &viewport.surface_info;
+    let swapchain_extents = swapchain_surface_info.extents;
+    // Everything before the upscale is rendered at the size of the main view, which is smaller than
+    // the viewport when dynamic resolution is scaling it down
+    let render_surface_info = SwapchainSurfaceInfo {
+        extents: RafxExtents2D {
+            width: main_view.extents_width(),
+            height: main_view.extents_height(),
+        },
+        ..swapchain_surface_info.clone()
+    };
+    let render_extents = render_surface_info.extents;
+    let is_upscaling = render_extents != swapchain_extents;
     let mut static_resources = render_resources.fetch_mut::<ModernPipelineStaticResources>();
     let mut shadow_atlas = render_resources.fetch_mut::<ShadowMapAtlas>();
     let previous_update_dt = render_resources
         .fetch::<TimeRenderResource>()
         .previous_update_dt();
 
-    if let Some(taa_history_rt) = &static_resources.taa_history_rt {
-        let taa_history_rt_extents = taa_history_rt
-            .get_raw()
-            .image
-            .get_raw()
-            .image
-            .texture_def()
-            .extents;
-        if swapchain_extents.width != taa_history_rt_extents.width
-            || swapchain_extents.height != taa_history_rt_extents.height
-        {
-            static_resources.taa_history_rt = None;
-        }
-    }
+    // Temporal history and exposure are kept per viewport. Resources of viewports that stop being
+    // drawn are released in finish_frame()
+    let viewport_name = viewport.unique_name();
+    static_resources
+        .drawn_viewports
+        .insert(viewport_name.clone());
 
     render_resources
         .fetch_mut::<DebugPipRenderResource>()
@@ -126,7 +154,7 @@ pub(super) fn generate_render_graph(
 
     // Configure options for this frame
     let graph_config = {
-        let swapchain_format = swapchain_info.swapchain_surface_info.format;
+        let swapchain_format = swapchain_surface_info.format;
         let sample_count = if render_options.anti_alias_method == AntiAliasMethodAdv::Msaa4x {
             RafxSampleCount::SampleCount4
         } else {
@@ -139,16 +167,6 @@ pub(super) fn generate_render_graph(
             swapchain_info.default_color_format_sdr
         };
 
-        let jitter_amount = if render_options.anti_alias_method == AntiAliasMethodAdv::Taa {
-            super::internal::jitter::jitter_amount(
-                main_view.frame_index(),
-                render_options.taa_options.jitter_pattern,
-                main_view.extents_vec2(),
-            ) * render_options.taa_options.jitter_multiplier
-        } else {
-            glam::Vec2::ZERO
-        };
-
         ModernPipelineRenderGraphConfig {
             color_format,
             depth_format: swapchain_info.default_depth_format,
@@ -157,11 +175,29 @@ pub(super) fn generate_render_graph(
             swapchain_format,
             enable_ssao: render_options.enable_ssao
                 && sample_count == RafxSampleCount::SampleCount1,
+            enable_ssr: render_options.enable_ssr
+                && render_options.show_surfaces
+                && sample_count == RafxSampleCount::SampleCount1,
             enable_bloom: render_options.enable_bloom,
+            // The OIT targets are single-sampled, so fall back to sorted blending with MSAA
+            enable_oit: render_options.transparency_method
+                == TransparencyMethodAdv::WeightedBlendedOit
+                && sample_count == RafxSampleCount::SampleCount1,
+            enable_volumetric_fog: render_options.enable_volumetric_fog
+                && render_options.show_surfaces,
+            enable_depth_of_field: render_options.enable_depth_of_field
+                && sample_count == RafxSampleCount::SampleCount1,
+            enable_motion_blur: render_options.enable_motion_blur
+                && sample_count == RafxSampleCount::SampleCount1,
             show_surfaces: render_options.show_surfaces,
             blur_pass_count: render_options.blur_pass_count,
-            jitter_amount,
-            sharpening_amount: render_options.sharpening_amount,
+            // Chosen by ModernRendererPipelinePlugin::viewport_jitter_amount when the view was created
+            jitter_amount: main_view.jitter_amount(),
+            sharpening_amount: if render_options.enable_sharpening {
+                render_options.sharpening_amount
+            } else {
+                0.0
+            },
         }
     };
 
@@ -169,9 +205,10 @@ pub(super) fn generate_render_graph(
     {
         let mut mesh_render_pipeline_state =
             render_resources.fetch_mut::<MeshAdvRenderPipelineState>();
-        mesh_render_pipeline_state.jitter_amount = graph_config.jitter_amount;
         mesh_render_pipeline_state.forward_pass_mip_bias =
             render_options.taa_options.forward_pass_mip_bias;
+        mesh_render_pipeline_state.use_weighted_blended_oit = graph_config.enable_oit;
+        mesh_render_pipeline_state.write_surface_data = graph_config.enable_ssr;
     }
 
     let mut taa_history_rt_has_data = false;
@@ -183,7 +220,7 @@ pub(super) fn generate_render_graph(
         };
 
         let mut history_texture_compatible = false;
-        if let Some(taa_history) = &static_resources.taa_history_rt {
+        if let Some(taa_history) = static_resources.taa_history_rt.get(&viewport_name) {
             let history_image = taa_history.get_raw().image.get_raw().image.clone();
             let history_def = history_image.texture_def();
             history_texture_compatible = history_def.sample_count == graph_config.samples
@@ -212,14 +249,59 @@ pub(super) fn generate_render_graph(
             let taa_history_rt = asset_manager
                 .resources()
                 .get_or_create_image_view(&taa_history_rt, None)?;
-            static_resources.taa_history_rt = Some(taa_history_rt.clone());
+            static_resources
+                .taa_history_rt
+                .insert(viewport_name.clone(), taa_history_rt.clone());
             Some(taa_history_rt)
         } else {
             taa_history_rt_has_data = true;
-            Some(static_resources.taa_history_rt.clone().unwrap())
+            static_resources.taa_history_rt.get(&viewport_name).cloned()
+        }
+    } else {
+        static_resources.taa_history_rt.remove(&viewport_name);
+        None
+    };
+
+    let mut ssr_history_rt_has_data = false;
+    let ssr_history_rt = if graph_config.enable_ssr {
+        let required_extents = RafxExtents3D {
+            width: render_extents.width,
+            height: render_extents.height,
+            depth: 1,
+        };
+
+        let mut history_texture_compatible = false;
+        if let Some(ssr_history) = static_resources.ssr_history_rt.get(&viewport_name) {
+            let history_image = ssr_history.get_raw().image.get_raw().image.clone();
+            history_texture_compatible = history_image.texture_def().extents == required_extents;
+        }
+
+        if !history_texture_compatible {
+            let ssr_history_rt =
+                asset_manager
+                    .device_context()
+                    .create_texture(&RafxTextureDef {
+                        resource_type: RafxResourceType::RENDER_TARGET_COLOR
+                            | RafxResourceType::TEXTURE,
+                        format: RafxFormat::R16G16B16A16_SFLOAT,
+                        extents: required_extents,
+                        dimensions: RafxTextureDimensions::Dim2D,
+                        ..Default::default()
+                    })?;
+            let ssr_history_rt = asset_manager.resources().insert_image(ssr_history_rt);
+            let ssr_history_rt = asset_manager
+                .resources()
+                .get_or_create_image_view(&ssr_history_rt, None)?;
+            static_resources
+                .ssr_history_rt
+                .insert(viewport_name.clone(), ssr_history_rt.clone());
+            Some(ssr_history_rt)
+        } else {
+            ssr_history_rt_has_data = true;
+            static_resources.ssr_history_rt.get(&viewport_name).cloned()
         }
     } else {
-        static_resources.taa_history_rt = None;
+        static_resources.ssr_history_rt.remove(&viewport_name);
         None
     };
 
@@ -239,6 +321,7 @@ pub(super) fn generate_render_graph(
         asset_manager,
         graph_config: &graph_config,
         main_view: &main_view,
+        viewport,
         render_resources,
         extract_resources,
     };
@@ -246,16 +329,45 @@ pub(super) fn generate_render_graph(
     let swapchain_image_id = graph_context.graph.add_external_image(
         swapchain_image,
         Default::default(),
-        RafxResourceState::PRESENT,
-        RafxResourceState::PRESENT,
+        viewport.image_state,
+        viewport.image_state,
     );
 
     let shadow_atlas_image = shadow_atlas.add_to_render_graph(graph_context.graph);
     let shadow_atlas_needs_full_clear = shadow_atlas.take_requires_full_clear();
     drop(shadow_atlas);
 
+    let tonemap_histogram_result = match static_resources
+        .tonemap_histogram_result
+        .get(&viewport_name)
+    {
+        Some(tonemap_histogram_result) => tonemap_histogram_result.clone(),
+        None => {
+            let tonemap_histogram_result = asset_manager.device_context().create_buffer(
+                &RafxBufferDef {
+                    size: std::mem::size_of::<luma_average_histogram_comp::HistogramResultBuffer>()
+                        as u64,
+                    alignment: 256,
+                    memory_usage: RafxMemoryUsage::GpuOnly,
+                    queue_type: RafxQueueType::Graphics,
+                    resource_type: RafxResourceType::BUFFER_READ_WRITE,
+                    elements: Default::default(),
+                    format: RafxFormat::UNDEFINED,
+                    always_mapped: false,
+                },
+            )?;
+            let tonemap_histogram_result = asset_manager
+                .resources()
+                .insert_buffer(tonemap_histogram_result);
+            static_resources
+                .tonemap_histogram_result
+                .insert(viewport_name.clone(), tonemap_histogram_result.clone());
+            tonemap_histogram_result
+        }
+    };
+
     let tonemap_histogram_result = graph_context.graph.add_external_buffer(
-        static_resources.tonemap_histogram_result.clone(),
+        tonemap_histogram_result,
         RafxResourceState::UNORDERED_ACCESS,
         RafxResourceState::UNORDERED_ACCESS,
     );
@@ -284,7 +396,7 @@ pub(super) fn generate_render_graph(
         &mut graph_context,
         &depth_pyramid_pipeline,
         depth_prepass.depth,
-        &swapchain_info.swapchain_surface_info,
+        &render_surface_info,
     );
 
     let ssao_material_pass = asset_manager
@@ -337,6 +449,14 @@ pub(super) fn generate_render_graph(
     let build_light_lists_pass =
         light_binning::lights_build_lists_pass(&mut graph_context, light_bin_pass);
 
+    let volumetric_fog_pass = volumetric_fog_pass::volumetric_fog_pass(
+        &mut graph_context,
+        &render_options.volumetric_fog_options,
+        &shadow_map_pass_output,
+        &build_light_lists_pass,
+        render_options.anti_alias_method == AntiAliasMethodAdv::Taa,
+    );
+
     let mesh_culling_pipeline = asset_manager
         .committed_asset(&static_resources.mesh_culling_pipeline)
         .unwrap()
@@ -348,7 +468,7 @@ pub(super) fn generate_render_graph(
         Some(mesh_culling::mesh_culling_pass(
             &mut graph_context,
             &mesh_culling_pipeline,
-            &swapchain_info.swapchain_surface_info,
+            &render_surface_info,
             &depth_pyramid_pass,
             mesh_culling_debug_data,
             mesh_culling_debug_output,
@@ -363,14 +483,98 @@ pub(super) fn generate_render_graph(
         &shadow_map_pass_output,
         &build_light_lists_pass,
         ssao_rt,
+        &volumetric_fog_pass,
     );
 
     if render_options.enable_occlusion_culling {
         graph_context
             .graph
-            .add_explicit_dependency(mesh_culling_node.unwrap().node, opaque_pass.node);
+            .add_explicit_dependency(mesh_culling_node.as_ref().unwrap().node, opaque_pass.node);
     }
 
+    // Reflections only consider opaque geometry, so they are added before OIT transparency is
+    // composited. (Sorted transparency is drawn in the opaque pass and will receive reflections of
+    // whatever is behind it.)
+    let opaque_color = if graph_config.enable_ssr {
+        let ssr_trace_material_pass = asset_manager
+            .committed_asset(&static_resources.ssr_trace_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let ssr_temporal_material_pass = asset_manager
+            .committed_asset(&static_resources.ssr_temporal_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let ssr_composite_material_pass = asset_manager
+            .committed_asset(&static_resources.ssr_composite_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let ssr_history_rt_image_id = graph_context.graph.add_external_image(
+            ssr_history_rt.unwrap(),
+            Default::default(),
+            RafxResourceState::COPY_DST,
+            RafxResourceState::COPY_DST,
+        );
+
+        ssr_pass::ssr_pass(
+            &mut graph_context,
+            &render_options.ssr_options,
+            ssr_trace_material_pass,
+            ssr_temporal_material_pass,
+            ssr_composite_material_pass,
+            &depth_pyramid_pass,
+            depth_prepass.depth,
+            depth_prepass.velocity_rt,
+            opaque_pass.surface.unwrap(),
+            opaque_pass.color,
+            &noise_texture,
+            ssr_history_rt_image_id,
+            ssr_history_rt_has_data,
+        )
+        .color
+    } else {
+        opaque_pass.color
+    };
+
+    let opaque_color = if graph_config.enable_oit && graph_config.show_surfaces {
+        let oit_composite_material_pass = asset_manager
+            .committed_asset(&static_resources.oit_composite_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let oit_accumulate_pass = oit_pass::oit_accumulate_pass(
+            &mut graph_context,
+            depth_prepass.depth,
+            &shadow_map_pass_output,
+            &build_light_lists_pass,
+            ssao_rt,
+            &volumetric_fog_pass,
+        );
+
+        if render_options.enable_occlusion_culling {
+            graph_context.graph.add_explicit_dependency(
+                mesh_culling_node.as_ref().unwrap().node,
+                oit_accumulate_pass.node,
+            );
+        }
+
+        oit_pass::oit_composite_pass(
+            &mut graph_context,
+            oit_composite_material_pass,
+            &oit_accumulate_pass,
+            opaque_color,
+        )
+        .color
+    } else {
+        opaque_color
+    };
+
     let taa_material_pass = asset_manager
         .committed_asset(&static_resources.taa_material)
         .unwrap()
@@ -389,7 +593,7 @@ pub(super) fn generate_render_graph(
             &mut graph_context,
             &render_options.taa_options,
             taa_material_pass,
-            opaque_pass.color,
+            opaque_color,
             depth_prepass.depth,
             depth_prepass.velocity_rt,
             taa_history_rt_image_id,
@@ -398,7 +602,62 @@ pub(super) fn generate_render_graph(
 
         taa_pass.color_rt
     } else {
-        opaque_pass.color
+        opaque_color
+    };
+
+    // Camera effects run on the anti-aliased HDR color, before bloom so that blurred highlights
+    // still bloom
+    let color_rt = if graph_config.enable_depth_of_field {
+        let dof_prefilter_material_pass = asset_manager
+            .committed_asset(&static_resources.dof_prefilter_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let dof_bokeh_material_pass = asset_manager
+            .committed_asset(&static_resources.dof_bokeh_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        let dof_composite_material_pass = asset_manager
+            .committed_asset(&static_resources.dof_composite_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        depth_of_field_pass::depth_of_field_pass(
+            &mut graph_context,
+            &render_options.depth_of_field_options,
+            dof_prefilter_material_pass,
+            dof_bokeh_material_pass,
+            dof_composite_material_pass,
+            color_rt,
+            depth_prepass.depth,
+        )
+        .color
+    } else {
+        color_rt
+    };
+
+    let color_rt = if graph_config.enable_motion_blur {
+        let motion_blur_material_pass = asset_manager
+            .committed_asset(&static_resources.motion_blur_material)
+            .unwrap()
+            .get_single_material_pass()
+            .unwrap();
+
+        motion_blur_pass::motion_blur_pass(
+            &mut graph_context,
+            &render_options.motion_blur_options,
+            motion_blur_material_pass,
+            color_rt,
+            depth_prepass.depth,
+            depth_prepass.velocity_rt,
+        )
+        .color
+    } else {
+        color_rt
     };
 
     let mut previous_pass_color = if graph_config.enable_hdr {
@@ -448,14 +707,15 @@ pub(super) fn generate_render_graph(
             &mut graph_context,
             &cas_pipeline,
             bloom_extract_pass.sdr_image,
-            &swapchain_info.swapchain_surface_info,
+            &render_surface_info,
+            swapchain_surface_info,
         );
 
         let luma_build_histogram_pass = luma_pass::luma_build_histogram_pass(
             &mut graph_context,
             &luma_build_histogram,
             color_rt,
-            &swapchain_info.swapchain_surface_info,
+            &render_surface_info,
         );
 
         let luma_average_histogram_pass = luma_pass::luma_average_histogram_pass(
@@ -465,7 +725,7 @@ pub(super) fn generate_render_graph(
             tonemap_histogram_result,
             tonemap_debug_data,
             tonemap_debug_output,
-            &swapchain_info.swapchain_surface_info,
+            &render_surface_info,
             previous_update_dt,
         );
 
@@ -480,7 +740,9 @@ pub(super) fn generate_render_graph(
             bloom_extract_pass.hdr_image
         };
 
-        let sdr_image = if render_options.enable_sharpening {
+        // CAS also upscales to the viewport size, so it can't be skipped when rendering at a lower
+        // resolution
+        let sdr_image = if render_options.enable_sharpening || is_upscaling {
             cas_pass.color_rt
         } else {
             bloom_extract_pass.sdr_image
@@ -492,7 +754,8 @@ pub(super) fn generate_render_graph(
             sdr_image,
             blurred_color,
             &luma_average_histogram_pass,
-            &*swapchain_render_resource,
+            swapchain_render_resource.max_color_component_value,
+            static_resources.neutral_color_grading_lut.clone(),
         );
 
         bloom_combine_pass.color
@@ -505,13 +768,31 @@ pub(super) fn generate_render_graph(
 
     previous_pass_color = ui_pass::ui_pass(&mut graph_context, previous_pass_color).color;
 
-    graph.write_external_image(swapchain_image_id, previous_pass_color);
+    if viewport.covers_surface() {
+        graph.write_external_image(swapchain_image_id, previous_pass_color);
+    } else {
+        // Draw into the viewport's rect of the swapchain image, keeping whatever earlier viewports
+        // drew to the rest of it
+        let swapchain_image = graph.read_external_image(swapchain_image_id);
+        let (dst_min_uv, dst_max_uv) = viewport.rect_uv();
+        let swapchain_image = graph.blit_image_to_image(
+            "ViewportBlit",
+            RenderGraphQueue::DefaultGraphics,
+            previous_pass_color,
+            glam::Vec2::ZERO,
+            glam::Vec2::ONE,
+            swapchain_image,
+            dst_min_uv,
+            dst_max_uv,
+        );
+        graph.write_external_image(swapchain_image_id, swapchain_image);
+    }
 
     let prepared_render_graph = PreparedRenderGraph::new(
         &device_context,
         &resource_context,
         graph,
-        &swapchain_info.swapchain_surface_info,
+        &render_surface_info,
     )?;
 
     Ok(prepared_render_graph)
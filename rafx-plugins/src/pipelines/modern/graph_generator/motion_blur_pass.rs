@@ -0,0 +1,115 @@
+use crate::pipelines::modern::graph_generator::ModernPipelineContext;
+use crate::pipelines::modern::MotionBlurOptions;
+use crate::shaders::post_adv::motion_blur_frag;
+use rafx::framework::{MaterialPassResource, ResourceArc};
+use rafx::graph::{RenderGraphImageConstraint, RenderGraphImageUsageId, RenderGraphQueue};
+
+pub(super) struct MotionBlurPass {
+    pub(super) color: RenderGraphImageUsageId,
+}
+
+// Blurs the color along the per-pixel velocity written by the depth prepass. Pixels without
+// velocity (i.e. the sky) are reprojected with the previous frame's view matrices.
+pub(super) fn motion_blur_pass(
+    context: &mut ModernPipelineContext,
+    motion_blur_options: &MotionBlurOptions,
+    motion_blur_material_pass: ResourceArc<MaterialPassResource>,
+    color_rt: RenderGraphImageUsageId,
+    depth_rt: RenderGraphImageUsageId,
+    velocity_rt: RenderGraphImageUsageId,
+) -> MotionBlurPass {
+    let node = context
+        .graph
+        .add_node("MotionBlur", RenderGraphQueue::DefaultGraphics);
+
+    let color_rt = context.graph.sample_image(
+        node,
+        color_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let depth_rt = context.graph.sample_image(
+        node,
+        depth_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let velocity_rt = context.graph.sample_image(
+        node,
+        velocity_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let color = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(context.graph_config.color_format),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(color, "color");
+
+    let velocity_scale = motion_blur_options.shutter_angle.clamp(0.0, 360.0) / 360.0;
+    let max_blur_radius = motion_blur_options.max_blur_radius;
+    let sample_count = motion_blur_options.sample_count;
+    let main_view = context.main_view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        let color_tex = args.graph_context.image_view(color_rt).unwrap();
+        let depth_tex = args.graph_context.image_view(depth_rt).unwrap();
+        let velocity_tex = args.graph_context.image_view(velocity_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &motion_blur_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let current_view_proj_inv = main_view.view_proj().inverse();
+        let previous_view_proj = main_view.previous_view_proj();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[motion_blur_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            motion_blur_frag::DescriptorSet0Args {
+                color_tex: &color_tex,
+                depth_tex: &depth_tex,
+                velocity_tex: &velocity_tex,
+                config: &motion_blur_frag::ConfigUniform {
+                    current_view_proj_inv: current_view_proj_inv.to_cols_array_2d(),
+                    previous_view_proj: previous_view_proj.to_cols_array_2d(),
+                    velocity_scale,
+                    max_blur_radius,
+                    sample_count,
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    MotionBlurPass { color }
+}
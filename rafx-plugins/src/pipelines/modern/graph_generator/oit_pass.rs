@@ -0,0 +1,282 @@
+use crate::phases::{PostProcessRenderPhase, TransparentRenderPhase};
+use rafx::framework::{MaterialPassResource, ResourceArc};
+use rafx::graph::*;
+use rafx::render_features::RenderPhase;
+
+use super::ModernPipelineContext;
+use super::EMPTY_VERTEX_LAYOUT;
+use crate::assets::mesh_adv::MeshAdvShaderPassIndices;
+use crate::features::mesh_adv::{MeshAdvRenderPipelineState, MeshAdvStaticResources};
+use crate::pipelines::modern::graph_generator::light_binning::LightBuildListsPass;
+use crate::pipelines::modern::graph_generator::shadow_map_pass::ShadowMapPassOutput;
+use crate::pipelines::modern::graph_generator::volumetric_fog_pass::VolumetricFogPass;
+use crate::shaders::mesh_adv::mesh_adv_textured_frag;
+use crate::shaders::post_adv::oit_composite_frag;
+use rafx::api::{RafxColorClearValue, RafxFormat, RafxSampleCount};
+use rafx::render_features::RenderJobCommandBufferContext;
+use rafx::renderer::InvalidResources;
+
+pub(super) struct OitAccumulatePass {
+    #[allow(dead_code)]
+    pub(super) node: RenderGraphNodeId,
+    pub(super) accum: RenderGraphImageUsageId,
+    pub(super) revealage: RenderGraphImageUsageId,
+}
+
+// Draws the transparent render phase into an accumulation target (premultiplied color and alpha,
+// scaled by a depth weight) and a revealage target (product of 1 - alpha). Both targets are
+// blended in a way that doesn't depend on draw order.
+pub(super) fn oit_accumulate_pass(
+    context: &mut ModernPipelineContext,
+    depth_prepass: RenderGraphImageUsageId,
+    shadow_map_pass_output: &ShadowMapPassOutput,
+    light_build_lists_pass: &LightBuildListsPass,
+    ssao_rt: Option<RenderGraphImageUsageId>,
+    volumetric_fog_pass: &VolumetricFogPass,
+) -> OitAccumulatePass {
+    let node = context
+        .graph
+        .add_node("OitAccumulate", RenderGraphQueue::DefaultGraphics);
+
+    let accum = context.graph.create_color_attachment(
+        node,
+        0,
+        Some(RafxColorClearValue([0.0, 0.0, 0.0, 0.0])),
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(accum, "oit_accum");
+
+    let revealage = context.graph.create_color_attachment(
+        node,
+        1,
+        Some(RafxColorClearValue([1.0, 1.0, 1.0, 1.0])),
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(RafxFormat::R16_SFLOAT),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(revealage, "oit_revealage");
+
+    // Transparent surfaces are depth tested against the opaque geometry but do not write depth
+    context.graph.read_depth_attachment(
+        node,
+        depth_prepass,
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(context.graph_config.depth_format),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+
+    let ssao_rt = ssao_rt.map(|x| {
+        context.graph.sample_image(
+            node,
+            x,
+            RenderGraphImageConstraint::default(),
+            RenderGraphImageViewOptions::default(),
+        )
+    });
+
+    let volumetric_fog_froxels = context.graph.sample_image(
+        node,
+        volumetric_fog_pass.integrated_froxels,
+        Default::default(),
+        Default::default(),
+    );
+    let volumetric_fog = mesh_adv_textured_frag::VolumetricFogUniform {
+        near_z: volumetric_fog_pass.near_z,
+        far_z: volumetric_fog_pass.far_z,
+        enabled: volumetric_fog_pass.enabled as u32,
+        ..Default::default()
+    };
+
+    // This is a buffer owned by MeshAdvLightBinRenderResource
+    context.graph.read_storage_buffer(
+        node,
+        light_build_lists_pass.light_lists_buffer,
+        Default::default(),
+    );
+
+    context.graph.sample_image(
+        node,
+        shadow_map_pass_output.shadow_atlas_image,
+        Default::default(),
+        Default::default(),
+    );
+
+    context
+        .graph
+        .add_render_phase_dependency::<TransparentRenderPhase>(node);
+
+    let main_view = context.main_view.clone();
+
+    let default_pbr_material = context
+        .render_resources
+        .fetch::<MeshAdvStaticResources>()
+        .default_pbr_material
+        .clone();
+    let default_pbr_material = context
+        .asset_manager
+        .committed_asset(&default_pbr_material)
+        .unwrap()
+        .clone();
+
+    context.graph.set_renderpass_callback(node, move |args| {
+        let mut write_context =
+            RenderJobCommandBufferContext::from_graph_visit_render_pass_args(&args);
+
+        let invalid_image = args
+            .graph_context
+            .render_resources()
+            .fetch::<InvalidResources>()
+            .invalid_image_color
+            .clone();
+
+        let ssao_rt = ssao_rt.map(|x| args.graph_context.image_view(x).unwrap());
+        let ssao_rt = ssao_rt.unwrap_or(invalid_image);
+        let volumetric_fog_froxels = args
+            .graph_context
+            .image_view(volumetric_fog_froxels)
+            .unwrap();
+
+        let default_pbr_material_pass_indices =
+            MeshAdvShaderPassIndices::new(&default_pbr_material);
+        let default_pass = default_pbr_material
+            .get_material_pass_by_index(default_pbr_material_pass_indices.transparent_oit as usize)
+            .unwrap();
+
+        let descriptor_set_layouts = &default_pass.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[mesh_adv_textured_frag::SSAO_TEXTURE_DESCRIPTOR_SET_INDEX],
+            mesh_adv_textured_frag::DescriptorSet1Args {
+                ssao_texture: &ssao_rt,
+                volumetric_fog_texture: &volumetric_fog_froxels,
+                volumetric_fog: &volumetric_fog,
+            },
+        )?;
+        descriptor_set_allocator.flush_changes()?;
+
+        args.graph_context
+            .render_resources()
+            .fetch_mut::<MeshAdvRenderPipelineState>()
+            .ssao_descriptor_set = Some(descriptor_set);
+
+        {
+            profiling::scope!("OIT Accumulate Pass");
+            args.graph_context
+                .prepared_render_data()
+                .write_view_phase::<TransparentRenderPhase>(&main_view, &mut write_context)?;
+        }
+
+        args.graph_context
+            .render_resources()
+            .fetch_mut::<MeshAdvRenderPipelineState>()
+            .ssao_descriptor_set = None;
+
+        Ok(())
+    });
+
+    OitAccumulatePass {
+        node,
+        accum,
+        revealage,
+    }
+}
+
+pub(super) struct OitCompositePass {
+    #[allow(dead_code)]
+    pub(super) node: RenderGraphNodeId,
+    pub(super) color: RenderGraphImageUsageId,
+}
+
+// Resolves the accumulation/revealage targets and blends the result over the opaque color
+pub(super) fn oit_composite_pass(
+    context: &mut ModernPipelineContext,
+    oit_composite_material_pass: ResourceArc<MaterialPassResource>,
+    oit_accumulate_pass: &OitAccumulatePass,
+    opaque_color: RenderGraphImageUsageId,
+) -> OitCompositePass {
+    let node = context
+        .graph
+        .add_node("OitComposite", RenderGraphQueue::DefaultGraphics);
+
+    let color = context.graph.modify_color_attachment(
+        node,
+        opaque_color,
+        0,
+        None,
+        Default::default(),
+        Default::default(),
+    );
+    context.graph.set_image_name(color, "color");
+
+    let accum_image = context.graph.sample_image(
+        node,
+        oit_accumulate_pass.accum,
+        Default::default(),
+        Default::default(),
+    );
+
+    let revealage_image = context.graph.sample_image(
+        node,
+        oit_accumulate_pass.revealage,
+        Default::default(),
+        Default::default(),
+    );
+
+    context.graph.set_renderpass_callback(node, move |args| {
+        let accum_image = args.graph_context.image_view(accum_image);
+        let revealage_image = args.graph_context.image_view(revealage_image);
+
+        // Get the pipeline
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                Some(PostProcessRenderPhase::render_phase_index()),
+                &oit_composite_material_pass,
+                &args.render_target_meta,
+                &EMPTY_VERTEX_LAYOUT,
+            )?;
+
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let oit_composite_material_dyn_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[oit_composite_frag::ACCUM_TEX_DESCRIPTOR_SET_INDEX],
+            oit_composite_frag::DescriptorSet0Args {
+                accum_tex: accum_image.as_ref().unwrap(),
+                revealage_tex: revealage_image.as_ref().unwrap(),
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        oit_composite_material_dyn_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    OitCompositePass { node, color }
+}
@@ -6,8 +6,9 @@ use crate::assets::mesh_adv::MeshAdvShaderPassIndices;
 use crate::features::mesh_adv::{MeshAdvRenderPipelineState, MeshAdvStaticResources};
 use crate::pipelines::modern::graph_generator::light_binning::LightBuildListsPass;
 use crate::pipelines::modern::graph_generator::shadow_map_pass::ShadowMapPassOutput;
+use crate::pipelines::modern::graph_generator::volumetric_fog_pass::VolumetricFogPass;
 use crate::shaders::mesh_adv::mesh_adv_textured_frag;
-use rafx::api::RafxColorClearValue;
+use rafx::api::{RafxColorClearValue, RafxFormat};
 use rafx::render_features::RenderJobCommandBufferContext;
 use rafx::renderer::InvalidResources;
 
@@ -15,6 +16,7 @@ pub(super) struct OpaquePass {
     #[allow(dead_code)]
     pub(super) node: RenderGraphNodeId,
     pub(super) color: RenderGraphImageUsageId,
+    pub(super) surface: Option<RenderGraphImageUsageId>,
     #[allow(dead_code)]
     pub(super) shadow_map_atlas: RenderGraphImageUsageId,
 }
@@ -25,6 +27,7 @@ pub(super) fn opaque_pass(
     shadow_map_pass_output: &ShadowMapPassOutput,
     light_build_lists_pass: &LightBuildListsPass,
     ssao_rt: Option<RenderGraphImageUsageId>,
+    volumetric_fog_pass: &VolumetricFogPass,
 ) -> OpaquePass {
     let node = context
         .graph
@@ -43,6 +46,28 @@ pub(super) fn opaque_pass(
     );
     context.graph.set_image_name(color, "color");
 
+    // Normal/roughness/metalness of opaque meshes, consumed by SSR. Only added when SSR is enabled,
+    // which also switches mesh_adv to the opaque passes that write it. Other materials drawn in
+    // this pass only write the color target, so their pixels keep the clear value of full roughness
+    // and don't get reflections.
+    let surface = if context.graph_config.enable_ssr {
+        let surface = context.graph.create_color_attachment(
+            node,
+            1,
+            Some(RafxColorClearValue([0.0, 0.0, 1.0, 0.0])),
+            RenderGraphImageConstraint {
+                samples: Some(context.graph_config.samples),
+                format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        context.graph.set_image_name(surface, "surface");
+        Some(surface)
+    } else {
+        None
+    };
+
     //let mut shadow_maps = Vec::with_capacity(shadow_map_passes.len());
 
     if context.graph_config.show_surfaces {
@@ -67,6 +92,19 @@ pub(super) fn opaque_pass(
         )
     });
 
+    let volumetric_fog_froxels = context.graph.sample_image(
+        node,
+        volumetric_fog_pass.integrated_froxels,
+        Default::default(),
+        Default::default(),
+    );
+    let volumetric_fog = mesh_adv_textured_frag::VolumetricFogUniform {
+        near_z: volumetric_fog_pass.near_z,
+        far_z: volumetric_fog_pass.far_z,
+        enabled: volumetric_fog_pass.enabled as u32,
+        ..Default::default()
+    };
+
     // This is a buffer owned by MeshAdvLightBinRenderResource
     context.graph.read_storage_buffer(
         node,
@@ -87,6 +125,8 @@ pub(super) fn opaque_pass(
 
     let main_view = context.main_view.clone();
     let show_models = context.graph_config.show_surfaces;
+    // With OIT enabled, transparent meshes are drawn by a separate accumulate/composite pass
+    let draw_transparent = !context.graph_config.enable_oit;
 
     let default_pbr_material = context
         .render_resources
@@ -112,6 +152,10 @@ pub(super) fn opaque_pass(
 
         let ssao_rt = ssao_rt.map(|x| args.graph_context.image_view(x).unwrap());
         let ssao_rt = ssao_rt.unwrap_or(invalid_image);
+        let volumetric_fog_froxels = args
+            .graph_context
+            .image_view(volumetric_fog_froxels)
+            .unwrap();
 
         let default_pbr_material_pass_indices =
             MeshAdvShaderPassIndices::new(&default_pbr_material);
@@ -128,6 +172,8 @@ pub(super) fn opaque_pass(
             &descriptor_set_layouts[mesh_adv_textured_frag::SSAO_TEXTURE_DESCRIPTOR_SET_INDEX],
             mesh_adv_textured_frag::DescriptorSet1Args {
                 ssao_texture: &ssao_rt,
+                volumetric_fog_texture: &volumetric_fog_froxels,
+                volumetric_fog: &volumetric_fog,
             },
         )?;
         descriptor_set_allocator.flush_changes()?;
@@ -145,7 +191,7 @@ pub(super) fn opaque_pass(
                     .write_view_phase::<OpaqueRenderPhase>(&main_view, &mut write_context)?;
             }
 
-            {
+            if draw_transparent {
                 profiling::scope!("Transparent Pass");
                 args.graph_context
                     .prepared_render_data()
@@ -171,6 +217,7 @@ pub(super) fn opaque_pass(
     OpaquePass {
         node,
         color,
+        surface,
         shadow_map_atlas,
     }
 }
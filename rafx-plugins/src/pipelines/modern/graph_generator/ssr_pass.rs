@@ -0,0 +1,395 @@
+use crate::pipelines::modern::graph_generator::depth_pyramid::{
+    DepthPyramidPass, MAX_DEPTH_PYRAMID_MIP_LAYERS,
+};
+use crate::pipelines::modern::graph_generator::ModernPipelineContext;
+use crate::pipelines::modern::ScreenSpaceReflectionOptions;
+use crate::shaders::post_adv::{ssr_composite_frag, ssr_temporal_frag, ssr_trace_frag};
+use rafx::api::RafxFormat;
+use rafx::framework::{ImageViewResource, MaterialPassResource, ResourceArc};
+use rafx::graph::*;
+use rafx::renderer::InvalidResources;
+
+pub(super) struct SsrPass {
+    pub(super) color: RenderGraphImageUsageId,
+}
+
+// Screen-space reflections, split into three nodes:
+// - Trace: hierarchical ray march against the depth pyramid, sampling the opaque color at the hit
+// - Temporal: accumulate the (noisy) result with the previous frame's reflections
+// - Composite: add the reflections to the opaque color
+pub(super) fn ssr_pass(
+    context: &mut ModernPipelineContext,
+    ssr_options: &ScreenSpaceReflectionOptions,
+    ssr_trace_material_pass: ResourceArc<MaterialPassResource>,
+    ssr_temporal_material_pass: ResourceArc<MaterialPassResource>,
+    ssr_composite_material_pass: ResourceArc<MaterialPassResource>,
+    depth_pyramid_pass: &DepthPyramidPass,
+    depth_rt: RenderGraphImageUsageId,
+    velocity_rt: RenderGraphImageUsageId,
+    surface_rt: RenderGraphImageUsageId,
+    color_rt: RenderGraphImageUsageId,
+    noise_texture: &ResourceArc<ImageViewResource>,
+    ssr_history_rt_external_image_id: RenderGraphExternalImageId,
+    ssr_history_rt_has_data: bool,
+) -> SsrPass {
+    let reflection_rt = ssr_trace_pass(
+        context,
+        ssr_options,
+        ssr_trace_material_pass,
+        depth_pyramid_pass,
+        surface_rt,
+        color_rt,
+        noise_texture,
+    );
+
+    let reflection_rt = ssr_temporal_pass(
+        context,
+        ssr_options,
+        ssr_temporal_material_pass,
+        reflection_rt,
+        depth_rt,
+        velocity_rt,
+        ssr_history_rt_external_image_id,
+        ssr_history_rt_has_data,
+    );
+
+    let color = ssr_composite_pass(
+        context,
+        ssr_composite_material_pass,
+        reflection_rt,
+        color_rt,
+    );
+
+    SsrPass { color }
+}
+
+fn ssr_trace_pass(
+    context: &mut ModernPipelineContext,
+    ssr_options: &ScreenSpaceReflectionOptions,
+    ssr_trace_material_pass: ResourceArc<MaterialPassResource>,
+    depth_pyramid_pass: &DepthPyramidPass,
+    surface_rt: RenderGraphImageUsageId,
+    color_rt: RenderGraphImageUsageId,
+    noise_texture: &ResourceArc<ImageViewResource>,
+) -> RenderGraphImageUsageId {
+    let invalid_image = context
+        .render_resources
+        .fetch::<InvalidResources>()
+        .invalid_image_color
+        .clone();
+
+    let node = context
+        .graph
+        .add_node("SsrTrace", RenderGraphQueue::DefaultGraphics);
+
+    let depth_pyramid_mips: Vec<_> = depth_pyramid_pass
+        .depth_pyramid_mips
+        .iter()
+        .map(|x| {
+            context.graph.sample_image(
+                node,
+                *x,
+                RenderGraphImageConstraint::default(),
+                Default::default(),
+            )
+        })
+        .collect();
+
+    let surface_rt = context.graph.sample_image(
+        node,
+        surface_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let color_rt = context.graph.sample_image(
+        node,
+        color_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let reflection_rt = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(reflection_rt, "ssr_trace");
+
+    let noise_texture = noise_texture.clone();
+    let ssr_options = ssr_options.clone();
+    let main_view = context.main_view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        let depth_pyramid_mips: Vec<_> = depth_pyramid_mips
+            .iter()
+            .map(|&x| args.graph_context.image_view(x).unwrap())
+            .collect();
+        let mut depth_mip_slices: [Option<&ResourceArc<ImageViewResource>>;
+            MAX_DEPTH_PYRAMID_MIP_LAYERS as usize] = Default::default();
+        for (i, depth_mip_slice) in depth_mip_slices.iter_mut().enumerate() {
+            *depth_mip_slice = Some(depth_pyramid_mips.get(i).unwrap_or(&invalid_image));
+        }
+
+        let surface_tex = args.graph_context.image_view(surface_rt).unwrap();
+        let color_tex = args.graph_context.image_view(color_rt).unwrap();
+
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &ssr_trace_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let proj = main_view.projection_matrix();
+        let proj_inv = proj.inverse();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[ssr_trace_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            ssr_trace_frag::DescriptorSet0Args {
+                depth_mip_slices: &depth_mip_slices,
+                surface_tex: &surface_tex,
+                color_tex: &color_tex,
+                noise_tex: &noise_texture,
+                config: &ssr_trace_frag::ConfigUniform {
+                    proj: proj.to_cols_array_2d(),
+                    proj_inv: proj_inv.to_cols_array_2d(),
+                    viewport_width: main_view.extents_width(),
+                    viewport_height: main_view.extents_height(),
+                    depth_mip_slice_count: depth_pyramid_mips.len() as u32,
+                    max_steps: ssr_options.max_steps,
+                    max_distance: ssr_options.max_distance,
+                    thickness: ssr_options.thickness,
+                    max_roughness: ssr_options.max_roughness,
+                    roughness_fade_start: ssr_options.roughness_fade_start,
+                    edge_fade: 0.1,
+                    intensity: ssr_options.intensity,
+                    frame_index: main_view.frame_index() as u32,
+                    ..Default::default()
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    reflection_rt
+}
+
+fn ssr_temporal_pass(
+    context: &mut ModernPipelineContext,
+    ssr_options: &ScreenSpaceReflectionOptions,
+    ssr_temporal_material_pass: ResourceArc<MaterialPassResource>,
+    reflection_rt: RenderGraphImageUsageId,
+    depth_rt: RenderGraphImageUsageId,
+    velocity_rt: RenderGraphImageUsageId,
+    ssr_history_rt_external_image_id: RenderGraphExternalImageId,
+    ssr_history_rt_has_data: bool,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("SsrTemporal", RenderGraphQueue::DefaultGraphics);
+
+    let current_rt = context.graph.sample_image(
+        node,
+        reflection_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let velocity_rt = context.graph.sample_image(
+        node,
+        velocity_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let depth_rt = context.graph.sample_image(
+        node,
+        depth_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let ssr_history_rt = context
+        .graph
+        .read_external_image(ssr_history_rt_external_image_id);
+    let ssr_history_rt = context.graph.sample_image(
+        node,
+        ssr_history_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    let reflection_rt = context.graph.create_color_attachment(
+        node,
+        0,
+        None,
+        RenderGraphImageConstraint {
+            format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(reflection_rt, "ssr_temporal");
+
+    let history_weight = ssr_options.history_weight;
+    let main_view = context.main_view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        let history_tex = args.graph_context.image_view(ssr_history_rt).unwrap();
+        let current_tex = args.graph_context.image_view(current_rt).unwrap();
+        let velocity_tex = args.graph_context.image_view(velocity_rt).unwrap();
+        let depth_tex = args.graph_context.image_view(depth_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &ssr_temporal_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let current_view_proj_inv = main_view.view_proj().inverse();
+        let previous_view_proj = main_view.previous_view_proj();
+
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[ssr_temporal_frag::CONFIG_DESCRIPTOR_SET_INDEX],
+            ssr_temporal_frag::DescriptorSet0Args {
+                history_tex: &history_tex,
+                current_tex: &current_tex,
+                velocity_tex: &velocity_tex,
+                depth_tex: &depth_tex,
+                config: &ssr_temporal_frag::ConfigUniform {
+                    current_view_proj_inv: current_view_proj_inv.to_cols_array_2d(),
+                    previous_view_proj: previous_view_proj.to_cols_array_2d(),
+                    has_history_data: ssr_history_rt_has_data as u32,
+                    history_weight,
+                    viewport_width: main_view.extents_width(),
+                    viewport_height: main_view.extents_height(),
+                },
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    let ssr_history_rt = context.graph.copy_image_to_image(
+        "copy ssr to history",
+        RenderGraphQueue::DefaultGraphics,
+        reflection_rt,
+        Some(ssr_history_rt),
+        None,
+    );
+
+    context
+        .graph
+        .write_external_image(ssr_history_rt_external_image_id, ssr_history_rt);
+
+    reflection_rt
+}
+
+fn ssr_composite_pass(
+    context: &mut ModernPipelineContext,
+    ssr_composite_material_pass: ResourceArc<MaterialPassResource>,
+    reflection_rt: RenderGraphImageUsageId,
+    color_rt: RenderGraphImageUsageId,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("SsrComposite", RenderGraphQueue::DefaultGraphics);
+
+    let color = context.graph.modify_color_attachment(
+        node,
+        color_rt,
+        0,
+        None,
+        Default::default(),
+        Default::default(),
+    );
+    context.graph.set_image_name(color, "color");
+
+    let reflection_rt = context.graph.sample_image(
+        node,
+        reflection_rt,
+        RenderGraphImageConstraint::default(),
+        Default::default(),
+    );
+
+    context.graph.set_renderpass_callback(node, move |args| {
+        let reflection_tex = args.graph_context.image_view(reflection_rt).unwrap();
+        let pipeline = args
+            .graph_context
+            .resource_context()
+            .graphics_pipeline_cache()
+            .get_or_create_graphics_pipeline(
+                None,
+                &ssr_composite_material_pass,
+                &args.render_target_meta,
+                &super::EMPTY_VERTEX_LAYOUT,
+            )?;
+
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let descriptor_set_layouts = &pipeline.get_raw().descriptor_set_layouts;
+        let descriptor_set = descriptor_set_allocator.create_descriptor_set(
+            &descriptor_set_layouts[ssr_composite_frag::REFLECTION_TEX_DESCRIPTOR_SET_INDEX],
+            ssr_composite_frag::DescriptorSet0Args {
+                reflection_tex: &reflection_tex,
+            },
+        )?;
+
+        // Explicit flush since we're going to use the descriptors immediately
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+        command_buffer.cmd_bind_pipeline(&*pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_draw(3, 0)?;
+
+        Ok(())
+    });
+
+    color
+}
@@ -6,7 +6,6 @@ use rafx::graph::{
     RenderGraphExternalImageId, RenderGraphImageConstraint, RenderGraphImageUsageId,
     RenderGraphQueue,
 };
-use rafx::renderer::MainViewRenderResource;
 
 pub(super) struct TaaPass {
     pub(super) color_rt: RenderGraphImageUsageId,
@@ -71,6 +70,7 @@ pub(super) fn taa_pass(
     let taa_material_pass = taa_material_pass.clone();
     let taa_options = taa_options.clone();
     let jitter_amount = context.graph_config.jitter_amount;
+    let main_view = context.main_view.clone();
     context.graph.set_renderpass_callback(node, move |args| {
         let history_tex = args.graph_context.image_view(taa_history_rt).unwrap();
         let current_tex = args.graph_context.image_view(current_rt).unwrap();
@@ -92,18 +92,8 @@ pub(super) fn taa_pass(
             .resource_context()
             .create_descriptor_set_allocator();
 
-        let main_view_resource = args
-            .graph_context
-            .render_resources()
-            .fetch::<MainViewRenderResource>();
-        let main_view = main_view_resource.main_view.clone().unwrap();
         let current_view_proj_inv = main_view.view_proj().inverse();
-        let previous_view_proj =
-            if let Some(previous_main_view_info) = &main_view_resource.previous_main_view_info {
-                previous_main_view_info.projection_matrix * previous_main_view_info.view_matrix
-            } else {
-                main_view.view_proj()
-            };
+        let previous_view_proj = main_view.previous_view_proj();
 
         let descriptor_set = descriptor_set_allocator.create_descriptor_set(
             &descriptor_set_layouts[taa_frag::CONFIG_DESCRIPTOR_SET_INDEX],
@@ -143,13 +133,31 @@ pub(super) fn taa_pass(
         Ok(())
     });
 
-    let taa_history_rt = context.graph.copy_image_to_image(
-        "copy color to history",
-        RenderGraphQueue::DefaultGraphics,
-        color_rt,
-        Some(taa_history_rt),
-        None,
-    );
+    // The history is kept at the size of the viewport so that it survives changes to the render
+    // size. The shader samples it by UV, so when rendering at a lower resolution it is blitted.
+    let history_extents = context.viewport.surface_info.extents;
+    let render_extents_match_history =
+        context.main_view.extents() == (history_extents.width, history_extents.height);
+    let taa_history_rt = if render_extents_match_history {
+        context.graph.copy_image_to_image(
+            "copy color to history",
+            RenderGraphQueue::DefaultGraphics,
+            color_rt,
+            Some(taa_history_rt),
+            None,
+        )
+    } else {
+        context.graph.blit_image_to_image(
+            "blit color to history",
+            RenderGraphQueue::DefaultGraphics,
+            color_rt,
+            glam::Vec2::ZERO,
+            glam::Vec2::ONE,
+            taa_history_rt,
+            glam::Vec2::ZERO,
+            glam::Vec2::ONE,
+        )
+    };
 
     context
         .graph
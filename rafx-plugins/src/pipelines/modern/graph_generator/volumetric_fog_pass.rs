@@ -0,0 +1,297 @@
+use rafx::framework::DescriptorSetBindings;
+use rafx::graph::*;
+
+use super::ModernPipelineContext;
+use crate::features::mesh_adv::{MeshAdvStaticResources, MeshAdvVolumetricFogRenderResource};
+use crate::pipelines::modern::graph_generator::light_binning::LightBuildListsPass;
+use crate::pipelines::modern::graph_generator::shadow_map_pass::ShadowMapPassOutput;
+use crate::pipelines::modern::VolumetricFogOptions;
+use crate::shaders::mesh_adv::{volumetric_fog_inject_comp, volumetric_fog_integrate_comp};
+use rafx::api::{RafxExtents3D, RafxFormat, RafxSampleCount, RafxTextureDimensions};
+
+// Froxel grid resolution. Slices are distributed exponentially in depth so that nearby fog gets
+// more detail
+const FROXEL_COUNT_X: u32 = 160;
+const FROXEL_COUNT_Y: u32 = 90;
+const FROXEL_COUNT_Z: u32 = 64;
+
+// View-space depth of the first slice
+const FROXEL_NEAR_Z: f32 = 0.5;
+
+pub(super) struct VolumetricFogPass {
+    // rgb = in-scattered light, a = transmittance, for the fog between the eye and each froxel
+    pub(super) integrated_froxels: RenderGraphImageUsageId,
+    pub(super) enabled: bool,
+    pub(super) near_z: f32,
+    pub(super) far_z: f32,
+}
+
+// When fog is disabled the passes still run on a single froxel so that the forward shaders always
+// have a volume to bind. The integrate pass fills it with empty fog.
+pub(super) fn volumetric_fog_pass(
+    context: &mut ModernPipelineContext,
+    fog_options: &VolumetricFogOptions,
+    shadow_map_pass_output: &ShadowMapPassOutput,
+    light_build_lists_pass: &LightBuildListsPass,
+    temporal_jitter: bool,
+) -> VolumetricFogPass {
+    let enabled = context.graph_config.enable_volumetric_fog;
+    let froxel_counts = if enabled {
+        RafxExtents3D {
+            width: FROXEL_COUNT_X,
+            height: FROXEL_COUNT_Y,
+            depth: FROXEL_COUNT_Z,
+        }
+    } else {
+        RafxExtents3D {
+            width: 1,
+            height: 1,
+            depth: 1,
+        }
+    };
+
+    let near_z = FROXEL_NEAR_Z;
+    let far_z = fog_options.max_distance.max(near_z + 1.0);
+
+    // Move the sample point within each slice every frame so that TAA can hide the slicing
+    let jitter = if temporal_jitter {
+        (((context.main_view.frame_index() * 5) % 8) as f32 + 0.5) / 8.0
+    } else {
+        0.5
+    };
+
+    let view_inv = context.main_view.view_matrix().inverse();
+    let proj_inv = context.main_view.projection_matrix().inverse();
+
+    let static_resources = context.render_resources.fetch::<MeshAdvStaticResources>();
+    let inject_pipeline = context
+        .asset_manager
+        .committed_asset(&static_resources.volumetric_fog_inject_compute_pipeline)
+        .unwrap()
+        .compute_pipeline
+        .clone();
+    let integrate_pipeline = context
+        .asset_manager
+        .committed_asset(&static_resources.volumetric_fog_integrate_compute_pipeline)
+        .unwrap()
+        .compute_pipeline
+        .clone();
+
+    let froxel_constraint = RenderGraphImageConstraint {
+        samples: Some(RafxSampleCount::SampleCount1),
+        format: Some(RafxFormat::R16G16B16A16_SFLOAT),
+        extents: Some(RenderGraphImageExtents::Custom(froxel_counts)),
+        // Always 3D, even when fog is disabled and the froxel grid is 1x1x1
+        dimensions: Some(RafxTextureDimensions::Dim3D),
+        ..Default::default()
+    };
+
+    //
+    // Inject: light each froxel
+    //
+    let inject_node = context
+        .graph
+        .add_node("VolumetricFogInject", RenderGraphQueue::DefaultGraphics);
+
+    // This is a buffer owned by MeshAdvLightBinRenderResource
+    context.graph.read_storage_buffer(
+        inject_node,
+        light_build_lists_pass.light_lists_buffer,
+        Default::default(),
+    );
+
+    context.graph.sample_image(
+        inject_node,
+        shadow_map_pass_output.shadow_atlas_image,
+        Default::default(),
+        Default::default(),
+    );
+
+    let froxels = context.graph.create_storage_image(
+        inject_node,
+        froxel_constraint.clone(),
+        Default::default(),
+    );
+    context
+        .graph
+        .set_image_name(froxels, "volumetric_fog_froxels");
+
+    let inject_config = volumetric_fog_inject_comp::VolumetricFogConfigUniform {
+        view_inv: view_inv.to_cols_array_2d(),
+        proj_inv: proj_inv.to_cols_array_2d(),
+        scattering_color: fog_options.scattering_color.into(),
+        density: fog_options.density,
+        anisotropy: fog_options.anisotropy.clamp(-0.99, 0.99),
+        height_falloff: fog_options.height_falloff,
+        height_offset: fog_options.height_offset,
+        near_z,
+        far_z,
+        jitter,
+        froxel_count_x: froxel_counts.width,
+        froxel_count_y: froxel_counts.height,
+        froxel_count_z: froxel_counts.depth,
+        ..Default::default()
+    };
+
+    context.graph.set_callback(inject_node, move |args| {
+        let fog_resource = args
+            .graph_context
+            .render_resources()
+            .fetch::<MeshAdvVolumetricFogRenderResource>();
+
+        // Nothing was lit this frame, the integrate pass will write empty fog
+        let lighting_data = match &fog_resource.lighting_data {
+            Some(lighting_data) if enabled => lighting_data,
+            _ => return Ok(()),
+        };
+
+        let froxels = args.graph_context.image_view(froxels).unwrap();
+
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+
+        let mut per_view_descriptor_set = descriptor_set_allocator
+            .create_dyn_descriptor_set_uninitialized(
+                &inject_pipeline.get_raw().descriptor_set_layouts
+                    [volumetric_fog_inject_comp::PER_VIEW_DATA_DESCRIPTOR_SET_INDEX],
+            )?;
+        per_view_descriptor_set.set_buffer_data(
+            volumetric_fog_inject_comp::PER_VIEW_DATA_DESCRIPTOR_BINDING_INDEX as u32,
+            &*lighting_data.per_view_data,
+        );
+        per_view_descriptor_set.set_image(
+            volumetric_fog_inject_comp::SHADOW_MAP_ATLAS_DESCRIPTOR_BINDING_INDEX as u32,
+            &lighting_data.shadow_map_atlas,
+        );
+        per_view_descriptor_set.set_buffer(
+            volumetric_fog_inject_comp::LIGHT_BIN_OUTPUT_DESCRIPTOR_BINDING_INDEX as u32,
+            &lighting_data.light_bin_output,
+        );
+        per_view_descriptor_set.set_buffer(
+            volumetric_fog_inject_comp::ALL_LIGHTS_DESCRIPTOR_BINDING_INDEX as u32,
+            &lighting_data.all_lights,
+        );
+        per_view_descriptor_set.flush(&mut descriptor_set_allocator)?;
+
+        let mut fog_descriptor_set = descriptor_set_allocator
+            .create_dyn_descriptor_set_uninitialized(
+                &inject_pipeline.get_raw().descriptor_set_layouts
+                    [volumetric_fog_inject_comp::CONFIG_DESCRIPTOR_SET_INDEX],
+            )?;
+        fog_descriptor_set.set_buffer_data(
+            volumetric_fog_inject_comp::CONFIG_DESCRIPTOR_BINDING_INDEX as u32,
+            &inject_config,
+        );
+        fog_descriptor_set.set_image(
+            volumetric_fog_inject_comp::FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            &froxels,
+        );
+        fog_descriptor_set.flush(&mut descriptor_set_allocator)?;
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+
+        command_buffer.cmd_bind_pipeline(&*inject_pipeline.get_raw().pipeline)?;
+        per_view_descriptor_set.bind(command_buffer)?;
+        fog_descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_dispatch(
+            (froxel_counts.width + 7) / 8,
+            (froxel_counts.height + 7) / 8,
+            froxel_counts.depth,
+        )?;
+
+        Ok(())
+    });
+
+    //
+    // Integrate: accumulate scattering and transmittance front-to-back
+    //
+    let integrate_node = context
+        .graph
+        .add_node("VolumetricFogIntegrate", RenderGraphQueue::DefaultGraphics);
+
+    let froxels = context.graph.read_storage_image(
+        integrate_node,
+        froxels,
+        froxel_constraint.clone(),
+        Default::default(),
+    );
+
+    let integrated_froxels =
+        context
+            .graph
+            .create_storage_image(integrate_node, froxel_constraint, Default::default());
+    context
+        .graph
+        .set_image_name(integrated_froxels, "volumetric_fog_integrated_froxels");
+
+    let mut integrate_config = volumetric_fog_integrate_comp::VolumetricFogIntegrateConfigUniform {
+        proj_inv: proj_inv.to_cols_array_2d(),
+        near_z,
+        far_z,
+        froxel_count_x: froxel_counts.width,
+        froxel_count_y: froxel_counts.height,
+        froxel_count_z: froxel_counts.depth,
+        ..Default::default()
+    };
+
+    context.graph.set_callback(integrate_node, move |args| {
+        // The lighting data is only valid for this frame
+        let has_lighting_data = args
+            .graph_context
+            .render_resources()
+            .fetch_mut::<MeshAdvVolumetricFogRenderResource>()
+            .lighting_data
+            .take()
+            .is_some();
+        integrate_config.enabled = (enabled && has_lighting_data) as u32;
+
+        let froxels = args.graph_context.image_view(froxels).unwrap();
+        let integrated_froxels = args.graph_context.image_view(integrated_froxels).unwrap();
+
+        let mut descriptor_set_allocator = args
+            .graph_context
+            .resource_context()
+            .create_descriptor_set_allocator();
+        let mut descriptor_set = descriptor_set_allocator.create_dyn_descriptor_set_uninitialized(
+            &integrate_pipeline.get_raw().descriptor_set_layouts[0],
+        )?;
+        descriptor_set.set_buffer_data(
+            volumetric_fog_integrate_comp::CONFIG_DESCRIPTOR_BINDING_INDEX as u32,
+            &integrate_config,
+        );
+        descriptor_set.set_image(
+            volumetric_fog_integrate_comp::FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            &froxels,
+        );
+        descriptor_set.set_image(
+            volumetric_fog_integrate_comp::INTEGRATED_FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            &integrated_froxels,
+        );
+        descriptor_set.flush(&mut descriptor_set_allocator)?;
+        descriptor_set_allocator.flush_changes()?;
+
+        // Draw calls
+        let command_buffer = &args.command_buffer;
+
+        command_buffer.cmd_bind_pipeline(&*integrate_pipeline.get_raw().pipeline)?;
+        descriptor_set.bind(command_buffer)?;
+        command_buffer.cmd_dispatch(
+            (froxel_counts.width + 7) / 8,
+            (froxel_counts.height + 7) / 8,
+            1,
+        )?;
+
+        Ok(())
+    });
+
+    VolumetricFogPass {
+        integrated_froxels,
+        enabled,
+        near_z,
+        far_z,
+    }
+}
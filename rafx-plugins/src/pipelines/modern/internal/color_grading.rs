@@ -0,0 +1,83 @@
+use rafx::api::extra::upload::RafxTransferUpload;
+use rafx::api::{RafxDeviceContext, RafxError, RafxResult};
+use rafx::assets::image_upload::ImageUploadParams;
+use rafx::assets::{image_upload, GpuImageData, GpuImageDataColorSpace};
+use rafx::framework::{DynResourceAllocatorSet, ImageViewResource, ResourceArc};
+
+// Bound in place of a LUT that isn't set or hasn't loaded. LUTs are sampled with linear filtering
+// at texel centers, so the 8 corners of the cube are enough to reproduce the input exactly.
+pub fn create_neutral_lut_image_view(
+    device_context: &RafxDeviceContext,
+    upload: &mut RafxTransferUpload,
+    dyn_resource_allocator: &DynResourceAllocatorSet,
+) -> RafxResult<ResourceArc<ImageViewResource>> {
+    let mut slices = Vec::with_capacity(2);
+    for b in 0..2u8 {
+        let mut slice = Vec::with_capacity(2 * 2 * 4);
+        for g in 0..2u8 {
+            for r in 0..2u8 {
+                slice.extend_from_slice(&[r * 255, g * 255, b * 255, 255]);
+            }
+        }
+        slices.push(slice);
+    }
+
+    let image_data = GpuImageData::new_3d_image_from_slices(
+        2,
+        2,
+        GpuImageDataColorSpace::Linear.rgba8(),
+        slices,
+    );
+
+    let texture = image_upload::enqueue_load_image(
+        device_context,
+        upload,
+        &image_data,
+        ImageUploadParams {
+            generate_mips: false,
+            ..Default::default()
+        },
+    )
+    .map_err(|x| Into::<RafxError>::into(x))?;
+
+    let image = dyn_resource_allocator.insert_texture(texture);
+
+    Ok(dyn_resource_allocator.insert_image_view(&image, None)?)
+}
+
+// Per-channel LMS scale that shifts the white point from D65 to the one described by temperature
+// and tint (both in [-100, 100], 0 is neutral).
+// Source: Unity post processing (ColorUtilities.ComputeColorBalance)
+pub fn white_balance_lms_scale(
+    temperature: f32,
+    tint: f32,
+) -> glam::Vec3 {
+    let t1 = temperature / 65.0;
+    let t2 = tint / 65.0;
+
+    // Get the CIE xy chromaticity of the reference white point. 0.31271 is x value on the D65
+    // white point
+    let x = 0.31271 - t1 * if t1 < 0.0 { 0.1 } else { 0.05 };
+    let standard_illuminant_y = 2.87 * x - 3.0 * x * x - 0.27509507;
+    let y = standard_illuminant_y + t2 * 0.05;
+
+    // D65 white point in LMS space
+    let w1 = glam::Vec3::new(0.949237, 1.03542, 1.08728);
+    let w2 = cie_xy_to_lms(x, y);
+    w1 / w2
+}
+
+fn cie_xy_to_lms(
+    x: f32,
+    y: f32,
+) -> glam::Vec3 {
+    let cie_y = 1.0;
+    let cie_x = cie_y * x / y;
+    let cie_z = cie_y * (1.0 - x - y) / y;
+
+    glam::Vec3::new(
+        0.7328 * cie_x + 0.4296 * cie_y - 0.1624 * cie_z,
+        -0.7036 * cie_x + 1.6975 * cie_y + 0.0061 * cie_z,
+        0.0030 * cie_x + 0.0136 * cie_y + 0.9834 * cie_z,
+    )
+}
@@ -0,0 +1,248 @@
+use crate::pipelines::modern::DynamicResolutionOptions;
+use rafx::api::RafxExtents2D;
+
+// Render extents are snapped to multiples of this fraction of the viewport so that the graph's
+// images are not reallocated every time the scale moves slightly
+const SCALE_STEP: f32 = 1.0 / 32.0;
+
+// Weight of the newest sample in the smoothed frame time
+const FRAME_TIME_SMOOTHING: f32 = 0.1;
+
+pub struct DynamicResolutionController {
+    scale: f32,
+    smoothed_frame_time_ms: Option<f32>,
+    last_update_count: u64,
+}
+
+impl Default for DynamicResolutionController {
+    fn default() -> Self {
+        DynamicResolutionController {
+            scale: 1.0,
+            smoothed_frame_time_ms: None,
+            last_update_count: 0,
+        }
+    }
+}
+
+impl DynamicResolutionController {
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    // Feeds the frame time for the frame identified by update_count into the controller. Only the
+    // first call for a given update_count has an effect, so this can be called once per viewport.
+    pub fn update(
+        &mut self,
+        options: &DynamicResolutionOptions,
+        update_count: u64,
+        frame_time_ms: f32,
+    ) {
+        if update_count == self.last_update_count {
+            return;
+        }
+        self.last_update_count = update_count;
+
+        let min_scale = options.min_scale.max(SCALE_STEP);
+        let max_scale = options.max_scale.max(min_scale);
+
+        if frame_time_ms <= 0.0 {
+            self.scale = self.scale.clamp(min_scale, max_scale);
+            return;
+        }
+
+        let smoothed_frame_time_ms = match self.smoothed_frame_time_ms {
+            Some(previous) => previous + (frame_time_ms - previous) * FRAME_TIME_SMOOTHING,
+            None => frame_time_ms,
+        };
+        self.smoothed_frame_time_ms = Some(smoothed_frame_time_ms);
+
+        // GPU cost is assumed to be roughly proportional to the number of pixels, which is the
+        // square of the scale
+        let target_frame_time_ms = options.target_frame_time_ms;
+        let ideal_scale = if smoothed_frame_time_ms > target_frame_time_ms {
+            self.scale * (target_frame_time_ms / smoothed_frame_time_ms).sqrt()
+        } else if smoothed_frame_time_ms < target_frame_time_ms * options.headroom {
+            self.scale * (target_frame_time_ms * options.headroom / smoothed_frame_time_ms).sqrt()
+        } else {
+            self.scale
+        };
+
+        let ideal_scale = ideal_scale.clamp(min_scale, max_scale);
+        self.scale += (ideal_scale - self.scale) * options.adjust_rate.clamp(0.0, 1.0);
+        self.scale = self.scale.clamp(min_scale, max_scale);
+    }
+
+    pub fn render_extents(
+        &self,
+        viewport_extents: RafxExtents2D,
+    ) -> RafxExtents2D {
+        let scale = ((self.scale / SCALE_STEP).round() * SCALE_STEP).min(1.0);
+        RafxExtents2D {
+            width: ((viewport_extents.width as f32 * scale).round() as u32).max(1),
+            height: ((viewport_extents.height as f32 * scale).round() as u32).max(1),
+        }
+    }
+
+    // Forgets the measured frame time and renders at max_scale
+    pub fn reset(
+        &mut self,
+        options: &DynamicResolutionOptions,
+    ) {
+        self.scale = options.max_scale.clamp(SCALE_STEP, 1.0);
+        self.smoothed_frame_time_ms = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET_MS: f32 = 1000.0 / 60.0;
+
+    fn run_frames(
+        controller: &mut DynamicResolutionController,
+        options: &DynamicResolutionOptions,
+        first_update_count: u64,
+        frame_count: u64,
+        frame_time_ms: f32,
+    ) -> u64 {
+        for i in 0..frame_count {
+            controller.update(options, first_update_count + i, frame_time_ms);
+        }
+        first_update_count + frame_count
+    }
+
+    #[test]
+    fn test_scale_down_when_over_target() {
+        let options = DynamicResolutionOptions::default();
+        let mut controller = DynamicResolutionController::default();
+
+        let mut previous_scale = controller.scale();
+        for update_count in 1..30 {
+            controller.update(&options, update_count, TARGET_MS * 2.0);
+            assert!(controller.scale() < previous_scale);
+            previous_scale = controller.scale();
+        }
+        assert!(controller.scale() >= options.min_scale);
+    }
+
+    #[test]
+    fn test_scale_up_when_under_headroom() {
+        let options = DynamicResolutionOptions::default();
+        let mut controller = DynamicResolutionController::default();
+
+        let update_count = run_frames(&mut controller, &options, 1, 30, TARGET_MS * 2.0);
+        let scaled_down = controller.scale();
+        assert!(scaled_down < 1.0);
+
+        // Give the smoothed frame time time to come back down, then it should climb to max_scale
+        run_frames(
+            &mut controller,
+            &options,
+            update_count,
+            500,
+            TARGET_MS * 0.25,
+        );
+        assert!(controller.scale() > scaled_down);
+        assert!((controller.scale() - options.max_scale).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_clamp_to_min_and_max_scale() {
+        let options = DynamicResolutionOptions {
+            min_scale: 0.5,
+            max_scale: 0.75,
+            adjust_rate: 1.0,
+            ..Default::default()
+        };
+        let mut controller = DynamicResolutionController::default();
+
+        // The controller starts at 1.0, above max_scale
+        controller.update(&options, 1, TARGET_MS * 0.5);
+        assert_eq!(controller.scale(), 0.75);
+
+        run_frames(&mut controller, &options, 2, 100, TARGET_MS * 100.0);
+        assert_eq!(controller.scale(), 0.5);
+
+        // A bad frame time still applies the bounds
+        controller.update(
+            &DynamicResolutionOptions {
+                min_scale: 0.6,
+                ..options
+            },
+            200,
+            0.0,
+        );
+        assert_eq!(controller.scale(), 0.6);
+
+        // max_scale below min_scale is raised to min_scale
+        controller.update(
+            &DynamicResolutionOptions {
+                min_scale: 0.7,
+                max_scale: 0.1,
+                ..options
+            },
+            201,
+            TARGET_MS * 0.5,
+        );
+        assert_eq!(controller.scale(), 0.7);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let options = DynamicResolutionOptions::default();
+        let mut controller = DynamicResolutionController::default();
+        controller.reset(&DynamicResolutionOptions {
+            max_scale: 0.75,
+            ..options
+        });
+        assert_eq!(controller.scale(), 0.75);
+
+        // Between target * headroom and the target, the scale is left alone
+        let frame_time_ms = TARGET_MS * (1.0 + options.headroom) * 0.5;
+        run_frames(&mut controller, &options, 1, 100, frame_time_ms);
+        assert_eq!(controller.scale(), 0.75);
+
+        // Only the first update for a given update count has an effect
+        controller.update(&options, 100, TARGET_MS * 10.0);
+        assert_eq!(controller.scale(), 0.75);
+        controller.update(&options, 101, TARGET_MS * 10.0);
+        assert!(controller.scale() < 0.75);
+    }
+
+    #[test]
+    fn test_render_extents() {
+        let options = DynamicResolutionOptions {
+            max_scale: 0.75,
+            ..Default::default()
+        };
+        let mut controller = DynamicResolutionController::default();
+        let viewport_extents = RafxExtents2D {
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(
+            controller.render_extents(viewport_extents),
+            viewport_extents
+        );
+
+        controller.reset(&options);
+        assert_eq!(
+            controller.render_extents(viewport_extents),
+            RafxExtents2D {
+                width: 1440,
+                height: 810
+            }
+        );
+
+        // Small changes in scale snap to the same extents
+        controller.scale = 0.75 + SCALE_STEP * 0.25;
+        assert_eq!(
+            controller.render_extents(viewport_extents),
+            RafxExtents2D {
+                width: 1440,
+                height: 810
+            }
+        );
+    }
+}
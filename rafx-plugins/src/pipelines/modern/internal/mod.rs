@@ -1 +1,3 @@
+pub(super) mod color_grading;
+pub(super) mod dynamic_resolution;
 pub(super) mod jitter;
@@ -1,12 +1,16 @@
+use super::internal::dynamic_resolution::DynamicResolutionController;
+use super::{AntiAliasMethodAdv, ModernPipelineRenderOptions};
 use crate::phases::{
     DebugPipRenderPhase, DepthPrepassRenderPhase, OpaqueRenderPhase, PostProcessRenderPhase,
     ShadowMapRenderPhase, TransparentRenderPhase, UiRenderPhase, WireframeRenderPhase,
 };
 use crate::shaders::mesh_adv::mesh_culling_comp;
 use crate::shaders::post_adv::luma_average_histogram_comp;
+use fnv::{FnvHashMap, FnvHashSet};
 use rafx::api::extra::upload::RafxTransferUpload;
 use rafx::api::{
-    RafxBufferDef, RafxFormat, RafxMemoryUsage, RafxQueueType, RafxResourceType, RafxResult,
+    RafxBufferDef, RafxExtents2D, RafxFormat, RafxMemoryUsage, RafxQueueType, RafxResourceType,
+    RafxResult,
 };
 use rafx::assets::distill_impl::AssetResource;
 use rafx::assets::{AssetManager, ComputePipelineAsset, ImageAsset, MaterialAsset};
@@ -16,7 +20,10 @@ use rafx::framework::{
 };
 use rafx::graph::PreparedRenderGraph;
 use rafx::render_features::{ExtractResources, RenderRegistryBuilder, RenderView};
-use rafx::renderer::{RendererLoadContext, RendererPipelinePlugin};
+use rafx::renderer::{
+    RenderViewportTarget, RendererLoadContext, RendererPipelinePlugin, TimeRenderResource,
+    MAIN_SURFACE_NAME,
+};
 use std::sync::{Arc, Mutex};
 
 // A plugin that add demo-specific configuration
@@ -105,6 +112,40 @@ impl Default for ModernPipelineMeshCullingDebugData {
     }
 }
 
+#[derive(Debug)]
+pub struct ModernPipelineDynamicResolutionDataInner {
+    // GPU time of the most recent frame the GPU finished, in milliseconds, as measured by the
+    // renderer. None if the renderer can't measure it (backends without a render thread).
+    pub gpu_frame_time_ms: Option<f32>,
+
+    // Fraction of the main viewport's size the scene was most recently rendered at
+    pub render_scale: f32,
+}
+
+impl Default for ModernPipelineDynamicResolutionDataInner {
+    fn default() -> Self {
+        ModernPipelineDynamicResolutionDataInner {
+            gpu_frame_time_ms: None,
+            render_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ModernPipelineDynamicResolutionData {
+    pub inner: Arc<Mutex<ModernPipelineDynamicResolutionDataInner>>,
+}
+
+impl Default for ModernPipelineDynamicResolutionData {
+    fn default() -> Self {
+        ModernPipelineDynamicResolutionData {
+            inner: Arc::new(Mutex::new(
+                ModernPipelineDynamicResolutionDataInner::default(),
+            )),
+        }
+    }
+}
+
 pub struct ModernPipelineStaticResources {
     pub bloom_extract_material: Handle<MaterialAsset>,
     pub bloom_blur_material: Handle<MaterialAsset>,
@@ -112,15 +153,31 @@ pub struct ModernPipelineStaticResources {
     pub ssao_material: Handle<MaterialAsset>,
     pub blue_noise_texture: Handle<ImageAsset>,
     pub taa_material: Handle<MaterialAsset>,
+    pub oit_composite_material: Handle<MaterialAsset>,
+    pub ssr_trace_material: Handle<MaterialAsset>,
+    pub ssr_temporal_material: Handle<MaterialAsset>,
+    pub ssr_composite_material: Handle<MaterialAsset>,
+    pub dof_prefilter_material: Handle<MaterialAsset>,
+    pub dof_bokeh_material: Handle<MaterialAsset>,
+    pub dof_composite_material: Handle<MaterialAsset>,
+    pub motion_blur_material: Handle<MaterialAsset>,
     pub luma_build_histogram: Handle<ComputePipelineAsset>,
     pub luma_average_histogram: Handle<ComputePipelineAsset>,
     pub cas_pipeline: Handle<ComputePipelineAsset>,
     pub mesh_culling_pipeline: Handle<ComputePipelineAsset>,
     pub depth_pyramid_pipeline: Handle<ComputePipelineAsset>,
-    pub tonemap_histogram_result: ResourceArc<BufferResource>,
+    // Identity LUT bound in place of color grading LUTs that are not set or not loaded
+    pub neutral_color_grading_lut: ResourceArc<ImageViewResource>,
     pub tonemap_debug_output: Vec<ResourceArc<BufferResource>>,
     pub mesh_culling_debug_output: Vec<ResourceArc<BufferResource>>,
-    pub taa_history_rt: Option<ResourceArc<ImageViewResource>>,
+    // Keyed by RenderViewportTarget::unique_name(). Entries for viewports that were not drawn in a
+    // frame are removed by finish_frame()
+    pub tonemap_histogram_result: FnvHashMap<String, ResourceArc<BufferResource>>,
+    pub taa_history_rt: FnvHashMap<String, ResourceArc<ImageViewResource>>,
+    pub ssr_history_rt: FnvHashMap<String, ResourceArc<ImageViewResource>>,
+    // Unique names of the viewports drawn since the last finish_frame()
+    pub drawn_viewports: FnvHashSet<String>,
+    pub dynamic_resolution: DynamicResolutionController,
 }
 
 pub struct ModernPipelineRendererPlugin;
@@ -148,7 +205,7 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
         asset_resource: &mut AssetResource,
         _extract_resources: &ExtractResources,
         render_resources: &mut RenderResources,
-        _upload: &mut RafxTransferUpload,
+        upload: &mut RafxTransferUpload,
     ) -> RafxResult<()> {
         //
         // Bloom extract resources
@@ -176,6 +233,44 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             "rafx-plugins/materials/modern_pipeline/taa.material",
         );
 
+        let oit_composite_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/oit_composite.material",
+        );
+
+        //
+        // SSR resources
+        //
+        let ssr_trace_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/ssr_trace.material",
+        );
+
+        let ssr_temporal_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/ssr_temporal.material",
+        );
+
+        let ssr_composite_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/ssr_composite.material",
+        );
+
+        //
+        // Depth of field resources
+        //
+        let dof_prefilter_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/dof_prefilter.material",
+        );
+
+        let dof_bokeh_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/dof_bokeh.material",
+        );
+
+        let dof_composite_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/dof_composite.material",
+        );
+
+        let motion_blur_material = asset_resource.load_asset_path::<MaterialAsset, _>(
+            "rafx-plugins/materials/modern_pipeline/motion_blur.material",
+        );
+
         //
         // Bloom combine resources
         //
@@ -240,6 +335,70 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             "taa material",
         )?;
 
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &oit_composite_material,
+            asset_resource,
+            "oit composite material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &ssr_trace_material,
+            asset_resource,
+            "ssr trace material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &ssr_temporal_material,
+            asset_resource,
+            "ssr temporal material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &ssr_composite_material,
+            asset_resource,
+            "ssr composite material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &dof_prefilter_material,
+            asset_resource,
+            "dof prefilter material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &dof_bokeh_material,
+            asset_resource,
+            "dof bokeh material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &dof_composite_material,
+            asset_resource,
+            "dof composite material",
+        )?;
+
+        renderer_load_context.wait_for_asset_to_load(
+            render_resources,
+            asset_manager,
+            &motion_blur_material,
+            asset_resource,
+            "motion blur material",
+        )?;
+
         renderer_load_context.wait_for_asset_to_load(
             render_resources,
             asset_manager,
@@ -288,25 +447,13 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             "depth_pyramid_pipeline",
         )?;
 
-        let tonemap_histogram_result =
-            asset_manager
-                .device_context()
-                .create_buffer(&RafxBufferDef {
-                    size: std::mem::size_of::<luma_average_histogram_comp::HistogramResultBuffer>()
-                        as u64,
-                    alignment: 256,
-                    memory_usage: RafxMemoryUsage::GpuOnly,
-                    queue_type: RafxQueueType::Graphics,
-                    resource_type: RafxResourceType::BUFFER_READ_WRITE,
-                    elements: Default::default(),
-                    format: RafxFormat::UNDEFINED,
-                    always_mapped: false,
-                })?;
-
-        let tonemap_histogram_result = asset_manager
-            .resource_manager()
-            .resources()
-            .insert_buffer(tonemap_histogram_result);
+        let dyn_resource_allocator = asset_manager.create_dyn_resource_allocator_set();
+        let neutral_color_grading_lut =
+            super::internal::color_grading::create_neutral_lut_image_view(
+                asset_manager.device_context(),
+                upload,
+                &dyn_resource_allocator,
+            )?;
 
         let mut tonemap_debug_output = Vec::with_capacity(MAX_FRAMES_IN_FLIGHT + 1);
         for _ in 0..=MAX_FRAMES_IN_FLIGHT {
@@ -355,7 +502,9 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             );
         }
 
-        let taa_history_rt = None;
+        let tonemap_histogram_result = Default::default();
+        let taa_history_rt = Default::default();
+        let ssr_history_rt = Default::default();
 
         render_resources.insert(ModernPipelineStaticResources {
             bloom_extract_material,
@@ -364,26 +513,112 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             ssao_material,
             blue_noise_texture,
             taa_material,
+            oit_composite_material,
+            ssr_trace_material,
+            ssr_temporal_material,
+            ssr_composite_material,
+            dof_prefilter_material,
+            dof_bokeh_material,
+            dof_composite_material,
+            motion_blur_material,
             luma_build_histogram,
             luma_average_histogram,
             cas_pipeline,
             mesh_culling_pipeline,
             depth_pyramid_pipeline,
-            tonemap_histogram_result,
+            neutral_color_grading_lut,
             tonemap_debug_output,
             mesh_culling_debug_output,
+            tonemap_histogram_result,
             taa_history_rt,
+            ssr_history_rt,
+            drawn_viewports: Default::default(),
+            dynamic_resolution: Default::default(),
         });
 
         Ok(())
     }
 
+    fn viewport_render_extents(
+        &self,
+        viewport: &RenderViewportTarget,
+        extract_resources: &ExtractResources,
+        render_resources: &RenderResources,
+    ) -> RafxExtents2D {
+        let viewport_extents = viewport.rect.extents();
+        let render_options = extract_resources.fetch::<ModernPipelineRenderOptions>();
+        let dynamic_resolution_options = &render_options.dynamic_resolution_options;
+        let dynamic_resolution_data = extract_resources
+            .try_fetch::<ModernPipelineDynamicResolutionData>()
+            .map(|x| x.clone());
+
+        let mut static_resources = render_resources.fetch_mut::<ModernPipelineStaticResources>();
+        let controller = &mut static_resources.dynamic_resolution;
+
+        // Upscaling is done by CAS, which only runs when HDR is enabled
+        if !render_options.enable_dynamic_resolution || !render_options.enable_hdr {
+            controller.reset(dynamic_resolution_options);
+            return viewport_extents;
+        }
+
+        let time_render_resource = render_resources.fetch::<TimeRenderResource>();
+        let gpu_frame_time_ms = time_render_resource
+            .previous_gpu_frame_time()
+            .map(|x| x.as_secs_f32() * 1000.0);
+
+        // Without a GPU measurement, fall back to the time between updates. That will not drop below
+        // the refresh interval with vsync enabled, so in that case the resolution can be lowered but
+        // will not scale back up.
+        let frame_time_ms =
+            gpu_frame_time_ms.unwrap_or_else(|| time_render_resource.previous_update_dt() * 1000.0);
+
+        // The frame time covers all viewports, so they all share one scale
+        controller.update(
+            dynamic_resolution_options,
+            time_render_resource.update_count(),
+            frame_time_ms,
+        );
+
+        let render_extents = controller.render_extents(viewport_extents);
+        if viewport.surface_name == MAIN_SURFACE_NAME && viewport.viewport_index == 0 {
+            if let Some(dynamic_resolution_data) = &dynamic_resolution_data {
+                let mut dynamic_resolution_data = dynamic_resolution_data.inner.lock().unwrap();
+                dynamic_resolution_data.gpu_frame_time_ms = gpu_frame_time_ms;
+                dynamic_resolution_data.render_scale =
+                    render_extents.width as f32 / viewport_extents.width as f32;
+            }
+        }
+
+        render_extents
+    }
+
+    fn viewport_jitter_amount(
+        &self,
+        _viewport: &RenderViewportTarget,
+        render_extents: RafxExtents2D,
+        frame_index: usize,
+        extract_resources: &ExtractResources,
+        _render_resources: &RenderResources,
+    ) -> glam::Vec2 {
+        let render_options = extract_resources.fetch::<ModernPipelineRenderOptions>();
+        if render_options.anti_alias_method != AntiAliasMethodAdv::Taa {
+            return glam::Vec2::ZERO;
+        }
+
+        super::internal::jitter::jitter_amount(
+            frame_index,
+            render_options.taa_options.jitter_pattern,
+            glam::Vec2::new(render_extents.width as f32, render_extents.height as f32),
+        ) * render_options.taa_options.jitter_multiplier
+    }
+
     fn generate_render_graph(
         &self,
         asset_manager: &AssetManager,
         swapchain_image: ResourceArc<ImageViewResource>,
         rotating_frame_index: usize,
         main_view: RenderView,
+        viewport: &RenderViewportTarget,
         extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph> {
@@ -392,8 +627,27 @@ impl RendererPipelinePlugin for ModernPipelineRendererPlugin {
             swapchain_image,
             rotating_frame_index,
             main_view,
+            viewport,
             extract_resources,
             render_resources,
         )
     }
+
+    fn finish_frame(
+        &self,
+        render_resources: &RenderResources,
+    ) {
+        let mut static_resources = render_resources.fetch_mut::<ModernPipelineStaticResources>();
+        let static_resources = &mut *static_resources;
+        let drawn_viewports = std::mem::take(&mut static_resources.drawn_viewports);
+        static_resources
+            .tonemap_histogram_result
+            .retain(|viewport_name, _| drawn_viewports.contains(viewport_name));
+        static_resources
+            .taa_history_rt
+            .retain(|viewport_name, _| drawn_viewports.contains(viewport_name));
+        static_resources
+            .ssr_history_rt
+            .retain(|viewport_name, _| drawn_viewports.contains(viewport_name));
+    }
 }
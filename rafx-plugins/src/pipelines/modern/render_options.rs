@@ -1,3 +1,6 @@
+use rafx::assets::ImageAsset;
+use rafx::distill::loader::handle::Handle;
+
 // Should be kept in sync with the constants in bloom_combine.frag prefixed with OUTPUT_COLOR_SPACE_
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
@@ -126,6 +129,184 @@ impl Default for TemporalAAOptions {
     }
 }
 
+#[derive(Clone)]
+pub struct ScreenSpaceReflectionOptions {
+    // Maximum number of hierarchical steps per ray
+    pub max_steps: u32,
+    // Maximum length of a reflection ray in world units
+    pub max_distance: f32,
+    // How far behind the depth buffer a ray may pass and still count as a hit, in world units
+    pub thickness: f32,
+    // Surfaces rougher than this get no screen-space reflections and rely on forward lighting only
+    pub max_roughness: f32,
+    // Reflections fade out between this roughness and max_roughness
+    pub roughness_fade_start: f32,
+    // Fraction of the previous frame's reflections kept each frame
+    pub history_weight: f32,
+    pub intensity: f32,
+}
+
+impl Default for ScreenSpaceReflectionOptions {
+    fn default() -> Self {
+        ScreenSpaceReflectionOptions {
+            max_steps: 64,
+            max_distance: 50.0,
+            thickness: 0.5,
+            max_roughness: 0.6,
+            roughness_fade_start: 0.3,
+            history_weight: 0.9,
+            intensity: 1.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VolumetricFogOptions {
+    // Extinction per world unit at (and below) height_offset
+    pub density: f32,
+    // Henyey-Greenstein g. Positive values scatter more light forward, making lights glow when
+    // looking towards them. 0 scatters evenly in all directions
+    pub anisotropy: f32,
+    // How quickly the fog thins out above height_offset. 0 is uniform fog
+    pub height_falloff: f32,
+    // World-space height (Z) where fog starts thinning out
+    pub height_offset: f32,
+    // Fraction of extinguished light that is scattered rather than absorbed, per channel
+    pub scattering_color: glam::Vec3,
+    // Fog is only simulated up to this view-space distance. Surfaces farther away use the fog
+    // accumulated up to here
+    pub max_distance: f32,
+}
+
+impl Default for VolumetricFogOptions {
+    fn default() -> Self {
+        VolumetricFogOptions {
+            density: 0.02,
+            anisotropy: 0.4,
+            height_falloff: 0.1,
+            height_offset: 0.0,
+            scattering_color: glam::Vec3::ONE,
+            max_distance: 100.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DepthOfFieldOptions {
+    // Distance from the camera that is in perfect focus, in world units (assumed to be meters)
+    pub focus_distance: f32,
+    pub focal_length_mm: f32,
+    // Aperture diameter is focal_length / f_stop. Lower values give a shallower depth of field
+    pub f_stop: f32,
+    // Height of the camera sensor. 24mm is a full-frame 35mm sensor
+    pub sensor_height_mm: f32,
+    // Upper bound for the blur radius in pixels (at the render resolution)
+    pub max_coc_radius: f32,
+    // Spacing between the rings of bokeh samples, in half-res pixels. Smaller values take more
+    // samples
+    pub radius_step: f32,
+}
+
+impl Default for DepthOfFieldOptions {
+    fn default() -> Self {
+        DepthOfFieldOptions {
+            focus_distance: 10.0,
+            focal_length_mm: 50.0,
+            f_stop: 2.8,
+            sensor_height_mm: 24.0,
+            max_coc_radius: 16.0,
+            radius_step: 0.5,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MotionBlurOptions {
+    // Fraction of the frame the shutter is open for, in degrees. 360 blurs over the full frame
+    pub shutter_angle: f32,
+    // Upper bound for the blur length in pixels (at the render resolution)
+    pub max_blur_radius: f32,
+    pub sample_count: u32,
+}
+
+impl Default for MotionBlurOptions {
+    fn default() -> Self {
+        MotionBlurOptions {
+            shutter_angle: 180.0,
+            max_blur_radius: 32.0,
+            sample_count: 12,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ColorGradingOptions {
+    // Raises the shadows. 0 is neutral
+    pub lift: glam::Vec3,
+    // Bends the midtones. 1 is neutral, higher values brighten
+    pub gamma: glam::Vec3,
+    // Scales the highlights. 1 is neutral
+    pub gain: glam::Vec3,
+    // 0 is grayscale, 1 is neutral
+    pub saturation: f32,
+    // Shifts the white point between blue (-100) and yellow (100)
+    pub temperature: f32,
+    // Shifts the white point between green (-100) and magenta (100)
+    pub tint: f32,
+    // 3D LUTs (i.e. imported from .cube files) applied after the other controls. An unset LUT
+    // leaves the color unchanged, so blending from a LUT to None fades the LUT out
+    pub lut_a: Option<Handle<ImageAsset>>,
+    pub lut_b: Option<Handle<ImageAsset>>,
+    // 0 uses only lut_a, 1 uses only lut_b
+    pub lut_blend: f32,
+    // How much of the LUT result replaces the color. LUTs only cover [0, 1], so values above 1
+    // (on HDR displays) are clipped by the amount of contribution
+    pub lut_contribution: f32,
+}
+
+impl Default for ColorGradingOptions {
+    fn default() -> Self {
+        ColorGradingOptions {
+            lift: glam::Vec3::ZERO,
+            gamma: glam::Vec3::ONE,
+            gain: glam::Vec3::ONE,
+            saturation: 1.0,
+            temperature: 0.0,
+            tint: 0.0,
+            lut_a: None,
+            lut_b: None,
+            lut_blend: 0.0,
+            lut_contribution: 1.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DynamicResolutionOptions {
+    // GPU frame time the controller tries to stay under, in milliseconds
+    pub target_frame_time_ms: f32,
+    // Bounds for the fraction of the viewport's width and height the scene is rendered at
+    pub min_scale: f32,
+    pub max_scale: f32,
+    // The scale is only raised when the frame time is below this fraction of the target, so that it
+    // doesn't oscillate around the target
+    pub headroom: f32,
+    // Fraction of the distance to the ideal scale that is covered each frame
+    pub adjust_rate: f32,
+}
+
+impl Default for DynamicResolutionOptions {
+    fn default() -> Self {
+        DynamicResolutionOptions {
+            target_frame_time_ms: 1000.0 / 60.0,
+            min_scale: 0.5,
+            max_scale: 1.0,
+            headroom: 0.85,
+            adjust_rate: 0.1,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum AntiAliasMethodAdv {
@@ -159,12 +340,56 @@ impl From<i32> for AntiAliasMethodAdv {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TransparencyMethodAdv {
+    // Transparent meshes are sorted back-to-front and alpha blended into the color target
+    SortedBlend,
+    // Transparent meshes are accumulated into separate targets and composited over the opaque
+    // color. Order independent, but approximate. Falls back to SortedBlend when using MSAA
+    WeightedBlendedOit,
+    MAX,
+}
+
+impl Default for TransparencyMethodAdv {
+    fn default() -> Self {
+        TransparencyMethodAdv::SortedBlend
+    }
+}
+
+impl TransparencyMethodAdv {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TransparencyMethodAdv::SortedBlend => "Sorted Blend",
+            TransparencyMethodAdv::WeightedBlendedOit => "Weighted Blended OIT",
+            TransparencyMethodAdv::MAX => "TransparencyMethodAdv MAX VALUE",
+        }
+    }
+}
+
+impl From<i32> for TransparencyMethodAdv {
+    fn from(v: i32) -> Self {
+        assert!(v <= Self::MAX as i32);
+        unsafe { std::mem::transmute(v) }
+    }
+}
+
 #[derive(Clone)]
 pub struct ModernPipelineRenderOptions {
     pub anti_alias_method: AntiAliasMethodAdv,
     pub taa_options: TemporalAAOptions,
+    pub transparency_method: TransparencyMethodAdv,
     pub enable_hdr: bool,
     pub enable_ssao: bool,
+    pub enable_ssr: bool,
+    pub ssr_options: ScreenSpaceReflectionOptions,
+    pub enable_volumetric_fog: bool,
+    pub volumetric_fog_options: VolumetricFogOptions,
+    // Depth of field and motion blur are disabled when using MSAA
+    pub enable_depth_of_field: bool,
+    pub depth_of_field_options: DepthOfFieldOptions,
+    pub enable_motion_blur: bool,
+    pub motion_blur_options: MotionBlurOptions,
     pub enable_bloom: bool,
     pub enable_textures: bool,
     pub show_surfaces: bool,
@@ -175,10 +400,17 @@ pub struct ModernPipelineRenderOptions {
     pub show_feature_toggles: bool,
     pub blur_pass_count: usize,
     pub tonemapper_type: TonemapperTypeAdv,
+    // Applied after tonemapping. Requires HDR to be enabled
+    pub enable_color_grading: bool,
+    pub color_grading_options: ColorGradingOptions,
     pub enable_visibility_update: bool,
     pub enable_sharpening: bool,
     pub sharpening_amount: f32,
     pub enable_occlusion_culling: bool,
+    // Scales the internal resolution based on GPU frame time and upscales to the viewport with CAS.
+    // Requires HDR to be enabled
+    pub enable_dynamic_resolution: bool,
+    pub dynamic_resolution_options: DynamicResolutionOptions,
 }
 
 impl Default for ModernPipelineRenderOptions {
@@ -186,8 +418,17 @@ impl Default for ModernPipelineRenderOptions {
         ModernPipelineRenderOptions {
             anti_alias_method: AntiAliasMethodAdv::Taa,
             taa_options: TemporalAAOptions::default(),
+            transparency_method: TransparencyMethodAdv::default(),
             enable_hdr: true,
             enable_ssao: true,
+            enable_ssr: true,
+            ssr_options: ScreenSpaceReflectionOptions::default(),
+            enable_volumetric_fog: false,
+            volumetric_fog_options: VolumetricFogOptions::default(),
+            enable_depth_of_field: false,
+            depth_of_field_options: DepthOfFieldOptions::default(),
+            enable_motion_blur: false,
+            motion_blur_options: MotionBlurOptions::default(),
             enable_bloom: true,
             enable_textures: true,
             show_surfaces: true,
@@ -198,10 +439,14 @@ impl Default for ModernPipelineRenderOptions {
             show_feature_toggles: true,
             blur_pass_count: 5,
             tonemapper_type: TonemapperTypeAdv::LogDerivative,
+            enable_color_grading: false,
+            color_grading_options: ColorGradingOptions::default(),
             enable_visibility_update: true,
             enable_sharpening: true,
             sharpening_amount: 1.0,
             enable_occlusion_culling: true,
+            enable_dynamic_resolution: false,
+            dynamic_resolution_options: DynamicResolutionOptions::default(),
         }
     }
 }
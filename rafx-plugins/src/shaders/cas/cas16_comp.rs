@@ -16,8 +16,10 @@ pub struct ConfigStd140 {
     pub image_width: u32,    // +0 (size: 4)
     pub image_height: u32,   // +4 (size: 4)
     pub sharpen_amount: f32, // +8 (size: 4)
-    pub _padding0: [u8; 4],  // +12 (size: 4)
-} // 16 bytes
+    pub output_width: u32,   // +12 (size: 4)
+    pub output_height: u32,  // +16 (size: 4)
+    pub _padding0: [u8; 12], // +20 (size: 12)
+} // 32 bytes
 
 impl Default for ConfigStd140 {
     fn default() -> Self {
@@ -25,7 +27,9 @@ impl Default for ConfigStd140 {
             image_width: <u32>::default(),
             image_height: <u32>::default(),
             sharpen_amount: <f32>::default(),
-            _padding0: [u8::default(); 4],
+            output_width: <u32>::default(),
+            output_height: <u32>::default(),
+            _padding0: [u8::default(); 12],
         }
     }
 }
@@ -138,7 +142,7 @@ mod test {
 
     #[test]
     fn test_struct_config_std140() {
-        assert_eq!(std::mem::size_of::<ConfigStd140>(), 16);
+        assert_eq!(std::mem::size_of::<ConfigStd140>(), 32);
         assert_eq!(std::mem::size_of::<u32>(), 4);
         assert_eq!(std::mem::align_of::<u32>(), 4);
         assert_eq!(memoffset::offset_of!(ConfigStd140, image_width), 0);
@@ -148,8 +152,14 @@ mod test {
         assert_eq!(std::mem::size_of::<f32>(), 4);
         assert_eq!(std::mem::align_of::<f32>(), 4);
         assert_eq!(memoffset::offset_of!(ConfigStd140, sharpen_amount), 8);
-        assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
-        assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
-        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 12);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, output_width), 12);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, output_height), 16);
+        assert_eq!(std::mem::size_of::<[u8; 12]>(), 12);
+        assert_eq!(std::mem::align_of::<[u8; 12]>(), 1);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 20);
     }
 }
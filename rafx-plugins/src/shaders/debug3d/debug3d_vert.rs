@@ -106,6 +106,36 @@ impl DescriptorSet0 {
     }
 }
 
+#[allow(unused_imports)]
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};
+
+#[allow(unused_imports)]
+use rafx::framework::{VertexDataLayout, VertexDataSetLayout};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct VertexInput {
+    pub in_pos: [f32; 3],   // +0 (size: 12)
+    pub in_color: [f32; 4], // +12 (size: 16)
+} // 28 bytes
+
+impl VertexInput {
+    pub fn vertex_data_layout() -> VertexDataLayout {
+        VertexDataLayout::build_vertex_layout(
+            &VertexInput::default(),
+            RafxVertexAttributeRate::Vertex,
+            |builder, vertex| {
+                builder.add_member(&vertex.in_pos, "POSITION", RafxFormat::R32G32B32_SFLOAT);
+                builder.add_member(&vertex.in_color, "COLOR", RafxFormat::R32G32B32A32_SFLOAT);
+            },
+        )
+    }
+}
+
+pub fn vertex_data_set_layout(primitive_topology: RafxPrimitiveTopology) -> VertexDataSetLayout {
+    VertexDataSetLayout::new(vec![VertexInput::vertex_data_layout()], primitive_topology)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -117,4 +147,19 @@ mod test {
         assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
         assert_eq!(memoffset::offset_of!(PerFrameUboStd140, view_proj), 0);
     }
+
+    #[test]
+    fn test_vertex_data_set_layout() {
+        let layout = vertex_data_set_layout(RafxPrimitiveTopology::TriangleList);
+        assert_eq!(layout.members().len(), 2);
+        let binding = layout.binding(0).unwrap();
+        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::Vertex);
+        let member = layout.member("POSITION").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("COLOR").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        assert_eq!(binding.vertex_stride(), 28);
+    }
 }
@@ -204,6 +204,34 @@ impl DescriptorSet0 {
     }
 }
 
+#[allow(unused_imports)]
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};
+
+#[allow(unused_imports)]
+use rafx::framework::{VertexDataLayout, VertexDataSetLayout};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct VertexInput {
+    pub in_pos: [f32; 3], // +0 (size: 12)
+} // 12 bytes
+
+impl VertexInput {
+    pub fn vertex_data_layout() -> VertexDataLayout {
+        VertexDataLayout::build_vertex_layout(
+            &VertexInput::default(),
+            RafxVertexAttributeRate::Vertex,
+            |builder, vertex| {
+                builder.add_member(&vertex.in_pos, "POSITION", RafxFormat::R32G32B32_SFLOAT);
+            },
+        )
+    }
+}
+
+pub fn vertex_data_set_layout(primitive_topology: RafxPrimitiveTopology) -> VertexDataSetLayout {
+    VertexDataSetLayout::new(vec![VertexInput::vertex_data_layout()], primitive_topology)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -320,4 +348,16 @@ mod test {
         assert_eq!(std::mem::align_of::<[f32; 2]>(), 4);
         assert_eq!(memoffset::offset_of!(PerViewDataStd140, jitter_amount), 200);
     }
+
+    #[test]
+    fn test_vertex_data_set_layout() {
+        let layout = vertex_data_set_layout(RafxPrimitiveTopology::TriangleList);
+        assert_eq!(layout.members().len(), 1);
+        let binding = layout.binding(0).unwrap();
+        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::Vertex);
+        let member = layout.member("POSITION").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        assert_eq!(binding.vertex_stride(), 12);
+    }
 }
@@ -178,6 +178,38 @@ impl DescriptorSet1 {
     }
 }
 
+#[allow(unused_imports)]
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};
+
+#[allow(unused_imports)]
+use rafx::framework::{VertexDataLayout, VertexDataSetLayout};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct VertexInput {
+    pub pos: [f32; 2],      // +0 (size: 8)
+    pub in_uv: [f32; 2],    // +8 (size: 8)
+    pub in_color: [f32; 4], // +16 (size: 16)
+} // 32 bytes
+
+impl VertexInput {
+    pub fn vertex_data_layout() -> VertexDataLayout {
+        VertexDataLayout::build_vertex_layout(
+            &VertexInput::default(),
+            RafxVertexAttributeRate::Vertex,
+            |builder, vertex| {
+                builder.add_member(&vertex.pos, "POSITION", RafxFormat::R32G32_SFLOAT);
+                builder.add_member(&vertex.in_uv, "TEXCOORD", RafxFormat::R32G32_SFLOAT);
+                builder.add_member(&vertex.in_color, "COLOR", RafxFormat::R32G32B32A32_SFLOAT);
+            },
+        )
+    }
+}
+
+pub fn vertex_data_set_layout(primitive_topology: RafxPrimitiveTopology) -> VertexDataSetLayout {
+    VertexDataSetLayout::new(vec![VertexInput::vertex_data_layout()], primitive_topology)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -189,4 +221,22 @@ mod test {
         assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
         assert_eq!(memoffset::offset_of!(ArgsStd140, mvp), 0);
     }
+
+    #[test]
+    fn test_vertex_data_set_layout() {
+        let layout = vertex_data_set_layout(RafxPrimitiveTopology::TriangleList);
+        assert_eq!(layout.members().len(), 3);
+        let binding = layout.binding(0).unwrap();
+        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::Vertex);
+        let member = layout.member("POSITION").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("TEXCOORD").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("COLOR").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        assert_eq!(binding.vertex_stride(), 32);
+    }
 }
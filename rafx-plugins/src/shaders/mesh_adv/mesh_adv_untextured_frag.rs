@@ -10,6 +10,28 @@ use rafx::framework::{
     ResourceArc,
 };
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct VolumetricFogStd140 {
+    pub near_z: f32,        // +0 (size: 4)
+    pub far_z: f32,         // +4 (size: 4)
+    pub enabled: u32,       // +8 (size: 4)
+    pub _padding0: [u8; 4], // +12 (size: 4)
+} // 16 bytes
+
+impl Default for VolumetricFogStd140 {
+    fn default() -> Self {
+        VolumetricFogStd140 {
+            near_z: <f32>::default(),
+            far_z: <f32>::default(),
+            enabled: <u32>::default(),
+            _padding0: [u8::default(); 4],
+        }
+    }
+}
+
+pub type VolumetricFogUniform = VolumetricFogStd140;
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
 pub struct TransformWithHistoryStd140 {
@@ -343,6 +365,12 @@ pub const ALL_LIGHTS_DESCRIPTOR_SET_INDEX: usize = 0;
 pub const ALL_LIGHTS_DESCRIPTOR_BINDING_INDEX: usize = 6;
 pub const SSAO_TEXTURE_DESCRIPTOR_SET_INDEX: usize = 1;
 pub const SSAO_TEXTURE_DESCRIPTOR_BINDING_INDEX: usize = 0;
+pub const VOLUMETRIC_FOG_TEXTURE_DESCRIPTOR_SET_INDEX: usize = 1;
+pub const VOLUMETRIC_FOG_TEXTURE_DESCRIPTOR_BINDING_INDEX: usize = 1;
+pub const SMP_VOLUMETRIC_FOG_DESCRIPTOR_SET_INDEX: usize = 1;
+pub const SMP_VOLUMETRIC_FOG_DESCRIPTOR_BINDING_INDEX: usize = 2;
+pub const VOLUMETRIC_FOG_DESCRIPTOR_SET_INDEX: usize = 1;
+pub const VOLUMETRIC_FOG_DESCRIPTOR_BINDING_INDEX: usize = 3;
 pub const ALL_TRANSFORMS_DESCRIPTOR_SET_INDEX: usize = 2;
 pub const ALL_TRANSFORMS_DESCRIPTOR_BINDING_INDEX: usize = 0;
 pub const ALL_DRAW_DATA_DESCRIPTOR_SET_INDEX: usize = 2;
@@ -481,6 +509,8 @@ impl DescriptorSet0 {
 
 pub struct DescriptorSet1Args<'a> {
     pub ssao_texture: &'a ResourceArc<ImageViewResource>,
+    pub volumetric_fog_texture: &'a ResourceArc<ImageViewResource>,
+    pub volumetric_fog: &'a VolumetricFogUniform,
 }
 
 impl<'a> DescriptorSetInitializer<'a> for DescriptorSet1Args<'a> {
@@ -515,6 +545,14 @@ impl<'a> DescriptorSetWriter<'a> for DescriptorSet1Args<'a> {
             SSAO_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32,
             args.ssao_texture,
         );
+        descriptor_set.set_image(
+            VOLUMETRIC_FOG_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32,
+            args.volumetric_fog_texture,
+        );
+        descriptor_set.set_buffer_data(
+            VOLUMETRIC_FOG_DESCRIPTOR_BINDING_INDEX as u32,
+            args.volumetric_fog,
+        );
     }
 }
 
@@ -529,6 +567,14 @@ impl DescriptorSet1 {
             SSAO_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32,
             args.ssao_texture,
         );
+        descriptor_set.set_image(
+            VOLUMETRIC_FOG_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32,
+            args.volumetric_fog_texture,
+        );
+        descriptor_set.set_buffer_data(
+            VOLUMETRIC_FOG_DESCRIPTOR_BINDING_INDEX as u32,
+            args.volumetric_fog,
+        );
     }
 
     pub fn set_args(
@@ -536,6 +582,8 @@ impl DescriptorSet1 {
         args: DescriptorSet1Args,
     ) {
         self.set_ssao_texture(args.ssao_texture);
+        self.set_volumetric_fog_texture(args.volumetric_fog_texture);
+        self.set_volumetric_fog(args.volumetric_fog);
     }
 
     pub fn set_ssao_texture(
@@ -546,6 +594,26 @@ impl DescriptorSet1 {
             .set_image(SSAO_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32, ssao_texture);
     }
 
+    pub fn set_volumetric_fog_texture(
+        &mut self,
+        volumetric_fog_texture: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0.set_image(
+            VOLUMETRIC_FOG_TEXTURE_DESCRIPTOR_BINDING_INDEX as u32,
+            volumetric_fog_texture,
+        );
+    }
+
+    pub fn set_volumetric_fog(
+        &mut self,
+        volumetric_fog: &VolumetricFogUniform,
+    ) {
+        self.0.set_buffer_data(
+            VOLUMETRIC_FOG_DESCRIPTOR_BINDING_INDEX as u32,
+            volumetric_fog,
+        );
+    }
+
     pub fn flush(
         &mut self,
         descriptor_set_allocator: &mut DescriptorSetAllocator,
@@ -558,6 +626,23 @@ impl DescriptorSet1 {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_struct_volumetric_fog_std140() {
+        assert_eq!(std::mem::size_of::<VolumetricFogStd140>(), 16);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(VolumetricFogStd140, near_z), 0);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(VolumetricFogStd140, far_z), 4);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(VolumetricFogStd140, enabled), 8);
+        assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
+        assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
+        assert_eq!(memoffset::offset_of!(VolumetricFogStd140, _padding0), 12);
+    }
+
     #[test]
     fn test_struct_transform_with_history_std140() {
         assert_eq!(std::mem::size_of::<TransformWithHistoryStd140>(), 128);
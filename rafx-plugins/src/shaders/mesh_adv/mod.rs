@@ -5,11 +5,17 @@
 pub mod lights_bin_comp;
 pub mod lights_build_lists_comp;
 pub mod mesh_adv_textured_frag;
+pub mod mesh_adv_textured_oit_frag;
+pub mod mesh_adv_textured_surface_frag;
 pub mod mesh_adv_textured_vert;
 pub mod mesh_adv_untextured_frag;
+pub mod mesh_adv_untextured_oit_frag;
+pub mod mesh_adv_untextured_surface_frag;
 pub mod mesh_adv_untextured_vert;
 pub mod mesh_adv_wireframe_frag;
 pub mod mesh_adv_wireframe_vert;
 pub mod mesh_culling_comp;
 pub mod shadow_atlas_clear_tiles_vert;
 pub mod shadow_atlas_depth_vert;
+pub mod volumetric_fog_inject_comp;
+pub mod volumetric_fog_integrate_comp;
@@ -0,0 +1,210 @@
+// This code is auto-generated by the shader processor.
+
+#[allow(unused_imports)]
+use rafx::RafxResult;
+
+#[allow(unused_imports)]
+use rafx::framework::{
+    DescriptorSetAllocator, DescriptorSetArc, DescriptorSetBindings, DescriptorSetInitializer,
+    DescriptorSetWriter, DescriptorSetWriterContext, DynDescriptorSet, ImageViewResource,
+    ResourceArc,
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct VolumetricFogIntegrateConfigStd140 {
+    pub proj_inv: [[f32; 4]; 4], // +0 (size: 64)
+    pub near_z: f32,             // +64 (size: 4)
+    pub far_z: f32,              // +68 (size: 4)
+    pub froxel_count_x: u32,     // +72 (size: 4)
+    pub froxel_count_y: u32,     // +76 (size: 4)
+    pub froxel_count_z: u32,     // +80 (size: 4)
+    pub enabled: u32,            // +84 (size: 4)
+    pub _padding0: [u8; 8],      // +88 (size: 8)
+} // 96 bytes
+
+impl Default for VolumetricFogIntegrateConfigStd140 {
+    fn default() -> Self {
+        VolumetricFogIntegrateConfigStd140 {
+            proj_inv: <[[f32; 4]; 4]>::default(),
+            near_z: <f32>::default(),
+            far_z: <f32>::default(),
+            froxel_count_x: <u32>::default(),
+            froxel_count_y: <u32>::default(),
+            froxel_count_z: <u32>::default(),
+            enabled: <u32>::default(),
+            _padding0: [u8::default(); 8],
+        }
+    }
+}
+
+pub type VolumetricFogIntegrateConfigUniform = VolumetricFogIntegrateConfigStd140;
+
+pub const CONFIG_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const CONFIG_DESCRIPTOR_BINDING_INDEX: usize = 0;
+pub const FROXELS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const FROXELS_DESCRIPTOR_BINDING_INDEX: usize = 1;
+pub const INTEGRATED_FROXELS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const INTEGRATED_FROXELS_DESCRIPTOR_BINDING_INDEX: usize = 2;
+
+pub struct DescriptorSet0Args<'a> {
+    pub config: &'a VolumetricFogIntegrateConfigUniform,
+    pub froxels: &'a ResourceArc<ImageViewResource>,
+    pub integrated_froxels: &'a ResourceArc<ImageViewResource>,
+}
+
+impl<'a> DescriptorSetInitializer<'a> for DescriptorSet0Args<'a> {
+    type Output = DescriptorSet0;
+
+    fn create_dyn_descriptor_set(
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> Self::Output {
+        let mut descriptor = DescriptorSet0(descriptor_set);
+        descriptor.set_args(args);
+        descriptor
+    }
+
+    fn create_descriptor_set(
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> RafxResult<DescriptorSetArc> {
+        let mut descriptor = Self::create_dyn_descriptor_set(descriptor_set, args);
+        descriptor.0.flush(descriptor_set_allocator)?;
+        Ok(descriptor.0.descriptor_set().clone())
+    }
+}
+
+impl<'a> DescriptorSetWriter<'a> for DescriptorSet0Args<'a> {
+    fn write_to(
+        descriptor_set: &mut DescriptorSetWriterContext,
+        args: Self,
+    ) {
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+        descriptor_set.set_image(FROXELS_DESCRIPTOR_BINDING_INDEX as u32, args.froxels);
+        descriptor_set.set_image(
+            INTEGRATED_FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            args.integrated_froxels,
+        );
+    }
+}
+
+pub struct DescriptorSet0(pub DynDescriptorSet);
+
+impl DescriptorSet0 {
+    pub fn set_args_static(
+        descriptor_set: &mut DynDescriptorSet,
+        args: DescriptorSet0Args,
+    ) {
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+        descriptor_set.set_image(FROXELS_DESCRIPTOR_BINDING_INDEX as u32, args.froxels);
+        descriptor_set.set_image(
+            INTEGRATED_FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            args.integrated_froxels,
+        );
+    }
+
+    pub fn set_args(
+        &mut self,
+        args: DescriptorSet0Args,
+    ) {
+        self.set_config(args.config);
+        self.set_froxels(args.froxels);
+        self.set_integrated_froxels(args.integrated_froxels);
+    }
+
+    pub fn set_config(
+        &mut self,
+        config: &VolumetricFogIntegrateConfigUniform,
+    ) {
+        self.0
+            .set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, config);
+    }
+
+    pub fn set_froxels(
+        &mut self,
+        froxels: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(FROXELS_DESCRIPTOR_BINDING_INDEX as u32, froxels);
+    }
+
+    pub fn set_integrated_froxels(
+        &mut self,
+        integrated_froxels: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0.set_image(
+            INTEGRATED_FROXELS_DESCRIPTOR_BINDING_INDEX as u32,
+            integrated_froxels,
+        );
+    }
+
+    pub fn flush(
+        &mut self,
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+    ) -> RafxResult<()> {
+        self.0.flush(descriptor_set_allocator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_struct_volumetric_fog_integrate_config_std140() {
+        assert_eq!(
+            std::mem::size_of::<VolumetricFogIntegrateConfigStd140>(),
+            96
+        );
+        assert_eq!(std::mem::size_of::<[[f32; 4]; 4]>(), 64);
+        assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, proj_inv),
+            0
+        );
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, near_z),
+            64
+        );
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, far_z),
+            68
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, froxel_count_x),
+            72
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, froxel_count_y),
+            76
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, froxel_count_z),
+            80
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, enabled),
+            84
+        );
+        assert_eq!(std::mem::size_of::<[u8; 8]>(), 8);
+        assert_eq!(std::mem::align_of::<[u8; 8]>(), 1);
+        assert_eq!(
+            memoffset::offset_of!(VolumetricFogIntegrateConfigStd140, _padding0),
+            88
+        );
+    }
+}
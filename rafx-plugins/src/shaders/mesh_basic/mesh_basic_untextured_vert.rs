@@ -459,6 +459,89 @@ impl DescriptorSet1 {
     }
 }
 
+#[allow(unused_imports)]
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};
+
+#[allow(unused_imports)]
+use rafx::framework::{VertexDataLayout, VertexDataSetLayout};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct VertexInput {
+    pub in_pos: [f32; 3],      // +0 (size: 12)
+    pub in_normal: [f32; 3],   // +12 (size: 12)
+    pub in_tangent: [f32; 3],  // +24 (size: 12)
+    pub in_binormal: [f32; 3], // +36 (size: 12)
+    pub in_uv: [f32; 2],       // +48 (size: 8)
+} // 56 bytes
+
+impl VertexInput {
+    pub fn vertex_data_layout() -> VertexDataLayout {
+        VertexDataLayout::build_vertex_layout(
+            &VertexInput::default(),
+            RafxVertexAttributeRate::Vertex,
+            |builder, vertex| {
+                builder.add_member(&vertex.in_pos, "POSITION", RafxFormat::R32G32B32_SFLOAT);
+                builder.add_member(&vertex.in_normal, "NORMAL", RafxFormat::R32G32B32_SFLOAT);
+                builder.add_member(&vertex.in_tangent, "TANGENT", RafxFormat::R32G32B32_SFLOAT);
+                builder.add_member(
+                    &vertex.in_binormal,
+                    "BINORMAL",
+                    RafxFormat::R32G32B32_SFLOAT,
+                );
+                builder.add_member(&vertex.in_uv, "TEXCOORD", RafxFormat::R32G32_SFLOAT);
+            },
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct InstanceInput {
+    pub in_model_matrix: [[f32; 4]; 4], // +0 (size: 64)
+} // 64 bytes
+
+impl InstanceInput {
+    pub fn vertex_data_layout() -> VertexDataLayout {
+        VertexDataLayout::build_vertex_layout(
+            &InstanceInput::default(),
+            RafxVertexAttributeRate::Instance,
+            |builder, vertex| {
+                builder.add_member(
+                    &vertex.in_model_matrix[0],
+                    "MODELMATRIX0",
+                    RafxFormat::R32G32B32A32_SFLOAT,
+                );
+                builder.add_member(
+                    &vertex.in_model_matrix[1],
+                    "MODELMATRIX1",
+                    RafxFormat::R32G32B32A32_SFLOAT,
+                );
+                builder.add_member(
+                    &vertex.in_model_matrix[2],
+                    "MODELMATRIX2",
+                    RafxFormat::R32G32B32A32_SFLOAT,
+                );
+                builder.add_member(
+                    &vertex.in_model_matrix[3],
+                    "MODELMATRIX3",
+                    RafxFormat::R32G32B32A32_SFLOAT,
+                );
+            },
+        )
+    }
+}
+
+pub fn vertex_data_set_layout(primitive_topology: RafxPrimitiveTopology) -> VertexDataSetLayout {
+    VertexDataSetLayout::new(
+        vec![
+            VertexInput::vertex_data_layout(),
+            InstanceInput::vertex_data_layout(),
+        ],
+        primitive_topology,
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -745,4 +828,43 @@ mod test {
         assert_eq!(std::mem::align_of::<MaterialDataStd140>(), 4);
         assert_eq!(memoffset::offset_of!(MaterialDataUboStd140, data), 0);
     }
+
+    #[test]
+    fn test_vertex_data_set_layout() {
+        let layout = vertex_data_set_layout(RafxPrimitiveTopology::TriangleList);
+        assert_eq!(layout.members().len(), 9);
+        let binding = layout.binding(0).unwrap();
+        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::Vertex);
+        let member = layout.member("POSITION").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("NORMAL").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("TANGENT").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("BINORMAL").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        let member = layout.member("TEXCOORD").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32_SFLOAT);
+        assert_eq!(member.binding, 0);
+        assert_eq!(binding.vertex_stride(), 56);
+        let binding = layout.binding(1).unwrap();
+        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::Instance);
+        let member = layout.member("MODELMATRIX0").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 1);
+        let member = layout.member("MODELMATRIX1").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 1);
+        let member = layout.member("MODELMATRIX2").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 1);
+        let member = layout.member("MODELMATRIX3").unwrap();
+        assert_eq!(member.format, RafxFormat::R32G32B32A32_SFLOAT);
+        assert_eq!(member.binding, 1);
+        assert_eq!(binding.vertex_stride(), 64);
+    }
 }
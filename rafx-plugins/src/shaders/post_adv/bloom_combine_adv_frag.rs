@@ -16,8 +16,16 @@ pub struct ConfigStd140 {
     pub tonemapper_type: i32,           // +0 (size: 4)
     pub output_color_space: i32,        // +4 (size: 4)
     pub max_color_component_value: f32, // +8 (size: 4)
-    pub _padding0: [u8; 4],             // +12 (size: 4)
-} // 16 bytes
+    pub enable_color_grading: u32,      // +12 (size: 4)
+    pub lift: [f32; 3],                 // +16 (size: 12)
+    pub saturation: f32,                // +28 (size: 4)
+    pub gamma: [f32; 3],                // +32 (size: 12)
+    pub lut_blend: f32,                 // +44 (size: 4)
+    pub gain: [f32; 3],                 // +48 (size: 12)
+    pub lut_contribution: f32,          // +60 (size: 4)
+    pub white_balance: [f32; 3],        // +64 (size: 12)
+    pub _padding0: [u8; 4],             // +76 (size: 4)
+} // 80 bytes
 
 impl Default for ConfigStd140 {
     fn default() -> Self {
@@ -25,6 +33,14 @@ impl Default for ConfigStd140 {
             tonemapper_type: <i32>::default(),
             output_color_space: <i32>::default(),
             max_color_component_value: <f32>::default(),
+            enable_color_grading: <u32>::default(),
+            lift: <[f32; 3]>::default(),
+            saturation: <f32>::default(),
+            gamma: <[f32; 3]>::default(),
+            lut_blend: <f32>::default(),
+            gain: <[f32; 3]>::default(),
+            lut_contribution: <f32>::default(),
+            white_balance: <[f32; 3]>::default(),
             _padding0: [u8::default(); 4],
         }
     }
@@ -42,11 +58,19 @@ pub const CONFIG_DESCRIPTOR_SET_INDEX: usize = 0;
 pub const CONFIG_DESCRIPTOR_BINDING_INDEX: usize = 3;
 pub const HISTOGRAM_RESULT_DESCRIPTOR_SET_INDEX: usize = 0;
 pub const HISTOGRAM_RESULT_DESCRIPTOR_BINDING_INDEX: usize = 4;
+pub const LUT_A_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const LUT_A_DESCRIPTOR_BINDING_INDEX: usize = 5;
+pub const LUT_B_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const LUT_B_DESCRIPTOR_BINDING_INDEX: usize = 6;
+pub const SMP_LUT_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SMP_LUT_DESCRIPTOR_BINDING_INDEX: usize = 7;
 
 pub struct DescriptorSet0Args<'a> {
     pub in_color: &'a ResourceArc<ImageViewResource>,
     pub in_blur: &'a ResourceArc<ImageViewResource>,
     pub config: &'a ConfigUniform,
+    pub lut_a: &'a ResourceArc<ImageViewResource>,
+    pub lut_b: &'a ResourceArc<ImageViewResource>,
 }
 
 impl<'a> DescriptorSetInitializer<'a> for DescriptorSet0Args<'a> {
@@ -80,6 +104,8 @@ impl<'a> DescriptorSetWriter<'a> for DescriptorSet0Args<'a> {
         descriptor_set.set_image(IN_COLOR_DESCRIPTOR_BINDING_INDEX as u32, args.in_color);
         descriptor_set.set_image(IN_BLUR_DESCRIPTOR_BINDING_INDEX as u32, args.in_blur);
         descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+        descriptor_set.set_image(LUT_A_DESCRIPTOR_BINDING_INDEX as u32, args.lut_a);
+        descriptor_set.set_image(LUT_B_DESCRIPTOR_BINDING_INDEX as u32, args.lut_b);
     }
 }
 
@@ -93,6 +119,8 @@ impl DescriptorSet0 {
         descriptor_set.set_image(IN_COLOR_DESCRIPTOR_BINDING_INDEX as u32, args.in_color);
         descriptor_set.set_image(IN_BLUR_DESCRIPTOR_BINDING_INDEX as u32, args.in_blur);
         descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+        descriptor_set.set_image(LUT_A_DESCRIPTOR_BINDING_INDEX as u32, args.lut_a);
+        descriptor_set.set_image(LUT_B_DESCRIPTOR_BINDING_INDEX as u32, args.lut_b);
     }
 
     pub fn set_args(
@@ -102,6 +130,8 @@ impl DescriptorSet0 {
         self.set_in_color(args.in_color);
         self.set_in_blur(args.in_blur);
         self.set_config(args.config);
+        self.set_lut_a(args.lut_a);
+        self.set_lut_b(args.lut_b);
     }
 
     pub fn set_in_color(
@@ -128,6 +158,22 @@ impl DescriptorSet0 {
             .set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, config);
     }
 
+    pub fn set_lut_a(
+        &mut self,
+        lut_a: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(LUT_A_DESCRIPTOR_BINDING_INDEX as u32, lut_a);
+    }
+
+    pub fn set_lut_b(
+        &mut self,
+        lut_b: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(LUT_B_DESCRIPTOR_BINDING_INDEX as u32, lut_b);
+    }
+
     pub fn flush(
         &mut self,
         descriptor_set_allocator: &mut DescriptorSetAllocator,
@@ -142,7 +188,7 @@ mod test {
 
     #[test]
     fn test_struct_config_std140() {
-        assert_eq!(std::mem::size_of::<ConfigStd140>(), 16);
+        assert_eq!(std::mem::size_of::<ConfigStd140>(), 80);
         assert_eq!(std::mem::size_of::<i32>(), 4);
         assert_eq!(std::mem::align_of::<i32>(), 4);
         assert_eq!(memoffset::offset_of!(ConfigStd140, tonemapper_type), 0);
@@ -155,8 +201,35 @@ mod test {
             memoffset::offset_of!(ConfigStd140, max_color_component_value),
             8
         );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(ConfigStd140, enable_color_grading),
+            12
+        );
+        assert_eq!(std::mem::size_of::<[f32; 3]>(), 12);
+        assert_eq!(std::mem::align_of::<[f32; 3]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, lift), 16);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, saturation), 28);
+        assert_eq!(std::mem::size_of::<[f32; 3]>(), 12);
+        assert_eq!(std::mem::align_of::<[f32; 3]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, gamma), 32);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, lut_blend), 44);
+        assert_eq!(std::mem::size_of::<[f32; 3]>(), 12);
+        assert_eq!(std::mem::align_of::<[f32; 3]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, gain), 48);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, lut_contribution), 60);
+        assert_eq!(std::mem::size_of::<[f32; 3]>(), 12);
+        assert_eq!(std::mem::align_of::<[f32; 3]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, white_balance), 64);
         assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
         assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
-        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 12);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 76);
     }
 }
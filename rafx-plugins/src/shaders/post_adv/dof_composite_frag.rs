@@ -0,0 +1,178 @@
+// This code is auto-generated by the shader processor.
+
+#[allow(unused_imports)]
+use rafx::RafxResult;
+
+#[allow(unused_imports)]
+use rafx::framework::{
+    DescriptorSetAllocator, DescriptorSetArc, DescriptorSetBindings, DescriptorSetInitializer,
+    DescriptorSetWriter, DescriptorSetWriterContext, DynDescriptorSet, ImageViewResource,
+    ResourceArc,
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct ConfigStd140 {
+    pub proj_inv: [[f32; 4]; 4], // +0 (size: 64)
+    pub focus_distance: f32,     // +64 (size: 4)
+    pub coc_scale: f32,          // +68 (size: 4)
+    pub max_coc_radius: f32,     // +72 (size: 4)
+    pub _padding0: [u8; 4],      // +76 (size: 4)
+} // 80 bytes
+
+impl Default for ConfigStd140 {
+    fn default() -> Self {
+        ConfigStd140 {
+            proj_inv: <[[f32; 4]; 4]>::default(),
+            focus_distance: <f32>::default(),
+            coc_scale: <f32>::default(),
+            max_coc_radius: <f32>::default(),
+            _padding0: [u8::default(); 4],
+        }
+    }
+}
+
+pub type ConfigUniform = ConfigStd140;
+
+pub const COLOR_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const COLOR_TEX_DESCRIPTOR_BINDING_INDEX: usize = 0;
+pub const DEPTH_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const DEPTH_TEX_DESCRIPTOR_BINDING_INDEX: usize = 1;
+pub const BOKEH_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const BOKEH_TEX_DESCRIPTOR_BINDING_INDEX: usize = 2;
+pub const SMP_NEAREST_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SMP_NEAREST_DESCRIPTOR_BINDING_INDEX: usize = 3;
+pub const SMP_LINEAR_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SMP_LINEAR_DESCRIPTOR_BINDING_INDEX: usize = 4;
+pub const CONFIG_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const CONFIG_DESCRIPTOR_BINDING_INDEX: usize = 5;
+
+pub struct DescriptorSet0Args<'a> {
+    pub color_tex: &'a ResourceArc<ImageViewResource>,
+    pub depth_tex: &'a ResourceArc<ImageViewResource>,
+    pub bokeh_tex: &'a ResourceArc<ImageViewResource>,
+    pub config: &'a ConfigUniform,
+}
+
+impl<'a> DescriptorSetInitializer<'a> for DescriptorSet0Args<'a> {
+    type Output = DescriptorSet0;
+
+    fn create_dyn_descriptor_set(
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> Self::Output {
+        let mut descriptor = DescriptorSet0(descriptor_set);
+        descriptor.set_args(args);
+        descriptor
+    }
+
+    fn create_descriptor_set(
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> RafxResult<DescriptorSetArc> {
+        let mut descriptor = Self::create_dyn_descriptor_set(descriptor_set, args);
+        descriptor.0.flush(descriptor_set_allocator)?;
+        Ok(descriptor.0.descriptor_set().clone())
+    }
+}
+
+impl<'a> DescriptorSetWriter<'a> for DescriptorSet0Args<'a> {
+    fn write_to(
+        descriptor_set: &mut DescriptorSetWriterContext,
+        args: Self,
+    ) {
+        descriptor_set.set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.color_tex);
+        descriptor_set.set_image(DEPTH_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.depth_tex);
+        descriptor_set.set_image(BOKEH_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.bokeh_tex);
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+    }
+}
+
+pub struct DescriptorSet0(pub DynDescriptorSet);
+
+impl DescriptorSet0 {
+    pub fn set_args_static(
+        descriptor_set: &mut DynDescriptorSet,
+        args: DescriptorSet0Args,
+    ) {
+        descriptor_set.set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.color_tex);
+        descriptor_set.set_image(DEPTH_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.depth_tex);
+        descriptor_set.set_image(BOKEH_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.bokeh_tex);
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+    }
+
+    pub fn set_args(
+        &mut self,
+        args: DescriptorSet0Args,
+    ) {
+        self.set_color_tex(args.color_tex);
+        self.set_depth_tex(args.depth_tex);
+        self.set_bokeh_tex(args.bokeh_tex);
+        self.set_config(args.config);
+    }
+
+    pub fn set_color_tex(
+        &mut self,
+        color_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, color_tex);
+    }
+
+    pub fn set_depth_tex(
+        &mut self,
+        depth_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(DEPTH_TEX_DESCRIPTOR_BINDING_INDEX as u32, depth_tex);
+    }
+
+    pub fn set_bokeh_tex(
+        &mut self,
+        bokeh_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(BOKEH_TEX_DESCRIPTOR_BINDING_INDEX as u32, bokeh_tex);
+    }
+
+    pub fn set_config(
+        &mut self,
+        config: &ConfigUniform,
+    ) {
+        self.0
+            .set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, config);
+    }
+
+    pub fn flush(
+        &mut self,
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+    ) -> RafxResult<()> {
+        self.0.flush(descriptor_set_allocator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_struct_config_std140() {
+        assert_eq!(std::mem::size_of::<ConfigStd140>(), 80);
+        assert_eq!(std::mem::size_of::<[[f32; 4]; 4]>(), 64);
+        assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, proj_inv), 0);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, focus_distance), 64);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, coc_scale), 68);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, max_coc_radius), 72);
+        assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
+        assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 76);
+    }
+}
@@ -3,6 +3,14 @@
 #![allow(dead_code)]
 
 pub mod bloom_combine_adv_frag;
+pub mod dof_bokeh_frag;
+pub mod dof_composite_frag;
+pub mod dof_prefilter_frag;
 pub mod luma_average_histogram_comp;
 pub mod luma_build_histogram_comp;
+pub mod motion_blur_frag;
+pub mod oit_composite_frag;
+pub mod ssr_composite_frag;
+pub mod ssr_temporal_frag;
+pub mod ssr_trace_frag;
 pub mod taa_frag;
@@ -0,0 +1,269 @@
+// This code is auto-generated by the shader processor.
+
+#[allow(unused_imports)]
+use rafx::RafxResult;
+
+#[allow(unused_imports)]
+use rafx::framework::{
+    DescriptorSetAllocator, DescriptorSetArc, DescriptorSetBindings, DescriptorSetInitializer,
+    DescriptorSetWriter, DescriptorSetWriterContext, DynDescriptorSet, ImageViewResource,
+    ResourceArc,
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct ConfigStd140 {
+    pub proj: [[f32; 4]; 4],        // +0 (size: 64)
+    pub proj_inv: [[f32; 4]; 4],    // +64 (size: 64)
+    pub viewport_width: u32,        // +128 (size: 4)
+    pub viewport_height: u32,       // +132 (size: 4)
+    pub depth_mip_slice_count: u32, // +136 (size: 4)
+    pub max_steps: u32,             // +140 (size: 4)
+    pub max_distance: f32,          // +144 (size: 4)
+    pub thickness: f32,             // +148 (size: 4)
+    pub max_roughness: f32,         // +152 (size: 4)
+    pub roughness_fade_start: f32,  // +156 (size: 4)
+    pub edge_fade: f32,             // +160 (size: 4)
+    pub intensity: f32,             // +164 (size: 4)
+    pub frame_index: u32,           // +168 (size: 4)
+    pub _padding0: [u8; 4],         // +172 (size: 4)
+} // 176 bytes
+
+impl Default for ConfigStd140 {
+    fn default() -> Self {
+        ConfigStd140 {
+            proj: <[[f32; 4]; 4]>::default(),
+            proj_inv: <[[f32; 4]; 4]>::default(),
+            viewport_width: <u32>::default(),
+            viewport_height: <u32>::default(),
+            depth_mip_slice_count: <u32>::default(),
+            max_steps: <u32>::default(),
+            max_distance: <f32>::default(),
+            thickness: <f32>::default(),
+            max_roughness: <f32>::default(),
+            roughness_fade_start: <f32>::default(),
+            edge_fade: <f32>::default(),
+            intensity: <f32>::default(),
+            frame_index: <u32>::default(),
+            _padding0: [u8::default(); 4],
+        }
+    }
+}
+
+pub type ConfigUniform = ConfigStd140;
+
+pub const DEPTH_MIP_SLICES_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const DEPTH_MIP_SLICES_DESCRIPTOR_BINDING_INDEX: usize = 0;
+pub const SURFACE_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SURFACE_TEX_DESCRIPTOR_BINDING_INDEX: usize = 1;
+pub const COLOR_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const COLOR_TEX_DESCRIPTOR_BINDING_INDEX: usize = 2;
+pub const NOISE_TEX_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const NOISE_TEX_DESCRIPTOR_BINDING_INDEX: usize = 3;
+pub const SMP_NEAREST_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SMP_NEAREST_DESCRIPTOR_BINDING_INDEX: usize = 4;
+pub const SMP_LINEAR_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SMP_LINEAR_DESCRIPTOR_BINDING_INDEX: usize = 5;
+pub const CONFIG_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const CONFIG_DESCRIPTOR_BINDING_INDEX: usize = 6;
+
+pub struct DescriptorSet0Args<'a> {
+    pub depth_mip_slices: &'a [Option<&'a ResourceArc<ImageViewResource>>; 16],
+    pub surface_tex: &'a ResourceArc<ImageViewResource>,
+    pub color_tex: &'a ResourceArc<ImageViewResource>,
+    pub noise_tex: &'a ResourceArc<ImageViewResource>,
+    pub config: &'a ConfigUniform,
+}
+
+impl<'a> DescriptorSetInitializer<'a> for DescriptorSet0Args<'a> {
+    type Output = DescriptorSet0;
+
+    fn create_dyn_descriptor_set(
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> Self::Output {
+        let mut descriptor = DescriptorSet0(descriptor_set);
+        descriptor.set_args(args);
+        descriptor
+    }
+
+    fn create_descriptor_set(
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> RafxResult<DescriptorSetArc> {
+        let mut descriptor = Self::create_dyn_descriptor_set(descriptor_set, args);
+        descriptor.0.flush(descriptor_set_allocator)?;
+        Ok(descriptor.0.descriptor_set().clone())
+    }
+}
+
+impl<'a> DescriptorSetWriter<'a> for DescriptorSet0Args<'a> {
+    fn write_to(
+        descriptor_set: &mut DescriptorSetWriterContext,
+        args: Self,
+    ) {
+        descriptor_set.set_images(
+            DEPTH_MIP_SLICES_DESCRIPTOR_BINDING_INDEX as u32,
+            args.depth_mip_slices,
+        );
+        descriptor_set.set_image(
+            SURFACE_TEX_DESCRIPTOR_BINDING_INDEX as u32,
+            args.surface_tex,
+        );
+        descriptor_set.set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.color_tex);
+        descriptor_set.set_image(NOISE_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.noise_tex);
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+    }
+}
+
+pub struct DescriptorSet0(pub DynDescriptorSet);
+
+impl DescriptorSet0 {
+    pub fn set_args_static(
+        descriptor_set: &mut DynDescriptorSet,
+        args: DescriptorSet0Args,
+    ) {
+        descriptor_set.set_images(
+            DEPTH_MIP_SLICES_DESCRIPTOR_BINDING_INDEX as u32,
+            args.depth_mip_slices,
+        );
+        descriptor_set.set_image(
+            SURFACE_TEX_DESCRIPTOR_BINDING_INDEX as u32,
+            args.surface_tex,
+        );
+        descriptor_set.set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.color_tex);
+        descriptor_set.set_image(NOISE_TEX_DESCRIPTOR_BINDING_INDEX as u32, args.noise_tex);
+        descriptor_set.set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, args.config);
+    }
+
+    pub fn set_args(
+        &mut self,
+        args: DescriptorSet0Args,
+    ) {
+        self.set_depth_mip_slices(args.depth_mip_slices);
+        self.set_surface_tex(args.surface_tex);
+        self.set_color_tex(args.color_tex);
+        self.set_noise_tex(args.noise_tex);
+        self.set_config(args.config);
+    }
+
+    pub fn set_depth_mip_slices(
+        &mut self,
+        depth_mip_slices: &[Option<&ResourceArc<ImageViewResource>>; 16],
+    ) {
+        self.0.set_images(
+            DEPTH_MIP_SLICES_DESCRIPTOR_BINDING_INDEX as u32,
+            depth_mip_slices,
+        );
+    }
+
+    pub fn set_depth_mip_slices_element(
+        &mut self,
+        index: usize,
+        element: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0.set_image_at_index(
+            DEPTH_MIP_SLICES_DESCRIPTOR_BINDING_INDEX as u32,
+            index,
+            element,
+        );
+    }
+
+    pub fn set_surface_tex(
+        &mut self,
+        surface_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(SURFACE_TEX_DESCRIPTOR_BINDING_INDEX as u32, surface_tex);
+    }
+
+    pub fn set_color_tex(
+        &mut self,
+        color_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(COLOR_TEX_DESCRIPTOR_BINDING_INDEX as u32, color_tex);
+    }
+
+    pub fn set_noise_tex(
+        &mut self,
+        noise_tex: &ResourceArc<ImageViewResource>,
+    ) {
+        self.0
+            .set_image(NOISE_TEX_DESCRIPTOR_BINDING_INDEX as u32, noise_tex);
+    }
+
+    pub fn set_config(
+        &mut self,
+        config: &ConfigUniform,
+    ) {
+        self.0
+            .set_buffer_data(CONFIG_DESCRIPTOR_BINDING_INDEX as u32, config);
+    }
+
+    pub fn flush(
+        &mut self,
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+    ) -> RafxResult<()> {
+        self.0.flush(descriptor_set_allocator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_struct_config_std140() {
+        assert_eq!(std::mem::size_of::<ConfigStd140>(), 176);
+        assert_eq!(std::mem::size_of::<[[f32; 4]; 4]>(), 64);
+        assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, proj), 0);
+        assert_eq!(std::mem::size_of::<[[f32; 4]; 4]>(), 64);
+        assert_eq!(std::mem::align_of::<[[f32; 4]; 4]>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, proj_inv), 64);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, viewport_width), 128);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, viewport_height), 132);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(ConfigStd140, depth_mip_slice_count),
+            136
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, max_steps), 140);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, max_distance), 144);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, thickness), 148);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, max_roughness), 152);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(ConfigStd140, roughness_fade_start),
+            156
+        );
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, edge_fade), 160);
+        assert_eq!(std::mem::size_of::<f32>(), 4);
+        assert_eq!(std::mem::align_of::<f32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, intensity), 164);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, frame_index), 168);
+        assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
+        assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
+        assert_eq!(memoffset::offset_of!(ConfigStd140, _padding0), 172);
+    }
+}
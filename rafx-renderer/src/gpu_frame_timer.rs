@@ -0,0 +1,133 @@
+use crossbeam_channel::{Receiver, Sender};
+use rafx_api::{
+    RafxCommandBufferDef, RafxCommandPoolDef, RafxDeviceContext, RafxFence, RafxQueue, RafxResult,
+};
+use rafx_framework::ResourceContext;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+// A fence submitted after a frame's command buffers, and when the frame was submitted
+struct GpuFrameTimerFence {
+    fence: RafxFence,
+    submit_time: rafx_base::Instant,
+}
+
+/// Measures how long the GPU spends on each frame. A fence is submitted after the frame's command
+/// buffers and a thread waits on it to find out when the GPU finished the frame. The frame is
+/// assumed to start on the GPU when it is submitted, or when the previous frame finished if the GPU
+/// was still busy with it. This is an estimate: time the GPU spends waiting within the frame (for
+/// example, for a swapchain image to become available) is included.
+pub struct GpuFrameTimer {
+    fence_tx: Option<Sender<GpuFrameTimerFence>>,
+    free_fence_rx: Receiver<RafxFence>,
+    previous_gpu_frame_time: Arc<Mutex<Option<Duration>>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl GpuFrameTimer {
+    pub fn start() -> Self {
+        let (fence_tx, fence_rx) = crossbeam_channel::unbounded();
+        let (free_fence_tx, free_fence_rx) = crossbeam_channel::unbounded();
+        let previous_gpu_frame_time = Arc::new(Mutex::new(None));
+
+        let thread_previous_gpu_frame_time = previous_gpu_frame_time.clone();
+        let thread_builder = std::thread::Builder::new().name("GPU Frame Timer".to_string());
+        let join_handle = thread_builder
+            .spawn(move || {
+                Self::timer_thread(fence_rx, free_fence_tx, thread_previous_gpu_frame_time)
+            })
+            .unwrap();
+
+        GpuFrameTimer {
+            fence_tx: Some(fence_tx),
+            free_fence_rx,
+            previous_gpu_frame_time,
+            join_handle: Some(join_handle),
+        }
+    }
+
+    /// The GPU time of the most recent frame that finished, or None if no frame has finished yet
+    pub fn previous_gpu_frame_time(&self) -> Option<Duration> {
+        *self.previous_gpu_frame_time.lock().unwrap()
+    }
+
+    /// Submits a fence that signals when all work submitted to the queue so far is finished. Call
+    /// this after submitting a frame's command buffers. `submit_time` is when the first of them
+    /// was submitted.
+    pub fn submit(
+        &self,
+        device_context: &RafxDeviceContext,
+        resource_context: &ResourceContext,
+        queue: &RafxQueue,
+        submit_time: rafx_base::Instant,
+    ) -> RafxResult<()> {
+        let fence = match self.free_fence_rx.try_recv() {
+            Ok(fence) => fence,
+            Err(_) => device_context.create_fence()?,
+        };
+
+        // Some backends can't submit without a command buffer, so submit an empty one. The fence
+        // signals after everything submitted before it, so it also covers the frame.
+        let mut command_pool = resource_context
+            .create_dyn_command_pool_allocator()
+            .allocate_dyn_pool(queue, &RafxCommandPoolDef { transient: true }, 0)?;
+
+        let command_buffer = command_pool.allocate_dyn_command_buffer(&RafxCommandBufferDef {
+            is_secondary: false,
+        })?;
+
+        command_buffer.begin()?;
+        command_buffer.end()?;
+
+        queue.submit(&[&*command_buffer], &[], &[], Some(&fence))?;
+
+        self.fence_tx
+            .as_ref()
+            .unwrap()
+            .send(GpuFrameTimerFence { fence, submit_time })
+            .unwrap();
+
+        Ok(())
+    }
+
+    fn timer_thread(
+        fence_rx: Receiver<GpuFrameTimerFence>,
+        free_fence_tx: Sender<RafxFence>,
+        previous_gpu_frame_time: Arc<Mutex<Option<Duration>>>,
+    ) {
+        profiling::register_thread!();
+
+        let mut previous_complete_time: Option<rafx_base::Instant> = None;
+
+        // Ends when the GpuFrameTimer is dropped
+        for frame in fence_rx {
+            if let Err(err) = frame.fence.wait() {
+                log::error!("Failed to wait for GPU frame timer fence: {:?}", err);
+                continue;
+            }
+
+            let complete_time = rafx_base::Instant::now();
+            let start_time = match previous_complete_time {
+                Some(previous_complete_time) if previous_complete_time > frame.submit_time => {
+                    previous_complete_time
+                }
+                _ => frame.submit_time,
+            };
+
+            *previous_gpu_frame_time.lock().unwrap() = Some(complete_time - start_time);
+            previous_complete_time = Some(complete_time);
+
+            // The GpuFrameTimer may have been dropped already
+            let _ = free_fence_tx.send(frame.fence);
+        }
+    }
+}
+
+impl Drop for GpuFrameTimer {
+    fn drop(&mut self) {
+        // Dropping the sender ends the thread after it has waited on the remaining fences
+        self.fence_tx.take();
+        self.join_handle.take().unwrap().join().unwrap();
+    }
+}
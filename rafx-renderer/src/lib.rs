@@ -8,12 +8,30 @@ mod renderer_pipeline_plugin;
 pub use renderer_pipeline_plugin::RendererPipelinePlugin;
 
 mod viewports_resource;
+pub use viewports_resource::RenderSurfaceViewports;
 pub use viewports_resource::RenderViewMeta;
+pub use viewports_resource::RenderViewport;
+pub use viewports_resource::RenderViewportRect;
+pub use viewports_resource::RenderViewportTarget;
 pub use viewports_resource::ViewportsResource;
+pub use viewports_resource::MAIN_SURFACE_NAME;
+
+mod surface_swapchains_resource;
+pub use surface_swapchains_resource::SurfaceSwapchainsResource;
+
+mod offscreen_render_target;
+pub use offscreen_render_target::OffscreenRenderFrame;
+pub use offscreen_render_target::OffscreenRenderHandle;
+pub use offscreen_render_target::OffscreenRenderRequest;
+pub use offscreen_render_target::OffscreenRenderResult;
+pub use offscreen_render_target::OffscreenRenderTarget;
 
 mod render_thread;
 use render_thread::RenderThread;
 
+mod gpu_frame_timer;
+pub use gpu_frame_timer::GpuFrameTimer;
+
 mod time_render_resource;
 pub use time_render_resource::TimeRenderResource;
 
@@ -25,6 +43,9 @@ pub use main_view_render_resource::MainViewRenderResource;
 
 mod render_frame_job;
 pub use render_frame_job::RenderFrameJob;
+pub use render_frame_job::RenderFrameJobReadback;
+pub use render_frame_job::RenderFrameJobSurface;
+pub use render_frame_job::RenderFrameTarget;
 
 mod renderer_builder;
 pub use renderer_builder::AssetSource;
@@ -0,0 +1,224 @@
+use crate::{RenderViewMeta, RenderViewport, RenderViewportRect};
+use crossbeam_channel::{Receiver, Sender};
+use rafx_api::{
+    RafxCommandBuffer, RafxError, RafxExtents2D, RafxFence, RafxFenceStatus, RafxFormat, RafxQueue,
+    RafxResult, RafxSwapchainColorSpace,
+};
+use rafx_framework::{BufferResource, ImageViewResource, ResourceArc};
+use std::sync::Arc;
+
+/// The image an offscreen frame is drawn to
+#[derive(Clone)]
+pub enum OffscreenRenderTarget {
+    /// Draw to an existing image. It must be a single-sampled 2D image created with
+    /// `RENDER_TARGET_COLOR | TEXTURE`. It is expected to be in the `SHADER_RESOURCE` state before
+    /// the frame is drawn, and is left in that state so that it can be sampled afterwards.
+    Image(ResourceArc<ImageViewResource>),
+
+    /// The renderer creates a new image. It can be retrieved from the `OffscreenRenderHandle`.
+    Create {
+        extents: RafxExtents2D,
+        format: RafxFormat,
+    },
+}
+
+impl OffscreenRenderTarget {
+    pub fn extents(&self) -> RafxExtents2D {
+        match self {
+            OffscreenRenderTarget::Image(image) => {
+                let texture_extents = image.get_raw().image.get_raw().image.texture_def().extents;
+                RafxExtents2D {
+                    width: texture_extents.width,
+                    height: texture_extents.height,
+                }
+            }
+            OffscreenRenderTarget::Create { extents, .. } => *extents,
+        }
+    }
+
+    pub fn format(&self) -> RafxFormat {
+        match self {
+            OffscreenRenderTarget::Image(image) => {
+                image.get_raw().image.get_raw().image.texture_def().format
+            }
+            OffscreenRenderTarget::Create { format, .. } => *format,
+        }
+    }
+}
+
+/// Describes a frame drawn by `Renderer::render_offscreen`. No window or swapchain is required.
+#[derive(Clone)]
+pub struct OffscreenRenderRequest {
+    /// Identifies the target to the pipeline. (See `RenderViewportTarget::surface_name`.) State
+    /// that the pipeline keeps per viewport, like temporal history, is keyed by this name, so the
+    /// same name should be used when drawing a sequence of frames of the same scene.
+    pub surface_name: String,
+    pub target: OffscreenRenderTarget,
+    pub color_space: RafxSwapchainColorSpace,
+    pub viewports: Vec<RenderViewport>,
+    /// If true, the image is copied into a CPU-visible buffer after it is drawn. The data is
+    /// returned by `OffscreenRenderHandle::wait`.
+    pub readback: bool,
+}
+
+impl OffscreenRenderRequest {
+    /// Draw a single view that covers the whole target
+    pub fn new(
+        surface_name: String,
+        target: OffscreenRenderTarget,
+        view_meta: RenderViewMeta,
+    ) -> Self {
+        let rect = RenderViewportRect::full_surface(target.extents());
+        OffscreenRenderRequest {
+            viewports: vec![RenderViewport {
+                name: surface_name.clone(),
+                rect,
+                view_meta,
+            }],
+            surface_name,
+            target,
+            color_space: RafxSwapchainColorSpace::Srgb,
+            readback: false,
+        }
+    }
+}
+
+/// The result of a completed offscreen frame
+pub struct OffscreenRenderResult {
+    pub image: ResourceArc<ImageViewResource>,
+    /// Tightly packed rows of pixels, in the format of the image. Only set if the request enabled
+    /// readback.
+    pub readback_data: Option<Vec<u8>>,
+}
+
+/// Returned by `Renderer::render_offscreen`. The frame is drawn asynchronously, this can be used
+/// to check if it has completed or to block until it completes.
+pub struct OffscreenRenderHandle {
+    image: ResourceArc<ImageViewResource>,
+    readback_buffer: Option<ResourceArc<BufferResource>>,
+    fence: Arc<RafxFence>,
+    submit_result_rx: Receiver<RafxResult<()>>,
+    submit_result: Option<RafxResult<()>>,
+}
+
+impl OffscreenRenderHandle {
+    /// The image the frame is drawn to. It must not be used until the frame is complete.
+    pub fn image(&self) -> &ResourceArc<ImageViewResource> {
+        &self.image
+    }
+
+    /// Returns true if the GPU has finished drawing the frame. This does not block. If drawing
+    /// the frame failed, the error is returned.
+    pub fn is_complete(&mut self) -> RafxResult<bool> {
+        if self.submit_result.is_none() {
+            match self.submit_result_rx.try_recv() {
+                Ok(result) => self.submit_result = Some(result),
+                Err(crossbeam_channel::TryRecvError::Empty) => return Ok(false),
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.submit_result = Some(Err(Self::dropped_error()))
+                }
+            }
+        }
+
+        self.submit_result.clone().unwrap()?;
+
+        // The fence returns to the unsubmitted state after it is waited on, which only happens
+        // after it is complete
+        Ok(self.fence.get_fence_status()? != RafxFenceStatus::Incomplete)
+    }
+
+    /// Blocks until the GPU finishes drawing the frame, returning the image and readback data
+    pub fn wait(mut self) -> RafxResult<OffscreenRenderResult> {
+        let submit_result = match self.submit_result.take() {
+            Some(result) => result,
+            None => self
+                .submit_result_rx
+                .recv()
+                .unwrap_or_else(|_| Err(Self::dropped_error())),
+        };
+        submit_result?;
+
+        self.fence.wait()?;
+
+        let readback_data = if let Some(readback_buffer) = &self.readback_buffer {
+            let buffer = &readback_buffer.get_raw().buffer;
+            let size = buffer.buffer_def().size as usize;
+            let mut data = vec![0; size];
+            let src = buffer.map_buffer()?;
+            unsafe {
+                std::ptr::copy_nonoverlapping(src, data.as_mut_ptr(), size);
+            }
+            buffer.unmap_buffer()?;
+            Some(data)
+        } else {
+            None
+        };
+
+        Ok(OffscreenRenderResult {
+            image: self.image,
+            readback_data,
+        })
+    }
+
+    fn dropped_error() -> RafxError {
+        RafxError::StringError(
+            "The offscreen frame was dropped without being submitted".to_string(),
+        )
+    }
+}
+
+/// The offscreen half of a `RenderFrameTarget`. It is submitted by the render thread, which
+/// notifies the `OffscreenRenderHandle`.
+pub struct OffscreenRenderFrame {
+    pub(crate) image: ResourceArc<ImageViewResource>,
+    pub(crate) readback_buffer: Option<ResourceArc<BufferResource>>,
+    fence: Arc<RafxFence>,
+    submit_result_tx: Sender<RafxResult<()>>,
+}
+
+impl OffscreenRenderFrame {
+    pub(crate) fn new(
+        image: ResourceArc<ImageViewResource>,
+        readback_buffer: Option<ResourceArc<BufferResource>>,
+        fence: Arc<RafxFence>,
+    ) -> (OffscreenRenderFrame, OffscreenRenderHandle) {
+        let (submit_result_tx, submit_result_rx) = crossbeam_channel::bounded(1);
+
+        let handle = OffscreenRenderHandle {
+            image: image.clone(),
+            readback_buffer: readback_buffer.clone(),
+            fence: fence.clone(),
+            submit_result_rx,
+            submit_result: None,
+        };
+
+        let frame = OffscreenRenderFrame {
+            image,
+            readback_buffer,
+            fence,
+            submit_result_tx,
+        };
+
+        (frame, handle)
+    }
+
+    /// Submits the given command buffers, signaling the frame's fence when they complete
+    pub fn submit(
+        self,
+        queue: &RafxQueue,
+        command_buffers: &[&RafxCommandBuffer],
+    ) -> RafxResult<()> {
+        let result = queue.submit(command_buffers, &[], &[], Some(&*self.fence));
+        // The handle may have been dropped, that's fine
+        let _ = self.submit_result_tx.send(result.clone());
+        result
+    }
+
+    /// Passes the error on to the `OffscreenRenderHandle` without submitting anything
+    pub fn submit_with_error(
+        self,
+        error: RafxError,
+    ) {
+        let _ = self.submit_result_tx.send(Err(error));
+    }
+}
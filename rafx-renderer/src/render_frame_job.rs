@@ -1,20 +1,106 @@
-use crate::{RenderFeaturePlugin, RendererPipelinePlugin, RendererThreadPool};
+use crate::{
+    GpuFrameTimer, OffscreenRenderFrame, RenderFeaturePlugin, RendererPipelinePlugin,
+    RendererThreadPool,
+};
 use fnv::FnvBuildHasher;
-use rafx_api::{RafxCommandBuffer, RafxDeviceContext, RafxQueue};
+use rafx_api::{
+    RafxCmdCopyTextureToBufferParams, RafxCommandBuffer, RafxCommandBufferDef, RafxCommandPoolDef,
+    RafxDeviceContext, RafxError, RafxQueue, RafxResourceState, RafxTextureBarrier,
+};
 use rafx_api::{RafxPresentableFrame, RafxResult};
 use rafx_framework::graph::PreparedRenderGraph;
 use rafx_framework::render_features::render_features_prelude::*;
-use rafx_framework::{DynCommandBuffer, RenderResources, ResourceContext};
+use rafx_framework::{
+    BufferResource, DynCommandBuffer, ImageViewResource, RenderResources, ResourceArc,
+    ResourceContext,
+};
 use std::sync::Arc;
 
 pub struct RenderFrameJobResult;
 
+/// Where a surface's graphs are drawn to. Swapchain images are presented, offscreen images are
+/// submitted with a fence that the `OffscreenRenderHandle` can wait on.
+pub enum RenderFrameTarget {
+    Swapchain(RafxPresentableFrame),
+    Offscreen(OffscreenRenderFrame),
+}
+
+impl RenderFrameTarget {
+    /// The state the target image is in before and after the frame's graphs execute
+    pub fn image_state(&self) -> RafxResourceState {
+        match self {
+            RenderFrameTarget::Swapchain(_) => RafxResourceState::PRESENT,
+            RenderFrameTarget::Offscreen(_) => RafxResourceState::SHADER_RESOURCE,
+        }
+    }
+
+    pub fn max_color_component_value(&self) -> f32 {
+        match self {
+            RenderFrameTarget::Swapchain(frame) => {
+                let swapchain_guard = frame.swapchain().lock().unwrap();
+                match &*swapchain_guard {
+                    #[cfg(feature = "rafx-metal")]
+                    rafx_api::RafxSwapchain::Metal(swapchain) => {
+                        swapchain.edr_info().max_edr_color_component_value
+                    }
+                    #[allow(unreachable_patterns)]
+                    _ => 1.0,
+                }
+            }
+            RenderFrameTarget::Offscreen(_) => 1.0,
+        }
+    }
+
+    pub fn submit(
+        self,
+        queue: &RafxQueue,
+        command_buffers: &[&RafxCommandBuffer],
+    ) {
+        match self {
+            RenderFrameTarget::Swapchain(frame) => {
+                // ignore the error, we will receive it when we try to acquire the next image
+                let _ = frame.present(queue, command_buffers);
+            }
+            RenderFrameTarget::Offscreen(frame) => {
+                // ignore the error, it is passed on to the offscreen render handle
+                let _ = frame.submit(queue, command_buffers);
+            }
+        }
+    }
+
+    pub fn submit_with_error(
+        self,
+        queue: &RafxQueue,
+        error: RafxError,
+    ) {
+        match self {
+            RenderFrameTarget::Swapchain(frame) => frame.present_with_error(queue, error),
+            RenderFrameTarget::Offscreen(frame) => frame.submit_with_error(error),
+        }
+    }
+}
+
+/// Copies a surface's image into a CPU-visible buffer after its graphs execute
+pub struct RenderFrameJobReadback {
+    pub image: ResourceArc<ImageViewResource>,
+    pub image_state: RafxResourceState,
+    pub buffer: ResourceArc<BufferResource>,
+}
+
+/// The graphs drawn to a surface in a frame, one per viewport in the order they are drawn
+pub struct RenderFrameJobSurface {
+    pub surface_name: String,
+    pub prepared_render_graphs: Vec<PreparedRenderGraph>,
+    pub readback: Option<RenderFrameJobReadback>,
+}
+
 /// The `RenderFrameJob` is responsible for the `prepare` and `write` steps of the `Renderer` pipeline.
 /// This is created by `Renderer::try_create_render_job` with the results of the `extract` step.
 pub struct RenderFrameJob {
     pub thread_pool: Box<dyn RendererThreadPool>,
     pub render_resources: Arc<RenderResources>,
-    pub prepared_render_graph: PreparedRenderGraph,
+    // Index-aligned with the targets passed to render_async
+    pub surfaces: Vec<RenderFrameJobSurface>,
     pub resource_context: ResourceContext,
     pub frame_packets: Vec<Box<dyn RenderFeatureFramePacket>>,
     pub render_registry: RenderRegistry,
@@ -23,18 +109,23 @@ pub struct RenderFrameJob {
     pub render_views: Vec<RenderView>,
     pub feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
     pub pipeline_plugin: Arc<dyn RendererPipelinePlugin>,
+    // None if the renderer doesn't measure GPU frame times
+    pub gpu_frame_timer: Option<Arc<GpuFrameTimer>>,
 }
 
 impl RenderFrameJob {
     pub fn render_async(
         mut self,
-        presentable_frame: RafxPresentableFrame,
+        targets: Vec<RenderFrameTarget>,
     ) -> RenderFrameJobResult {
         let t0 = rafx_base::Instant::now();
 
+        assert_eq!(self.surfaces.len(), targets.len());
+
         let graphics_queue = self.graphics_queue.clone();
+        let resource_context = self.resource_context.clone();
         let result = Self::do_render_async(
-            self.prepared_render_graph,
+            self.surfaces,
             self.resource_context,
             self.frame_packets,
             self.render_registry,
@@ -53,17 +144,33 @@ impl RenderFrameJob {
         );
 
         match result {
-            Ok(command_buffers) => {
-                // ignore the error, we will receive it when we try to acquire the next image
-                let refs: Vec<&RafxCommandBuffer> = command_buffers.iter().map(|x| &**x).collect();
-                //graphics_queue.wait_for_queue_idle().unwrap();
-                let _ = presentable_frame.present(&graphics_queue, &refs);
-                //graphics_queue.wait_for_queue_idle().unwrap();
+            Ok(surface_command_buffers) => {
+                let submit_time = rafx_base::Instant::now();
+                for (target, command_buffers) in targets.into_iter().zip(surface_command_buffers) {
+                    let refs: Vec<&RafxCommandBuffer> =
+                        command_buffers.iter().map(|x| &**x).collect();
+                    //graphics_queue.wait_for_queue_idle().unwrap();
+                    target.submit(&graphics_queue, &refs);
+                    //graphics_queue.wait_for_queue_idle().unwrap();
+                }
+
+                if let Some(gpu_frame_timer) = &self.gpu_frame_timer {
+                    if let Err(err) = gpu_frame_timer.submit(
+                        &self.device_context,
+                        &resource_context,
+                        &graphics_queue,
+                        submit_time,
+                    ) {
+                        log::warn!("Failed to submit GPU frame timer fence: {:?}", err);
+                    }
+                }
             }
             Err(err) => {
                 log::error!("Render thread failed with error {:?}", err);
-                // Pass error on to the next swapchain image acquire call
-                presentable_frame.present_with_error(&graphics_queue, err);
+                // Pass error on to the next swapchain image acquire call (or the offscreen handle)
+                for target in targets {
+                    target.submit_with_error(&graphics_queue, err.clone());
+                }
             }
         }
 
@@ -78,7 +185,7 @@ impl RenderFrameJob {
 
     #[allow(clippy::too_many_arguments)]
     fn do_render_async(
-        prepared_render_graph: PreparedRenderGraph,
+        surfaces: Vec<RenderFrameJobSurface>,
         resource_context: ResourceContext,
         frame_packets: Vec<Box<dyn RenderFeatureFramePacket>>,
         render_registry: RenderRegistry,
@@ -88,7 +195,7 @@ impl RenderFrameJob {
         feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
         pipeline_plugin: Arc<dyn RendererPipelinePlugin>,
         thread_pool: &mut dyn RendererThreadPool,
-    ) -> RafxResult<Vec<DynCommandBuffer>> {
+    ) -> RafxResult<Vec<Vec<DynCommandBuffer>>> {
         let t0 = rafx_base::Instant::now();
 
         //
@@ -141,7 +248,7 @@ impl RenderFrameJob {
             (t1 - t0).as_secs_f32() * 1000.0
         );
 
-        let command_buffers = {
+        let surface_command_buffers = {
             profiling::scope!("Renderer Write");
 
             let write_context =
@@ -156,16 +263,43 @@ impl RenderFrameJob {
                 )
             };
 
-            let prepared_render_data = PreparedRenderData::new(&submit_node_blocks, write_jobs);
+            // Every viewport's graph draws from the same prepared data. The command buffers for each
+            // surface are kept separate so they can be submitted with that surface's present.
+            let mut surface_command_buffers = Vec::with_capacity(surfaces.len());
+            let mut is_first_graph = true;
+            for surface in &surfaces {
+                profiling::scope!("Execute Render Graphs");
+                let mut command_buffers = Vec::default();
+                for prepared_render_graph in &surface.prepared_render_graphs {
+                    let prepared_render_data = if is_first_graph {
+                        PreparedRenderData::new(&submit_node_blocks, write_jobs.clone())
+                    } else {
+                        PreparedRenderData::new_for_additional_graph(
+                            &submit_node_blocks,
+                            write_jobs.clone(),
+                        )
+                    };
+                    is_first_graph = false;
+
+                    command_buffers.append(&mut prepared_render_graph.execute_graph(
+                        &write_context,
+                        prepared_render_data,
+                        &graphics_queue,
+                    )?);
+                }
 
-            {
-                profiling::scope!("Execute Render Graph");
-                prepared_render_graph.execute_graph(
-                    &write_context,
-                    prepared_render_data,
-                    &graphics_queue,
-                )?
+                if let Some(readback) = &surface.readback {
+                    command_buffers.push(Self::record_readback(
+                        &resource_context,
+                        &graphics_queue,
+                        readback,
+                    )?);
+                }
+
+                surface_command_buffers.push(command_buffers);
             }
+
+            surface_command_buffers
         };
 
         pipeline_plugin.finish_frame(render_resources);
@@ -176,7 +310,50 @@ impl RenderFrameJob {
             (t2 - t1).as_secs_f32() * 1000.0
         );
 
-        Ok(command_buffers)
+        Ok(surface_command_buffers)
+    }
+
+    fn record_readback(
+        resource_context: &ResourceContext,
+        graphics_queue: &RafxQueue,
+        readback: &RenderFrameJobReadback,
+    ) -> RafxResult<DynCommandBuffer> {
+        let mut command_pool = resource_context
+            .create_dyn_command_pool_allocator()
+            .allocate_dyn_pool(graphics_queue, &RafxCommandPoolDef { transient: true }, 0)?;
+
+        let command_buffer = command_pool.allocate_dyn_command_buffer(&RafxCommandBufferDef {
+            is_secondary: false,
+        })?;
+
+        let texture = readback.image.get_raw().image.get_raw().image.clone();
+        let buffer = readback.buffer.get_raw().buffer.clone();
+
+        command_buffer.begin()?;
+        command_buffer.cmd_resource_barrier(
+            &[],
+            &[RafxTextureBarrier::state_transition(
+                &texture,
+                readback.image_state,
+                RafxResourceState::COPY_SRC,
+            )],
+        )?;
+        command_buffer.cmd_copy_texture_to_buffer(
+            &texture,
+            &buffer,
+            &RafxCmdCopyTextureToBufferParams::default(),
+        )?;
+        command_buffer.cmd_resource_barrier(
+            &[],
+            &[RafxTextureBarrier::state_transition(
+                &texture,
+                RafxResourceState::COPY_SRC,
+                readback.image_state,
+            )],
+        )?;
+        command_buffer.end()?;
+
+        Ok(command_buffer)
     }
 
     fn create_prepare_jobs<'prepare>(
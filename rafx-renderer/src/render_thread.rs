@@ -1,12 +1,11 @@
 use super::render_frame_job::RenderFrameJobResult;
-use super::RenderFrameJob;
+use super::{RenderFrameJob, RenderFrameTarget};
 use crossbeam_channel::{Receiver, Sender};
-use rafx_api::RafxPresentableFrame;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::JoinHandle;
 
 enum RenderThreadMessage {
-    Render(RenderFrameJob, RafxPresentableFrame),
+    Render(RenderFrameJob, Vec<RenderFrameTarget>),
     Finish,
 }
 
@@ -42,13 +41,10 @@ impl RenderThread {
     pub fn render(
         &self,
         prepared_frame: RenderFrameJob,
-        presentable_frame: RafxPresentableFrame,
+        targets: Vec<RenderFrameTarget>,
     ) {
         self.job_tx
-            .send(RenderThreadMessage::Render(
-                prepared_frame,
-                presentable_frame,
-            ))
+            .send(RenderThreadMessage::Render(prepared_frame, targets))
             .unwrap();
 
         let was_expecting_result = self.expecting_result.swap(true, Ordering::Relaxed);
@@ -78,11 +74,11 @@ impl RenderThread {
             profiling::register_thread!();
 
             match job_rx.recv()? {
-                RenderThreadMessage::Render(prepared_frame, frame_in_flight) => {
+                RenderThreadMessage::Render(prepared_frame, targets) => {
                     profiling::scope!("Render Frame");
 
                     log::trace!("kick off render");
-                    let result = prepared_frame.render_async(frame_in_flight);
+                    let result = prepared_frame.render_async(targets);
                     result_tx.send(result).unwrap();
                 }
                 RenderThreadMessage::Finish => {
@@ -1,22 +1,25 @@
+use fnv::FnvHashMap;
 use rafx_assets::distill_impl::AssetResource;
 use rafx_assets::AssetManagerRenderResource;
 use rafx_assets::{distill, AssetManager};
 use rafx_framework::render_features::render_features_prelude::*;
 use rafx_framework::visibility::{VisibilityConfig, VisibilityResource};
-use rafx_framework::{ImageViewResource, ResourceArc};
+use rafx_framework::{ImageViewResource, ResourceArc, MAX_FRAMES_IN_FLIGHT};
 use rafx_framework::{RenderResources, ResourceLookupSet};
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use super::*;
 
 use super::{RenderFeaturePlugin, RendererPipelinePlugin, ViewportsResource};
-use crate::main_view_render_resource::PreviousMainViewInfo;
 use rafx_api::extra::upload::{RafxTransferUpload, RafxUploadError};
 use rafx_api::{
-    RafxDeviceContext, RafxError, RafxPresentableFrame, RafxQueue, RafxResourceType, RafxResult,
-    RafxSwapchainHelper,
+    RafxBufferDef, RafxDeviceContext, RafxError, RafxExtents3D, RafxFence, RafxFormat,
+    RafxMemoryUsage, RafxQueue, RafxQueueType, RafxResourceType, RafxResult, RafxSwapchainHelper,
+    RafxTextureDef, RafxTextureDimensions,
 };
+use rafx_framework::graph::SwapchainSurfaceInfo;
 use rafx_framework::upload::image_upload::ImageUploadParams;
 use rafx_framework::upload::{image_upload, GpuImageData, GpuImageDataColorSpace};
 
@@ -73,11 +76,30 @@ pub struct RendererInner {
     // This is a separate lock
     pub(super) temporary_work: RenderJobExtractAllocationContext,
     pub(super) thread_pool: Box<dyn RendererThreadPool>,
+    // Fences for offscreen frames that may still be in flight, oldest first
+    pub(super) offscreen_frame_fences: VecDeque<Arc<RafxFence>>,
+    // Swapchain frames are paced by their swapchain and have no fence in offscreen_frame_fences.
+    // This is set when one is drawn, so that the next offscreen frame knows to wait for it.
+    pub(super) swapchain_frames_in_flight: bool,
+    // Counts every frame drawn, to swapchains or offscreen. The pipeline picks per-frame resources
+    // with it, so swapchain and offscreen frames must share it to not use the same ones.
+    pub(super) frame_index: usize,
+    // The matrices each viewport was drawn with the last time its surface was drawn, keyed by
+    // surface name and then viewport name
+    pub(super) view_history: FnvHashMap<String, FnvHashMap<String, RenderViewMatrices>>,
+}
+
+// A surface drawn in a frame, the viewports drawn to it, and the image they are drawn to
+struct RenderFrameSurface {
+    surface_name: String,
+    viewports: RenderSurfaceViewports,
+    target: RenderFrameTarget,
 }
 
 pub struct Renderer {
     pub(super) inner: Arc<Mutex<RendererInner>>,
     pub(super) render_thread: Option<RenderThread>,
+    pub(super) gpu_frame_timer: Option<Arc<GpuFrameTimer>>,
     pub(super) pipeline_plugin: Arc<dyn RendererPipelinePlugin>,
     pub(super) asset_plugins: Arc<Vec<Arc<dyn RendererAssetPlugin>>>,
     pub(super) feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
@@ -236,15 +258,28 @@ impl Renderer {
             None
         };
 
+        // The timer waits on fences from its own thread, so it requires the same multithreading
+        // support as the render thread
+        let gpu_frame_timer = if use_render_thread {
+            Some(Arc::new(GpuFrameTimer::start()))
+        } else {
+            None
+        };
+
         let num_features = RenderRegistry::registered_feature_count() as usize;
         let renderer = RendererInner {
             thread_pool,
             temporary_work: RenderJobExtractAllocationContext::new(num_features),
+            offscreen_frame_fences: Default::default(),
+            swapchain_frames_in_flight: false,
+            frame_index: 0,
+            view_history: Default::default(),
         };
 
         Ok(Renderer {
             inner: Arc::new(Mutex::new(renderer)),
             render_thread,
+            gpu_frame_timer,
             asset_plugins,
             feature_plugins,
             pipeline_plugin,
@@ -328,18 +363,7 @@ impl Renderer {
         //
         let t0 = rafx_base::Instant::now();
 
-        let presentable_frame = {
-            let viewports_resource = extract_resources.fetch::<ViewportsResource>();
-            let mut swapchain_helper = extract_resources.fetch_mut::<RafxSwapchainHelper>();
-            let mut asset_manager = extract_resources.fetch_mut::<AssetManager>();
-            SwapchainHandler::acquire_next_image(
-                &mut *swapchain_helper,
-                &mut *asset_manager,
-                self,
-                viewports_resource.main_window_size.width,
-                viewports_resource.main_window_size.height,
-            )
-        }?;
+        let surfaces = self.acquire_next_images(extract_resources)?;
 
         if let Some(render_thread) = &self.render_thread {
             render_thread.wait_for_render_finish();
@@ -351,41 +375,261 @@ impl Renderer {
             (t1 - t0).as_secs_f32() * 1000.0
         );
 
+        if surfaces.is_empty() {
+            log::trace!("No surfaces have viewports to draw, skipping frame");
+            return Ok(());
+        }
+
+        // The swapchain only paces its own frames, so wait for any offscreen frames before reusing
+        // per-frame resources they might still be using
+        let wait_result = {
+            let mut guard = self.inner.lock().unwrap();
+            let renderer_inner = &mut *guard;
+            renderer_inner.swapchain_frames_in_flight = true;
+            renderer_inner
+                .offscreen_frame_fences
+                .drain(..)
+                .map(|fence| fence.wait())
+                .collect::<RafxResult<()>>()
+        };
+
+        if let Err(e) = wait_result {
+            for surface in surfaces {
+                surface
+                    .target
+                    .submit_with_error(&self.graphics_queue, e.clone());
+            }
+
+            return Err(e);
+        }
+
+        Self::create_and_start_render_job(self, extract_resources, surfaces, previous_update_time);
+
+        Ok(())
+    }
+
+    /// Draws a frame into an image instead of a swapchain. This runs the same extract, prepare and
+    /// write steps as `start_rendering_next_frame`, and does not require a window. The returned
+    /// handle can be used to wait for the GPU to finish drawing and to retrieve the image and
+    /// readback data.
+    #[profiling::function]
+    pub fn render_offscreen(
+        &self,
+        extract_resources: &mut ExtractResources,
+        request: OffscreenRenderRequest,
+        previous_update_time: Duration,
+    ) -> RafxResult<OffscreenRenderHandle> {
+        if request.viewports.is_empty() {
+            return Err("An offscreen render request must have at least one viewport")?;
+        }
+
+        // Make sure the previous frame is submitted so that its fence can be waited on
+        if let Some(render_thread) = &self.render_thread {
+            render_thread.wait_for_render_finish();
+        }
+
+        let extents = request.target.extents();
+        let format = request.target.format();
+
+        let (frame, handle) = {
+            let asset_manager = extract_resources.fetch::<AssetManager>();
+            let device_context = asset_manager.device_context();
+            let resources = asset_manager.resources();
+
+            let image = match request.target {
+                OffscreenRenderTarget::Image(image) => image,
+                OffscreenRenderTarget::Create { extents, format } => {
+                    let texture = device_context.create_texture(&RafxTextureDef {
+                        extents: RafxExtents3D {
+                            width: extents.width,
+                            height: extents.height,
+                            depth: 1,
+                        },
+                        format,
+                        resource_type: RafxResourceType::RENDER_TARGET_COLOR
+                            | RafxResourceType::TEXTURE,
+                        dimensions: RafxTextureDimensions::Dim2D,
+                        ..Default::default()
+                    })?;
+                    let image = resources.insert_image(texture);
+                    resources.get_or_create_image_view(&image, None)?
+                }
+            };
+
+            let readback_buffer = if request.readback {
+                let size = extents.width as u64
+                    * extents.height as u64
+                    * format.block_or_pixel_size_in_bytes() as u64;
+                let buffer = device_context.create_buffer(&RafxBufferDef {
+                    size,
+                    alignment: 0,
+                    memory_usage: RafxMemoryUsage::GpuToCpu,
+                    queue_type: RafxQueueType::Graphics,
+                    resource_type: RafxResourceType::BUFFER_READ_WRITE,
+                    elements: Default::default(),
+                    format: RafxFormat::UNDEFINED,
+                    always_mapped: false,
+                })?;
+                Some(resources.insert_buffer(buffer))
+            } else {
+                None
+            };
+
+            // Offscreen frames are not paced by a swapchain. Keep the GPU from falling more than
+            // MAX_FRAMES_IN_FLIGHT frames behind so that per-frame resources can be reused safely.
+            // Swapchain frames don't have a fence here, so wait for the queue if any were drawn.
+            let mut guard = self.inner.lock().unwrap();
+            let renderer_inner = &mut *guard;
+            if renderer_inner.swapchain_frames_in_flight {
+                self.graphics_queue.wait_for_queue_idle()?;
+                renderer_inner.swapchain_frames_in_flight = false;
+            }
+
+            while renderer_inner.offscreen_frame_fences.len() >= MAX_FRAMES_IN_FLIGHT {
+                let fence = renderer_inner.offscreen_frame_fences.pop_front().unwrap();
+                fence.wait()?;
+            }
+
+            let fence = Arc::new(device_context.create_fence()?);
+            renderer_inner
+                .offscreen_frame_fences
+                .push_back(fence.clone());
+
+            OffscreenRenderFrame::new(image, readback_buffer, fence)
+        };
+
+        // The pipeline looks up formats for the target by surface name
+        {
+            let asset_manager = extract_resources.fetch::<AssetManager>();
+            self.render_resources
+                .fetch_mut::<SwapchainRenderResource>()
+                .set_surface_swapchain_info(
+                    &request.surface_name,
+                    asset_manager.device_context(),
+                    SwapchainSurfaceInfo {
+                        extents,
+                        format,
+                        color_space: request.color_space,
+                    },
+                )?;
+        }
+
+        let surface = RenderFrameSurface {
+            surface_name: request.surface_name,
+            viewports: RenderSurfaceViewports {
+                size: extents,
+                viewports: request.viewports,
+            },
+            target: RenderFrameTarget::Offscreen(frame),
+        };
+
         Self::create_and_start_render_job(
             self,
             extract_resources,
-            presentable_frame,
+            vec![surface],
             previous_update_time,
         );
 
-        Ok(())
+        Ok(handle)
+    }
+
+    // Acquires the next image of each surface that has viewports to draw. The main window is always
+    // first, followed by the surfaces in SurfaceSwapchainsResource
+    fn acquire_next_images(
+        &self,
+        extract_resources: &ExtractResources,
+    ) -> RafxResult<Vec<RenderFrameSurface>> {
+        let viewports_resource = extract_resources.fetch::<ViewportsResource>();
+        let mut asset_manager = extract_resources.fetch_mut::<AssetManager>();
+        let mut main_swapchain_helper = extract_resources.try_fetch_mut::<RafxSwapchainHelper>();
+        let mut surface_swapchains = extract_resources.try_fetch_mut::<SurfaceSwapchainsResource>();
+
+        let mut surface_names = Vec::default();
+        if main_swapchain_helper.is_some() {
+            surface_names.push(MAIN_SURFACE_NAME.to_string());
+        }
+
+        if let Some(surface_swapchains) = &surface_swapchains {
+            surface_names.extend(surface_swapchains.surface_names());
+        }
+
+        let mut surfaces: Vec<RenderFrameSurface> = Vec::with_capacity(surface_names.len());
+        for surface_name in surface_names {
+            let viewports = match viewports_resource.surface_viewports(&surface_name) {
+                Some(viewports) if !viewports.viewports.is_empty() => viewports,
+                _ => continue,
+            };
+
+            let swapchain_helper = if surface_name == MAIN_SURFACE_NAME {
+                main_swapchain_helper.as_deref_mut()
+            } else {
+                surface_swapchains
+                    .as_deref_mut()
+                    .and_then(|x| x.get_mut(&surface_name))
+            }
+            .unwrap();
+
+            let result = SwapchainHandler::acquire_next_surface_image(
+                swapchain_helper,
+                &mut *asset_manager,
+                self,
+                &surface_name,
+                viewports.size.width,
+                viewports.size.height,
+            );
+
+            match result {
+                Ok(presentable_frame) => surfaces.push(RenderFrameSurface {
+                    surface_name,
+                    viewports,
+                    target: RenderFrameTarget::Swapchain(presentable_frame),
+                }),
+                Err(e) => {
+                    // Release the images we already acquired, the error will be returned for them
+                    // on their next acquire too
+                    for surface in surfaces {
+                        surface
+                            .target
+                            .submit_with_error(&self.graphics_queue, e.clone());
+                    }
+
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(surfaces)
     }
 
     fn create_and_start_render_job(
         renderer: &Renderer,
         extract_resources: &mut ExtractResources,
-        presentable_frame: RafxPresentableFrame,
+        surfaces: Vec<RenderFrameSurface>,
         previous_update_time: Duration,
     ) {
         let result = Self::try_create_render_job(
             &renderer,
             extract_resources,
-            &presentable_frame,
+            &surfaces,
             previous_update_time,
         );
 
+        let targets: Vec<_> = surfaces.into_iter().map(|x| x.target).collect();
+
         match result {
             Ok(prepared_frame) => {
                 if let Some(render_thread) = &renderer.render_thread {
-                    render_thread.render(prepared_frame, presentable_frame);
+                    render_thread.render(prepared_frame, targets);
                 } else {
                     // This path is required for backends that do not support multithreaded use
-                    prepared_frame.render_async(presentable_frame);
+                    prepared_frame.render_async(targets);
                 }
             }
             Err(e) => {
                 let graphics_queue = renderer.graphics_queue();
-                presentable_frame.present_with_error(graphics_queue, e)
+                for target in targets {
+                    target.submit_with_error(graphics_queue, e.clone())
+                }
             }
         };
     }
@@ -393,7 +637,7 @@ impl Renderer {
     fn try_create_render_job(
         renderer: &Renderer,
         extract_resources: &mut ExtractResources,
-        presentable_frame: &RafxPresentableFrame,
+        surfaces: &[RenderFrameSurface],
         previous_update_time: Duration,
     ) -> RafxResult<RenderFrameJob> {
         //
@@ -416,9 +660,16 @@ impl Renderer {
         let renderer_inner = &mut *guard;
         let render_resources = &renderer.render_resources;
 
-        render_resources
-            .fetch_mut::<TimeRenderResource>()
-            .update(previous_update_time);
+        {
+            let mut time_render_resource = render_resources.fetch_mut::<TimeRenderResource>();
+            time_render_resource.update(previous_update_time);
+            time_render_resource.set_previous_gpu_frame_time(
+                renderer
+                    .gpu_frame_timer
+                    .as_ref()
+                    .and_then(|x| x.previous_gpu_frame_time()),
+            );
+        }
 
         for plugin in &*renderer.asset_plugins {
             plugin.on_frame_complete(asset_manager, extract_resources, &*render_resources)?;
@@ -432,69 +683,127 @@ impl Renderer {
         //
         // Swapchain Status
         //
-        let swapchain_image = {
-            // Temporary hack to jam a swapchain image into the existing resource lookups.. may want
-            // to reconsider this later since the ResourceArc can be held past the lifetime of the
-            // swapchain image
-            let swapchain_image = presentable_frame.swapchain_texture().clone();
-
-            let swapchain_image = resource_context.resources().insert_image(swapchain_image);
-
-            resource_context
-                .resources()
-                .get_or_create_image_view(&swapchain_image, None)?
-        };
+        let mut target_images = Vec::with_capacity(surfaces.len());
+        for surface in surfaces {
+            let target_image = match &surface.target {
+                RenderFrameTarget::Swapchain(presentable_frame) => {
+                    // Temporary hack to jam a swapchain image into the existing resource lookups..
+                    // may want to reconsider this later since the ResourceArc can be held past the
+                    // lifetime of the swapchain image
+                    let swapchain_image = presentable_frame.swapchain_texture().clone();
+
+                    let swapchain_image =
+                        resource_context.resources().insert_image(swapchain_image);
+
+                    resource_context
+                        .resources()
+                        .get_or_create_image_view(&swapchain_image, None)?
+                }
+                RenderFrameTarget::Offscreen(offscreen_frame) => offscreen_frame.image.clone(),
+            };
 
-        let swapchain_guard = presentable_frame.swapchain().lock().unwrap();
-        let max_color_component_value = match &*swapchain_guard {
-            #[cfg(feature = "rafx-metal")]
-            rafx_api::RafxSwapchain::Metal(swapchain) => {
-                swapchain.edr_info().max_edr_color_component_value
-            }
-            #[allow(unreachable_patterns)]
-            _ => 1.0,
-        };
+            target_images.push(target_image);
+        }
 
+        // The first surface is the main window if it is being drawn
+        let first_target = &surfaces[0].target;
         render_resources
             .fetch_mut::<SwapchainRenderResource>()
-            .set_max_color_component_value(max_color_component_value);
+            .set_max_color_component_value(first_target.max_color_component_value());
+
+        let frame_index = renderer_inner.frame_index;
+        renderer_inner.frame_index += 1;
+        let rotating_frame_index = frame_index % (MAX_FRAMES_IN_FLIGHT + 1);
 
-        let render_view_set = RenderViewSet::new(presentable_frame.incrementing_frame_index());
+        let render_view_set = RenderViewSet::new(frame_index);
 
         //
         // Determine Camera Location
         //
 
-        let viewports_resource = extract_resources.fetch::<ViewportsResource>();
-        let view_meta = viewports_resource.main_view_meta.clone().unwrap();
-
-        let main_window_size = viewports_resource.main_window_size;
-
-        let main_view = render_view_set.create_view(
-            view_meta.view_frustum,
-            view_meta.eye_position,
-            view_meta.view,
-            view_meta.proj,
-            (main_window_size.width, main_window_size.height),
-            view_meta.depth_range,
-            view_meta.render_phase_mask,
-            view_meta.render_feature_mask,
-            view_meta.render_feature_flag_mask,
-            view_meta.debug_name,
-        );
-        {
-            let mut main_view_render_resource =
-                render_resources.fetch_mut::<MainViewRenderResource>();
+        // One view per viewport. The first viewport of the first surface is the main view. The
+        // pipeline decides what size each view is rendered at and how it is jittered. Each view
+        // carries the matrices its viewport was drawn with the last time its surface was drawn.
+        let mut viewport_views = Vec::with_capacity(surfaces.len());
+        for surface in surfaces {
+            let swapchain_surface_info = render_resources
+                .fetch::<SwapchainRenderResource>()
+                .surface_info_for_surface(&surface.surface_name)
+                .ok_or_else(|| {
+                    format!("Surface {} does not have a swapchain", surface.surface_name)
+                })?
+                .swapchain_surface_info
+                .clone();
+
+            let previous_view_history = renderer_inner
+                .view_history
+                .remove(&surface.surface_name)
+                .unwrap_or_default();
+            let mut view_history = FnvHashMap::default();
+
+            let mut views = Vec::with_capacity(surface.viewports.viewports.len());
+            for (viewport_index, viewport) in surface.viewports.viewports.iter().enumerate() {
+                let viewport_target = RenderViewportTarget {
+                    surface_name: surface.surface_name.clone(),
+                    viewport_name: viewport.name.clone(),
+                    viewport_index,
+                    rect: viewport.rect,
+                    surface_extents: surface.viewports.size,
+                    surface_info: SwapchainSurfaceInfo {
+                        extents: viewport.rect.extents(),
+                        ..swapchain_surface_info.clone()
+                    },
+                    image_state: surface.target.image_state(),
+                };
+
+                let render_extents = renderer.pipeline_plugin.viewport_render_extents(
+                    &viewport_target,
+                    extract_resources,
+                    render_resources,
+                );
 
-            if let Some(main_view) = &main_view_render_resource.main_view {
-                main_view_render_resource.previous_main_view_info = Some(PreviousMainViewInfo {
-                    view_matrix: main_view.view_matrix(),
-                    projection_matrix: main_view.projection_matrix(),
-                });
+                let jitter_amount = renderer.pipeline_plugin.viewport_jitter_amount(
+                    &viewport_target,
+                    render_extents,
+                    render_view_set.frame_index(),
+                    extract_resources,
+                    render_resources,
+                );
+
+                let previous_frame = previous_view_history.get(&viewport.name).copied();
+
+                let view_meta = viewport.view_meta.clone();
+                let view = render_view_set.create_view_with_history(
+                    view_meta.view_frustum,
+                    view_meta.eye_position,
+                    view_meta.view,
+                    view_meta.proj,
+                    (render_extents.width, render_extents.height),
+                    view_meta.depth_range,
+                    view_meta.render_phase_mask,
+                    view_meta.render_feature_mask,
+                    view_meta.render_feature_flag_mask,
+                    view_meta.debug_name,
+                    jitter_amount,
+                    previous_frame,
+                );
+
+                view_history.insert(viewport.name.clone(), view.matrices());
+                views.push((viewport_target, view));
             }
-            main_view_render_resource.main_view = Some(main_view.clone());
+
+            // Viewports that were not drawn this frame lose their history
+            renderer_inner
+                .view_history
+                .insert(surface.surface_name.clone(), view_history);
+            viewport_views.push(views);
         }
 
+        let main_view = viewport_views[0][0].1.clone();
+        render_resources
+            .fetch_mut::<MainViewRenderResource>()
+            .main_view = Some(main_view.clone());
+
         //
         // Compute Views
         //
@@ -503,7 +812,12 @@ impl Renderer {
 
         {
             profiling::scope!("Compute Views");
-            render_views.push(main_view.clone());
+            for views in &viewport_views {
+                for (_, view) in views {
+                    render_views.push(view.clone());
+                }
+            }
+
             for plugin in &*renderer.feature_plugins {
                 plugin.add_render_views(
                     extract_resources,
@@ -602,14 +916,42 @@ impl Renderer {
             .end_extract();
 
         //TODO: This is now possible to run on the render thread
-        let prepared_render_graph = renderer.pipeline_plugin.generate_render_graph(
-            asset_manager,
-            swapchain_image,
-            presentable_frame.rotating_frame_index(),
-            main_view.clone(),
-            extract_resources,
-            render_resources,
-        )?;
+        let mut job_surfaces = Vec::with_capacity(surfaces.len());
+        for (surface_index, views) in viewport_views.into_iter().enumerate() {
+            let surface_name = &surfaces[surface_index].surface_name;
+            let target = &surfaces[surface_index].target;
+
+            let mut prepared_render_graphs = Vec::with_capacity(views.len());
+            for (viewport, view) in views {
+                prepared_render_graphs.push(renderer.pipeline_plugin.generate_render_graph(
+                    asset_manager,
+                    target_images[surface_index].clone(),
+                    rotating_frame_index,
+                    view,
+                    &viewport,
+                    extract_resources,
+                    render_resources,
+                )?);
+            }
+
+            let readback = match target {
+                RenderFrameTarget::Offscreen(offscreen_frame) => offscreen_frame
+                    .readback_buffer
+                    .as_ref()
+                    .map(|buffer| RenderFrameJobReadback {
+                        image: target_images[surface_index].clone(),
+                        image_state: target.image_state(),
+                        buffer: buffer.clone(),
+                    }),
+                RenderFrameTarget::Swapchain(_) => None,
+            };
+
+            job_surfaces.push(RenderFrameJobSurface {
+                surface_name: surface_name.clone(),
+                prepared_render_graphs,
+                readback,
+            });
+        }
 
         let graphics_queue = renderer.graphics_queue.clone();
         let feature_plugins = renderer.feature_plugins.clone();
@@ -620,7 +962,7 @@ impl Renderer {
         let prepared_frame = RenderFrameJob {
             thread_pool,
             render_resources,
-            prepared_render_graph,
+            surfaces: job_surfaces,
             resource_context,
             frame_packets,
             render_registry,
@@ -628,6 +970,7 @@ impl Renderer {
             graphics_queue,
             feature_plugins,
             pipeline_plugin,
+            gpu_frame_timer: renderer.gpu_frame_timer.clone(),
             render_views,
         };
 
@@ -6,7 +6,7 @@ use crate::{RendererAssetPlugin, RendererThreadPool};
 use fnv::FnvHashSet;
 use rafx_api::{RafxApi, RafxQueueType, RafxResult};
 use rafx_assets::distill_impl::AssetResource;
-use rafx_assets::AssetManager;
+use rafx_assets::{AssetManager, TextureStreamingConfig};
 use rafx_framework::render_features::{ExtractResources, RenderRegistryBuilder};
 use rafx_framework::upload::UploadQueueConfig;
 use rafx_framework::RenderResources;
@@ -30,6 +30,7 @@ pub struct RendererBuilder {
     feature_plugins: Vec<Arc<dyn RenderFeaturePlugin>>,
     asset_plugins: Vec<Arc<dyn RendererAssetPlugin>>,
     allow_use_render_thread: bool,
+    texture_streaming_config: TextureStreamingConfig,
 }
 
 impl Default for RendererBuilder {
@@ -38,6 +39,7 @@ impl Default for RendererBuilder {
             feature_plugins: Default::default(),
             asset_plugins: Default::default(),
             allow_use_render_thread: true,
+            texture_streaming_config: Default::default(),
         }
     }
 }
@@ -67,6 +69,14 @@ impl RendererBuilder {
         self
     }
 
+    pub fn texture_streaming_config(
+        mut self,
+        texture_streaming_config: TextureStreamingConfig,
+    ) -> Self {
+        self.texture_streaming_config = texture_streaming_config;
+        self
+    }
+
     pub fn build(
         self,
         extract_resources: ExtractResources,
@@ -180,7 +190,9 @@ impl RendererBuilder {
                 max_concurrent_uploads: 2,
                 max_new_uploads_in_single_frame: 1,
                 max_bytes_per_upload: 64 * 1024 * 1024,
+                max_bytes_per_frame: 32 * 1024 * 1024,
             },
+            self.texture_streaming_config.clone(),
             &graphics_queue,
             &transfer_queue,
         )?;
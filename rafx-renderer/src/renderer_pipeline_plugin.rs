@@ -1,6 +1,6 @@
-use crate::RendererLoadContext;
+use crate::{RenderViewportTarget, RendererLoadContext};
 use rafx_api::extra::upload::RafxTransferUpload;
-use rafx_api::RafxResult;
+use rafx_api::{RafxExtents2D, RafxResult};
 use rafx_assets::distill_impl::AssetResource;
 use rafx_assets::AssetManager;
 use rafx_framework::graph::PreparedRenderGraph;
@@ -38,12 +38,43 @@ pub trait RendererPipelinePlugin: Send + Sync {
         Ok(())
     }
 
+    /// Called once per viewport each frame before the viewport's view is created. Returns the
+    /// extents the pipeline renders the viewport at internally, which become the view's extents.
+    /// A pipeline that renders at a different resolution must scale its output to the viewport's
+    /// rect in the graph. By default the view is the size of the viewport.
+    fn viewport_render_extents(
+        &self,
+        viewport: &RenderViewportTarget,
+        _extract_resources: &ExtractResources,
+        _render_resources: &RenderResources,
+    ) -> RafxExtents2D {
+        viewport.rect.extents()
+    }
+
+    /// Called once per viewport after `viewport_render_extents`. Returns the subpixel offset (in
+    /// NDC) to apply to the view's projection this frame. Pipelines that accumulate samples over
+    /// time (like TAA) return a different offset each frame.
+    fn viewport_jitter_amount(
+        &self,
+        _viewport: &RenderViewportTarget,
+        _render_extents: RafxExtents2D,
+        _frame_index: usize,
+        _extract_resources: &ExtractResources,
+        _render_resources: &RenderResources,
+    ) -> glam::Vec2 {
+        glam::Vec2::ZERO
+    }
+
+    /// Called once per viewport each frame. `main_view` is the viewport's view, and the graph
+    /// should draw it into the viewport's rect of `swapchain_image`.
+    #[allow(clippy::too_many_arguments)]
     fn generate_render_graph(
         &self,
         asset_manager: &AssetManager,
         swapchain_image: ResourceArc<ImageViewResource>,
         rotating_frame_index: usize,
         main_view: RenderView,
+        viewport: &RenderViewportTarget,
         extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph>;
@@ -0,0 +1,51 @@
+use fnv::FnvHashMap;
+use rafx_api::RafxSwapchainHelper;
+
+/// Swapchains for surfaces other than the main window, keyed by surface name. (The main window uses
+/// the `RafxSwapchainHelper` in the extract resources.) Swapchains should be created with
+/// `SwapchainHandler::create_surface_swapchain` and destroyed with
+/// `SwapchainHandler::destroy_surface_swapchain` so that the renderer is notified about them. What
+/// is drawn to each surface is configured in `ViewportsResource`.
+#[derive(Default)]
+pub struct SurfaceSwapchainsResource {
+    swapchain_helpers: FnvHashMap<String, RafxSwapchainHelper>,
+}
+
+impl SurfaceSwapchainsResource {
+    pub fn insert(
+        &mut self,
+        surface_name: String,
+        swapchain_helper: RafxSwapchainHelper,
+    ) -> Option<RafxSwapchainHelper> {
+        self.swapchain_helpers
+            .insert(surface_name, swapchain_helper)
+    }
+
+    pub fn remove(
+        &mut self,
+        surface_name: &str,
+    ) -> Option<RafxSwapchainHelper> {
+        self.swapchain_helpers.remove(surface_name)
+    }
+
+    pub fn get(
+        &self,
+        surface_name: &str,
+    ) -> Option<&RafxSwapchainHelper> {
+        self.swapchain_helpers.get(surface_name)
+    }
+
+    pub fn get_mut(
+        &mut self,
+        surface_name: &str,
+    ) -> Option<&mut RafxSwapchainHelper> {
+        self.swapchain_helpers.get_mut(surface_name)
+    }
+
+    /// Surface names, sorted so that surfaces are always drawn in the same order
+    pub fn surface_names(&self) -> Vec<String> {
+        let mut surface_names: Vec<_> = self.swapchain_helpers.keys().cloned().collect();
+        surface_names.sort();
+        surface_names
+    }
+}
@@ -1,5 +1,5 @@
 use super::swapchain_render_resource::SwapchainRenderResource;
-use super::Renderer;
+use super::{Renderer, MAIN_SURFACE_NAME};
 use rafx_api::raw_window_handle::HasRawWindowHandle;
 use rafx_api::{
     RafxDeviceContext, RafxExtents2D, RafxPresentableFrame, RafxResult, RafxSwapchain,
@@ -11,16 +11,38 @@ use rafx_framework::graph::SwapchainSurfaceInfo;
 pub struct SwapchainHandler<'a> {
     pub asset_manager: &'a mut AssetManager,
     pub renderer: &'a Renderer,
+    pub surface_name: &'a str,
 }
 
 impl<'a> SwapchainHandler<'a> {
-    #[profiling::function]
+    /// Create the swapchain for the main window
     pub fn create_swapchain(
         asset_manager: &mut AssetManager,
         renderer: &mut Renderer,
         window: &dyn HasRawWindowHandle,
         width: u32,
         height: u32,
+    ) -> RafxResult<RafxSwapchainHelper> {
+        Self::create_surface_swapchain(
+            asset_manager,
+            renderer,
+            MAIN_SURFACE_NAME,
+            window,
+            width,
+            height,
+        )
+    }
+
+    /// Create the swapchain for a named surface. Swapchains for surfaces other than the main window
+    /// should be placed in `SurfaceSwapchainsResource`
+    #[profiling::function]
+    pub fn create_surface_swapchain(
+        asset_manager: &mut AssetManager,
+        renderer: &mut Renderer,
+        surface_name: &str,
+        window: &dyn HasRawWindowHandle,
+        width: u32,
+        height: u32,
     ) -> RafxResult<RafxSwapchainHelper> {
         let swapchain_helper = {
             let device_context = asset_manager.device_context().clone();
@@ -40,6 +62,7 @@ impl<'a> SwapchainHandler<'a> {
             let mut lifetime_listener = SwapchainHandler {
                 asset_manager,
                 renderer,
+                surface_name,
             };
 
             RafxSwapchainHelper::new(&device_context, swapchain, Some(&mut lifetime_listener))?
@@ -48,17 +71,36 @@ impl<'a> SwapchainHandler<'a> {
         Ok(swapchain_helper)
     }
 
-    #[profiling::function]
     pub fn acquire_next_image(
         swapchain_helper: &mut RafxSwapchainHelper,
         asset_manager: &mut AssetManager,
         renderer: &Renderer,
         window_width: u32,
         window_height: u32,
+    ) -> RafxResult<RafxPresentableFrame> {
+        Self::acquire_next_surface_image(
+            swapchain_helper,
+            asset_manager,
+            renderer,
+            MAIN_SURFACE_NAME,
+            window_width,
+            window_height,
+        )
+    }
+
+    #[profiling::function]
+    pub fn acquire_next_surface_image(
+        swapchain_helper: &mut RafxSwapchainHelper,
+        asset_manager: &mut AssetManager,
+        renderer: &Renderer,
+        surface_name: &str,
+        window_width: u32,
+        window_height: u32,
     ) -> RafxResult<RafxPresentableFrame> {
         let mut lifetime_listener = SwapchainHandler {
             asset_manager,
             renderer,
+            surface_name,
         };
 
         swapchain_helper.acquire_next_image(
@@ -68,15 +110,30 @@ impl<'a> SwapchainHandler<'a> {
         )
     }
 
-    #[profiling::function]
     pub fn destroy_swapchain(
+        swapchain_helper: RafxSwapchainHelper,
+        asset_manager: &mut AssetManager,
+        renderer: &Renderer,
+    ) -> RafxResult<()> {
+        Self::destroy_surface_swapchain(
+            swapchain_helper,
+            asset_manager,
+            renderer,
+            MAIN_SURFACE_NAME,
+        )
+    }
+
+    #[profiling::function]
+    pub fn destroy_surface_swapchain(
         mut swapchain_helper: RafxSwapchainHelper,
         asset_manager: &mut AssetManager,
         renderer: &Renderer,
+        surface_name: &str,
     ) -> RafxResult<()> {
         let mut lifetime_listener = SwapchainHandler {
             asset_manager,
             renderer,
+            surface_name,
         };
 
         swapchain_helper.destroy(Some(&mut lifetime_listener))?;
@@ -95,7 +152,10 @@ impl<'a> RafxSwapchainEventListener for SwapchainHandler<'a> {
         //
         // Metadata about the swapchain
         //
-        log::debug!("renderer swapchain_created called");
+        log::debug!(
+            "renderer swapchain_created called for surface {}",
+            self.surface_name
+        );
 
         let swapchain_def = swapchain.swapchain_def();
         let extents = RafxExtents2D {
@@ -117,7 +177,11 @@ impl<'a> RafxSwapchainEventListener for SwapchainHandler<'a> {
             .renderer
             .render_resources
             .fetch_mut::<SwapchainRenderResource>();
-        swapchain_render_resource.set_swapchain_info(device_context, swapchain_surface_info)?;
+        swapchain_render_resource.set_surface_swapchain_info(
+            self.surface_name,
+            device_context,
+            swapchain_surface_info,
+        )?;
 
         log::debug!("renderer swapchain_created finished");
 
@@ -130,13 +194,16 @@ impl<'a> RafxSwapchainEventListener for SwapchainHandler<'a> {
         _device_context: &RafxDeviceContext,
         _swapchain: &RafxSwapchain,
     ) -> RafxResult<()> {
-        log::debug!("renderer swapchain destroyed");
+        log::debug!(
+            "renderer swapchain destroyed for surface {}",
+            self.surface_name
+        );
 
         let mut swapchain_render_resource = self
             .renderer
             .render_resources
             .fetch_mut::<SwapchainRenderResource>();
-        swapchain_render_resource.clear_swapchain_info();
+        swapchain_render_resource.clear_surface_swapchain_info(self.surface_name);
 
         //TODO: Explicitly remove the images instead of just dropping them. This prevents anything
         // from accidentally using them after they've been freed
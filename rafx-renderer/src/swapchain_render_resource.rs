@@ -1,10 +1,10 @@
+use crate::MAIN_SURFACE_NAME;
+use fnv::FnvHashMap;
 use rafx_api::{RafxDeviceContext, RafxFormat, RafxResourceType, RafxResult};
 use rafx_framework::graph::SwapchainSurfaceInfo;
 
 pub struct SwapchainRenderResourceSurfaceInfo {
     // The images presented by the swapchain
-    //TODO: We don't properly support multiple swapchains right now. This would ideally be a map
-    // of window/surface to info for the swapchain
     //pub swapchain_images: Vec<ResourceArc<ImageViewResource>>,
     pub swapchain_surface_info: SwapchainSurfaceInfo,
 
@@ -15,10 +15,11 @@ pub struct SwapchainRenderResourceSurfaceInfo {
 
 #[derive(Default)]
 pub struct SwapchainRenderResource {
-    // Contents are none if a swapchain does not exist. We allow this state so that we can insert this
-    // resource into the render resources map on init while we still have mut access to it, and not
-    // require adding/removing it when we create/destroy the swapchain
-    surface_info: Option<SwapchainRenderResourceSurfaceInfo>,
+    // Keyed by surface name. A surface has no entry if its swapchain does not exist. We allow this
+    // state so that we can insert this resource into the render resources map on init while we
+    // still have mut access to it, and not require adding/removing it when we create/destroy the
+    // swapchain
+    surface_infos: FnvHashMap<String, SwapchainRenderResourceSurfaceInfo>,
     pub max_color_component_value: f32,
 }
 
@@ -28,7 +29,16 @@ impl SwapchainRenderResource {
         device_context: &RafxDeviceContext,
         swapchain_surface_info: SwapchainSurfaceInfo,
     ) -> RafxResult<()> {
-        log::debug!("creating swapchain resources");
+        self.set_surface_swapchain_info(MAIN_SURFACE_NAME, device_context, swapchain_surface_info)
+    }
+
+    pub fn set_surface_swapchain_info(
+        &mut self,
+        surface_name: &str,
+        device_context: &RafxDeviceContext,
+        swapchain_surface_info: SwapchainSurfaceInfo,
+    ) -> RafxResult<()> {
+        log::debug!("creating swapchain resources for surface {}", surface_name);
 
         // Use swapchain format for SDR color
         let default_color_format_sdr = swapchain_surface_info.format;
@@ -47,18 +57,28 @@ impl SwapchainRenderResource {
             )
             .ok_or_else(|| "Could not find a supported depth format")?;
 
-        self.surface_info = Some(SwapchainRenderResourceSurfaceInfo {
-            swapchain_surface_info,
-            default_color_format_hdr,
-            default_color_format_sdr,
-            default_depth_format,
-        });
+        self.surface_infos.insert(
+            surface_name.to_string(),
+            SwapchainRenderResourceSurfaceInfo {
+                swapchain_surface_info,
+                default_color_format_hdr,
+                default_color_format_sdr,
+                default_depth_format,
+            },
+        );
 
         Ok(())
     }
 
     pub fn clear_swapchain_info(&mut self) {
-        self.surface_info = None;
+        self.clear_surface_swapchain_info(MAIN_SURFACE_NAME);
+    }
+
+    pub fn clear_surface_swapchain_info(
+        &mut self,
+        surface_name: &str,
+    ) {
+        self.surface_infos.remove(surface_name);
     }
 
     /// Set the max drawable color component value. On SDR displays, this should be 1. On HDR
@@ -72,7 +92,15 @@ impl SwapchainRenderResource {
         self.max_color_component_value = max_value;
     }
 
+    /// Info for the main window's swapchain
     pub fn surface_info(&self) -> Option<&SwapchainRenderResourceSurfaceInfo> {
-        self.surface_info.as_ref()
+        self.surface_info_for_surface(MAIN_SURFACE_NAME)
+    }
+
+    pub fn surface_info_for_surface(
+        &self,
+        surface_name: &str,
+    ) -> Option<&SwapchainRenderResourceSurfaceInfo> {
+        self.surface_infos.get(surface_name)
     }
 }
@@ -5,6 +5,7 @@ pub struct TimeRenderResource {
     previous_update_time: Duration,
     previous_update_dt: f32,
     update_count: u64,
+    previous_gpu_frame_time: Option<Duration>,
 }
 
 impl TimeRenderResource {
@@ -28,4 +29,17 @@ impl TimeRenderResource {
     pub fn update_count(&self) -> u64 {
         self.update_count
     }
+
+    // How long the GPU took for the most recent frame that it has finished. None if it can't be
+    // measured (when the renderer doesn't use a render thread) or no frame has finished yet.
+    pub fn previous_gpu_frame_time(&self) -> Option<Duration> {
+        self.previous_gpu_frame_time
+    }
+
+    pub fn set_previous_gpu_frame_time(
+        &mut self,
+        previous_gpu_frame_time: Option<Duration>,
+    ) {
+        self.previous_gpu_frame_time = previous_gpu_frame_time;
+    }
 }
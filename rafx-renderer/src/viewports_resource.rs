@@ -1,13 +1,14 @@
-use glam::{Mat4, Vec3};
-use rafx_api::RafxExtents2D;
+use fnv::FnvHashMap;
+use glam::{Mat4, Vec2, Vec3};
+use rafx_api::{RafxExtents2D, RafxResourceState};
+use rafx_framework::graph::SwapchainSurfaceInfo;
 use rafx_framework::render_features::{
     RenderFeatureFlagMask, RenderFeatureMask, RenderPhaseMask, RenderViewDepthRange,
 };
 use rafx_framework::visibility::ViewFrustumArc;
 
-// Very bare-bones for now, in the future this could support multiple windows, multiple viewports
-// per window, and some method for configuring the graph that's being drawn (maybe the graph
-// is provided some metadata like a string)
+/// The surface presented by the `RafxSwapchainHelper` in the extract resources (the main window)
+pub const MAIN_SURFACE_NAME: &str = "main";
 
 #[derive(Clone)]
 pub struct RenderViewMeta {
@@ -22,8 +23,146 @@ pub struct RenderViewMeta {
     pub debug_name: String,
 }
 
+/// A rectangle within a surface, in pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderViewportRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RenderViewportRect {
+    pub fn full_surface(extents: RafxExtents2D) -> Self {
+        RenderViewportRect {
+            x: 0,
+            y: 0,
+            width: extents.width,
+            height: extents.height,
+        }
+    }
+
+    pub fn extents(&self) -> RafxExtents2D {
+        RafxExtents2D {
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// A view that is drawn to part (or all) of a surface. Each viewport gets its own render graph.
+#[derive(Clone)]
+pub struct RenderViewport {
+    pub name: String,
+    pub rect: RenderViewportRect,
+    pub view_meta: RenderViewMeta,
+}
+
+/// The viewports drawn to a surface, in the order they are drawn
+#[derive(Clone, Default)]
+pub struct RenderSurfaceViewports {
+    pub size: RafxExtents2D,
+    pub viewports: Vec<RenderViewport>,
+}
+
+/// Describes what a render graph produced by `RendererPipelinePlugin::generate_render_graph` draws
+/// to. The graph should render at the size of the viewport (or the size returned by
+/// `RendererPipelinePlugin::viewport_render_extents`) and write the result into `rect` of the
+/// target image. (This is a swapchain image, or an image passed to `Renderer::render_offscreen`.)
+#[derive(Clone)]
+pub struct RenderViewportTarget {
+    pub surface_name: String,
+    pub viewport_name: String,
+    // Viewports are drawn in order. Anything a viewport draws outside its rect may be overwritten by
+    // later viewports, and it must not touch pixels outside its rect that earlier viewports drew.
+    pub viewport_index: usize,
+    pub rect: RenderViewportRect,
+    pub surface_extents: RafxExtents2D,
+    // The surface's swapchain info, but with extents matching the viewport's rect. Images in the
+    // graph that match the surface size will be the size of the viewport.
+    pub surface_info: SwapchainSurfaceInfo,
+    // The state the target image is in before the graph executes, and must be left in. (PRESENT for
+    // swapchain images)
+    pub image_state: RafxResourceState,
+}
+
+impl RenderViewportTarget {
+    /// True if the viewport covers the whole surface and can write to the swapchain image directly
+    pub fn covers_surface(&self) -> bool {
+        self.rect == RenderViewportRect::full_surface(self.surface_extents)
+    }
+
+    /// The viewport's rect in UV space of the swapchain image, (min, max)
+    pub fn rect_uv(&self) -> (Vec2, Vec2) {
+        let surface_size = Vec2::new(
+            self.surface_extents.width as f32,
+            self.surface_extents.height as f32,
+        );
+        let min = Vec2::new(self.rect.x as f32, self.rect.y as f32) / surface_size;
+        let max = Vec2::new(
+            (self.rect.x + self.rect.width) as f32,
+            (self.rect.y + self.rect.height) as f32,
+        ) / surface_size;
+        (min, max)
+    }
+
+    /// A name that uniquely identifies this viewport across all surfaces. Useful for keeping
+    /// per-viewport state like temporal history between frames.
+    pub fn unique_name(&self) -> String {
+        format!("{}/{}", self.surface_name, self.viewport_name)
+    }
+}
+
+/// Describes what the renderer should draw. Simple applications can set `main_window_size` and
+/// `main_view_meta` to draw a single view to the entire main window. Split-screen and additional
+/// windows are configured by adding entries to `surfaces`, keyed by surface name. (Surfaces other
+/// than the main window also need a swapchain in `SurfaceSwapchainsResource`.) If an entry for
+/// `MAIN_SURFACE_NAME` exists, it is used instead of `main_window_size` and `main_view_meta`.
 #[derive(Default)]
 pub struct ViewportsResource {
     pub main_window_size: RafxExtents2D,
     pub main_view_meta: Option<RenderViewMeta>,
+    pub surfaces: FnvHashMap<String, RenderSurfaceViewports>,
+}
+
+impl ViewportsResource {
+    /// Returns the size and viewports to draw for the given surface, or None if the surface has
+    /// nothing to draw
+    pub fn surface_viewports(
+        &self,
+        surface_name: &str,
+    ) -> Option<RenderSurfaceViewports> {
+        if let Some(surface) = self.surfaces.get(surface_name) {
+            return Some(surface.clone());
+        }
+
+        if surface_name == MAIN_SURFACE_NAME {
+            if let Some(view_meta) = &self.main_view_meta {
+                return Some(RenderSurfaceViewports {
+                    size: self.main_window_size,
+                    viewports: vec![RenderViewport {
+                        name: MAIN_SURFACE_NAME.to_string(),
+                        rect: RenderViewportRect::full_surface(self.main_window_size),
+                        view_meta: view_meta.clone(),
+                    }],
+                });
+            }
+        }
+
+        None
+    }
+
+    /// The size of the given surface, used when acquiring swapchain images
+    pub fn surface_size(
+        &self,
+        surface_name: &str,
+    ) -> Option<RafxExtents2D> {
+        if let Some(surface) = self.surfaces.get(surface_name) {
+            Some(surface.size)
+        } else if surface_name == MAIN_SURFACE_NAME {
+            Some(self.main_window_size)
+        } else {
+            None
+        }
+    }
 }
@@ -2,8 +2,9 @@ use crate::parse_declarations::{
     BindingType, ParseDeclarationsResult, ParsedBindingWithAnnotations,
 };
 use crate::shader_types::*;
+use crate::variants::ShaderVariantDeclaration;
 use fnv::{FnvHashMap, FnvHashSet};
-use rafx_api::RafxResourceType;
+use rafx_api::{RafxFormat, RafxResourceType, RafxShaderStageFlags};
 use rafx_framework::reflected_shader::ReflectedEntryPoint;
 use std::collections::BTreeMap;
 
@@ -43,6 +44,7 @@ pub(crate) fn generate_rust_code(
     parsed_declarations: &ParseDeclarationsResult,
     shader_module: &spirv_reflect::ShaderModule,
     reflected_entry_point: &ReflectedEntryPoint,
+    variants: Option<&[ShaderVariantDeclaration]>,
     for_rafx_framework_crate: bool,
 ) -> Result<String, String> {
     //
@@ -109,6 +111,7 @@ pub(crate) fn generate_rust_code(
         &builtin_types,
         &user_types,
         reflected_entry_point,
+        variants,
         for_rafx_framework_crate,
     )
 }
@@ -118,6 +121,7 @@ fn generate_rust_file(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
     reflected_entry_point: &ReflectedEntryPoint,
+    variants: Option<&[ShaderVariantDeclaration]>,
     for_rafx_framework_crate: bool,
 ) -> Result<String, String> {
     let mut rust_code = Vec::<String>::default();
@@ -136,7 +140,18 @@ fn generate_rust_file(
         reflected_entry_point,
     )?;
 
-    rust_tests(&mut rust_code, &structs);
+    let vertex_input_tests = rust_vertex_inputs(
+        &mut rust_code,
+        &parsed_declarations,
+        reflected_entry_point,
+        for_rafx_framework_crate,
+    )?;
+
+    if let Some(variants) = variants {
+        rust_variant_key(&mut rust_code, variants);
+    }
+
+    rust_tests(&mut rust_code, &structs, vertex_input_tests.as_deref());
 
     let mut rust_code_str = String::default();
     for s in rust_code {
@@ -689,19 +704,359 @@ fn rust_binding_constants(
     rust_code.push("\n".to_string());
 }
 
+fn rust_variant_key(
+    rust_code: &mut Vec<String>,
+    variants: &[ShaderVariantDeclaration],
+) {
+    use heck::CamelCase;
+
+    let variant_idents: Vec<_> = variants.iter().map(|x| x.name.to_camel_case()).collect();
+
+    rust_code.push("#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]\n".to_string());
+    rust_code.push("pub enum ShaderVariant {\n".to_string());
+    for ident in &variant_idents {
+        rust_code.push(format!("    {},\n", ident));
+    }
+    rust_code.push("}\n\n".to_string());
+
+    rust_code.push("impl Default for ShaderVariant {\n".to_string());
+    rust_code.push("    fn default() -> Self {\n".to_string());
+    rust_code.push(format!("        ShaderVariant::{}\n", variant_idents[0]));
+    rust_code.push("    }\n".to_string());
+    rust_code.push("}\n\n".to_string());
+
+    rust_code.push("impl ShaderVariant {\n".to_string());
+    let all_variants: Vec<_> = variant_idents
+        .iter()
+        .map(|x| format!("ShaderVariant::{}", x))
+        .collect();
+    rust_code.push(format!(
+        "    pub const ALL: [ShaderVariant; {}] = [{}];\n\n",
+        variants.len(),
+        all_variants.join(", ")
+    ));
+
+    // The name is what ShaderAsset::variant() and material passes use to select a permutation
+    rust_code.push("    pub fn name(self) -> &'static str {\n".to_string());
+    rust_code.push("        match self {\n".to_string());
+    for (variant, ident) in variants.iter().zip(&variant_idents) {
+        rust_code.push(format!(
+            "            ShaderVariant::{} => \"{}\",\n",
+            ident, variant.name
+        ));
+    }
+    rust_code.push("        }\n".to_string());
+    rust_code.push("    }\n\n".to_string());
+
+    rust_code.push("    pub fn defines(self) -> &'static [&'static str] {\n".to_string());
+    rust_code.push("        match self {\n".to_string());
+    for (variant, ident) in variants.iter().zip(&variant_idents) {
+        let defines: Vec<_> = variant.defines.iter().map(|x| format!("{:?}", x)).collect();
+        rust_code.push(format!(
+            "            ShaderVariant::{} => &[{}],\n",
+            ident,
+            defines.join(", ")
+        ));
+    }
+    rust_code.push("        }\n".to_string());
+    rust_code.push("    }\n".to_string());
+    rust_code.push("}\n\n".to_string());
+}
+
 fn rust_tests(
     rust_code: &mut Vec<String>,
     structs: &[GenerateStructResult],
+    vertex_input_tests: Option<&str>,
 ) {
-    if !structs.is_empty() {
+    if !structs.is_empty() || vertex_input_tests.is_some() {
         rust_code.push("#[cfg(test)]\nmod test {\n    use super::*;\n".to_string());
         for s in structs {
             rust_code.push(generate_struct_test_code(&s));
         }
+        if let Some(vertex_input_tests) = vertex_input_tests {
+            rust_code.push(vertex_input_tests.to_string());
+        }
         rust_code.push("}\n".to_string());
     }
 }
 
+// A vertex input as it appears in the generated struct. mat4 inputs occupy 4 locations and are
+// split into 4 members in the vertex layout (i.e. MODELMATRIX0 through MODELMATRIX3)
+#[derive(Debug)]
+struct VertexInputMember {
+    name: String,
+    semantic: String,
+    location: usize,
+    rust_type: String,
+    format: RafxFormat,
+    size: usize,
+    column_count: Option<usize>,
+}
+
+impl VertexInputMember {
+    fn new(binding: &ParsedBindingWithAnnotations) -> Result<Option<Self>, String> {
+        let name = binding.parsed.instance_name.clone();
+        let location = binding.parsed.layout_parts.location.ok_or_else(|| {
+            format!(
+                "Vertex input '{}' has no location, it is required to generate a vertex layout",
+                name
+            )
+        })?;
+
+        // Vertex layouts can't be generated without semantics. reflect_data() will already have
+        // failed if they were required
+        let semantic = match &binding.annotations.semantic {
+            Some(semantic) => semantic.0.clone(),
+            None => return Ok(None),
+        };
+
+        if !binding.parsed.array_sizes.is_empty() {
+            Err(format!(
+                "Vertex input '{}' is an array, generating a vertex layout for it is not supported",
+                name
+            ))?;
+        }
+
+        // The format is the most direct match for the GLSL type. Vertex data in a different format
+        // (like normalized bytes) needs a hand-written layout
+        let (format, location_count) = vertex_input_format(&binding.parsed.type_name)
+            .ok_or_else(|| {
+                format!(
+                    "Vertex input '{}' has type {}, generating a vertex layout for it is not supported",
+                    name, binding.parsed.type_name
+                )
+            })?;
+
+        let scalar_type = if format.is_sint() {
+            "i32"
+        } else if format.is_uint() {
+            "u32"
+        } else {
+            "f32"
+        };
+
+        let channel_count = format.channel_count() as usize;
+        let column_type = if channel_count > 1 {
+            format!("[{}; {}]", scalar_type, channel_count)
+        } else {
+            scalar_type.to_string()
+        };
+
+        let (rust_type, column_count) = if location_count > 1 {
+            (
+                format!("[{}; {}]", column_type, location_count),
+                Some(location_count as usize),
+            )
+        } else {
+            (column_type, None)
+        };
+
+        let size = 4 * channel_count * column_count.unwrap_or(1);
+
+        Ok(Some(VertexInputMember {
+            name,
+            semantic,
+            location,
+            rust_type,
+            format,
+            size,
+            column_count,
+        }))
+    }
+
+    // The semantic and byte offset (relative to the member) of each entry in the vertex layout
+    fn layout_entries(&self) -> Vec<(String, String, usize)> {
+        match self.column_count {
+            Some(column_count) => (0..column_count)
+                .map(|i| {
+                    (
+                        format!("&vertex.{}[{}]", self.name, i),
+                        format!("{}{}", self.semantic, i),
+                        i * self.size / column_count,
+                    )
+                })
+                .collect(),
+            None => vec![(format!("&vertex.{}", self.name), self.semantic.clone(), 0)],
+        }
+    }
+}
+
+//
+// Generates a #[repr(C)] struct for the inputs of a vertex shader (and a second one for inputs
+// annotated with @[per_instance]), plus functions that build the matching VertexDataLayout and
+// VertexDataSetLayout. Returns test code that checks the layouts against the reflection data.
+//
+fn rust_vertex_inputs(
+    rust_code: &mut Vec<String>,
+    parsed_declarations: &ParseDeclarationsResult,
+    reflected_entry_point: &ReflectedEntryPoint,
+    for_rafx_framework_crate: bool,
+) -> Result<Option<String>, String> {
+    if reflected_entry_point.rafx_api_reflection.shader_stage != RafxShaderStageFlags::VERTEX {
+        return Ok(None);
+    }
+
+    let mut per_vertex = Vec::default();
+    let mut per_instance = Vec::default();
+    for binding in &parsed_declarations.bindings {
+        if binding.parsed.binding_type != BindingType::In {
+            continue;
+        }
+
+        let member = match VertexInputMember::new(binding)? {
+            Some(member) => member,
+            None => return Ok(None),
+        };
+
+        if binding.annotations.per_instance.is_some() {
+            per_instance.push(member);
+        } else {
+            per_vertex.push(member);
+        }
+    }
+
+    if per_vertex.is_empty() && per_instance.is_empty() {
+        return Ok(None);
+    }
+
+    per_vertex.sort_by_key(|x| x.location);
+    per_instance.sort_by_key(|x| x.location);
+
+    // Make sure the generated layout provides every input in the reflection data that materials are
+    // built from. (Inputs the shader doesn't use may be missing from reflection, that's fine.)
+    let generated_semantics: Vec<_> = per_vertex
+        .iter()
+        .chain(&per_instance)
+        .flat_map(|x| {
+            x.layout_entries()
+                .into_iter()
+                .map(|(_, semantic, _)| semantic)
+        })
+        .collect();
+    for reflected_input in &reflected_entry_point.vertex_inputs {
+        if !generated_semantics.contains(&reflected_input.semantic) {
+            Err(format!(
+                "Reflected vertex input '{}' with semantic {} is not in the generated vertex layout {:?}",
+                reflected_input.name, reflected_input.semantic, generated_semantics
+            ))?;
+        }
+    }
+
+    if for_rafx_framework_crate {
+        rust_code.push("#[allow(unused_imports)]\n".to_string());
+        rust_code.push(
+            "use rafx_api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};\n\n"
+                .to_string(),
+        );
+        rust_code.push("#[allow(unused_imports)]\n".to_string());
+        rust_code.push("use crate::{VertexDataLayout, VertexDataSetLayout};\n\n".to_string());
+    } else {
+        rust_code.push("#[allow(unused_imports)]\n".to_string());
+        rust_code.push(
+            "use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};\n\n"
+                .to_string(),
+        );
+        rust_code.push("#[allow(unused_imports)]\n".to_string());
+        rust_code
+            .push("use rafx::framework::{VertexDataLayout, VertexDataSetLayout};\n\n".to_string());
+    }
+
+    let mut buffers = Vec::default();
+    if !per_vertex.is_empty() {
+        buffers.push(("VertexInput", "Vertex", &per_vertex));
+    }
+    if !per_instance.is_empty() {
+        buffers.push(("InstanceInput", "Instance", &per_instance));
+    }
+
+    let mut test_code = String::default();
+    test_code += "\n    #[test]\n    fn test_vertex_data_set_layout() {\n";
+    test_code +=
+        "        let layout = vertex_data_set_layout(RafxPrimitiveTopology::TriangleList);\n";
+    test_code += &format!(
+        "        assert_eq!(layout.members().len(), {});\n",
+        generated_semantics.len()
+    );
+
+    for (buffer_index, (struct_name, rate, members)) in buffers.iter().enumerate() {
+        // Check that each buffer is bound at the expected index and rate. The per-instance data is
+        // only read correctly if it is in its own binding with RafxVertexAttributeRate::Instance
+        test_code += &format!(
+            "        let binding = layout.binding({}).unwrap();\n",
+            buffer_index
+        );
+        test_code += &format!(
+            "        assert_eq!(binding.vertex_rate(), RafxVertexAttributeRate::{});\n",
+            rate
+        );
+
+        let mut offset = 0;
+        rust_code.push(
+            "#[derive(Copy, Clone, Debug, Default)]\n#[repr(C)]\npub struct ".to_string()
+                + struct_name
+                + " {\n",
+        );
+        for member in members.iter() {
+            rust_code.push(format_member(
+                &member.name,
+                &member.rust_type,
+                offset,
+                member.size,
+            ));
+
+            for (_, semantic, _) in member.layout_entries() {
+                test_code += &format!(
+                    "        let member = layout.member({:?}).unwrap();\n",
+                    semantic
+                );
+                test_code += &format!(
+                    "        assert_eq!(member.format, RafxFormat::{:?});\n",
+                    member.format
+                );
+                test_code += &format!("        assert_eq!(member.binding, {});\n", buffer_index);
+            }
+
+            offset += member.size;
+        }
+        rust_code.push(format!("}} // {} bytes\n\n", offset));
+
+        test_code += &format!("        assert_eq!(binding.vertex_stride(), {});\n", offset);
+
+        rust_code.push(format!("impl {} {{\n", struct_name));
+        rust_code.push("    pub fn vertex_data_layout() -> VertexDataLayout {\n".to_string());
+        rust_code.push(format!(
+            "        VertexDataLayout::build_vertex_layout(&{}::default(), RafxVertexAttributeRate::{}, |builder, vertex| {{\n",
+            struct_name, rate
+        ));
+        for member in members.iter() {
+            for (member_ref, semantic, _) in member.layout_entries() {
+                rust_code.push(format!(
+                    "            builder.add_member({}, {:?}, RafxFormat::{:?});\n",
+                    member_ref, semantic, member.format
+                ));
+            }
+        }
+        rust_code.push("        })\n".to_string());
+        rust_code.push("    }\n".to_string());
+        rust_code.push("}\n\n".to_string());
+    }
+
+    test_code += "    }\n";
+
+    let layouts: Vec<_> = buffers
+        .iter()
+        .map(|(struct_name, _, _)| format!("{}::vertex_data_layout()", struct_name))
+        .collect();
+    rust_code.push("pub fn vertex_data_set_layout(primitive_topology: RafxPrimitiveTopology) -> VertexDataSetLayout {\n".to_string());
+    rust_code.push(format!(
+        "    VertexDataSetLayout::new(vec![{}], primitive_topology)\n",
+        layouts.join(", ")
+    ));
+    rust_code.push("}\n\n".to_string());
+
+    Ok(Some(test_code))
+}
+
 fn get_rust_type_name(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
@@ -1,8 +1,8 @@
 use rafx_api::{
     RafxShaderPackage, RafxShaderPackageGles2, RafxShaderPackageGles3, RafxShaderPackageMetal,
-    RafxShaderPackageVulkan,
+    RafxShaderPackageVulkan, RafxShaderPackageWgsl,
 };
-use rafx_framework::CookedShaderPackage;
+use rafx_framework::{CookedShaderPackage, CookedShaderVariantSet};
 use rafx_framework::{ReflectedEntryPoint, ShaderModuleHash};
 
 pub(crate) fn cook_shader(
@@ -11,7 +11,8 @@ pub(crate) fn cook_shader(
     metal_source: Option<String>,
     gles2_source: Option<String>,
     gles3_source: Option<String>,
-) -> Result<Vec<u8>, String> {
+    wgsl_source: Option<String>,
+) -> CookedShaderPackage {
     let shader_package = RafxShaderPackage {
         vk: vk_spv.map(|x| RafxShaderPackageVulkan::SpvBytes(x.to_vec())),
 
@@ -23,14 +24,26 @@ pub(crate) fn cook_shader(
 
         gles2: gles2_source.map(|x| RafxShaderPackageGles2::Src(x)),
         gles3: gles3_source.map(|x| RafxShaderPackageGles3::Src(x)),
+        wgsl: wgsl_source.map(|x| RafxShaderPackageWgsl::Src(x)),
     };
 
-    let cooked_shader = CookedShaderPackage {
+    CookedShaderPackage {
         entry_points: reflected_data.to_vec(),
         hash: ShaderModuleHash::new(&shader_package),
         shader_package,
-    };
+    }
+}
 
-    bincode::serialize(&cooked_shader)
+pub(crate) fn serialize_cooked_shader(
+    cooked_shader: &CookedShaderPackage
+) -> Result<Vec<u8>, String> {
+    bincode::serialize(cooked_shader)
         .map_err(|x| format!("Failed to serialize cooked shader: {}", x))
 }
+
+pub(crate) fn serialize_cooked_shader_variants(
+    variant_set: &CookedShaderVariantSet
+) -> Result<Vec<u8>, String> {
+    bincode::serialize(variant_set)
+        .map_err(|x| format!("Failed to serialize cooked shader variants: {}", x))
+}
@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Bump this if a change to the shader processor changes its output for the same inputs
+const CACHE_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ShaderCacheEntry {
+    // Hash of the source, everything it depends on, and the args that affect the output
+    pub(crate) hash: u64,
+    pub(crate) dependencies: Vec<PathBuf>,
+    pub(crate) outputs: Vec<PathBuf>,
+}
+
+// Tracks the last successful processing of each shader so that unchanged shaders can be skipped
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct ShaderCache {
+    version: u32,
+    entries: BTreeMap<PathBuf, ShaderCacheEntry>,
+}
+
+impl ShaderCache {
+    pub(crate) fn load(cache_file: Option<&PathBuf>) -> Self {
+        let empty = ShaderCache {
+            version: CACHE_VERSION,
+            entries: Default::default(),
+        };
+
+        let cache_file = match cache_file {
+            Some(cache_file) if cache_file.exists() => cache_file,
+            _ => return empty,
+        };
+
+        let cache = std::fs::read(cache_file)
+            .map_err(|x| x.to_string())
+            .and_then(|x| bincode::deserialize::<ShaderCache>(&x).map_err(|x| x.to_string()));
+
+        match cache {
+            Ok(cache) if cache.version == CACHE_VERSION => cache,
+            Ok(_) => {
+                log::info!(
+                    "Shader cache {:?} is from a different version, ignoring it",
+                    cache_file
+                );
+                empty
+            }
+            Err(e) => {
+                log::warn!(
+                    "Could not read shader cache {:?}, ignoring it: {}",
+                    cache_file,
+                    e
+                );
+                empty
+            }
+        }
+    }
+
+    pub(crate) fn save(
+        &self,
+        cache_file: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = cache_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(cache_file, bincode::serialize(self)?)?;
+        Ok(())
+    }
+
+    pub(crate) fn entry(
+        &self,
+        glsl_file: &Path,
+    ) -> Option<&ShaderCacheEntry> {
+        self.entries.get(glsl_file)
+    }
+
+    // Returns true if the shader was last processed with the same hash and all of its outputs
+    // still exist
+    pub(crate) fn is_up_to_date(
+        &self,
+        glsl_file: &Path,
+        hash: u64,
+    ) -> bool {
+        self.entries
+            .get(glsl_file)
+            .map(|entry| entry.hash == hash && entry.outputs.iter().all(|x| x.exists()))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        glsl_file: PathBuf,
+        entry: ShaderCacheEntry,
+    ) {
+        self.entries.insert(glsl_file, entry);
+    }
+
+    pub(crate) fn remove(
+        &mut self,
+        glsl_file: &Path,
+    ) {
+        self.entries.remove(glsl_file);
+    }
+
+    pub(crate) fn all_dependencies(&self) -> impl Iterator<Item = &PathBuf> {
+        self.entries.values().flat_map(|x| x.dependencies.iter())
+    }
+}
+
+// Finds every file that can affect the output for a shader: the source, all the files it includes,
+// and any files next to it that share its name (overrides like a.frag.metal, and a.frag.variants)
+pub(crate) fn collect_dependencies(glsl_file: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let parsed_source = crate::parse_source::parse_glsl(glsl_file)?;
+    let mut dependencies = parsed_source.included_files;
+
+    let file_name_prefix = format!(
+        "{}.",
+        glsl_file
+            .file_name()
+            .ok_or_else(|| format!("{:?} is not a file", glsl_file))?
+            .to_string_lossy()
+    );
+    let parent_dir = glsl_file.parent().unwrap_or_else(|| Path::new(""));
+    let read_dir_path = if parent_dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        parent_dir
+    };
+
+    for entry in std::fs::read_dir(read_dir_path)? {
+        let file_name = entry?.file_name();
+        if file_name.to_string_lossy().starts_with(&file_name_prefix) {
+            dependencies.push(parent_dir.join(file_name));
+        }
+    }
+
+    dependencies.sort();
+    dependencies.dedup();
+    Ok(dependencies)
+}
+
+// The hash changes if any dependency's path or contents change, or if the job description (output
+// paths and args that affect output) changes
+pub(crate) fn hash_shader_job(
+    dependencies: &[PathBuf],
+    job_description: &str,
+) -> Result<u64, Box<dyn Error>> {
+    let mut hasher = fnv::FnvHasher::default();
+    hasher.write_u32(CACHE_VERSION);
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write(job_description.as_bytes());
+
+    for dependency in dependencies {
+        hasher.write(dependency.to_string_lossy().as_bytes());
+        hasher.write(&std::fs::read(dependency)?);
+    }
+
+    Ok(hasher.finish())
+}
+
+// Writes a makefile-style depfile, one rule per shader listing its outputs and dependencies
+pub(crate) fn write_depfile(
+    depfile: &Path,
+    cache: &ShaderCache,
+    glsl_files: &[PathBuf],
+) -> Result<(), Box<dyn Error>> {
+    fn escape(path: &Path) -> String {
+        path.to_string_lossy()
+            .replace('\\', "/")
+            .replace(' ', "\\ ")
+            .replace('$', "$$")
+    }
+
+    let mut contents = String::default();
+    for glsl_file in glsl_files {
+        if let Some(entry) = cache.entry(glsl_file) {
+            if entry.outputs.is_empty() {
+                continue;
+            }
+
+            let outputs: Vec<_> = entry.outputs.iter().map(|x| escape(x)).collect();
+            let dependencies: Vec<_> = entry.dependencies.iter().map(|x| escape(x)).collect();
+            contents += &format!("{}: {}\n", outputs.join(" "), dependencies.join(" "));
+        }
+    }
+
+    if let Some(parent) = depfile.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(depfile, contents)?;
+    Ok(())
+}
+
+// A snapshot of modification times used by watch mode to detect changes
+#[derive(PartialEq, Default)]
+pub(crate) struct WatchSnapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl WatchSnapshot {
+    // Captures every file under the given directories (so new shaders are noticed) along with all
+    // known dependencies (includes may live outside the shader directory)
+    pub(crate) fn capture(
+        watched_dirs: &[PathBuf],
+        cache: &ShaderCache,
+    ) -> Self {
+        let mut snapshot = BTreeMap::default();
+
+        for watched_dir in watched_dirs {
+            let walker = globwalk::GlobWalkerBuilder::from_patterns(watched_dir, &["**/*"])
+                .file_type(globwalk::FileType::FILE)
+                .build();
+
+            if let Ok(walker) = walker {
+                for entry in walker.filter_map(|x| x.ok()) {
+                    let path = entry.path().to_path_buf();
+                    let modified = modified_time(&path);
+                    snapshot.insert(path, modified);
+                }
+            }
+        }
+
+        for dependency in cache.all_dependencies() {
+            snapshot
+                .entry(dependency.clone())
+                .or_insert_with(|| modified_time(dependency));
+        }
+
+        WatchSnapshot(snapshot)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|x| x.modified()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A scratch directory under the system temp dir, removed when dropped
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "rafx-shader-processor-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            TestDir(path)
+        }
+
+        fn write(
+            &self,
+            file_name: &str,
+            contents: &str,
+        ) -> PathBuf {
+            let path = self.0.join(file_name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn hash_file(
+        glsl_file: &Path,
+        job_description: &str,
+    ) -> u64 {
+        let dependencies = collect_dependencies(glsl_file).unwrap();
+        hash_shader_job(&dependencies, job_description).unwrap()
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let dir = TestDir::new("cache");
+        let shader = dir.write("a.frag", "#include \"a.glsl\"\nvoid main() {}\n");
+        let include = dir.write("a.glsl", "const float A = 1.0;\n");
+        let output = dir.write("a.frag.spv", "");
+
+        let hash = hash_file(&shader, "--spv");
+        let mut cache = ShaderCache::default();
+        cache.insert(
+            shader.clone(),
+            ShaderCacheEntry {
+                hash,
+                dependencies: collect_dependencies(&shader).unwrap(),
+                outputs: vec![output.clone()],
+            },
+        );
+
+        assert_eq!(hash_file(&shader, "--spv"), hash);
+        assert!(cache.is_up_to_date(&shader, hash_file(&shader, "--spv")));
+
+        // Changing the args that affect output is a miss
+        assert!(!cache.is_up_to_date(&shader, hash_file(&shader, "--spv --metal")));
+
+        // Changing an include is a miss
+        std::fs::write(&include, "const float A = 2.0;\n").unwrap();
+        let include_changed_hash = hash_file(&shader, "--spv");
+        assert_ne!(include_changed_hash, hash);
+        assert!(!cache.is_up_to_date(&shader, include_changed_hash));
+
+        // Changing the source is a miss
+        std::fs::write(&shader, "#include \"a.glsl\"\nvoid main() { }\n").unwrap();
+        let source_changed_hash = hash_file(&shader, "--spv");
+        assert_ne!(source_changed_hash, include_changed_hash);
+        assert!(!cache.is_up_to_date(&shader, source_changed_hash));
+
+        // Adding an override next to the shader is a miss
+        dir.write("a.frag.metal", "");
+        assert_ne!(hash_file(&shader, "--spv"), source_changed_hash);
+
+        // A missing output is a miss even if the hash matches
+        cache.insert(
+            shader.clone(),
+            ShaderCacheEntry {
+                hash: source_changed_hash,
+                dependencies: vec![],
+                outputs: vec![output.clone()],
+            },
+        );
+        assert!(cache.is_up_to_date(&shader, source_changed_hash));
+        std::fs::remove_file(&output).unwrap();
+        assert!(!cache.is_up_to_date(&shader, source_changed_hash));
+    }
+
+    #[test]
+    fn test_collect_dependencies_nested_includes() {
+        let dir = TestDir::new("nested");
+        let shader = dir.write("a.frag", "#include \"b.glsl\"\n");
+        let b = dir.write("b.glsl", "#include \"c.glsl\"\n");
+        let c = dir.write("c.glsl", "const float C = 1.0;\n");
+        let variants = dir.write("a.frag.variants", "[(name: \"a\")]");
+        dir.write("a.vert", "");
+        dir.write("unrelated.glsl", "");
+
+        let mut expected = vec![shader.clone(), b, c, variants];
+        expected.sort();
+        assert_eq!(collect_dependencies(&shader).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_collect_dependencies_cyclic_includes() {
+        let dir = TestDir::new("cyclic");
+        let shader = dir.write("a.frag", "#include \"b.glsl\"\n");
+        let b = dir.write("b.glsl", "#include \"c.glsl\"\n");
+        let c = dir.write("c.glsl", "#include \"b.glsl\"\n#include \"a.frag\"\n");
+
+        let mut expected = vec![shader.clone(), b, c];
+        expected.sort();
+        assert_eq!(collect_dependencies(&shader).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_depfile_escapes_paths() {
+        let dir = TestDir::new("depfile");
+        let mut cache = ShaderCache::default();
+        cache.insert(
+            PathBuf::from("shaders/my shader.frag"),
+            ShaderCacheEntry {
+                hash: 0,
+                dependencies: vec![
+                    PathBuf::from("shaders/my shader.frag"),
+                    PathBuf::from("shaders/$common.glsl"),
+                ],
+                outputs: vec![PathBuf::from("out dir/my shader.frag.spv")],
+            },
+        );
+        cache.insert(
+            PathBuf::from("shaders/no_outputs.frag"),
+            ShaderCacheEntry {
+                hash: 0,
+                dependencies: vec![PathBuf::from("shaders/no_outputs.frag")],
+                outputs: vec![],
+            },
+        );
+
+        let depfile = dir.0.join("shaders.d");
+        write_depfile(
+            &depfile,
+            &cache,
+            &[
+                PathBuf::from("shaders/my shader.frag"),
+                PathBuf::from("shaders/no_outputs.frag"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&depfile).unwrap(),
+            "out\\ dir/my\\ shader.frag.spv: shaders/my\\ shader.frag shaders/$$common.glsl\n"
+        );
+    }
+}
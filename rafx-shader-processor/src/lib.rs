@@ -13,6 +13,7 @@ use crate::reflect::ShaderProcessorRefectionData;
 use fnv::{FnvHashMap, FnvHashSet};
 use include::include_impl;
 use include::IncludeType;
+use rafx_framework::CookedShaderPackage;
 use shaderc::ShaderKind;
 use spirv_cross::glsl::Target;
 use spirv_cross::spirv::{Ast, ShaderResources};
@@ -25,6 +26,14 @@ mod reflect;
 
 mod shader_types;
 
+mod variants;
+use variants::ShaderVariantDeclaration;
+
+mod incremental;
+use incremental::{ShaderCache, ShaderCacheEntry, WatchSnapshot};
+
+mod wgsl;
+
 #[derive(Clone, Copy, Debug)]
 enum RsFileType {
     Lib,
@@ -54,6 +63,8 @@ pub struct ShaderProcessorArgs {
     pub gles2_generated_src_file: Option<PathBuf>,
     #[structopt(name = "gles3-generated-src-file", long, parse(from_os_str))]
     pub gles3_generated_src_file: Option<PathBuf>,
+    #[structopt(name = "wgsl-generated-src-file", long, parse(from_os_str))]
+    pub wgsl_generated_src_file: Option<PathBuf>,
     #[structopt(name = "cooked-shader-file", long, parse(from_os_str))]
     pub cooked_shader_file: Option<PathBuf>,
 
@@ -74,6 +85,8 @@ pub struct ShaderProcessorArgs {
     pub gles2_generated_src_path: Option<PathBuf>,
     #[structopt(name = "gles3-generated-src-path", long, parse(from_os_str))]
     pub gles3_generated_src_path: Option<PathBuf>,
+    #[structopt(name = "wgsl-generated-src-path", long, parse(from_os_str))]
+    pub wgsl_generated_src_path: Option<PathBuf>,
     #[structopt(name = "cooked-shaders-path", long, parse(from_os_str))]
     pub cooked_shaders_path: Option<PathBuf>,
 
@@ -94,11 +107,23 @@ pub struct ShaderProcessorArgs {
     pub package_gles2: bool,
     #[structopt(name = "package-gles3", long)]
     pub package_gles3: bool,
+    #[structopt(name = "package-wgsl", long)]
+    pub package_wgsl: bool,
     #[structopt(name = "package-all", long)]
     pub package_all: bool,
 
     #[structopt(name = "for-rafx-framework-crate", long)]
     pub for_rafx_framework_crate: bool,
+
+    //
+    // Incremental processing
+    //
+    #[structopt(name = "cache-file", long, parse(from_os_str))]
+    pub cache_file: Option<PathBuf>,
+    #[structopt(name = "depfile", long, parse(from_os_str))]
+    pub depfile: Option<PathBuf>,
+    #[structopt(name = "watch", long)]
+    pub watch: bool,
 }
 
 pub fn run(args: &ShaderProcessorArgs) -> Result<(), Box<dyn Error>> {
@@ -121,6 +146,68 @@ pub fn run(args: &ShaderProcessorArgs) -> Result<(), Box<dyn Error>> {
         None
     };
 
+    let mut cache = ShaderCache::load(args.cache_file.as_ref());
+
+    if !args.watch {
+        return run_once(args, &rs_file_option, &mut cache);
+    }
+
+    //
+    // Watch mode: poll the shader directory (or the directory of the single file) and every known
+    // dependency, and process again when anything changes. The cache skips shaders that were not
+    // affected by the change.
+    //
+    let watched_dirs = if let Some(glsl_files) = &args.glsl_files {
+        vec![glsl_files.clone()]
+    } else if let Some(glsl_file) = &args.glsl_file {
+        vec![glsl_file
+            .parent()
+            .filter(|x| !x.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()]
+    } else {
+        Err("--watch requires --glsl-file or --glsl-path")?
+    };
+
+    loop {
+        if let Err(e) = run_once(args, &rs_file_option, &mut cache) {
+            log::error!("{}", e);
+        }
+
+        log::info!("Watching for changes in {:?}", watched_dirs);
+        let snapshot = WatchSnapshot::capture(&watched_dirs, &cache);
+        while WatchSnapshot::capture(&watched_dirs, &cache) == snapshot {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+    }
+}
+
+fn run_once(
+    args: &ShaderProcessorArgs,
+    rs_file_option: &Option<RsFileOption>,
+    cache: &mut ShaderCache,
+) -> Result<(), Box<dyn Error>> {
+    let result = process_shaders(args, rs_file_option, cache);
+
+    // Save even if processing failed so that the shaders that succeeded are not processed again
+    if let Some(cache_file) = &args.cache_file {
+        cache.save(cache_file)?;
+    }
+
+    let glsl_files_processed = result?;
+    if let Some(depfile) = &args.depfile {
+        incremental::write_depfile(depfile, cache, &glsl_files_processed)?;
+    }
+
+    Ok(())
+}
+
+// Returns the shaders that were processed (or skipped because they were up to date)
+fn process_shaders(
+    args: &ShaderProcessorArgs,
+    rs_file_option: &Option<RsFileOption>,
+    cache: &mut ShaderCache,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     if let Some(glsl_file) = &args.glsl_file {
         //
         // Handle a single file given via --glsl_file. In this mode, the output files are explicit
@@ -137,25 +224,27 @@ pub fn run(args: &ShaderProcessorArgs) -> Result<(), Box<dyn Error>> {
         //
         // Process this shader and write to output files
         //
-        process_glsl_shader(
+        process_glsl_shader_incremental(
+            cache,
             glsl_file,
             args.spv_file.as_ref(),
             &rs_file_option,
             args.metal_generated_src_file.as_ref(),
             args.gles2_generated_src_file.as_ref(),
             args.gles3_generated_src_file.as_ref(),
+            args.wgsl_generated_src_file.as_ref(),
             args.cooked_shader_file.as_ref(),
             shader_kind,
             &args,
         )
         .map_err(|x| format!("{}: {}", glsl_file.to_string_lossy(), x.to_string()))?;
 
-        Ok(())
+        Ok(vec![glsl_file.clone()])
     } else if let Some(glsl_files) = &args.glsl_files {
         log::trace!("glsl files {:?}", args.glsl_files);
-        process_directory(glsl_files, &args, &rs_file_option)
+        process_directory(glsl_files, &args, &rs_file_option, cache)
     } else {
-        Ok(())
+        Ok(Vec::default())
     }
 }
 
@@ -167,15 +256,17 @@ fn process_directory(
     glsl_files: &PathBuf,
     args: &ShaderProcessorArgs,
     rs_file_option: &Option<RsFileOption>,
-) -> Result<(), Box<dyn Error>> {
+    cache: &mut ShaderCache,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     // This will accumulate rust module names so we can produce a lib.rs if needed
     let mut module_names = FnvHashMap::<PathBuf, FnvHashSet<String>>::default();
+    let mut glsl_files_processed = Vec::default();
 
     log::trace!("GLSL Root Dir: {:?}", glsl_files);
 
     let glob_walker = globwalk::GlobWalkerBuilder::from_patterns(
         glsl_files.to_str().unwrap(),
-        &["*.{vert,frag,comp}"],
+        &["*.{vert,frag,comp,geom,tesc,tese}"],
     )
     .file_type(globwalk::FileType::FILE)
     .build()?;
@@ -227,6 +318,12 @@ fn process_directory(
             .as_ref()
             .map(|x| x.join(outfile_prefix).join(gles3_src_name));
 
+        let wgsl_src_name = format!("{}.wgsl", file_name);
+        let wgsl_generated_src_path = args
+            .wgsl_generated_src_path
+            .as_ref()
+            .map(|x| x.join(outfile_prefix).join(wgsl_src_name));
+
         let cooked_shader_name = format!("{}.cookedshaderpackage", file_name);
         let cooked_shader_path = args
             .cooked_shaders_path
@@ -243,19 +340,23 @@ fn process_directory(
         //
         // Process this shader and write to output files
         //
-        process_glsl_shader(
+        process_glsl_shader_incremental(
+            cache,
             glsl_file.path(),
             spv_path.as_ref(),
             &rs_file_option,
             metal_generated_src_path.as_ref(),
             gles2_generated_src_path.as_ref(),
             gles3_generated_src_path.as_ref(),
+            wgsl_generated_src_path.as_ref(),
             cooked_shader_path.as_ref(),
             shader_kind,
             &args,
         )
         .map_err(|x| format!("{}: {}", glsl_file.path().to_string_lossy(), x.to_string()))?;
 
+        glsl_files_processed.push(glsl_file.path().to_path_buf());
+
         //
         // Add the module name to this list so we can generate a lib.rs later
         //
@@ -311,7 +412,24 @@ fn process_directory(
         }
     }
 
-    Ok(())
+    Ok(glsl_files_processed)
+}
+
+// A variant without its own override (a.frag.<variant>.metal) uses the shader's override
+// (a.frag.metal) if there is one
+fn try_load_variant_override_src(
+    original_path: &Path,
+    variant_name: Option<&str>,
+    extension: &str,
+) -> std::io::Result<Option<String>> {
+    if let Some(variant_name) = variant_name {
+        let variant_extension = format!(".{}.{}", variant_name, extension);
+        if let Some(src) = try_load_override_src(original_path, &variant_extension)? {
+            return Ok(Some(src));
+        }
+    }
+
+    try_load_override_src(original_path, &format!(".{}", extension))
 }
 
 fn try_load_override_src(
@@ -333,6 +451,77 @@ fn try_load_override_src(
     }
 }
 
+// Skips the shader if nothing it depends on has changed since it was last processed
+fn process_glsl_shader_incremental(
+    cache: &mut ShaderCache,
+    glsl_file: &Path,
+    spv_file: Option<&PathBuf>,
+    rs_file: &Option<RsFileOption>,
+    metal_generated_src_file: Option<&PathBuf>,
+    gles2_generated_src_file: Option<&PathBuf>,
+    gles3_generated_src_file: Option<&PathBuf>,
+    wgsl_generated_src_file: Option<&PathBuf>,
+    cooked_shader_file: Option<&PathBuf>,
+    shader_kind: shaderc::ShaderKind,
+    args: &ShaderProcessorArgs,
+) -> Result<(), Box<dyn Error>> {
+    // Everything other than the source files that affects the output
+    let job_description = format!(
+        "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {} {} {} {} {} {} {} {}",
+        spv_file,
+        rs_file,
+        metal_generated_src_file,
+        gles2_generated_src_file,
+        gles3_generated_src_file,
+        wgsl_generated_src_file,
+        cooked_shader_file,
+        shader_kind,
+        args.shader_kind,
+        args.optimize_shaders,
+        args.package_vk,
+        args.package_metal,
+        args.package_gles2,
+        args.package_gles3,
+        args.package_wgsl,
+        args.package_all,
+        args.for_rafx_framework_crate
+    );
+
+    let dependencies = incremental::collect_dependencies(glsl_file)?;
+    let hash = incremental::hash_shader_job(&dependencies, &job_description)?;
+    if cache.is_up_to_date(glsl_file, hash) {
+        log::info!("  Up to date, skipping");
+        return Ok(());
+    }
+
+    // Drop the entry first so that a failure forces the shader to be processed again next time
+    cache.remove(glsl_file);
+
+    let outputs = process_glsl_shader(
+        glsl_file,
+        spv_file,
+        rs_file,
+        metal_generated_src_file,
+        gles2_generated_src_file,
+        gles3_generated_src_file,
+        wgsl_generated_src_file,
+        cooked_shader_file,
+        shader_kind,
+        args,
+    )?;
+
+    cache.insert(
+        glsl_file.to_path_buf(),
+        ShaderCacheEntry {
+            hash,
+            dependencies,
+            outputs,
+        },
+    );
+
+    Ok(())
+}
+
 fn process_glsl_shader(
     glsl_file: &Path,
     spv_file: Option<&PathBuf>,
@@ -340,17 +529,232 @@ fn process_glsl_shader(
     metal_generated_src_file: Option<&PathBuf>,
     gles2_generated_src_file: Option<&PathBuf>,
     gles3_generated_src_file: Option<&PathBuf>,
+    wgsl_generated_src_file: Option<&PathBuf>,
     cooked_shader_file: Option<&PathBuf>,
     shader_kind: shaderc::ShaderKind,
     args: &ShaderProcessorArgs,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut written_files = Vec::default();
+
+    let variants = match variants::load_variant_declarations(glsl_file)? {
+        Some(variants) => variants,
+        None => {
+            let compiled_shader = compile_glsl_shader(
+                glsl_file,
+                None,
+                spv_file,
+                rs_file,
+                metal_generated_src_file,
+                gles2_generated_src_file,
+                gles3_generated_src_file,
+                wgsl_generated_src_file,
+                cooked_shader_file,
+                shader_kind,
+                args,
+            )?;
+
+            write_compiled_shader(
+                compiled_shader,
+                spv_file,
+                rs_file,
+                metal_generated_src_file,
+                gles2_generated_src_file,
+                gles3_generated_src_file,
+                wgsl_generated_src_file,
+                cooked_shader_file,
+                &mut written_files,
+            )?;
+
+            return Ok(written_files);
+        }
+    };
+
+    //
+    // Each variant writes its own spv/metal/gles files. The rust code is generated from the first
+    // (default) variant and the cooked packages for all variants are bundled into a single file
+    //
+    let mut cooked_variants = Vec::with_capacity(variants.len());
+    let no_rs_file = None;
+    for (variant_index, variant) in variants.iter().enumerate() {
+        log::info!(
+            "  Variant {:?} defines: {:?}",
+            variant.name,
+            variant.defines
+        );
+
+        let variant_rs_file = if variant_index == 0 {
+            rs_file
+        } else {
+            &no_rs_file
+        };
+        let variant_spv_file = spv_file.map(|x| variants::variant_output_path(x, &variant.name));
+        let variant_metal_generated_src_file =
+            metal_generated_src_file.map(|x| variants::variant_output_path(x, &variant.name));
+        let variant_gles2_generated_src_file =
+            gles2_generated_src_file.map(|x| variants::variant_output_path(x, &variant.name));
+        let variant_gles3_generated_src_file =
+            gles3_generated_src_file.map(|x| variants::variant_output_path(x, &variant.name));
+        let variant_wgsl_generated_src_file =
+            wgsl_generated_src_file.map(|x| variants::variant_output_path(x, &variant.name));
+
+        let mut compiled_shader = compile_glsl_shader(
+            glsl_file,
+            Some((variant, &variants)),
+            variant_spv_file.as_ref(),
+            variant_rs_file,
+            variant_metal_generated_src_file.as_ref(),
+            variant_gles2_generated_src_file.as_ref(),
+            variant_gles3_generated_src_file.as_ref(),
+            variant_wgsl_generated_src_file.as_ref(),
+            cooked_shader_file,
+            shader_kind,
+            args,
+        )
+        .map_err(|x| format!("variant {}: {}", variant.name, x.to_string()))?;
+
+        if let Some(cooked_shader) = compiled_shader.cooked_shader.take() {
+            cooked_variants.push(rafx_framework::CookedShaderVariant {
+                name: variant.name.clone(),
+                defines: variant.defines.clone(),
+                package: cooked_shader,
+            });
+        }
+
+        write_compiled_shader(
+            compiled_shader,
+            variant_spv_file.as_ref(),
+            variant_rs_file,
+            variant_metal_generated_src_file.as_ref(),
+            variant_gles2_generated_src_file.as_ref(),
+            variant_gles3_generated_src_file.as_ref(),
+            variant_wgsl_generated_src_file.as_ref(),
+            None,
+            &mut written_files,
+        )?;
+    }
+
+    if let Some(cooked_shader_file) = &cooked_shader_file {
+        let variant_set = rafx_framework::CookedShaderVariantSet {
+            variants: cooked_variants,
+        };
+
+        let variants_file = cooked_shader_file.with_extension("cookedshadervariants");
+        write_output_file(
+            &variants_file,
+            cook::serialize_cooked_shader_variants(&variant_set)?,
+        )?;
+        written_files.push(variants_file);
+    }
+
+    Ok(written_files)
+}
+
+struct CompiledShader {
+    spv: Vec<u8>,
+    rust_code: Option<String>,
+    metal_src: Option<String>,
+    gles2_src: Option<String>,
+    gles3_src: Option<String>,
+    wgsl_src: Option<String>,
+    cooked_shader: Option<CookedShaderPackage>,
+}
+
+fn write_compiled_shader(
+    compiled_shader: CompiledShader,
+    spv_file: Option<&PathBuf>,
+    rs_file: &Option<RsFileOption>,
+    metal_generated_src_file: Option<&PathBuf>,
+    gles2_generated_src_file: Option<&PathBuf>,
+    gles3_generated_src_file: Option<&PathBuf>,
+    wgsl_generated_src_file: Option<&PathBuf>,
+    cooked_shader_file: Option<&PathBuf>,
+    written_files: &mut Vec<PathBuf>,
 ) -> Result<(), Box<dyn Error>> {
+    if let Some(spv_file) = &spv_file {
+        write_output_file(spv_file, compiled_shader.spv)?;
+        written_files.push(spv_file.to_path_buf());
+    }
+
+    if let Some(rs_file) = &rs_file {
+        write_output_file(&rs_file.path, compiled_shader.rust_code.unwrap())?;
+        written_files.push(rs_file.path.clone());
+    }
+
+    // These may be missing for stages that are only supported on vulkan
+    if let (Some(metal_generated_src_file), Some(metal_src)) =
+        (&metal_generated_src_file, compiled_shader.metal_src)
+    {
+        write_output_file(metal_generated_src_file, metal_src)?;
+        written_files.push(metal_generated_src_file.to_path_buf());
+    }
+
+    if let (Some(gles2_generated_src_file), Some(gles2_src)) =
+        (&gles2_generated_src_file, compiled_shader.gles2_src)
+    {
+        write_output_file(gles2_generated_src_file, gles2_src)?;
+        written_files.push(gles2_generated_src_file.to_path_buf());
+    }
+
+    if let (Some(gles3_generated_src_file), Some(gles3_src)) =
+        (&gles3_generated_src_file, compiled_shader.gles3_src)
+    {
+        write_output_file(gles3_generated_src_file, gles3_src)?;
+        written_files.push(gles3_generated_src_file.to_path_buf());
+    }
+
+    // This may be missing if the shader could not be translated to WGSL
+    if let (Some(wgsl_generated_src_file), Some(wgsl_src)) =
+        (&wgsl_generated_src_file, compiled_shader.wgsl_src)
+    {
+        write_output_file(wgsl_generated_src_file, wgsl_src)?;
+        written_files.push(wgsl_generated_src_file.to_path_buf());
+    }
+
+    if let Some(cooked_shader_file) = &cooked_shader_file {
+        write_output_file(
+            cooked_shader_file,
+            cook::serialize_cooked_shader(compiled_shader.cooked_shader.as_ref().unwrap())?,
+        )?;
+        written_files.push(cooked_shader_file.to_path_buf());
+    }
+
+    Ok(())
+}
+
+fn add_variant_macro_definitions(
+    compile_options: &mut shaderc::CompileOptions,
+    variant: Option<&ShaderVariantDeclaration>,
+) {
+    if let Some(variant) = variant {
+        for (name, value) in variant.macro_definitions() {
+            compile_options.add_macro_definition(name, value);
+        }
+    }
+}
+
+// variant is the variant being compiled along with all the declared variants
+fn compile_glsl_shader(
+    glsl_file: &Path,
+    variant: Option<(&ShaderVariantDeclaration, &[ShaderVariantDeclaration])>,
+    spv_file: Option<&PathBuf>,
+    rs_file: &Option<RsFileOption>,
+    metal_generated_src_file: Option<&PathBuf>,
+    gles2_generated_src_file: Option<&PathBuf>,
+    gles3_generated_src_file: Option<&PathBuf>,
+    wgsl_generated_src_file: Option<&PathBuf>,
+    cooked_shader_file: Option<&PathBuf>,
+    shader_kind: shaderc::ShaderKind,
+    args: &ShaderProcessorArgs,
+) -> Result<CompiledShader, Box<dyn Error>> {
     log::trace!("--- Start processing shader job ---");
     log::trace!("glsl: {:?}", glsl_file);
+    log::trace!("variant: {:?}", variant.map(|x| &x.0.name));
     log::trace!("spv: {:?}", spv_file);
     log::trace!("rs: {:?}", rs_file);
     log::trace!("metal: {:?}", metal_generated_src_file);
     log::trace!("gles2: {:?}", gles2_generated_src_file);
     log::trace!("gles3: {:?}", gles3_generated_src_file);
+    log::trace!("wgsl: {:?}", wgsl_generated_src_file);
     log::trace!("cooked: {:?}", cooked_shader_file);
     log::trace!("shader kind: {:?}", shader_kind);
 
@@ -358,21 +762,26 @@ fn process_glsl_shader(
     let package_metal = (args.package_all || args.package_metal) && cooked_shader_file.is_some();
     let package_gles2 = (args.package_all || args.package_gles2) && cooked_shader_file.is_some();
     let package_gles3 = (args.package_all || args.package_gles3) && cooked_shader_file.is_some();
+    let package_wgsl = (args.package_all || args.package_wgsl) && cooked_shader_file.is_some();
 
     log::trace!(
-        "package VK: {} Metal: {} GLES2: {} GLES3: {}",
+        "package VK: {} Metal: {} GLES2: {} GLES3: {} WGSL: {}",
         package_vk,
         package_metal,
         package_gles2,
-        package_gles3
+        package_gles3,
+        package_wgsl
     );
 
     if cooked_shader_file.is_some()
-        && !(package_vk || package_metal || package_gles2 || package_gles3)
+        && !(package_vk || package_metal || package_gles2 || package_gles3 || package_wgsl)
     {
-        Err("A cooked shader file or path was specified but no shader types are specified to package. Pass --package-vk, --package-metal, --package-gles2, --package-gles3, or --package-all")?;
+        Err("A cooked shader file or path was specified but no shader types are specified to package. Pass --package-vk, --package-metal, --package-gles2, --package-gles3, --package-wgsl, or --package-all")?;
     }
 
+    // Hand-written overrides for a variant are named like a.frag.<variant>.metal
+    let variant_name = variant.map(|x| x.0.name.as_str());
+
     let code = std::fs::read_to_string(&glsl_file)?;
     let entry_point_name = "main";
 
@@ -392,6 +801,7 @@ fn process_glsl_shader(
         let mut compile_options = shaderc::CompileOptions::new().unwrap();
         compile_options.set_include_callback(include::shaderc_include_callback);
         compile_options.set_generate_debug_info();
+        add_variant_macro_definitions(&mut compile_options, variant.map(|x| x.0));
 
         compiler.compile_into_spirv(
             &code,
@@ -488,6 +898,7 @@ fn process_glsl_shader(
             &parsed_declarations,
             &spirv_reflect_module,
             &reflected_entry_point,
+            variant.map(|x| x.1),
             args.for_rafx_framework_crate,
         )?)
     } else {
@@ -510,6 +921,7 @@ fn process_glsl_shader(
         let mut compile_options = shaderc::CompileOptions::new().unwrap();
         compile_options.set_include_callback(include::shaderc_include_callback);
         compile_options.set_optimization_level(shaderc::OptimizationLevel::Performance);
+        add_variant_macro_definitions(&mut compile_options, variant.map(|x| x.0));
         //NOTE: Could also use shaderc::OptimizationLevel::Size
 
         compiler
@@ -527,97 +939,153 @@ fn process_glsl_shader(
         unoptimized_compile_spirv_result.as_binary_u8().to_vec()
     };
 
-    let metal_src = if let Some(src) = try_load_override_src(glsl_file, ".metal")? {
-        Some(src)
-    } else if metal_generated_src_file.is_some() || package_metal {
-        log::trace!("{:?}: create msl", glsl_file);
-        let mut msl_ast =
-            spirv_cross::spirv::Ast::<spirv_cross::msl::Target>::parse(&spirv_cross_module)?;
-        let mut spirv_cross_msl_options = spirv_cross::msl::CompilerOptions::default();
-        spirv_cross_msl_options.version = spirv_cross::msl::Version::V2_0;
-        spirv_cross_msl_options.enable_argument_buffers = true;
-        spirv_cross_msl_options.force_active_argument_buffer_resources = true;
-        //TODO: Add equivalent to --msl-no-clip-distance-user-varying
-
-        //TODO: Set this up
-        spirv_cross_msl_options.resource_binding_overrides = reflected_data
-            .as_ref()
-            .unwrap()
-            .msl_argument_buffer_assignments
-            .clone();
-        //println!(" binding overrides {:?}", spirv_cross_msl_options.resource_binding_overrides);
-        //spirv_cross_msl_options.vertex_attribute_overrides
-        spirv_cross_msl_options.const_samplers =
-            reflected_data.as_ref().unwrap().msl_const_samplers.clone();
+    // spirv_cross can't produce MSL or GLSL ES for these stages, WGSL doesn't have them, and the
+    // metal and GL ES backends can't use them. Skip those outputs so that the shader can still be
+    // used on vulkan.
+    let is_vulkan_only_stage = match normalize_shader_kind(shader_kind) {
+        ShaderKind::Geometry | ShaderKind::TessControl | ShaderKind::TessEvaluation => true,
+        _ => false,
+    };
+    if is_vulkan_only_stage
+        && (metal_generated_src_file.is_some()
+            || gles2_generated_src_file.is_some()
+            || gles3_generated_src_file.is_some()
+            || wgsl_generated_src_file.is_some()
+            || package_metal
+            || package_gles2
+            || package_gles3
+            || package_wgsl)
+    {
+        log::warn!(
+            "  {:?} is a geometry or tessellation shader, which is only supported on vulkan. Metal, GL ES and WGSL outputs will not be produced",
+            glsl_file
+        );
+    }
 
-        msl_ast.set_compiler_options(&spirv_cross_msl_options)?;
-        let metal_src = msl_ast.compile()?;
+    let metal_src =
+        if let Some(src) = try_load_variant_override_src(glsl_file, variant_name, "metal")? {
+            Some(src)
+        } else if is_vulkan_only_stage {
+            None
+        } else if metal_generated_src_file.is_some() || package_metal {
+            log::trace!("{:?}: create msl", glsl_file);
+            let mut msl_ast =
+                spirv_cross::spirv::Ast::<spirv_cross::msl::Target>::parse(&spirv_cross_module)?;
+            let mut spirv_cross_msl_options = spirv_cross::msl::CompilerOptions::default();
+            spirv_cross_msl_options.version = spirv_cross::msl::Version::V2_0;
+            spirv_cross_msl_options.enable_argument_buffers = true;
+            spirv_cross_msl_options.force_active_argument_buffer_resources = true;
+            //TODO: Add equivalent to --msl-no-clip-distance-user-varying
+
+            //TODO: Set this up
+            spirv_cross_msl_options.resource_binding_overrides = reflected_data
+                .as_ref()
+                .unwrap()
+                .msl_argument_buffer_assignments
+                .clone();
+            //println!(" binding overrides {:?}", spirv_cross_msl_options.resource_binding_overrides);
+            //spirv_cross_msl_options.vertex_attribute_overrides
+            spirv_cross_msl_options.const_samplers =
+                reflected_data.as_ref().unwrap().msl_const_samplers.clone();
+
+            msl_ast.set_compiler_options(&spirv_cross_msl_options)?;
+            let metal_src = msl_ast.compile()?;
+
+            Some(metal_src)
+        } else {
+            None
+        };
 
-        Some(metal_src)
-    } else {
-        None
-    };
+    let gles2_src =
+        if let Some(src) = try_load_variant_override_src(glsl_file, variant_name, "gles2")? {
+            Some(src)
+        } else if is_vulkan_only_stage {
+            None
+        } else if gles2_generated_src_file.is_some() || package_gles2 {
+            log::trace!("{:?}: create gles2", glsl_file);
+            let mut gles2_ast =
+                spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
+            let mut spirv_cross_gles2_options = spirv_cross::glsl::CompilerOptions::default();
+            spirv_cross_gles2_options.version = spirv_cross::glsl::Version::V1_00Es;
+            spirv_cross_gles2_options.vulkan_semantics = false;
+            spirv_cross_gles2_options.vertex.transform_clip_space = true;
+            spirv_cross_gles2_options.vertex.invert_y = true;
+
+            let shader_resources = ast.get_shader_resources()?;
+
+            // Rename uniform blocks to be consistent with how they would appear in GL ES 3.0. This way
+            // we can consistently use the same GL name across both backends
+            for resource in &shader_resources.uniform_buffers {
+                let block_name = gles2_ast.get_name(resource.base_type_id)?;
+                gles2_ast.set_name(
+                    resource.base_type_id,
+                    &format!("{}_UniformBlock", block_name),
+                )?;
+                gles2_ast.set_name(resource.id, &block_name)?;
+            }
 
-    let gles2_src = if let Some(src) = try_load_override_src(glsl_file, ".gles2")? {
-        Some(src)
-    } else if gles2_generated_src_file.is_some() || package_gles2 {
-        log::trace!("{:?}: create gles2", glsl_file);
-        let mut gles2_ast =
-            spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
-        let mut spirv_cross_gles2_options = spirv_cross::glsl::CompilerOptions::default();
-        spirv_cross_gles2_options.version = spirv_cross::glsl::Version::V1_00Es;
-        spirv_cross_gles2_options.vulkan_semantics = false;
-        spirv_cross_gles2_options.vertex.transform_clip_space = true;
-        spirv_cross_gles2_options.vertex.invert_y = true;
-
-        let shader_resources = ast.get_shader_resources()?;
-
-        // Rename uniform blocks to be consistent with how they would appear in GL ES 3.0. This way
-        // we can consistently use the same GL name across both backends
-        for resource in &shader_resources.uniform_buffers {
-            let block_name = gles2_ast.get_name(resource.base_type_id)?;
-            gles2_ast.set_name(
-                resource.base_type_id,
-                &format!("{}_UniformBlock", block_name),
-            )?;
-            gles2_ast.set_name(resource.id, &block_name)?;
-        }
+            rename_gl_samplers(&mut reflected_data, &mut gles2_ast)?;
+            rename_gl_in_out_attributes(shader_kind, &mut gles2_ast, &shader_resources)?;
 
-        rename_gl_samplers(&mut reflected_data, &mut gles2_ast)?;
-        rename_gl_in_out_attributes(shader_kind, &mut gles2_ast, &shader_resources)?;
+            gles2_ast.set_compiler_options(&spirv_cross_gles2_options)?;
+            let gles2_src = gles2_ast.compile()?;
 
-        gles2_ast.set_compiler_options(&spirv_cross_gles2_options)?;
-        let gles2_src = gles2_ast.compile()?;
+            Some(gles2_src)
+        } else {
+            None
+        };
 
-        Some(gles2_src)
-    } else {
-        None
-    };
+    let gles3_src =
+        if let Some(src) = try_load_variant_override_src(glsl_file, variant_name, "gles3")? {
+            Some(src)
+        } else if is_vulkan_only_stage {
+            None
+        } else if gles3_generated_src_file.is_some() || package_gles3 {
+            log::trace!("{:?}: create gles3", glsl_file);
+            let mut gles3_ast =
+                spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
+            let mut spirv_cross_gles3_options = spirv_cross::glsl::CompilerOptions::default();
+            spirv_cross_gles3_options.version = spirv_cross::glsl::Version::V3_00Es;
+            spirv_cross_gles3_options.vulkan_semantics = false;
+            spirv_cross_gles3_options.vertex.transform_clip_space = true;
+            spirv_cross_gles3_options.vertex.invert_y = true;
 
-    let gles3_src = if let Some(src) = try_load_override_src(glsl_file, ".gles3")? {
-        Some(src)
-    } else if gles3_generated_src_file.is_some() || package_gles3 {
-        log::trace!("{:?}: create gles3", glsl_file);
-        let mut gles3_ast =
-            spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
-        let mut spirv_cross_gles3_options = spirv_cross::glsl::CompilerOptions::default();
-        spirv_cross_gles3_options.version = spirv_cross::glsl::Version::V3_00Es;
-        spirv_cross_gles3_options.vulkan_semantics = false;
-        spirv_cross_gles3_options.vertex.transform_clip_space = true;
-        spirv_cross_gles3_options.vertex.invert_y = true;
+            let shader_resources = ast.get_shader_resources()?;
 
-        let shader_resources = ast.get_shader_resources()?;
+            rename_gl_samplers(&mut reflected_data, &mut gles3_ast)?;
+            rename_gl_in_out_attributes(shader_kind, &mut gles3_ast, &shader_resources)?;
 
-        rename_gl_samplers(&mut reflected_data, &mut gles3_ast)?;
-        rename_gl_in_out_attributes(shader_kind, &mut gles3_ast, &shader_resources)?;
+            gles3_ast.set_compiler_options(&spirv_cross_gles3_options)?;
+            let gles3_src = gles3_ast.compile()?;
 
-        gles3_ast.set_compiler_options(&spirv_cross_gles3_options)?;
-        let gles3_src = gles3_ast.compile()?;
+            Some(gles3_src)
+        } else {
+            None
+        };
 
-        Some(gles3_src)
-    } else {
-        None
-    };
+    // WGSL is translated from the final SPIR-V with naga. It does not support every feature that
+    // spirv_cross does, so a failure here is reported but does not fail the shader.
+    let wgsl_src =
+        if let Some(src) = try_load_variant_override_src(glsl_file, variant_name, "wgsl")? {
+            Some(src)
+        } else if is_vulkan_only_stage {
+            None
+        } else if wgsl_generated_src_file.is_some() || package_wgsl {
+            log::trace!("{:?}: create wgsl", glsl_file);
+            match wgsl::translate_spv_to_wgsl(&output_spv) {
+                Ok(wgsl_src) => Some(wgsl_src),
+                Err(e) => {
+                    log::warn!(
+                        "  {:?} could not be translated to WGSL, it will not be produced: {}",
+                        glsl_file,
+                        e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
     // Don't worry about the return value
     log::trace!("{:?}: cook shader", glsl_file);
@@ -625,62 +1093,46 @@ fn process_glsl_shader(
         let output_spv = if package_vk { Some(&output_spv) } else { None };
 
         let metal_src = if package_metal {
-            Some(metal_src.as_ref().unwrap().clone())
+            metal_src.clone()
         } else {
             None
         };
 
         let gles2_src = if package_gles2 {
-            Some(gles2_src.as_ref().unwrap().clone())
+            gles2_src.clone()
         } else {
             None
         };
 
         let gles3_src = if package_gles3 {
-            Some(gles3_src.as_ref().unwrap().clone())
+            gles3_src.clone()
         } else {
             None
         };
 
+        let wgsl_src = if package_wgsl { wgsl_src.clone() } else { None };
+
         Some(cook::cook_shader(
             &reflected_data.as_ref().unwrap().reflection,
             output_spv,
             metal_src,
             gles2_src,
             gles3_src,
-        )?)
+            wgsl_src,
+        ))
     } else {
         None
     };
 
-    //
-    // Write out the spv and rust files if desired
-    //
-    if let Some(spv_file) = &spv_file {
-        write_output_file(spv_file, output_spv)?;
-    }
-
-    if let Some(rs_file) = &rs_file {
-        write_output_file(&rs_file.path, rust_code.unwrap())?;
-    }
-
-    if let Some(metal_generated_src_file) = &metal_generated_src_file {
-        write_output_file(metal_generated_src_file, metal_src.unwrap())?;
-    }
-
-    if let Some(gles2_generated_src_file) = &gles2_generated_src_file {
-        write_output_file(gles2_generated_src_file, gles2_src.unwrap())?;
-    }
-
-    if let Some(gles3_generated_src_file) = &gles3_generated_src_file {
-        write_output_file(gles3_generated_src_file, gles3_src.unwrap())?;
-    }
-
-    if let Some(cooked_shader_file) = &cooked_shader_file {
-        write_output_file(cooked_shader_file, cooked_shader.unwrap())?;
-    }
-
-    Ok(())
+    Ok(CompiledShader {
+        spv: output_spv,
+        rust_code,
+        metal_src,
+        gles2_src,
+        gles3_src,
+        wgsl_src,
+        cooked_shader,
+    })
 }
 
 fn write_output_file<C: AsRef<[u8]>>(
@@ -27,6 +27,18 @@ pub(crate) struct SlotNameAnnotation(pub(crate) String);
 #[serde(rename = "semantic")]
 pub(crate) struct SemanticAnnotation(pub(crate) String);
 
+// Sets the number of descriptors in an unsized array (i.e. texture2D textures[]), which can't be
+// reflected from the shader
+#[derive(Default, Deserialize, Debug)]
+#[serde(rename = "max_element_count")]
+pub(crate) struct MaxElementCountAnnotation(pub(crate) u32);
+
+// Marks a vertex input as per-instance data. Generated vertex layouts put these in a separate
+// buffer with RafxVertexAttributeRate::Instance
+#[derive(Default, Deserialize, Debug)]
+#[serde(rename = "per_instance")]
+pub(crate) struct PerInstanceAnnotation;
+
 fn parse_ron_or_default<'de, T: Default + Deserialize<'de>>(data: &'de str) -> Result<T, String> {
     if !data.is_empty() {
         ron::de::from_str(&data)
@@ -81,6 +93,8 @@ pub(crate) struct BindingAnnotations {
     pub(crate) immutable_samplers: Option<ImmutableSamplersAnnotation>,
     pub(crate) slot_name: Option<SlotNameAnnotation>,
     pub(crate) semantic: Option<SemanticAnnotation>,
+    pub(crate) per_instance: Option<PerInstanceAnnotation>,
+    pub(crate) max_element_count: Option<MaxElementCountAnnotation>,
 }
 
 impl BindingAnnotations {
@@ -117,6 +131,13 @@ impl BindingAnnotations {
                 "semantic" => {
                     parsed_annotations.semantic = Some(parse_ron_or_default(&annotation_data)?);
                 }
+                "per_instance" => {
+                    parsed_annotations.per_instance = Some(parse_ron_or_default(&annotation_data)?);
+                }
+                "max_element_count" => {
+                    parsed_annotations.max_element_count =
+                        Some(parse_ron_or_default(&annotation_data)?);
+                }
                 _ => {
                     return Err(format!(
                         "Annotation named '{}' not allowed for bindings",
@@ -151,7 +172,10 @@ fn parse_array_sizes(
     let mut array_sizes = Vec::<usize>::default();
     while crate::parse_source::try_consume_literal(code, position, "[").is_some() {
         crate::parse_source::skip_whitespace(code, position);
-        let array_index = crate::parse_source::try_consume_array_index(code, position).unwrap_or(0);
+        // A size given by a macro can't be evaluated here, so it is treated like an unsized array
+        let array_index = crate::parse_source::try_consume_array_index(code, position)
+            .or_else(|| crate::parse_source::try_consume_identifier(code, position).map(|_| 0))
+            .unwrap_or(0);
         array_sizes.push(array_index);
         crate::parse_source::skip_whitespace(code, position);
         crate::parse_source::try_consume_literal(code, position, "]").ok_or(format!(
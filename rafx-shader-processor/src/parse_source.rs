@@ -621,6 +621,8 @@ pub struct AnnotationText {
 
 pub struct ShaderText {
     pub declarations: Vec<DeclarationText>,
+    // The parsed file and everything it includes, sorted
+    pub included_files: Vec<PathBuf>,
 }
 
 pub fn parse_glsl(file_path: &Path) -> Result<ShaderText, String> {
@@ -640,7 +642,13 @@ pub fn parse_glsl(file_path: &Path) -> Result<ShaderText, String> {
     let code: Vec<char> = content.chars().collect();
     parse_shader_source_text(&first_file, &mut declarations, &mut included_files, &code)?;
 
-    Ok(ShaderText { declarations })
+    let mut included_files: Vec<_> = included_files.into_iter().collect();
+    included_files.sort();
+
+    Ok(ShaderText {
+        declarations,
+        included_files,
+    })
 }
 
 pub fn parse_shader_source_recursive(
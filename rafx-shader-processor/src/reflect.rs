@@ -4,13 +4,13 @@ use rafx_framework::reflected_shader::{
 };
 
 use crate::shader_types::{
-    element_count, generate_struct, MemoryLayout, TypeAlignmentInfo, UserType,
+    element_count, generate_struct, vertex_input_format, MemoryLayout, TypeAlignmentInfo, UserType,
 };
 use fnv::FnvHashMap;
 use rafx_api::{
-    RafxAddressMode, RafxCompareOp, RafxFilterType, RafxGlUniformMember, RafxMipMapMode,
-    RafxResourceType, RafxResult, RafxSamplerDef, RafxShaderResource, RafxShaderStageFlags,
-    RafxShaderStageReflection, MAX_DESCRIPTOR_SET_LAYOUTS,
+    RafxAddressMode, RafxCompareOp, RafxFilterType, RafxFormat, RafxGlUniformMember,
+    RafxMipMapMode, RafxResourceType, RafxResult, RafxSamplerDef, RafxShaderResource,
+    RafxShaderStageFlags, RafxShaderStageReflection, MAX_DESCRIPTOR_SET_LAYOUTS,
 };
 use spirv_cross::msl::{ResourceBinding, ResourceBindingLocation, SamplerData, SamplerLocation};
 use spirv_cross::spirv::{ExecutionModel, Type};
@@ -120,6 +120,21 @@ where
         .or_else(|| declarations.bindings.iter().find(|x| x.parsed.instance_name == *resource.name))
         .ok_or_else(|| format!("A resource named {} in spirv reflection data was not matched up to a resource scanned in source code.", resource.name))?;
 
+    // Unsized arrays reflect as 0 elements (or are implicitly sized if they are not indexed
+    // dynamically), the annotation provides the count
+    let element_count = if let Some(annotation) = &parsed_binding.annotations.max_element_count {
+        if parsed_binding.parsed.array_sizes != [0] {
+            Err(format!(
+                "The resource {} has a max_element_count annotation but is not declared as an unsized array",
+                resource.name
+            ))?;
+        }
+
+        annotation.0
+    } else {
+        element_count
+    };
+
     let slot_name = if let Some(annotation) = &parsed_binding.annotations.slot_name {
         Some(annotation.0.clone())
     } else {
@@ -395,6 +410,9 @@ pub(crate) fn msl_assign_argument_buffer_ids(
         if stages.intersects(RafxShaderStageFlags::TESSELLATION_EVALUATION) {
             out.push(ExecutionModel::TessellationEvaluation)
         }
+        if stages.intersects(RafxShaderStageFlags::GEOMETRY) {
+            out.push(ExecutionModel::Geometry)
+        }
         out
     }
 
@@ -805,13 +823,17 @@ where
                     "".to_string()
                 };
 
-                // TODO(dvd): Might need other special type handling here.
-                if parsed_binding.parsed.type_name == "mat4" {
-                    for index in 0..4 {
+                // Matrices take a location per column, the semantic is suffixed with the column index
+                let (format, location_count) =
+                    vertex_input_format(&parsed_binding.parsed.type_name)
+                        .unwrap_or((RafxFormat::UNDEFINED, 1));
+                if location_count > 1 {
+                    for index in 0..location_count {
                         dsc_vertex_inputs.push(ReflectedVertexInput {
                             name: name.clone(),
                             semantic: format!("{}{}", semantic, index),
                             location: location + index,
+                            format,
                         });
                     }
                 } else {
@@ -819,6 +841,7 @@ where
                         name: name.clone(),
                         semantic,
                         location,
+                        format,
                     });
                 }
             }
@@ -1,5 +1,6 @@
 use crate::parse_declarations::{ParseDeclarationsResult, ParseFieldResult};
 use fnv::FnvHashMap;
+use rafx_api::RafxFormat;
 use std::sync::Arc;
 
 // https://graphics.stanford.edu/~seander/bithacks.html#RoundUpPowerOf2
@@ -168,6 +169,31 @@ pub(crate) fn create_builtin_type_lookup() -> FnvHashMap<String, TypeAlignmentIn
     builtin_types
 }
 
+// The format that exactly matches a vertex input of the given GLSL type, and the number of
+// locations it occupies. Matrices use one location per column, each with the column's format.
+pub(crate) fn vertex_input_format(type_name: &str) -> Option<(RafxFormat, u32)> {
+    let format = match type_name {
+        "float" => (RafxFormat::R32_SFLOAT, 1),
+        "vec2" => (RafxFormat::R32G32_SFLOAT, 1),
+        "vec3" => (RafxFormat::R32G32B32_SFLOAT, 1),
+        "vec4" => (RafxFormat::R32G32B32A32_SFLOAT, 1),
+        "int" => (RafxFormat::R32_SINT, 1),
+        "ivec2" => (RafxFormat::R32G32_SINT, 1),
+        "ivec3" => (RafxFormat::R32G32B32_SINT, 1),
+        "ivec4" => (RafxFormat::R32G32B32A32_SINT, 1),
+        "uint" => (RafxFormat::R32_UINT, 1),
+        "uvec2" => (RafxFormat::R32G32_UINT, 1),
+        "uvec3" => (RafxFormat::R32G32B32_UINT, 1),
+        "uvec4" => (RafxFormat::R32G32B32A32_UINT, 1),
+        "mat2" => (RafxFormat::R32G32_SFLOAT, 2),
+        "mat3" => (RafxFormat::R32G32B32_SFLOAT, 3),
+        "mat4" => (RafxFormat::R32G32B32A32_SFLOAT, 4),
+        _ => return None,
+    };
+
+    Some(format)
+}
+
 // Memory layouts we have to deal with (C = repr(C))
 #[derive(Copy, Clone, Debug)]
 pub enum MemoryLayout {
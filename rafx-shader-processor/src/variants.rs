@@ -0,0 +1,156 @@
+use fnv::FnvHashSet;
+use serde::Deserialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+// Permutations of a shader are declared in a RON file next to the shader source with ".variants"
+// appended to the file name (i.e. mesh_adv.frag.variants). For example:
+//
+// [
+//     (name: "textured", defines: ["USE_TEXTURES", "MAX_LIGHTS=16"]),
+//     (name: "untextured"),
+// ]
+//
+// Each variant is compiled with its defines. The first variant is the default, and is used to
+// generate the rust code for the shader. The declaration scan does not evaluate #if/#ifdef, so
+// descriptor bindings should be declared the same way in every variant.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct ShaderVariantDeclaration {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) defines: Vec<String>,
+}
+
+impl ShaderVariantDeclaration {
+    // Defines are written as NAME or NAME=VALUE
+    pub(crate) fn macro_definitions(&self) -> Vec<(&str, Option<&str>)> {
+        self.defines
+            .iter()
+            .map(|define| {
+                let mut parts = define.splitn(2, '=');
+                let name = parts.next().unwrap().trim();
+                let value = parts.next().map(|x| x.trim());
+                (name, value)
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn variants_path(glsl_file: &Path) -> PathBuf {
+    let mut path = glsl_file.as_os_str().to_os_string();
+    path.push(".variants");
+    PathBuf::from(path)
+}
+
+pub(crate) fn load_variant_declarations(
+    glsl_file: &Path
+) -> Result<Option<Vec<ShaderVariantDeclaration>>, Box<dyn Error>> {
+    let path = variants_path(glsl_file);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    log::info!("  Shader variants declared in {:?}", path);
+    let variants = parse_variant_declarations(&std::fs::read_to_string(&path)?)
+        .map_err(|x| format!("{}: {}", path.to_string_lossy(), x))?;
+    Ok(Some(variants))
+}
+
+fn parse_variant_declarations(source: &str) -> Result<Vec<ShaderVariantDeclaration>, String> {
+    let variants: Vec<ShaderVariantDeclaration> =
+        ron::de::from_str(source).map_err(|x| format!("Failed to parse variants: {}", x))?;
+
+    if variants.is_empty() {
+        Err("At least one variant must be declared")?;
+    }
+
+    let mut names = FnvHashSet::default();
+    for variant in &variants {
+        // Variant names become part of output file names and rust identifiers
+        let is_valid_name = variant
+            .name
+            .chars()
+            .next()
+            .map(|x| x.is_ascii_lowercase())
+            .unwrap_or(false)
+            && variant
+                .name
+                .chars()
+                .all(|x| x.is_ascii_lowercase() || x.is_ascii_digit() || x == '_');
+        if !is_valid_name {
+            Err(format!(
+                "Variant name {:?} is invalid, names must be lower snake case (i.e. \"no_shadows\")",
+                variant.name
+            ))?;
+        }
+
+        if !names.insert(variant.name.clone()) {
+            Err(format!(
+                "Variant name {:?} is declared more than once",
+                variant.name
+            ))?;
+        }
+
+        for (define, _) in variant.macro_definitions() {
+            if define.is_empty() {
+                Err(format!("Variant {:?} has an empty define", variant.name))?;
+            }
+        }
+    }
+
+    Ok(variants)
+}
+
+// Inserts the variant name before the extension of an output file (a.frag.spv -> a.frag.textured.spv)
+pub(crate) fn variant_output_path(
+    path: &Path,
+    variant_name: &str,
+) -> PathBuf {
+    match path.extension() {
+        Some(extension) => {
+            path.with_extension(format!("{}.{}", variant_name, extension.to_string_lossy()))
+        }
+        None => path.with_extension(variant_name),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_variant_declarations() {
+        let variants = parse_variant_declarations(
+            r#"[
+                (name: "textured", defines: ["USE_TEXTURES", "MAX_LIGHTS = 16"]),
+                (name: "untextured"),
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].name, "textured");
+        assert_eq!(
+            variants[0].macro_definitions(),
+            vec![("USE_TEXTURES", None), ("MAX_LIGHTS", Some("16"))]
+        );
+        assert_eq!(variants[1].name, "untextured");
+        assert!(variants[1].defines.is_empty());
+    }
+
+    #[test]
+    fn test_parse_variant_declarations_invalid() {
+        assert!(parse_variant_declarations("[]").is_err());
+        assert!(parse_variant_declarations(r#"[(name: "Textured")]"#).is_err());
+        assert!(parse_variant_declarations(r#"[(name: "a"), (name: "a")]"#).is_err());
+        assert!(parse_variant_declarations(r#"[(name: "a", defines: ["=1"])]"#).is_err());
+    }
+
+    #[test]
+    fn test_variant_output_path() {
+        assert_eq!(
+            variant_output_path(Path::new("shaders/a.frag.spv"), "textured"),
+            PathBuf::from("shaders/a.frag.textured.spv")
+        );
+    }
+}
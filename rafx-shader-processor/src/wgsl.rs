@@ -0,0 +1,16 @@
+// Translates SPIR-V to WGSL. naga's SPIR-V frontend does not handle everything glslang can emit,
+// so this can fail for shaders that work fine on other backends.
+pub(crate) fn translate_spv_to_wgsl(spv: &[u8]) -> Result<String, String> {
+    let module = naga::front::spv::parse_u8_slice(spv, &naga::front::spv::Options::default())
+        .map_err(|x| format!("Failed to parse SPIR-V: {:?}", x))?;
+
+    let module_info = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|x| format!("SPIR-V failed validation: {:?}", x))?;
+
+    naga::back::wgsl::write_string(&module, &module_info)
+        .map_err(|x| format!("Failed to write WGSL: {:?}", x))
+}
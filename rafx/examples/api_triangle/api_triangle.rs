@@ -211,6 +211,7 @@ fn run() -> RafxResult<()> {
         let root_signature = device_context.create_root_signature(&RafxRootSignatureDef {
             shaders: &[shader.clone()],
             immutable_samplers: &[],
+            bindless_descriptors: &[],
         })?;
 
         //
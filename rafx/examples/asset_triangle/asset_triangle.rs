@@ -146,7 +146,9 @@ fn run() -> RafxResult<()> {
                 max_concurrent_uploads: 2,
                 max_new_uploads_in_single_frame: 1,
                 max_bytes_per_upload: 64 * 1024 * 1024,
+                max_bytes_per_frame: 64 * 1024 * 1024,
             },
+            Default::default(),
             &graphics_queue,
             &transfer_queue,
         )?;
@@ -9,7 +9,7 @@ use rafx::graph::{
 use rafx::render_features::RenderJobCommandBufferContext;
 use rafx::render_features::RenderRegistryBuilder;
 use rafx::renderer::SwapchainRenderResource;
-use rafx_renderer::RendererPipelinePlugin;
+use rafx_renderer::{RenderViewportTarget, RendererPipelinePlugin};
 
 pub struct ExampleRendererPipelinePlugin;
 
@@ -27,6 +27,7 @@ impl RendererPipelinePlugin for ExampleRendererPipelinePlugin {
         swapchain_image: ResourceArc<ImageViewResource>,
         _rotating_frame_index: usize,
         main_view: RenderView,
+        viewport: &RenderViewportTarget,
         _extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph> {
@@ -35,7 +36,9 @@ impl RendererPipelinePlugin for ExampleRendererPipelinePlugin {
         let device_context = asset_manager.device_context();
         let resource_context = asset_manager.resource_manager().resource_context();
         let swapchain_render_resource = render_resources.fetch::<SwapchainRenderResource>();
-        let swapchain_info = swapchain_render_resource.surface_info().unwrap();
+        let swapchain_info = swapchain_render_resource
+            .surface_info_for_surface(&viewport.surface_name)
+            .unwrap();
 
         //
         // Create a graph to describe how we will draw the frame. Here we just have a single
@@ -82,8 +85,8 @@ impl RendererPipelinePlugin for ExampleRendererPipelinePlugin {
         let external_image_id = graph_builder.add_external_image(
             swapchain_image,
             Default::default(),
-            RafxResourceState::PRESENT,
-            RafxResourceState::PRESENT,
+            viewport.image_state,
+            viewport.image_state,
         );
 
         let _ = graph_builder.write_external_image(external_image_id, color_attachment);
@@ -92,7 +95,7 @@ impl RendererPipelinePlugin for ExampleRendererPipelinePlugin {
             &device_context,
             &resource_context,
             graph_builder,
-            &swapchain_info.swapchain_surface_info,
+            &viewport.surface_info,
         )?;
 
         Ok(prepared_render_graph)